@@ -250,6 +250,12 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         self.stack_offset.0
     }
 
+    /// Bytes the machine stack moves by when the stack values grow from `low` to `high` bytes,
+    /// or shrink back. The machine may keep its stack pointer more aligned than a value slot.
+    fn stack_adjustment(&self, low: usize, high: usize) -> usize {
+        self.machine.round_stack_adjust(high) - self.machine.round_stack_adjust(low)
+    }
+
     /// Acquires locations from the machine state.
     ///
     /// If the returned locations are used for stack value, `release_location` needs to be called on them;
@@ -290,6 +296,10 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             ret.push(loc);
         }
 
+        let delta_stack_offset = self.stack_adjustment(
+            self.stack_offset.0 - delta_stack_offset,
+            self.stack_offset.0,
+        );
         if delta_stack_offset != 0 {
             self.machine.adjust_stack(delta_stack_offset as u32);
         }
@@ -336,8 +346,12 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             self.state.wasm_stack.pop().unwrap();
         }
 
+        let delta_stack_offset = self.stack_adjustment(
+            self.stack_offset.0,
+            self.stack_offset.0 + delta_stack_offset,
+        );
         if delta_stack_offset != 0 {
-            self.machine.adjust_stack(delta_stack_offset as u32);
+            self.machine.restore_stack(delta_stack_offset as u32);
         }
     }
     /// Releases locations used for stack value.
@@ -376,8 +390,12 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             self.state.wasm_stack.pop().unwrap();
         }
 
+        let delta_stack_offset = self.stack_adjustment(
+            self.stack_offset.0,
+            self.stack_offset.0 + delta_stack_offset,
+        );
         if delta_stack_offset != 0 {
-            self.machine.adjust_stack(delta_stack_offset as u32);
+            self.machine.restore_stack(delta_stack_offset as u32);
         }
     }
 
@@ -421,6 +439,10 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             // Wasm state popping is deferred to `release_locations_only_osr_state`.
         }
 
+        let delta_stack_offset = self.stack_adjustment(
+            self.stack_offset.0,
+            self.stack_offset.0 + delta_stack_offset,
+        );
         if delta_stack_offset != 0 {
             self.machine.pop_stack_locals(delta_stack_offset as u32);
         }
@@ -457,6 +479,10 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             }
         }
 
+        let delta_stack_offset = self.stack_adjustment(
+            self.stack_offset.0 - delta_stack_offset,
+            self.stack_offset.0,
+        );
         if delta_stack_offset != 0 {
             self.machine.pop_stack_locals(delta_stack_offset as u32);
         }
@@ -467,7 +493,7 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         n: usize,
         n_params: usize,
         calling_convention: CallingConvention,
-    ) -> Vec<Location<M::GPR, M::SIMD>> {
+    ) -> Result<Vec<Location<M::GPR, M::SIMD>>, CodegenError> {
         // How many machine stack slots will all the locals use?
         let num_mem_slots = (0..n)
             .filter(|&x| self.machine.is_local_on_stack(x))
@@ -492,6 +518,11 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         // Some ABI (like Windows) needs extrat reg save
         static_area_size += 8 * self.machine.list_to_save(calling_convention).len();
 
+        // Saved floating-point control state.
+        if self.config.enable_fpcr_canonicalization {
+            static_area_size += 8;
+        }
+
         // Total size of callee saved registers.
         let callee_saved_regs_size = static_area_size;
 
@@ -504,7 +535,8 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         static_area_size += num_mem_slots * 8;
 
         // Allocate save area, without actually writing to it.
-        self.machine.adjust_stack(static_area_size as _);
+        self.machine
+            .adjust_stack(self.machine.round_stack_adjust(static_area_size) as _);
 
         // Save callee-saved registers.
         for loc in locations.iter() {
@@ -534,6 +566,11 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             self.machine.move_local(self.stack_offset.0 as i32, *loc);
         }
 
+        // Room for the floating-point control state, saved by `emit_head`.
+        if self.config.enable_fpcr_canonicalization {
+            self.stack_offset.0 += 8;
+        }
+
         // Save the offset of register save area.
         self.save_area_offset = Some(MachineStackOffset(self.stack_offset.0));
 
@@ -597,29 +634,42 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         // Add the size of all locals allocated to stack.
         self.stack_offset.0 += static_area_size - callee_saved_regs_size;
 
-        locations
+        Ok(locations)
     }
 
-    fn finalize_locals(&mut self, calling_convention: CallingConvention) {
-        // Unwind stack to the "save area".
-        self.machine
-            .restore_saved_area(self.save_area_offset.as_ref().unwrap().0 as i32);
+    fn finalize_locals(
+        &mut self,
+        calling_convention: CallingConvention,
+    ) -> Result<(), CodegenError> {
+        let mut saved_area_offset = self.save_area_offset.as_ref().unwrap().0 as i32;
 
-        let regs_to_save = self.machine.list_to_save(calling_convention);
-        for loc in regs_to_save.iter().rev() {
-            self.machine.pop_location(*loc);
+        // The floating-point control state is the last value saved.
+        if self.config.enable_fpcr_canonicalization {
+            self.machine.restore_fp_control(saved_area_offset)?;
+            saved_area_offset -= 8;
+        }
+
+        if self.config.call_depth_limit.is_some() {
+            self.machine
+                .emit_call_depth_release(self.vmoffsets.vmctx_call_depth() as i32);
         }
 
+        let mut saved = self.machine.list_to_save(calling_convention);
+        saved.reverse();
+
         // Restore register used by vmctx.
-        self.machine
-            .pop_location(Location::GPR(self.machine.get_vmctx_reg()));
+        saved.push(Location::GPR(self.machine.get_vmctx_reg()));
 
         // Restore callee-saved registers.
         for loc in self.locals.iter().rev() {
             if let Location::GPR(_) = *loc {
-                self.machine.pop_location(*loc);
+                saved.push(*loc);
             }
         }
+
+        // Unwind stack to the "save area".
+        self.machine.restore_saved_area(saved_area_offset, &saved);
+        Ok(())
     }
 
     /// Set the source location of the Wasm to the given offset.
@@ -672,6 +722,14 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         );
     }
     fn mark_offset_trappable(&mut self, offset: usize) {
+        // The offset comes from a division helper and must be the DIV opcode
+        // it recorded in the trap table, or the trap would be misreported.
+        debug_assert_eq!(
+            self.machine.trap_code_at(offset),
+            Some(TrapCode::IntegerOverflow),
+            "offset {} is not a recorded division trap",
+            offset
+        );
         let state_diff_id = self.get_state_diff();
         self.fsm.trappable_offsets.insert(
             offset,
@@ -751,16 +809,19 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         }
 
         // Align stack to 16 bytes.
-        if (self.get_stack_offset() + used_gprs.len() * 8 + used_simds.len() * 8 + stack_offset)
+        let padding = (self.machine.round_stack_adjust(self.get_stack_offset())
+            + self.machine.round_stack_adjust(used_gprs.len() * 8)
+            + self.machine.round_stack_adjust(used_simds.len() * 8)
+            + stack_offset)
             % 16
-            != 0
-        {
-            self.machine.adjust_stack(8);
+            != 0;
+        if padding {
             stack_offset += 8;
             self.state.stack_values.push(MachineValue::Undefined);
         }
 
         let mut call_movs: Vec<(Location<M::GPR, M::SIMD>, M::GPR)> = vec![];
+        let mut stack_params: Vec<Location<M::GPR, M::SIMD>> = vec![];
         // Prepare register & stack parameters.
         for (i, param) in params.iter().enumerate().rev() {
             let loc = self.machine.get_param_location(1 + i, calling_convention);
@@ -803,7 +864,7 @@ impl<'a, M: Machine> FuncGen<'a, M> {
                             self.state.stack_values.push(MachineValue::Undefined);
                         }
                     }
-                    self.machine.push_location_for_native(*param);
+                    stack_params.push(*param);
                 }
                 _ => {
                     return Err(CodegenError {
@@ -813,6 +874,8 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             }
         }
 
+        self.machine.push_params_for_native(&stack_params, padding);
+
         // Sort register moves so that register are not overwritten before read.
         Self::sort_call_movs(&mut call_movs);
 
@@ -831,7 +894,9 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             self.machine.get_param_location(0, calling_convention),
         ); // vmctx
 
-        if (self.state.stack_values.len() % 2) != 1 {
+        // The machine state only has a slot for each 8-byte value, so its parity follows
+        // the alignment of the stack if nothing else pads it.
+        if self.machine.round_stack_adjust(8) == 8 && (self.state.stack_values.len() % 2) != 1 {
             return Err(CodegenError {
                 message: "emit_call_native: explicit shadow takes one slot".to_string(),
             });
@@ -897,6 +962,422 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         Ok(())
     }
 
+    /// Pops the `count` parameters of a call off the value stack.
+    ///
+    /// Canonicalization state will be lost across function calls, so early canonicalization
+    /// is necessary here.
+    fn pop_call_params(&mut self, count: usize) -> SmallVec<[Location<M::GPR, M::SIMD>; 8]> {
+        let params: SmallVec<[_; 8]> = self
+            .value_stack
+            .drain(self.value_stack.len() - count..)
+            .collect();
+        self.release_locations_only_regs(&params);
+
+        // Pop arguments off the FP stack and canonicalize them if needed.
+        while let Some(fp) = self.fp_stack.last() {
+            if fp.depth >= self.value_stack.len() {
+                let index = fp.depth - self.value_stack.len();
+                if self.machine.arch_supports_canonicalize_nan()
+                    && self.config.enable_nan_canonicalization
+                    && fp.canonicalization.is_some()
+                {
+                    let size = fp.canonicalization.unwrap().to_size();
+                    self.machine
+                        .canonicalize_nan(size, params[index], params[index]);
+                }
+                self.fp_stack.pop().unwrap();
+            } else {
+                break;
+            }
+        }
+        params
+    }
+
+    /// Returns the relocation target of a direct call to `function_index`.
+    fn call_reloc_target(&self, function_index: usize) -> RelocationTarget {
+        // Imported functions are called through trampolines placed as custom sections.
+        if function_index < self.module.num_imported_functions {
+            RelocationTarget::CustomSection(SectionIndex::new(function_index))
+        } else {
+            RelocationTarget::LocalFunc(LocalFunctionIndex::new(
+                function_index - self.module.num_imported_functions,
+            ))
+        }
+    }
+
+    /// Looks up entry `func_index` of table `table_index` for an indirect call with signature
+    /// `index`, trapping if it is out of bounds, null or of another signature.
+    ///
+    /// Returns the register for call, which holds the address of the `VMCallerCheckedAnyfunc`.
+    fn emit_indirect_call_target(
+        &mut self,
+        table_index: TableIndex,
+        index: SignatureIndex,
+        func_index: Location<M::GPR, M::SIMD>,
+    ) -> M::GPR {
+        let table_base = self.machine.acquire_temp_gpr().unwrap();
+        let table_count = self.machine.acquire_temp_gpr().unwrap();
+        let sigidx = self.machine.acquire_temp_gpr().unwrap();
+
+        if let Some(local_table_index) = self.module.local_table_index(table_index) {
+            let (vmctx_offset_base, vmctx_offset_len) = (
+                self.vmoffsets.vmctx_vmtable_definition(local_table_index),
+                self.vmoffsets
+                    .vmctx_vmtable_definition_current_elements(local_table_index),
+            );
+            self.machine.move_location(
+                Size::S64,
+                Location::Memory(self.machine.get_vmctx_reg(), vmctx_offset_base as i32),
+                Location::GPR(table_base),
+            );
+            self.machine.move_location(
+                Size::S32,
+                Location::Memory(self.machine.get_vmctx_reg(), vmctx_offset_len as i32),
+                Location::GPR(table_count),
+            );
+        } else {
+            // Do an indirection.
+            let import_offset = self.vmoffsets.vmctx_vmtable_import(table_index);
+            self.machine.move_location(
+                Size::S64,
+                Location::Memory(self.machine.get_vmctx_reg(), import_offset as i32),
+                Location::GPR(table_base),
+            );
+
+            // Load len.
+            self.machine.move_location(
+                Size::S32,
+                Location::Memory(
+                    table_base,
+                    self.vmoffsets.vmtable_definition_current_elements() as _,
+                ),
+                Location::GPR(table_count),
+            );
+
+            // Load base.
+            self.machine.move_location(
+                Size::S64,
+                Location::Memory(table_base, self.vmoffsets.vmtable_definition_base() as _),
+                Location::GPR(table_base),
+            );
+        }
+
+        self.machine
+            .location_cmp(Size::S32, func_index, Location::GPR(table_count));
+        self.machine
+            .jmp_on_belowequal(self.special_labels.table_access_oob);
+        self.machine
+            .move_location(Size::S32, func_index, Location::GPR(table_count));
+        self.machine.emit_imul_imm32(
+            Size::S64,
+            self.vmoffsets.size_of_vm_funcref() as u32,
+            table_count,
+        );
+        self.machine.location_add(
+            Size::S64,
+            Location::GPR(table_base),
+            Location::GPR(table_count),
+            false,
+        );
+
+        // deref the table to get a VMFuncRef
+        self.machine.move_location(
+            Size::S64,
+            Location::Memory(table_count, self.vmoffsets.vm_funcref_anyfunc_ptr() as i32),
+            Location::GPR(table_count),
+        );
+        // Trap if the FuncRef is null
+        self.machine
+            .location_cmp(Size::S64, Location::Imm32(0), Location::GPR(table_count));
+        self.machine
+            .jmp_on_equal(self.special_labels.indirect_call_null);
+        self.machine.move_location(
+            Size::S64,
+            Location::Memory(
+                self.machine.get_vmctx_reg(),
+                self.vmoffsets.vmctx_vmshared_signature_id(index) as i32,
+            ),
+            Location::GPR(sigidx),
+        );
+
+        // Trap if signature mismatches.
+        self.machine.location_cmp(
+            Size::S32,
+            Location::GPR(sigidx),
+            Location::Memory(
+                table_count,
+                (self.vmoffsets.vmcaller_checked_anyfunc_type_index() as usize) as i32,
+            ),
+        );
+        self.machine
+            .jmp_on_different(self.special_labels.bad_signature);
+
+        self.machine.release_gpr(sigidx);
+        self.machine.release_gpr(table_count);
+        self.machine.release_gpr(table_base);
+
+        let gpr_for_call = self.machine.get_grp_for_call();
+        if table_count != gpr_for_call {
+            self.machine.move_location(
+                Size::S64,
+                Location::GPR(table_count),
+                Location::GPR(gpr_for_call),
+            );
+        }
+        gpr_for_call
+    }
+
+    /// Number of the first `count` parameters that are passed on the stack.
+    fn count_stack_params(&self, count: usize) -> usize {
+        let calling_convention = self.calling_convention;
+        (0..count)
+            .filter(|i| {
+                matches!(
+                    self.machine.get_param_location(1 + i, calling_convention),
+                    Location::Memory(_, _)
+                )
+            })
+            .count()
+    }
+
+    /// Whether a callee taking `count` parameters can be tail called. Its stack parameters are
+    /// written over the incoming argument area of the current function, and our caller reserved
+    /// only as many slots as the current function takes, and is the one releasing them. Other
+    /// tail calls are lowered as a call followed by a return.
+    fn fits_return_call_params(&self, count: usize) -> bool {
+        self.count_stack_params(count) <= self.count_stack_params(self.signature.params().len())
+    }
+
+    /// Moves the parameters of a tail call to where the callee expects them.
+    ///
+    /// Stack parameters are written over the incoming argument area of the current function,
+    /// which is where the callee finds them once the frame is torn down, so they must fit in
+    /// it, see `fits_return_call_params`.
+    ///
+    /// Moving a value between memory locations can clobber the register for call, so `cb`
+    /// loads the call target after the stack parameters are written, but before the argument
+    /// registers are.
+    fn emit_return_call_params<F: FnOnce(&mut Self) -> M::GPR>(
+        &mut self,
+        params: &[Location<M::GPR, M::SIMD>],
+        cb: F,
+    ) -> Result<M::GPR, CodegenError> {
+        let calling_convention = self.calling_convention;
+
+        debug_assert!(self.fits_return_call_params(params.len()));
+
+        let mut call_movs: Vec<(Location<M::GPR, M::SIMD>, M::GPR)> = vec![];
+        for (i, param) in params.iter().enumerate().rev() {
+            match self.machine.get_param_location(1 + i, calling_convention) {
+                Location::GPR(x) => {
+                    call_movs.push((*param, x));
+                }
+                loc @ Location::Memory(_, _) => {
+                    self.machine.move_location(Size::S64, *param, loc);
+                }
+                _ => {
+                    return Err(CodegenError {
+                        message: "emit_return_call_params loc: unreachable code".to_string(),
+                    })
+                }
+            }
+        }
+
+        let gpr_for_call = cb(self);
+
+        // Sort register moves so that register are not overwritten before read.
+        Self::sort_call_movs(&mut call_movs);
+
+        // Emit register moves.
+        for (loc, gpr) in call_movs {
+            if loc != Location::GPR(gpr) {
+                self.machine
+                    .move_location(Size::S64, loc, Location::GPR(gpr));
+            }
+        }
+        Ok(gpr_for_call)
+    }
+
+    /// Emits a call to the function `function_index`, and pushes its result.
+    fn emit_call(&mut self, function_index: usize) -> Result<(), CodegenError> {
+        let sig_index = *self
+            .module
+            .functions
+            .get(FunctionIndex::new(function_index))
+            .unwrap();
+        let sig = self.module.signatures.get(sig_index).unwrap();
+        let param_types: SmallVec<[WpType; 8]> =
+            sig.params().iter().cloned().map(type_to_wp_type).collect();
+        let return_types: SmallVec<[WpType; 1]> =
+            sig.results().iter().cloned().map(type_to_wp_type).collect();
+
+        let params = self.pop_call_params(param_types.len());
+        self.release_locations_only_osr_state(params.len());
+
+        let reloc_target = self.call_reloc_target(function_index);
+        self.machine
+            .move_with_reloc(reloc_target, &mut self.relocations);
+
+        self.emit_call_native(
+            |this| {
+                let offset = this
+                    .machine
+                    .mark_instruction_with_trap_code(TrapCode::StackOverflow);
+                this.machine
+                    .emit_call_register(this.machine.get_grp_for_call());
+                this.machine.mark_instruction_address_end(offset);
+            },
+            params.iter().copied(),
+        )?;
+
+        self.release_locations_only_stack(&params);
+
+        if !return_types.is_empty() {
+            let ret = self.acquire_locations(
+                &[(
+                    return_types[0],
+                    MachineValue::WasmStack(self.value_stack.len()),
+                )],
+                false,
+            )[0];
+            self.value_stack.push(ret);
+            if return_types[0].is_float() {
+                self.machine.move_location(
+                    Size::S64,
+                    Location::SIMD(self.machine.get_simd_for_ret()),
+                    ret,
+                );
+                self.fp_stack
+                    .push(FloatValue::new(self.value_stack.len() - 1));
+            } else {
+                self.machine.move_location(
+                    Size::S64,
+                    Location::GPR(self.machine.get_gpr_for_ret()),
+                    ret,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Emits a call through the table `table_index` to a function of signature `index`,
+    /// and pushes its result.
+    fn emit_call_indirect(&mut self, index: u32, table_index: u32) -> Result<(), CodegenError> {
+        // TODO: removed restriction on always being table idx 0;
+        // does any code depend on this?
+        let table_index = TableIndex::new(table_index as _);
+        let index = SignatureIndex::new(index as usize);
+        let sig = self.module.signatures.get(index).unwrap();
+        let param_types: SmallVec<[WpType; 8]> =
+            sig.params().iter().cloned().map(type_to_wp_type).collect();
+        let return_types: SmallVec<[WpType; 1]> =
+            sig.results().iter().cloned().map(type_to_wp_type).collect();
+
+        let func_index = self.pop_value_released();
+
+        let params = self.pop_call_params(param_types.len());
+
+        let gpr_for_call = self.emit_indirect_call_target(table_index, index, func_index);
+
+        self.release_locations_only_osr_state(params.len());
+
+        let vmcaller_checked_anyfunc_func_ptr =
+            self.vmoffsets.vmcaller_checked_anyfunc_func_ptr() as usize;
+        let vmcaller_checked_anyfunc_vmctx =
+            self.vmoffsets.vmcaller_checked_anyfunc_vmctx() as usize;
+        let calling_convention = self.calling_convention;
+
+        self.emit_call_native(
+            |this| {
+                if this.machine.arch_requires_indirect_call_trampoline() {
+                    this.machine
+                        .arch_emit_indirect_call_with_trampoline(Location::Memory(
+                            gpr_for_call,
+                            vmcaller_checked_anyfunc_func_ptr as i32,
+                        ));
+                } else {
+                    let offset = this
+                        .machine
+                        .mark_instruction_with_trap_code(TrapCode::StackOverflow);
+
+                    // We set the context pointer
+                    this.machine.move_location(
+                        Size::S64,
+                        Location::Memory(gpr_for_call, vmcaller_checked_anyfunc_vmctx as i32),
+                        this.machine.get_param_location(0, calling_convention),
+                    );
+
+                    this.machine.emit_call_location(Location::Memory(
+                        gpr_for_call,
+                        vmcaller_checked_anyfunc_func_ptr as i32,
+                    ));
+                    this.machine.mark_instruction_address_end(offset);
+                }
+            },
+            params.iter().copied(),
+        )?;
+
+        self.release_locations_only_stack(&params);
+
+        if !return_types.is_empty() {
+            let ret = self.acquire_locations(
+                &[(
+                    return_types[0],
+                    MachineValue::WasmStack(self.value_stack.len()),
+                )],
+                false,
+            )[0];
+            self.value_stack.push(ret);
+            if return_types[0].is_float() {
+                self.machine.move_location(
+                    Size::S64,
+                    Location::SIMD(self.machine.get_simd_for_ret()),
+                    ret,
+                );
+                self.fp_stack
+                    .push(FloatValue::new(self.value_stack.len() - 1));
+            } else {
+                self.machine.move_location(
+                    Size::S64,
+                    Location::GPR(self.machine.get_gpr_for_ret()),
+                    ret,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the result to the return location and jumps to the end of the function.
+    fn emit_return(&mut self) -> Result<(), CodegenError> {
+        let frame = &self.control_stack[0];
+        if !frame.returns.is_empty() {
+            if frame.returns.len() != 1 {
+                return Err(CodegenError {
+                    message: "Return: incorrect frame.returns".to_string(),
+                });
+            }
+            let first_return = frame.returns[0];
+            let loc = *self.value_stack.last().unwrap();
+            let canonicalize = if first_return.is_float() {
+                let fp = self.fp_stack.peek1()?;
+                self.machine.arch_supports_canonicalize_nan()
+                    && self.config.enable_nan_canonicalization
+                    && fp.canonicalization.is_some()
+            } else {
+                false
+            };
+            self.machine
+                .emit_function_return_value(first_return, canonicalize, loc);
+        }
+        let frame = &self.control_stack[0];
+        let frame_depth = frame.value_stack_depth.clone();
+        let label = frame.label;
+        self.release_locations_keep_state(frame_depth);
+        self.machine.jmp_unconditionnal(label);
+        self.unreachable_depth = 1;
+        Ok(())
+    }
+
     /// Emits a System V call sequence, specialized for labels as the call target.
     fn _emit_call_native_label<I: Iterator<Item = Location<M::GPR, M::SIMD>>>(
         &mut self,
@@ -907,6 +1388,71 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         Ok(())
     }
 
+    /// Calls the builtin implementing `memory.atomic.wait32`, `memory.atomic.wait64` or
+    /// `memory.atomic.notify` with its `operands` topmost values on the stack, and pushes
+    /// the returned code.
+    fn emit_atomic_wait_notify(
+        &mut self,
+        memory: u32,
+        offset: u32,
+        operands: usize,
+        local_builtin: VMBuiltinFunctionIndex,
+        imported_builtin: VMBuiltinFunctionIndex,
+    ) -> Result<(), CodegenError> {
+        // [dst, val, timeout] or [dst, count]
+        let args = self
+            .value_stack
+            .split_off(self.value_stack.len() - operands);
+        self.release_locations_only_regs(&args);
+
+        let memory_index = MemoryIndex::new(memory as usize);
+        let builtin = if self.module.local_memory_index(memory_index).is_some() {
+            local_builtin
+        } else {
+            imported_builtin
+        };
+
+        self.machine.move_location(
+            Size::S64,
+            Location::Memory(
+                self.machine.get_vmctx_reg(),
+                self.vmoffsets.vmctx_builtin_function(builtin) as i32,
+            ),
+            Location::GPR(self.machine.get_grp_for_call()),
+        );
+
+        self.release_locations_only_osr_state(operands);
+
+        self.emit_call_native(
+            |this| {
+                this.machine
+                    .emit_call_register(this.machine.get_grp_for_call());
+            },
+            // [vmctx, memory_index, dst, offset, val, timeout] or [vmctx, memory_index, dst, offset, count]
+            [
+                Location::Imm32(memory_index.index() as u32),
+                args[0],
+                Location::Imm32(offset),
+            ]
+            .iter()
+            .chain(&args[1..])
+            .cloned(),
+        )?;
+        self.release_locations_only_stack(&args);
+
+        let ret = self.acquire_locations(
+            &[(WpType::I32, MachineValue::WasmStack(self.value_stack.len()))],
+            false,
+        )[0];
+        self.value_stack.push(ret);
+        self.machine.move_location(
+            Size::S32,
+            Location::GPR(self.machine.get_gpr_for_ret()),
+            ret,
+        );
+        Ok(())
+    }
+
     /// Emits a memory operation.
     fn op_memory<F: FnOnce(&mut Self, bool, bool, i32, Label)>(&mut self, cb: F) {
         let need_check = match self.memory_styles[MemoryIndex::new(0)] {
@@ -944,6 +1490,49 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         id
     }
 
+    /// Calls `builtin` if the caller is not in this instance, to have the
+    /// runtime save some state so that a trap can put it back.
+    fn emit_outside_entry_call(
+        &mut self,
+        builtin: VMBuiltinFunctionIndex,
+    ) -> Result<(), CodegenError> {
+        // `init_locals` saved the caller's vmctx register after the locals kept in registers.
+        let saved_regs = self
+            .locals
+            .iter()
+            .filter(|loc| matches!(loc, Location::GPR(_)))
+            .count()
+            + 1;
+        let caller_vmctx = Location::Memory(self.machine.local_pointer(), -8 * saved_regs as i32);
+        let same_instance = self.machine.get_label();
+        self.machine.location_cmp(
+            Size::S64,
+            caller_vmctx,
+            Location::GPR(self.machine.get_vmctx_reg()),
+        );
+        self.machine.jmp_on_equal(same_instance);
+
+        self.machine.move_location(
+            Size::S64,
+            Location::Memory(
+                self.machine.get_vmctx_reg(),
+                self.vmoffsets.vmctx_builtin_function(builtin) as i32,
+            ),
+            Location::GPR(self.machine.get_grp_for_call()),
+        );
+        self.emit_call_native(
+            |this| {
+                this.machine
+                    .emit_call_register(this.machine.get_grp_for_call());
+            },
+            // [vmctx]
+            iter::empty(),
+        )?;
+
+        self.machine.emit_label(same_instance);
+        Ok(())
+    }
+
     fn emit_head(&mut self) -> Result<(), CodegenError> {
         // TODO: Patchpoint is not emitted for now, and ARM trampoline is not prepended.
 
@@ -955,7 +1544,7 @@ impl<'a, M: Machine> FuncGen<'a, M> {
             self.local_types.len(),
             self.signature.params().len(),
             self.calling_convention,
-        );
+        )?;
 
         // Mark vmctx register. The actual loading of the vmctx value is handled by init_local.
         self.state.register_values[self.machine.index_from_gpr(self.machine.get_vmctx_reg()).0] =
@@ -987,6 +1576,21 @@ impl<'a, M: Machine> FuncGen<'a, M> {
 
         // TODO: Full preemption by explicit signal checking
 
+        // Save the floating-point control state and switch to the canonical one.
+        if self.config.enable_fpcr_canonicalization {
+            self.emit_outside_entry_call(VMBuiltinFunctionIndex::get_fp_control_enter_index())?;
+            let save_area_offset = self.save_area_offset.as_ref().unwrap().0;
+            self.machine
+                .canonicalize_fp_control(save_area_offset as i32)?;
+        }
+
+        // Count this call against the call depth limit.
+        if let Some(limit) = self.config.call_depth_limit {
+            self.emit_outside_entry_call(VMBuiltinFunctionIndex::get_call_depth_enter_index())?;
+            self.machine
+                .emit_call_depth_check(self.vmoffsets.vmctx_call_depth() as i32, limit);
+        }
+
         // We insert set StackOverflow as the default trap that can happen
         // anywhere in the function prologue.
         self.machine.insert_stackoverflow();
@@ -2499,7 +3103,11 @@ impl<'a, M: Machine> FuncGen<'a, M> {
                 self.machine.convert_f64_i64(loc, false, ret);
             }
 
-            Operator::Call { function_index } => {
+            Operator::Call { function_index } => self.emit_call(function_index as usize)?,
+            Operator::CallIndirect { index, table_index } => {
+                self.emit_call_indirect(index, table_index)?
+            }
+            Operator::ReturnCall { function_index } => {
                 let function_index = function_index as usize;
 
                 let sig_index = *self
@@ -2510,241 +3118,60 @@ impl<'a, M: Machine> FuncGen<'a, M> {
                 let sig = self.module.signatures.get(sig_index).unwrap();
                 let param_types: SmallVec<[WpType; 8]> =
                     sig.params().iter().cloned().map(type_to_wp_type).collect();
-                let return_types: SmallVec<[WpType; 1]> =
-                    sig.results().iter().cloned().map(type_to_wp_type).collect();
 
-                let params: SmallVec<[_; 8]> = self
-                    .value_stack
-                    .drain(self.value_stack.len() - param_types.len()..)
-                    .collect();
-                self.release_locations_only_regs(&params);
+                if !self.fits_return_call_params(param_types.len()) {
+                    self.emit_call(function_index)?;
+                    return self.emit_return();
+                }
 
+                let params = self.pop_call_params(param_types.len());
                 self.release_locations_only_osr_state(params.len());
 
-                // Pop arguments off the FP stack and canonicalize them if needed.
-                //
-                // Canonicalization state will be lost across function calls, so early canonicalization
-                // is necessary here.
-                while let Some(fp) = self.fp_stack.last() {
-                    if fp.depth >= self.value_stack.len() {
-                        let index = fp.depth - self.value_stack.len();
-                        if self.machine.arch_supports_canonicalize_nan()
-                            && self.config.enable_nan_canonicalization
-                            && fp.canonicalization.is_some()
-                        {
-                            let size = fp.canonicalization.unwrap().to_size();
-                            self.machine
-                                .canonicalize_nan(size, params[index], params[index]);
-                        }
-                        self.fp_stack.pop().unwrap();
-                    } else {
-                        break;
-                    }
-                }
+                let reloc_target = self.call_reloc_target(function_index);
+                let gpr_for_call = self.emit_return_call_params(&params, |this| {
+                    this.machine
+                        .move_with_reloc(reloc_target, &mut this.relocations);
+                    this.machine.get_grp_for_call()
+                })?;
 
-                // Imported functions are called through trampolines placed as custom sections.
-                let reloc_target = if function_index < self.module.num_imported_functions {
-                    RelocationTarget::CustomSection(SectionIndex::new(function_index))
-                } else {
-                    RelocationTarget::LocalFunc(LocalFunctionIndex::new(
-                        function_index - self.module.num_imported_functions,
-                    ))
-                };
-                self.machine
-                    .move_with_reloc(reloc_target, &mut self.relocations);
+                // Put vmctx as the first parameter.
+                let calling_convention = self.calling_convention;
+                self.machine.move_location(
+                    Size::S64,
+                    Location::GPR(self.machine.get_vmctx_reg()),
+                    self.machine.get_param_location(0, calling_convention),
+                );
 
-                self.emit_call_native(
-                    |this| {
-                        let offset = this
-                            .machine
-                            .mark_instruction_with_trap_code(TrapCode::StackOverflow);
-                        this.machine
-                            .emit_call_register(this.machine.get_grp_for_call());
-                        this.machine.mark_instruction_address_end(offset);
-                    },
-                    params.iter().copied(),
-                )?;
+                self.finalize_locals(calling_convention)?;
+                self.machine.emit_return_call(gpr_for_call);
 
                 self.release_locations_only_stack(&params);
-
-                if !return_types.is_empty() {
-                    let ret = self.acquire_locations(
-                        &[(
-                            return_types[0],
-                            MachineValue::WasmStack(self.value_stack.len()),
-                        )],
-                        false,
-                    )[0];
-                    self.value_stack.push(ret);
-                    if return_types[0].is_float() {
-                        self.machine.move_location(
-                            Size::S64,
-                            Location::SIMD(self.machine.get_simd_for_ret()),
-                            ret,
-                        );
-                        self.fp_stack
-                            .push(FloatValue::new(self.value_stack.len() - 1));
-                    } else {
-                        self.machine.move_location(
-                            Size::S64,
-                            Location::GPR(self.machine.get_gpr_for_ret()),
-                            ret,
-                        );
-                    }
-                }
+                self.unreachable_depth = 1;
             }
-            Operator::CallIndirect { index, table_index } => {
-                // TODO: removed restriction on always being table idx 0;
-                // does any code depend on this?
-                let table_index = TableIndex::new(table_index as _);
-                let index = SignatureIndex::new(index as usize);
-                let sig = self.module.signatures.get(index).unwrap();
+            Operator::ReturnCallIndirect { index, table_index } => {
+                let sig = self
+                    .module
+                    .signatures
+                    .get(SignatureIndex::new(index as usize))
+                    .unwrap();
                 let param_types: SmallVec<[WpType; 8]> =
                     sig.params().iter().cloned().map(type_to_wp_type).collect();
-                let return_types: SmallVec<[WpType; 1]> =
-                    sig.results().iter().cloned().map(type_to_wp_type).collect();
 
-                let func_index = self.pop_value_released();
-
-                let params: SmallVec<[_; 8]> = self
-                    .value_stack
-                    .drain(self.value_stack.len() - param_types.len()..)
-                    .collect();
-                self.release_locations_only_regs(&params);
-
-                // Pop arguments off the FP stack and canonicalize them if needed.
-                //
-                // Canonicalization state will be lost across function calls, so early canonicalization
-                // is necessary here.
-                while let Some(fp) = self.fp_stack.last() {
-                    if fp.depth >= self.value_stack.len() {
-                        let index = fp.depth - self.value_stack.len();
-                        if self.machine.arch_supports_canonicalize_nan()
-                            && self.config.enable_nan_canonicalization
-                            && fp.canonicalization.is_some()
-                        {
-                            let size = fp.canonicalization.unwrap().to_size();
-                            self.machine
-                                .canonicalize_nan(size, params[index], params[index]);
-                        }
-                        self.fp_stack.pop().unwrap();
-                    } else {
-                        break;
-                    }
+                if !self.fits_return_call_params(param_types.len()) {
+                    self.emit_call_indirect(index, table_index)?;
+                    return self.emit_return();
                 }
 
-                let table_base = self.machine.acquire_temp_gpr().unwrap();
-                let table_count = self.machine.acquire_temp_gpr().unwrap();
-                let sigidx = self.machine.acquire_temp_gpr().unwrap();
-
-                if let Some(local_table_index) = self.module.local_table_index(table_index) {
-                    let (vmctx_offset_base, vmctx_offset_len) = (
-                        self.vmoffsets.vmctx_vmtable_definition(local_table_index),
-                        self.vmoffsets
-                            .vmctx_vmtable_definition_current_elements(local_table_index),
-                    );
-                    self.machine.move_location(
-                        Size::S64,
-                        Location::Memory(self.machine.get_vmctx_reg(), vmctx_offset_base as i32),
-                        Location::GPR(table_base),
-                    );
-                    self.machine.move_location(
-                        Size::S32,
-                        Location::Memory(self.machine.get_vmctx_reg(), vmctx_offset_len as i32),
-                        Location::GPR(table_count),
-                    );
-                } else {
-                    // Do an indirection.
-                    let import_offset = self.vmoffsets.vmctx_vmtable_import(table_index);
-                    self.machine.move_location(
-                        Size::S64,
-                        Location::Memory(self.machine.get_vmctx_reg(), import_offset as i32),
-                        Location::GPR(table_base),
-                    );
-
-                    // Load len.
-                    self.machine.move_location(
-                        Size::S32,
-                        Location::Memory(
-                            table_base,
-                            self.vmoffsets.vmtable_definition_current_elements() as _,
-                        ),
-                        Location::GPR(table_count),
-                    );
-
-                    // Load base.
-                    self.machine.move_location(
-                        Size::S64,
-                        Location::Memory(table_base, self.vmoffsets.vmtable_definition_base() as _),
-                        Location::GPR(table_base),
-                    );
-                }
-
-                self.machine
-                    .location_cmp(Size::S32, func_index, Location::GPR(table_count));
-                self.machine
-                    .jmp_on_belowequal(self.special_labels.table_access_oob);
-                self.machine
-                    .move_location(Size::S32, func_index, Location::GPR(table_count));
-                self.machine.emit_imul_imm32(
-                    Size::S64,
-                    self.vmoffsets.size_of_vm_funcref() as u32,
-                    table_count,
-                );
-                self.machine.location_add(
-                    Size::S64,
-                    Location::GPR(table_base),
-                    Location::GPR(table_count),
-                    false,
-                );
-
-                // deref the table to get a VMFuncRef
-                self.machine.move_location(
-                    Size::S64,
-                    Location::Memory(table_count, self.vmoffsets.vm_funcref_anyfunc_ptr() as i32),
-                    Location::GPR(table_count),
-                );
-                // Trap if the FuncRef is null
-                self.machine.location_cmp(
-                    Size::S64,
-                    Location::Imm32(0),
-                    Location::GPR(table_count),
-                );
-                self.machine
-                    .jmp_on_equal(self.special_labels.indirect_call_null);
-                self.machine.move_location(
-                    Size::S64,
-                    Location::Memory(
-                        self.machine.get_vmctx_reg(),
-                        self.vmoffsets.vmctx_vmshared_signature_id(index) as i32,
-                    ),
-                    Location::GPR(sigidx),
-                );
+                let table_index = TableIndex::new(table_index as _);
+                let index = SignatureIndex::new(index as usize);
 
-                // Trap if signature mismatches.
-                self.machine.location_cmp(
-                    Size::S32,
-                    Location::GPR(sigidx),
-                    Location::Memory(
-                        table_count,
-                        (self.vmoffsets.vmcaller_checked_anyfunc_type_index() as usize) as i32,
-                    ),
-                );
-                self.machine
-                    .jmp_on_different(self.special_labels.bad_signature);
+                let func_index = self.pop_value_released();
 
-                self.machine.release_gpr(sigidx);
-                self.machine.release_gpr(table_count);
-                self.machine.release_gpr(table_base);
+                let params = self.pop_call_params(param_types.len());
 
-                let gpr_for_call = self.machine.get_grp_for_call();
-                if table_count != gpr_for_call {
-                    self.machine.move_location(
-                        Size::S64,
-                        Location::GPR(table_count),
-                        Location::GPR(gpr_for_call),
-                    );
-                }
+                let gpr_for_call = self.emit_return_call_params(&params, |this| {
+                    this.emit_indirect_call_target(table_index, index, func_index)
+                })?;
 
                 self.release_locations_only_osr_state(params.len());
 
@@ -2754,66 +3181,21 @@ impl<'a, M: Machine> FuncGen<'a, M> {
                     self.vmoffsets.vmcaller_checked_anyfunc_vmctx() as usize;
                 let calling_convention = self.calling_convention;
 
-                self.emit_call_native(
-                    |this| {
-                        if this.machine.arch_requires_indirect_call_trampoline() {
-                            this.machine
-                                .arch_emit_indirect_call_with_trampoline(Location::Memory(
-                                    gpr_for_call,
-                                    vmcaller_checked_anyfunc_func_ptr as i32,
-                                ));
-                        } else {
-                            let offset = this
-                                .machine
-                                .mark_instruction_with_trap_code(TrapCode::StackOverflow);
-
-                            // We set the context pointer
-                            this.machine.move_location(
-                                Size::S64,
-                                Location::Memory(
-                                    gpr_for_call,
-                                    vmcaller_checked_anyfunc_vmctx as i32,
-                                ),
-                                this.machine.get_param_location(0, calling_convention),
-                            );
-
-                            this.machine.emit_call_location(Location::Memory(
-                                gpr_for_call,
-                                vmcaller_checked_anyfunc_func_ptr as i32,
-                            ));
-                            this.machine.mark_instruction_address_end(offset);
-                        }
-                    },
-                    params.iter().copied(),
-                )?;
+                // We set the context pointer
+                self.machine.move_location(
+                    Size::S64,
+                    Location::Memory(gpr_for_call, vmcaller_checked_anyfunc_vmctx as i32),
+                    self.machine.get_param_location(0, calling_convention),
+                );
 
-                self.release_locations_only_stack(&params);
+                self.finalize_locals(calling_convention)?;
+                self.machine.emit_return_call_indirect(Location::Memory(
+                    gpr_for_call,
+                    vmcaller_checked_anyfunc_func_ptr as i32,
+                ));
 
-                if !return_types.is_empty() {
-                    let ret = self.acquire_locations(
-                        &[(
-                            return_types[0],
-                            MachineValue::WasmStack(self.value_stack.len()),
-                        )],
-                        false,
-                    )[0];
-                    self.value_stack.push(ret);
-                    if return_types[0].is_float() {
-                        self.machine.move_location(
-                            Size::S64,
-                            Location::SIMD(self.machine.get_simd_for_ret()),
-                            ret,
-                        );
-                        self.fp_stack
-                            .push(FloatValue::new(self.value_stack.len() - 1));
-                    } else {
-                        self.machine.move_location(
-                            Size::S64,
-                            Location::GPR(self.machine.get_gpr_for_ret()),
-                            ret,
-                        );
-                    }
-                }
+                self.release_locations_only_stack(&params);
+                self.unreachable_depth = 1;
             }
             Operator::If { ty } => {
                 let label_end = self.machine.get_label();
@@ -3697,34 +4079,7 @@ impl<'a, M: Machine> FuncGen<'a, M> {
                 self.machine.mark_instruction_address_end(offset);
                 self.unreachable_depth = 1;
             }
-            Operator::Return => {
-                let frame = &self.control_stack[0];
-                if !frame.returns.is_empty() {
-                    if frame.returns.len() != 1 {
-                        return Err(CodegenError {
-                            message: "Return: incorrect frame.returns".to_string(),
-                        });
-                    }
-                    let first_return = frame.returns[0];
-                    let loc = *self.value_stack.last().unwrap();
-                    let canonicalize = if first_return.is_float() {
-                        let fp = self.fp_stack.peek1()?;
-                        self.machine.arch_supports_canonicalize_nan()
-                            && self.config.enable_nan_canonicalization
-                            && fp.canonicalization.is_some()
-                    } else {
-                        false
-                    };
-                    self.machine
-                        .emit_function_return_value(first_return, canonicalize, loc);
-                }
-                let frame = &self.control_stack[0];
-                let frame_depth = frame.value_stack_depth.clone();
-                let label = frame.label;
-                self.release_locations_keep_state(frame_depth);
-                self.machine.jmp_unconditionnal(label);
-                self.unreachable_depth = 1;
-            }
+            Operator::Return => self.emit_return()?,
             Operator::Br { relative_depth } => {
                 let frame =
                     &self.control_stack[self.control_stack.len() - 1 - (relative_depth as usize)];
@@ -3917,7 +4272,7 @@ impl<'a, M: Machine> FuncGen<'a, M> {
 
                 if self.control_stack.is_empty() {
                     self.machine.emit_label(frame.label);
-                    self.finalize_locals(self.calling_convention);
+                    self.finalize_locals(self.calling_convention)?;
                     self.machine.emit_function_epilog();
 
                     // Make a copy of the return value in XMM0, as required by the SysV CC.
@@ -3969,6 +4324,33 @@ impl<'a, M: Machine> FuncGen<'a, M> {
                     }
                 }
             }
+            Operator::MemoryAtomicWait32 { ref memarg } => {
+                self.emit_atomic_wait_notify(
+                    memarg.memory,
+                    memarg.offset,
+                    3,
+                    VMBuiltinFunctionIndex::get_memory_atomic_wait32_index(),
+                    VMBuiltinFunctionIndex::get_imported_memory_atomic_wait32_index(),
+                )?;
+            }
+            Operator::MemoryAtomicWait64 { ref memarg } => {
+                self.emit_atomic_wait_notify(
+                    memarg.memory,
+                    memarg.offset,
+                    3,
+                    VMBuiltinFunctionIndex::get_memory_atomic_wait64_index(),
+                    VMBuiltinFunctionIndex::get_imported_memory_atomic_wait64_index(),
+                )?;
+            }
+            Operator::MemoryAtomicNotify { ref memarg } => {
+                self.emit_atomic_wait_notify(
+                    memarg.memory,
+                    memarg.offset,
+                    2,
+                    VMBuiltinFunctionIndex::get_memory_atomic_notify_index(),
+                    VMBuiltinFunctionIndex::get_imported_memory_atomic_notify_index(),
+                )?;
+            }
             Operator::AtomicFence { flags: _ } => {
                 // Fence is a nop.
                 //
@@ -5788,10 +6170,8 @@ impl<'a, M: Machine> FuncGen<'a, M> {
         self.machine.finalize_function();
 
         let body_len = self.machine.assembler_get_offset().0;
-        let address_map =
-            get_function_address_map(self.machine.instructions_address_map(), data, body_len);
-        let traps = self.machine.collect_trap_information();
-        let body = self.machine.assembler_finalize();
+        let (body, traps, instructions_address_map) = self.machine.finalize_with_metadata();
+        let address_map = get_function_address_map(instructions_address_map, data, body_len);
 
         CompiledFunction {
             body: FunctionBody {
@@ -112,6 +112,7 @@ pub enum Size {
     S16,
     S32,
     S64,
+    S128,
 }
 
 /// A kind of suspend offset.
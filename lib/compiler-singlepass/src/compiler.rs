@@ -5,8 +5,10 @@
 use crate::codegen::FuncGen;
 use crate::config::Singlepass;
 use crate::machine::{
-    gen_import_call_trampoline, gen_std_dynamic_import_trampoline, gen_std_trampoline, CodegenError,
+    gen_import_call_trampoline, gen_std_dynamic_import_trampoline, gen_std_trampoline,
+    CodegenError, Machine,
 };
+use crate::machine_arm64::MachineARM64;
 use crate::machine_x64::MachineX86_64;
 use loupe::MemoryUsage;
 #[cfg(feature = "rayon")]
@@ -41,6 +43,55 @@ impl SinglepassCompiler {
     fn config(&self) -> &Singlepass {
         &self.config
     }
+
+    /// Compiles the function `index` for the architecture of `machine`.
+    fn compile_function<M: Machine>(
+        &self,
+        machine: M,
+        compile_info: &CompileModuleInfo,
+        vmoffsets: &VMOffsets,
+        index: LocalFunctionIndex,
+        input: &FunctionBodyData<'_>,
+        calling_convention: CallingConvention,
+    ) -> Result<CompiledFunction, CompileError> {
+        let middleware_chain = self
+            .config
+            .middlewares
+            .generate_function_middleware_chain(index);
+        let mut reader = MiddlewareBinaryReader::new_with_offset(input.data, input.module_offset);
+        reader.set_middleware_chain(middleware_chain);
+
+        // This local list excludes arguments.
+        let mut locals = vec![];
+        let num_locals = reader.read_local_count()?;
+        for _ in 0..num_locals {
+            let (count, ty) = reader.read_local_decl()?;
+            for _ in 0..count {
+                locals.push(ty);
+            }
+        }
+
+        let mut generator = FuncGen::new(
+            &compile_info.module,
+            &self.config,
+            vmoffsets,
+            &compile_info.memory_styles,
+            &compile_info.table_styles,
+            index,
+            &locals,
+            machine,
+            calling_convention,
+        )
+        .map_err(to_compile_error)?;
+
+        while generator.has_control_frames() {
+            generator.set_srcloc(reader.original_position() as u32);
+            let op = reader.read_operator()?;
+            generator.feed_operator(op).map_err(to_compile_error)?;
+        }
+
+        Ok(generator.finalize(input))
+    }
 }
 
 impl Compiler for SinglepassCompiler {
@@ -63,15 +114,25 @@ impl Compiler for SinglepassCompiler {
                 OperatingSystem::Windows.to_string(),
             ));
         }*/
-        if target.triple().architecture != Architecture::X86_64 {
-            return Err(CompileError::UnsupportedTarget(
-                target.triple().architecture.to_string(),
-            ));
-        }
-        if !target.cpu_features().contains(CpuFeature::AVX) {
-            return Err(CompileError::UnsupportedTarget(
-                "x86_64 without AVX".to_string(),
-            ));
+        match target.triple().architecture {
+            Architecture::X86_64 => {
+                if !target.cpu_features().contains(CpuFeature::AVX) {
+                    return Err(CompileError::UnsupportedTarget(
+                        "x86_64 without AVX".to_string(),
+                    ));
+                }
+                if self.config.enable_fpcr_canonicalization {
+                    return Err(CompileError::UnsupportedTarget(
+                        "FPCR canonicalization on x86_64".to_string(),
+                    ));
+                }
+            }
+            Architecture::Aarch64(_) => {}
+            _ => {
+                return Err(CompileError::UnsupportedTarget(
+                    target.triple().architecture.to_string(),
+                ));
+            }
         }
         if compile_info.features.multi_value {
             return Err(CompileError::UnsupportedFeature("multivalue".to_string()));
@@ -79,12 +140,10 @@ impl Compiler for SinglepassCompiler {
         let calling_convention = match target.triple().default_calling_convention() {
             Ok(CallingConvention::WindowsFastcall) => CallingConvention::WindowsFastcall,
             Ok(CallingConvention::SystemV) => CallingConvention::SystemV,
-            //Ok(CallingConvention::AppleAarch64) => AppleAarch64,
+            Ok(CallingConvention::AppleAarch64) => CallingConvention::AppleAarch64,
             _ => panic!("Unsupported Calling convention for Singlepass compiler"),
         };
 
-        let memory_styles = &compile_info.memory_styles;
-        let table_styles = &compile_info.table_styles;
         let vmoffsets = VMOffsets::new(8, &compile_info.module);
         let module = &compile_info.module;
         let import_trampolines: PrimaryMap<SectionIndex, _> = (0..module.num_imported_functions)
@@ -99,57 +158,33 @@ impl Compiler for SinglepassCompiler {
                     target,
                     calling_convention,
                 )
+                .map_err(to_compile_error)
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, CompileError>>()?
             .into_iter()
             .collect();
         let functions = function_body_inputs
             .iter()
             .collect::<Vec<(LocalFunctionIndex, &FunctionBodyData<'_>)>>()
             .into_par_iter_if_rayon()
-            .map(|(i, input)| {
-                let middleware_chain = self
-                    .config
-                    .middlewares
-                    .generate_function_middleware_chain(i);
-                let mut reader =
-                    MiddlewareBinaryReader::new_with_offset(input.data, input.module_offset);
-                reader.set_middleware_chain(middleware_chain);
-
-                // This local list excludes arguments.
-                let mut locals = vec![];
-                let num_locals = reader.read_local_count()?;
-                for _ in 0..num_locals {
-                    let (count, ty) = reader.read_local_decl()?;
-                    for _ in 0..count {
-                        locals.push(ty);
-                    }
-                }
-
-                let machine = match target.triple().architecture {
-                    Architecture::X86_64 => MachineX86_64::new(),
-                    _ => unimplemented!(),
-                };
-                let mut generator = FuncGen::new(
-                    module,
-                    &self.config,
+            .map(|(i, input)| match target.triple().architecture {
+                Architecture::X86_64 => self.compile_function(
+                    MachineX86_64::new(),
+                    compile_info,
                     &vmoffsets,
-                    &memory_styles,
-                    &table_styles,
                     i,
-                    &locals,
-                    machine,
+                    input,
                     calling_convention,
-                )
-                .map_err(to_compile_error)?;
-
-                while generator.has_control_frames() {
-                    generator.set_srcloc(reader.original_position() as u32);
-                    let op = reader.read_operator()?;
-                    generator.feed_operator(op).map_err(to_compile_error)?;
-                }
-
-                Ok(generator.finalize(&input))
+                ),
+                Architecture::Aarch64(_) => self.compile_function(
+                    MachineARM64::new(),
+                    compile_info,
+                    &vmoffsets,
+                    i,
+                    input,
+                    calling_convention,
+                ),
+                _ => unimplemented!(),
             })
             .collect::<Result<Vec<CompiledFunction>, CompileError>>()?
             .into_iter()
@@ -160,8 +195,10 @@ impl Compiler for SinglepassCompiler {
             .values()
             .collect::<Vec<_>>()
             .into_par_iter_if_rayon()
-            .map(|func_type| gen_std_trampoline(&func_type, target, calling_convention))
-            .collect::<Vec<_>>()
+            .map(|func_type| {
+                gen_std_trampoline(&func_type, target, calling_convention).map_err(to_compile_error)
+            })
+            .collect::<Result<Vec<_>, CompileError>>()?
             .into_iter()
             .collect::<PrimaryMap<_, _>>();
 
@@ -176,8 +213,9 @@ impl Compiler for SinglepassCompiler {
                     target,
                     calling_convention,
                 )
+                .map_err(to_compile_error)
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, CompileError>>()?
             .into_iter()
             .collect::<PrimaryMap<FunctionIndex, FunctionBody>>();
 
@@ -11,6 +11,8 @@ use wasmer_types::Features;
 pub struct Singlepass {
     pub(crate) enable_nan_canonicalization: bool,
     pub(crate) enable_stack_check: bool,
+    pub(crate) enable_fpcr_canonicalization: bool,
+    pub(crate) call_depth_limit: Option<u32>,
     /// The middleware chain.
     pub(crate) middlewares: Vec<Arc<dyn ModuleMiddleware>>,
 }
@@ -22,6 +24,8 @@ impl Singlepass {
         Self {
             enable_nan_canonicalization: true,
             enable_stack_check: false,
+            enable_fpcr_canonicalization: false,
+            call_depth_limit: None,
             middlewares: vec![],
         }
     }
@@ -46,6 +50,33 @@ impl Singlepass {
         self.enable_nan_canonicalization = enable;
         self
     }
+
+    /// Canonicalize the floating-point control register (FPCR).
+    ///
+    /// When enabled, each function saves the FPCR on entry, sets it to
+    /// round-to-nearest with flush-to-zero disabled and all floating-point
+    /// exceptions masked, and restores it on exit. When a trap unwinds out of
+    /// WebAssembly, the runtime restores the FPCR instead. This makes float
+    /// results independent of the FPCR state the host left behind.
+    ///
+    /// Only supported on aarch64.
+    pub fn canonicalize_fpcr(&mut self, enable: bool) -> &mut Self {
+        self.enable_fpcr_canonicalization = enable;
+        self
+    }
+
+    /// Traps with a stack overflow when a call would make more than `limit`
+    /// wasm calls active at once in an instance, independently of the host
+    /// stack size.
+    ///
+    /// The depth is counted per instance. A function entered from another
+    /// instance or from the host has the runtime save the counter of its
+    /// instance, and a trap puts every saved counter back to its value
+    /// before the call into wasm.
+    pub fn call_depth_limit(&mut self, limit: Option<u32>) -> &mut Self {
+        self.call_depth_limit = limit;
+        self
+    }
 }
 
 impl CompilerConfig for Singlepass {
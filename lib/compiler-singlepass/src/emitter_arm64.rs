@@ -0,0 +1,3407 @@
+pub use crate::arm64_decl::{GPR, NEON};
+use crate::common_decl::Size;
+use crate::location::Location as AbstractLocation;
+pub use crate::location::Multiplier;
+use crate::location::Reg;
+pub use crate::machine::{Label, Offset};
+use dynasm::dynasm;
+use dynasmrt::{
+    aarch64::{self, Aarch64Relocation},
+    AssemblyOffset, DynamicLabel, DynasmApi, DynasmLabelApi, VecAssembler,
+};
+
+type Assembler = VecAssembler<Aarch64Relocation>;
+
+/// Force `dynasm!` to use the correct arch (aarch64) when cross-compiling.
+/// `dynasm!` proc-macro tries to auto-detect it by default by looking at the
+/// `target_arch`, but it sees the `target_arch` of the proc-macro itself, which
+/// is always equal to host, even when cross-compiling.
+macro_rules! dynasm {
+    ($a:expr ; $($tt:tt)*) => {
+        dynasm::dynasm!(
+            $a
+            ; .arch aarch64
+            ; $($tt)*
+        )
+    };
+}
+
+pub type Location = AbstractLocation<GPR, NEON>;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[allow(dead_code)]
+pub enum Condition {
+    // meaning for cmp or sub
+    /// Equal
+    Eq,
+    /// Not equal
+    Ne,
+    /// Unsigned higher or same (or carry set)
+    Cs,
+    /// Unsigned lower (or carry clear)
+    Cc,
+    /// Negative. The mnemonic stands for "minus"
+    Mi,
+    /// Positive or zero. The mnemonic stands for "plus"
+    Pl,
+    /// Signed overflow. The mnemonic stands for "V set"
+    Vs,
+    /// No signed overflow. The mnemonic stands for "V clear"
+    Vc,
+    /// Unsigned higher
+    Hi,
+    /// Unsigned lower or same
+    Ls,
+    /// Signed greater than or equal
+    Ge,
+    /// Signed less than
+    Lt,
+    /// Signed greater than
+    Gt,
+    /// Signed less than or equal
+    Le,
+    /// Always executed
+    Al,
+}
+
+/// Returns true if `imm` can be used as the 12-bit unsigned immediate of an
+/// `ADD`/`SUB` instruction.
+pub fn is_add_sub_imm(imm: u64) -> bool {
+    imm < 0x1000
+}
+
+/// Returns true if `imm` can be used as the 12-bit immediate shifted left by 12
+/// of an `ADD`/`SUB` instruction.
+pub fn is_add_sub_imm_lsl12(imm: u64) -> bool {
+    imm & 0xfff == 0 && imm < 0x100_0000
+}
+
+/// Returns true if `imm` can be encoded as the bitmask immediate of a 32-bit
+/// `AND`/`ORR`/`EOR` instruction.
+pub fn is_logical_imm32(imm: u32) -> bool {
+    aarch64::encode_logical_immediate_32bit(imm).is_some()
+}
+
+/// Returns true if `imm` can be encoded as the bitmask immediate of a 64-bit
+/// `AND`/`ORR`/`EOR` instruction.
+pub fn is_logical_imm64(imm: u64) -> bool {
+    aarch64::encode_logical_immediate_64bit(imm).is_some()
+}
+
+pub trait EmitterARM64 {
+    fn get_label(&mut self) -> Label;
+    fn get_offset(&self) -> Offset;
+    fn get_jmp_instr_size(&self) -> u8;
+
+    fn finalize_function(&mut self) {}
+
+    fn emit_u64(&mut self, x: u64);
+    fn emit_bytes(&mut self, bytes: &[u8]);
+
+    fn emit_label(&mut self, label: Label);
+
+    fn emit_brk(&mut self);
+    fn emit_udf(&mut self);
+    fn emit_nop(&mut self);
+    fn emit_sev(&mut self);
+    fn emit_wfe(&mut self);
+    fn emit_dc_cvau(&mut self, addr: GPR);
+    fn emit_ic_ivau(&mut self, addr: GPR);
+    fn emit_dsb_ish(&mut self);
+    fn emit_dmb_ish(&mut self);
+    fn emit_isb(&mut self);
+
+    fn emit_str(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldr(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_stur(&mut self, sz: Size, reg: Location, addr: GPR, offset: i32);
+    fn emit_ldur(&mut self, sz: Size, reg: Location, addr: GPR, offset: i32);
+    /// Stores the full 128 bits of a SIMD register, with no alignment requirement
+    fn emit_stur_q(&mut self, reg: Location, addr: GPR, offset: i32);
+    /// Loads the full 128 bits of a SIMD register, with no alignment requirement
+    fn emit_ldur_q(&mut self, reg: Location, addr: GPR, offset: i32);
+    /// Store with pre-decrement of the address register (`str reg, [addr, #-offset]!`).
+    fn emit_strdb(&mut self, sz: Size, reg: Location, addr: GPR, offset: u32);
+    /// Load with post-increment of the address register (`ldr reg, [addr], #offset`).
+    fn emit_ldria(&mut self, sz: Size, reg: Location, addr: GPR, offset: u32);
+    /// Store with post-increment of the address register (`str reg, [addr], #offset`).
+    fn emit_stria(&mut self, sz: Size, reg: Location, addr: GPR, offset: u32);
+    /// Store pair with pre-decrement of the address register.
+    fn emit_stpbd(&mut self, sz: Size, reg1: Location, reg2: Location, addr: GPR, offset: u32);
+    /// Load pair with post-increment of the address register.
+    fn emit_ldpai(&mut self, sz: Size, reg1: Location, reg2: Location, addr: GPR, offset: u32);
+    /// Store pair with post-increment of the address register.
+    fn emit_stpia(&mut self, sz: Size, reg1: Location, reg2: Location, addr: GPR, offset: u32);
+
+    fn emit_ldrb(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldrh(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldrsb(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldrsh(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldrsw(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_strb(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_strh(&mut self, sz: Size, reg: Location, addr: Location);
+
+    fn emit_ldar(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldarb(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldarh(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_stlr(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_stlrb(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_stlrh(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldaxr(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldaxrb(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_ldaxrh(&mut self, sz: Size, reg: Location, addr: Location);
+    fn emit_stlxr(&mut self, sz: Size, status: Location, reg: Location, addr: Location);
+    fn emit_stlxrb(&mut self, sz: Size, status: Location, reg: Location, addr: Location);
+    fn emit_stlxrh(&mut self, sz: Size, status: Location, reg: Location, addr: Location);
+
+    fn emit_mov(&mut self, sz: Size, src: Location, dst: Location);
+
+    fn emit_movn(&mut self, sz: Size, reg: Location, val: u32);
+    fn emit_movz(&mut self, reg: Location, val: u32);
+    fn emit_movk(&mut self, reg: Location, val: u32, shift: u32);
+
+    fn emit_mov_imm(&mut self, dst: Location, val: u64);
+
+    fn emit_add(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_sub(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_mul(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_umulh(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_smulh(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_adds(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_subs(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+
+    fn emit_add2(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_sub2(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_mul2(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_and2(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_orr2(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_eor2(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_add_lsl(&mut self, sz: Size, src1: Location, src2: Location, lsl: u32, dst: Location);
+    fn emit_neg(&mut self, sz: Size, src: Location, dst: Location);
+
+    /// Compares `dst` with `src`, setting the flags as `dst - src` would.
+    fn emit_cmp(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_tst(&mut self, sz: Size, src: Location, dst: Location);
+
+    fn emit_udiv(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_sdiv(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    /// msub : c - a*b -> dst
+    fn emit_msub(&mut self, sz: Size, a: Location, b: Location, c: Location, dst: Location);
+    fn emit_lsl(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_lsr(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_asr(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_ror(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_and(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_ands(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_orr(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_eor(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_bic(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+
+    fn emit_sxtb(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_sxth(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_sxtw(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_uxtb(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_uxth(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_ubfx(&mut self, sz: Size, src: Location, lsb: u32, width: u32, dst: Location);
+    fn emit_clz(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_rbit(&mut self, sz: Size, src: Location, dst: Location);
+
+    fn emit_cset(&mut self, sz: Size, reg: Location, cond: Condition);
+
+    fn emit_b_label(&mut self, label: Label);
+    fn emit_bcond_label(&mut self, condition: Condition, label: Label);
+    fn emit_cbz_label(&mut self, sz: Size, reg: Location, label: Label);
+    fn emit_cbnz_label(&mut self, sz: Size, reg: Location, label: Label);
+    fn emit_b_register(&mut self, reg: GPR);
+    fn emit_call_label(&mut self, label: Label);
+    fn emit_call_register(&mut self, reg: GPR);
+    fn emit_ret(&mut self);
+    fn emit_load_label(&mut self, reg: GPR, label: Label);
+    fn emit_adrp(&mut self, reg: GPR);
+
+    fn emit_read_fpcr(&mut self, reg: GPR);
+    fn emit_write_fpcr(&mut self, reg: GPR);
+    fn emit_read_ctr(&mut self, reg: GPR);
+
+    fn emit_fneg(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_fabs(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_frintp(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_frintm(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_frintz(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_frintn(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_fsqrt(&mut self, sz: Size, src: Location, dst: Location);
+    fn emit_fadd(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_fsub(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_fmul(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_fdiv(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_fmin(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_fmax(&mut self, sz: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_fcmp(&mut self, sz: Size, src1: Location, src2: Location);
+    fn emit_fcsel(
+        &mut self,
+        sz: Size,
+        src1: Location,
+        src2: Location,
+        dst: Location,
+        cond: Condition,
+    );
+
+    /// Converts between single and double precision, rounding as set in FPCR
+    fn emit_fcvt(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location);
+    fn emit_fcvtzs(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location);
+    fn emit_fcvtzu(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location);
+    fn emit_scvtf(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location);
+    fn emit_ucvtf(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location);
+
+    fn emit_vmvn(&mut self, src: Location, dst: Location);
+    fn emit_vand(&mut self, src1: Location, src2: Location, dst: Location);
+    fn emit_vorr(&mut self, src1: Location, src2: Location, dst: Location);
+    fn emit_veor(&mut self, src1: Location, src2: Location, dst: Location);
+    fn emit_vbic(&mut self, src1: Location, src2: Location, dst: Location);
+    fn emit_vmul(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_smull(&mut self, lane: Size, high: bool, src1: Location, src2: Location, dst: Location);
+    fn emit_umull(&mut self, lane: Size, high: bool, src1: Location, src2: Location, dst: Location);
+    fn emit_urhadd(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    /// Pairwise add: adjacent lanes of `src1` then `src2` are summed into `dst`
+    fn emit_addp(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    /// Broadcasts the low bits of the GPR `src` to every lane of `dst`
+    fn emit_dup(&mut self, lane: Size, src: Location, dst: Location);
+    /// Broadcasts lane 0 of the NEON register `src` to every lane of `dst`
+    fn emit_dup_element(&mut self, lane: Size, src: Location, dst: Location);
+    /// Moves the 8-bit immediate `imm`, shifted left by `lsl`, to every lane of `dst`.
+    /// For 64-bit lanes each bit of `imm` selects a whole byte instead.
+    fn emit_movi(&mut self, lane: Size, imm: u8, lsl: u32, dst: Location);
+    fn emit_vfneg(&mut self, lane: Size, src: Location, dst: Location);
+    /// Bitwise select: bits set in `dst` take `src1`, the others `src2`
+    fn emit_bsl(&mut self, dst: Location, src1: Location, src2: Location);
+    fn emit_sqrdmulh(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    /// Pairwise unsigned maximum of adjacent lanes of `src1` then `src2`
+    fn emit_umaxp(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    /// Per byte population count
+    fn emit_cnt(&mut self, src: Location, dst: Location);
+    /// Sums the 16 byte lanes of `src` into the low byte of `dst`, zeroing the rest
+    fn emit_addv(&mut self, src: Location, dst: Location);
+    fn emit_vfrintp(&mut self, lane: Size, src: Location, dst: Location);
+    fn emit_vfrintm(&mut self, lane: Size, src: Location, dst: Location);
+    fn emit_vfrintz(&mut self, lane: Size, src: Location, dst: Location);
+    fn emit_vfrintn(&mut self, lane: Size, src: Location, dst: Location);
+    /// Adds adjacent lanes of `src` into lanes of twice the width, sign extending them
+    fn emit_saddlp(&mut self, lane: Size, src: Location, dst: Location);
+    /// Adds adjacent lanes of `src` into lanes of twice the width, zero extending them
+    fn emit_uaddlp(&mut self, lane: Size, src: Location, dst: Location);
+    /// Loads a single lane of `dst` from `[addr]`, the other lanes are left untouched
+    fn emit_ld1_lane(&mut self, lane: Size, dst: Location, index: u8, addr: Location);
+    /// Stores a single lane of `src` to `[addr]`
+    fn emit_st1_lane(&mut self, lane: Size, src: Location, index: u8, addr: Location);
+    /// Lane-wise float compares, setting each lane of `dst` to all ones when true.
+    /// Any comparison with a NaN is false.
+    fn emit_vfcmeq(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vfcmgt(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vfcmge(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    /// Lane-wise integer compares, setting each lane of `dst` to all ones when true.
+    /// CMHI and CMHS are the unsigned forms of CMGT and CMGE.
+    fn emit_vcmeq(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vcmgt(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vcmge(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vcmhi(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vcmhs(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    /// Lane-wise integer minimum and maximum, not available for 64-bit lanes
+    fn emit_vsmin(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vumin(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vsmax(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    fn emit_vumax(&mut self, lane: Size, src1: Location, src2: Location, dst: Location);
+    /// Narrows the signed lanes of `src` into the low half of `dst` with unsigned saturation,
+    /// zeroing the high half. `lane` is the size of the source lanes
+    fn emit_sqxtun(&mut self, lane: Size, src: Location, dst: Location);
+    /// Narrows the signed lanes of `src` into the high half of `dst` with unsigned saturation
+    fn emit_sqxtun2(&mut self, lane: Size, src: Location, dst: Location);
+    /// Converts the 2 low f32 lanes of `src` to f64 lanes
+    fn emit_fcvtl(&mut self, src: Location, dst: Location);
+    /// Converts the f64 lanes of `src` to the 2 low f32 lanes of `dst`, zeroing the others
+    fn emit_fcvtn(&mut self, src: Location, dst: Location);
+    /// Sign extends the low half lanes of `src` to twice their width (SSHLL #0)
+    fn emit_sshll(&mut self, lane: Size, src: Location, dst: Location);
+    /// Zero extends the low half lanes of `src` to twice their width (USHLL #0)
+    fn emit_ushll(&mut self, lane: Size, src: Location, dst: Location);
+
+    fn arch_supports_canonicalize_nan(&self) -> bool {
+        true
+    }
+
+    // BLR reaches the whole address space, unlike BL, so indirect calls need no trampoline
+    fn arch_requires_indirect_call_trampoline(&self) -> bool {
+        false
+    }
+
+    /// Calls the full 64-bit address in `loc`, loading it into X26 first if it is in memory
+    fn arch_emit_indirect_call_with_trampoline(&mut self, loc: Location) {
+        match loc {
+            Location::GPR(reg) => self.emit_call_register(reg),
+            Location::Memory(_, _) => {
+                self.emit_ldr(Size::S64, Location::GPR(GPR::X26), loc);
+                self.emit_call_register(GPR::X26);
+            }
+            _ => panic!("singlepass can't emit indirect call {:?}", loc),
+        }
+    }
+}
+
+impl EmitterARM64 for Assembler {
+    fn get_label(&mut self) -> DynamicLabel {
+        self.new_dynamic_label()
+    }
+
+    fn get_offset(&self) -> AssemblyOffset {
+        self.offset()
+    }
+
+    fn get_jmp_instr_size(&self) -> u8 {
+        4 // relative jump, not full 32bits capable
+    }
+
+    fn emit_u64(&mut self, x: u64) {
+        self.push_u64(x);
+    }
+
+    fn emit_bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.push(b);
+        }
+    }
+
+    fn emit_label(&mut self, label: Label) {
+        dynasm!(self ; => label);
+    }
+
+    fn emit_brk(&mut self) {
+        dynasm!(self ; brk 0);
+    }
+    fn emit_udf(&mut self) {
+        dynasm!(self ; udf 0);
+    }
+    fn emit_nop(&mut self) {
+        dynasm!(self ; nop);
+    }
+    fn emit_sev(&mut self) {
+        dynasm!(self ; sev);
+    }
+    fn emit_wfe(&mut self) {
+        dynasm!(self ; wfe);
+    }
+
+    fn emit_dc_cvau(&mut self, addr: GPR) {
+        let addr = addr.into_index() as u32;
+        dynasm!(self ; dc cvau, X(addr));
+    }
+    fn emit_ic_ivau(&mut self, addr: GPR) {
+        let addr = addr.into_index() as u32;
+        dynasm!(self ; ic ivau, X(addr));
+    }
+    fn emit_dsb_ish(&mut self) {
+        dynasm!(self ; dsb ish);
+    }
+    fn emit_dmb_ish(&mut self) {
+        dynasm!(self ; dmb ish);
+    }
+    fn emit_isb(&mut self) {
+        dynasm!(self ; isb);
+    }
+    fn emit_str(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S64, Location::GPR(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x7) == 0 && (0..0x8000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; str X(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; stur X(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S32, Location::GPR(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x3) == 0 && (0..0x4000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; str W(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; stur W(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S64, Location::SIMD(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x7) == 0 && (0..0x8000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; str D(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; stur D(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S128, Location::SIMD(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0xf) == 0 && (0..0x10000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; str Q(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; stur Q(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S32, Location::SIMD(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x3) == 0 && (0..0x4000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; str S(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; stur S(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S64, Location::GPR(reg), Location::Memory2(addr, r2, mult, offs)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let r2 = r2.into_index() as u32;
+                assert!(offs == 0);
+                match mult {
+                    Multiplier::Zero => dynasm!(self ; str X(reg), [X(addr)]),
+                    Multiplier::One => dynasm!(self ; str X(reg), [X(addr), X(r2)]),
+                    Multiplier::Height => dynasm!(self ; str X(reg), [X(addr), X(r2), LSL 3]),
+                    _ => panic!("singlepass can't emit STR {:?} {:?}", reg, mult),
+                };
+            }
+            (Size::S32, Location::GPR(reg), Location::Memory2(addr, r2, mult, offs)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let r2 = r2.into_index() as u32;
+                assert!(offs == 0);
+                match mult {
+                    Multiplier::Zero => dynasm!(self ; str W(reg), [X(addr)]),
+                    Multiplier::One => dynasm!(self ; str W(reg), [X(addr), X(r2)]),
+                    Multiplier::Four => dynasm!(self ; str W(reg), [X(addr), X(r2), LSL 2]),
+                    _ => panic!("singlepass can't emit STR {:?} {:?}", reg, mult),
+                };
+            }
+            _ => panic!("singlepass can't emit STR {:?}, {:?}, {:?}", sz, reg, addr),
+        }
+    }
+    fn emit_ldr(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S64, Location::GPR(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x7) == 0 && (0..0x8000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; ldr X(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; ldur X(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S32, Location::GPR(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x3) == 0 && (0..0x4000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; ldr W(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; ldur W(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S64, Location::SIMD(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x7) == 0 && (0..0x8000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; ldr D(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; ldur D(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S128, Location::SIMD(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0xf) == 0 && (0..0x10000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; ldr Q(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; ldur Q(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S32, Location::SIMD(reg), Location::Memory(addr, disp)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (disp & 0x3) == 0 && (0..0x4000).contains(&disp) {
+                    let disp = disp as u32;
+                    dynasm!(self ; ldr S(reg), [X(addr), disp]);
+                } else {
+                    assert!((-256..256).contains(&disp));
+                    dynasm!(self ; ldur S(reg), [X(addr), disp]);
+                }
+            }
+            (Size::S64, Location::GPR(reg), Location::Memory2(addr, r2, mult, offs)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let r2 = r2.into_index() as u32;
+                assert!(offs == 0);
+                match mult {
+                    Multiplier::Zero => dynasm!(self ; ldr X(reg), [X(addr)]),
+                    Multiplier::One => dynasm!(self ; ldr X(reg), [X(addr), X(r2)]),
+                    Multiplier::Height => dynasm!(self ; ldr X(reg), [X(addr), X(r2), LSL 3]),
+                    _ => panic!("singlepass can't emit LDR {:?} {:?}", reg, mult),
+                };
+            }
+            (Size::S32, Location::GPR(reg), Location::Memory2(addr, r2, mult, offs)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let r2 = r2.into_index() as u32;
+                assert!(offs == 0);
+                match mult {
+                    Multiplier::Zero => dynasm!(self ; ldr W(reg), [X(addr)]),
+                    Multiplier::One => dynasm!(self ; ldr W(reg), [X(addr), X(r2)]),
+                    Multiplier::Four => dynasm!(self ; ldr W(reg), [X(addr), X(r2), LSL 2]),
+                    _ => panic!("singlepass can't emit LDR {:?} {:?}", reg, mult),
+                };
+            }
+            _ => panic!("singlepass can't emit LDR {:?}, {:?}, {:?}", sz, reg, addr),
+        }
+    }
+    fn emit_stur(&mut self, sz: Size, reg: Location, addr: GPR, offset: i32) {
+        assert!((-256..256).contains(&offset));
+        let addr = addr.into_index() as u32;
+        match (sz, reg) {
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; stur X(reg), [X(addr), offset]);
+            }
+            (Size::S32, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; stur W(reg), [X(addr), offset]);
+            }
+            (Size::S64, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; stur D(reg), [X(addr), offset]);
+            }
+            (Size::S32, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; stur S(reg), [X(addr), offset]);
+            }
+            _ => panic!(
+                "singlepass can't emit STUR {:?}, {:?}, {:?}, {:?}",
+                sz, reg, addr, offset
+            ),
+        }
+    }
+    fn emit_ldur(&mut self, sz: Size, reg: Location, addr: GPR, offset: i32) {
+        assert!((-256..256).contains(&offset));
+        let addr = addr.into_index() as u32;
+        match (sz, reg) {
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldur X(reg), [X(addr), offset]);
+            }
+            (Size::S32, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldur W(reg), [X(addr), offset]);
+            }
+            (Size::S64, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldur D(reg), [X(addr), offset]);
+            }
+            (Size::S32, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldur S(reg), [X(addr), offset]);
+            }
+            _ => panic!(
+                "singlepass can't emit LDUR {:?}, {:?}, {:?}, {:?}",
+                sz, reg, addr, offset
+            ),
+        }
+    }
+
+    fn emit_stur_q(&mut self, reg: Location, addr: GPR, offset: i32) {
+        assert!((-256..256).contains(&offset));
+        let addr = addr.into_index() as u32;
+        match reg {
+            Location::SIMD(reg) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; stur Q(reg), [X(addr), offset]);
+            }
+            _ => panic!(
+                "singlepass can't emit STUR {:?}, {:?}, {:?}",
+                reg, addr, offset
+            ),
+        }
+    }
+    fn emit_ldur_q(&mut self, reg: Location, addr: GPR, offset: i32) {
+        assert!((-256..256).contains(&offset));
+        let addr = addr.into_index() as u32;
+        match reg {
+            Location::SIMD(reg) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldur Q(reg), [X(addr), offset]);
+            }
+            _ => panic!(
+                "singlepass can't emit LDUR {:?}, {:?}, {:?}",
+                reg, addr, offset
+            ),
+        }
+    }
+    fn emit_strdb(&mut self, sz: Size, reg: Location, addr: GPR, offset: u32) {
+        assert!(offset < 256);
+        let addr = addr.into_index() as u32;
+        match (sz, reg) {
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; str X(reg), [X(addr), -(offset as i32)]!);
+            }
+            (Size::S64, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; str D(reg), [X(addr), -(offset as i32)]!);
+            }
+            (Size::S128, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; str Q(reg), [X(addr), -(offset as i32)]!);
+            }
+            _ => panic!(
+                "singlepass can't emit STRDB {:?}, {:?}, {:?}, {:?}",
+                sz, reg, addr, offset
+            ),
+        }
+    }
+    fn emit_ldria(&mut self, sz: Size, reg: Location, addr: GPR, offset: u32) {
+        assert!(offset < 256);
+        let addr = addr.into_index() as u32;
+        match (sz, reg) {
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldr X(reg), [X(addr)], offset);
+            }
+            (Size::S64, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldr D(reg), [X(addr)], offset);
+            }
+            (Size::S128, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; ldr Q(reg), [X(addr)], offset);
+            }
+            _ => panic!(
+                "singlepass can't emit LDRIA {:?}, {:?}, {:?}, {:?}",
+                sz, reg, addr, offset
+            ),
+        }
+    }
+    fn emit_stria(&mut self, sz: Size, reg: Location, addr: GPR, offset: u32) {
+        assert!(offset < 256);
+        let addr = addr.into_index() as u32;
+        match (sz, reg) {
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; str X(reg), [X(addr)], offset);
+            }
+            (Size::S64, Location::SIMD(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; str D(reg), [X(addr)], offset);
+            }
+            _ => panic!(
+                "singlepass can't emit STRIA {:?}, {:?}, {:?}, {:?}",
+                sz, reg, addr, offset
+            ),
+        }
+    }
+
+    fn emit_stpbd(&mut self, sz: Size, reg1: Location, reg2: Location, addr: GPR, offset: u32) {
+        assert!(offset < 512 && (offset & 0x7) == 0);
+        let addr = addr.into_index() as u32;
+        match (sz, reg1, reg2) {
+            (Size::S64, Location::GPR(reg1), Location::GPR(reg2)) => {
+                let reg1 = reg1.into_index() as u32;
+                let reg2 = reg2.into_index() as u32;
+                dynasm!(self ; stp X(reg1), X(reg2), [X(addr), -(offset as i32)]!);
+            }
+            (Size::S64, Location::SIMD(reg1), Location::SIMD(reg2)) => {
+                let reg1 = reg1.into_index() as u32;
+                let reg2 = reg2.into_index() as u32;
+                dynasm!(self ; stp D(reg1), D(reg2), [X(addr), -(offset as i32)]!);
+            }
+            _ => panic!(
+                "singlepass can't emit STPBD {:?}, {:?}, {:?}, {:?}, {:?}",
+                sz, reg1, reg2, addr, offset
+            ),
+        }
+    }
+    fn emit_ldpai(&mut self, sz: Size, reg1: Location, reg2: Location, addr: GPR, offset: u32) {
+        assert!(offset < 512 && (offset & 0x7) == 0);
+        let addr = addr.into_index() as u32;
+        match (sz, reg1, reg2) {
+            (Size::S64, Location::GPR(reg1), Location::GPR(reg2)) => {
+                let reg1 = reg1.into_index() as u32;
+                let reg2 = reg2.into_index() as u32;
+                dynasm!(self ; ldp X(reg1), X(reg2), [X(addr)], offset);
+            }
+            (Size::S64, Location::SIMD(reg1), Location::SIMD(reg2)) => {
+                let reg1 = reg1.into_index() as u32;
+                let reg2 = reg2.into_index() as u32;
+                dynasm!(self ; ldp D(reg1), D(reg2), [X(addr)], offset);
+            }
+            _ => panic!(
+                "singlepass can't emit LDPAI {:?}, {:?}, {:?}, {:?}, {:?}",
+                sz, reg1, reg2, addr, offset
+            ),
+        }
+    }
+    fn emit_stpia(&mut self, sz: Size, reg1: Location, reg2: Location, addr: GPR, offset: u32) {
+        assert!(offset < 512 && (offset & 0x7) == 0);
+        let addr = addr.into_index() as u32;
+        match (sz, reg1, reg2) {
+            (Size::S64, Location::GPR(reg1), Location::GPR(reg2)) => {
+                let reg1 = reg1.into_index() as u32;
+                let reg2 = reg2.into_index() as u32;
+                dynasm!(self ; stp X(reg1), X(reg2), [X(addr)], offset);
+            }
+            _ => panic!(
+                "singlepass can't emit STPIA {:?}, {:?}, {:?}, {:?}, {:?}",
+                sz, reg1, reg2, addr, offset
+            ),
+        }
+    }
+
+    fn emit_ldrb(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (0..0x1000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; ldrb W(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; ldurb W(reg), [X(addr), offset]);
+                }
+            }
+            (Location::GPR(reg), Location::Memory2(addr, r2, mult, offs)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let r2 = r2.into_index() as u32;
+                assert!(offs == 0);
+                match mult {
+                    Multiplier::Zero => dynasm!(self ; ldrb W(reg), [X(addr)]),
+                    Multiplier::One => dynasm!(self ; ldrb W(reg), [X(addr), X(r2)]),
+                    _ => panic!("singlepass can't emit LDRB {:?} {:?}", reg, mult),
+                };
+            }
+            _ => panic!("singlepass can't emit LDRB {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_ldrh(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (offset & 1) == 0 && (0..0x2000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; ldrh W(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; ldurh W(reg), [X(addr), offset]);
+                }
+            }
+            (Location::GPR(reg), Location::Memory2(addr, r2, mult, offs)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let r2 = r2.into_index() as u32;
+                assert!(offs == 0);
+                match mult {
+                    Multiplier::Zero => dynasm!(self ; ldrh W(reg), [X(addr)]),
+                    Multiplier::One => dynasm!(self ; ldrh W(reg), [X(addr), X(r2)]),
+                    Multiplier::Two => dynasm!(self ; ldrh W(reg), [X(addr), X(r2), LSL 1]),
+                    _ => panic!("singlepass can't emit LDRH {:?} {:?}", reg, mult),
+                };
+            }
+            _ => panic!("singlepass can't emit LDRH {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_ldrsb(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S64, Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (0..0x1000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; ldrsb X(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; ldursb X(reg), [X(addr), offset]);
+                }
+            }
+            (Size::S32, Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (0..0x1000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; ldrsb W(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; ldursb W(reg), [X(addr), offset]);
+                }
+            }
+            _ => panic!(
+                "singlepass can't emit LDRSB {:?}, {:?}, {:?}",
+                sz, reg, addr
+            ),
+        }
+    }
+    fn emit_ldrsh(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S64, Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (offset & 1) == 0 && (0..0x2000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; ldrsh X(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; ldursh X(reg), [X(addr), offset]);
+                }
+            }
+            (Size::S32, Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (offset & 1) == 0 && (0..0x2000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; ldrsh W(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; ldursh W(reg), [X(addr), offset]);
+                }
+            }
+            _ => panic!(
+                "singlepass can't emit LDRSH {:?}, {:?}, {:?}",
+                sz, reg, addr
+            ),
+        }
+    }
+    fn emit_ldrsw(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S64, Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (offset & 3) == 0 && (0..0x4000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; ldrsw X(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; ldursw X(reg), [X(addr), offset]);
+                }
+            }
+            _ => panic!(
+                "singlepass can't emit LDRSW {:?}, {:?}, {:?}",
+                sz, reg, addr
+            ),
+        }
+    }
+    fn emit_strb(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (0..0x1000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; strb W(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; sturb W(reg), [X(addr), offset]);
+                }
+            }
+            _ => panic!("singlepass can't emit STRB {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_strh(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::Memory(addr, offset)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                if (offset & 1) == 0 && (0..0x2000).contains(&offset) {
+                    let offset = offset as u32;
+                    dynasm!(self ; strh W(reg), [X(addr), offset]);
+                } else {
+                    assert!((-256..256).contains(&offset));
+                    dynasm!(self ; sturh W(reg), [X(addr), offset]);
+                }
+            }
+            _ => panic!("singlepass can't emit STRH {:?}, {:?}", reg, addr),
+        }
+    }
+
+    fn emit_ldar(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S32, Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldar W(reg), [X(addr)]);
+            }
+            (Size::S64, Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldar X(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit LDAR {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_ldarb(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldarb W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit LDARB {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_ldarh(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldarh W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit LDARH {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_stlr(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S32, Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; stlr W(reg), [X(addr)]);
+            }
+            (Size::S64, Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; stlr X(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit STLR {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_stlrb(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; stlrb W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit STLRB {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_stlrh(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; stlrh W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit STLRH {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_ldaxr(&mut self, sz: Size, reg: Location, addr: Location) {
+        match (sz, reg, addr) {
+            (Size::S32, Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldaxr W(reg), [X(addr)]);
+            }
+            (Size::S64, Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldaxr X(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit LDAXR {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_ldaxrb(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldaxrb W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit LDAXRB {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_ldaxrh(&mut self, _sz: Size, reg: Location, addr: Location) {
+        match (reg, addr) {
+            (Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                dynasm!(self ; ldaxrh W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit LDAXRH {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_stlxr(&mut self, sz: Size, status: Location, reg: Location, addr: Location) {
+        match (sz, status, reg, addr) {
+            (Size::S32, Location::GPR(status), Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let status = status.into_index() as u32;
+                dynasm!(self ; stlxr W(status), W(reg), [X(addr)]);
+            }
+            (Size::S64, Location::GPR(status), Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let status = status.into_index() as u32;
+                dynasm!(self ; stlxr W(status), X(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit STLXR {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_stlxrb(&mut self, _sz: Size, status: Location, reg: Location, addr: Location) {
+        match (status, reg, addr) {
+            (Location::GPR(status), Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let status = status.into_index() as u32;
+                dynasm!(self ; stlxrb W(status), W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit STLXRB {:?}, {:?}", reg, addr),
+        }
+    }
+    fn emit_stlxrh(&mut self, _sz: Size, status: Location, reg: Location, addr: Location) {
+        match (status, reg, addr) {
+            (Location::GPR(status), Location::GPR(reg), Location::GPR(addr)) => {
+                let reg = reg.into_index() as u32;
+                let addr = addr.into_index() as u32;
+                let status = status.into_index() as u32;
+                dynasm!(self ; stlxrh W(status), W(reg), [X(addr)]);
+            }
+            _ => panic!("singlepass can't emit STLXRH {:?}, {:?}", reg, addr),
+        }
+    }
+
+    fn emit_mov(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                if src == 31 || dst == 31 {
+                    // register 31 is SP here, `mov` would otherwise encode XZR
+                    dynasm!(self ; add XSP(dst), XSP(src), 0);
+                } else {
+                    dynasm!(self ; mov X(dst), X(src));
+                }
+            }
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; mov W(dst), W(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; mov V(dst).B16, V(src).B16);
+            }
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmov S(dst), S(src));
+            }
+            (Size::S64, Location::GPR(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmov D(dst), X(src));
+            }
+            (Size::S32, Location::GPR(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmov S(dst), W(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmov X(dst), D(src));
+            }
+            (Size::S32, Location::SIMD(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmov W(dst), S(src));
+            }
+            (Size::S64, Location::Imm32(val), Location::GPR(dst))
+            | (Size::S32, Location::Imm32(val), Location::GPR(dst))
+                if val < 0x10000 =>
+            {
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; movz X(dst), val);
+            }
+            (Size::S64, Location::Imm8(val), Location::GPR(dst))
+            | (Size::S32, Location::Imm8(val), Location::GPR(dst)) => {
+                let dst = dst.into_index() as u32;
+                let val = val as u32;
+                dynasm!(self ; movz X(dst), val);
+            }
+            _ => panic!("singlepass can't emit MOV {:?}, {:?}, {:?}", sz, src, dst),
+        }
+    }
+
+    fn emit_movn(&mut self, sz: Size, reg: Location, val: u32) {
+        match (sz, reg) {
+            (Size::S32, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; movn W(reg), val);
+            }
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; movn X(reg), val);
+            }
+            _ => panic!("singlepass can't emit MOVN"),
+        }
+    }
+    fn emit_movz(&mut self, reg: Location, val: u32) {
+        match reg {
+            Location::GPR(reg) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; movz W(reg), val);
+            }
+            _ => panic!("singlepass can't emit MOVZ"),
+        }
+    }
+    fn emit_movk(&mut self, reg: Location, val: u32, shift: u32) {
+        match reg {
+            Location::GPR(reg) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; movk X(reg), val, LSL shift);
+            }
+            _ => panic!("singlepass can't emit MOVK"),
+        }
+    }
+
+    fn emit_mov_imm(&mut self, dst: Location, val: u64) {
+        let reg = match dst {
+            Location::GPR(reg) => reg.into_index() as u32,
+            _ => panic!("singlepass can't emit MOV_IMM {:?}", dst),
+        };
+        let chunks: Vec<u32> = (0..4)
+            .map(|i| ((val >> (i * 16)) & 0xffff) as u32)
+            .collect();
+        let zeros = chunks.iter().filter(|&&c| c == 0).count();
+        let ones = chunks.iter().filter(|&&c| c == 0xffff).count();
+        if zeros.max(ones) < 3 {
+            // no single MOVZ or MOVN builds it, but one of these may
+            if val >> 32 == 0 && chunks[1] == 0xffff {
+                // the 32-bit MOVN clears the upper half
+                let imm = !chunks[0] & 0xffff;
+                dynasm!(self ; movn W(reg), imm);
+                return;
+            }
+            if is_logical_imm64(val) {
+                dynasm!(self ; orr X(reg), xzr, val);
+                return;
+            }
+            if val >> 32 == 0 && is_logical_imm32(val as u32) {
+                let imm = val as u32;
+                dynasm!(self ; orr W(reg), wzr, imm);
+                return;
+            }
+        }
+        // MOVZ or MOVN for the first chunk, whichever leaves the fewest chunks to fix with MOVK
+        let fill = if ones > zeros { 0xffff } else { 0 };
+        let first = chunks.iter().position(|&c| c != fill).unwrap_or(0);
+        let shift = first as u32 * 16;
+        if fill == 0xffff {
+            let imm = !chunks[first] & 0xffff;
+            dynasm!(self ; movn X(reg), imm, LSL shift);
+        } else if first == 0 {
+            self.emit_movz(dst, chunks[0]);
+        } else {
+            let imm = chunks[first];
+            dynasm!(self ; movz X(reg), imm, LSL shift);
+        }
+        for (i, &chunk) in chunks.iter().enumerate().skip(first + 1) {
+            if chunk != fill {
+                self.emit_movk(dst, chunk, i as u32 * 16);
+            }
+        }
+    }
+
+    fn emit_add(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                if src1 == 31 || dst == 31 {
+                    // register 31 is SP here, only the extended register form can encode it
+                    dynasm!(self ; add XSP(dst), XSP(src1), X(src2), UXTX);
+                } else {
+                    dynasm!(self ; add X(dst), X(src1), X(src2));
+                }
+            }
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; add W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+            | (Size::S64, Location::Imm8(imm), Location::GPR(src1), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; add XSP(dst), XSP(src1), imm);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+            | (Size::S32, Location::Imm8(imm), Location::GPR(src1), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; add WSP(dst), WSP(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+            | (Size::S64, Location::Imm32(imm), Location::GPR(src1), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; add XSP(dst), XSP(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_add_sub_imm_lsl12(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm >> 12;
+                dynasm!(self ; add XSP(dst), XSP(src1), imm, LSL 12);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+            | (Size::S32, Location::Imm32(imm), Location::GPR(src1), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; add WSP(dst), WSP(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit ADD {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_sub(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                if src1 == 31 || dst == 31 {
+                    // register 31 is SP here, only the extended register form can encode it
+                    dynasm!(self ; sub XSP(dst), XSP(src1), X(src2), UXTX);
+                } else {
+                    dynasm!(self ; sub X(dst), X(src1), X(src2));
+                }
+            }
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sub W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; sub XSP(dst), XSP(src1), imm);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; sub WSP(dst), WSP(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sub XSP(dst), XSP(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_add_sub_imm_lsl12(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm >> 12;
+                dynasm!(self ; sub XSP(dst), XSP(src1), imm, LSL 12);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sub WSP(dst), WSP(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit SUB {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_mul(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; mul X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; mul W(dst), W(src1), W(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit MUL {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_umulh(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; umulh X(dst), X(src1), X(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit UMULH {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_smulh(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; smulh X(dst), X(src1), X(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit SMULH {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_adds(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; adds X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; adds W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+            | (Size::S64, Location::Imm8(imm), Location::GPR(src1), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; adds X(dst), XSP(src1), imm);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+            | (Size::S32, Location::Imm8(imm), Location::GPR(src1), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; adds W(dst), WSP(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+            | (Size::S64, Location::Imm32(imm), Location::GPR(src1), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; adds X(dst), XSP(src1), imm);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+            | (Size::S32, Location::Imm32(imm), Location::GPR(src1), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; adds W(dst), WSP(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit ADDS {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_subs(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; subs X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; subs W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; subs X(dst), XSP(src1), imm);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; subs W(dst), WSP(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; subs X(dst), XSP(src1), imm);
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_add_sub_imm(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; subs W(dst), WSP(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit SUBS {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_add2(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_add(sz, dst, src, dst);
+    }
+    fn emit_sub2(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_sub(sz, dst, src, dst);
+    }
+    fn emit_mul2(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_mul(sz, dst, src, dst);
+    }
+    fn emit_and2(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_and(sz, dst, src, dst);
+    }
+    fn emit_orr2(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_orr(sz, dst, src, dst);
+    }
+    fn emit_eor2(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_eor(sz, dst, src, dst);
+    }
+    fn emit_add_lsl(&mut self, sz: Size, src1: Location, src2: Location, lsl: u32, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; add X(dst), X(src1), X(src2), LSL lsl);
+            }
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; add W(dst), W(src1), W(src2), LSL lsl);
+            }
+            _ => panic!(
+                "singlepass can't emit ADD {:?} {:?} {:?} {:?} LSL {:?}",
+                sz, src1, src2, dst, lsl
+            ),
+        }
+    }
+    fn emit_neg(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; neg W(dst), W(src));
+            }
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; neg X(dst), X(src));
+            }
+            _ => panic!("singlepass can't emit NEG {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+
+    fn emit_cmp(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; cmp X(dst), X(src));
+            }
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; cmp W(dst), W(src));
+            }
+            (Size::S64, Location::Imm8(imm), Location::GPR(dst)) => {
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; cmp XSP(dst), imm);
+            }
+            (Size::S32, Location::Imm8(imm), Location::GPR(dst)) => {
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; cmp WSP(dst), imm);
+            }
+            (Size::S64, Location::Imm32(imm), Location::GPR(dst)) if is_add_sub_imm(imm as u64) => {
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; cmp XSP(dst), imm);
+            }
+            (Size::S32, Location::Imm32(imm), Location::GPR(dst)) if is_add_sub_imm(imm as u64) => {
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; cmp WSP(dst), imm);
+            }
+            _ => panic!("singlepass can't emit CMP {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_tst(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; tst X(dst), X(src));
+            }
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; tst W(dst), W(src));
+            }
+            (Size::S32, Location::Imm32(imm), Location::GPR(dst)) if is_logical_imm32(imm) => {
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; tst W(dst), imm);
+            }
+            (Size::S64, Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm64(imm as u64) =>
+            {
+                let dst = dst.into_index() as u32;
+                let imm = imm as u64;
+                dynasm!(self ; tst X(dst), imm);
+            }
+            (Size::S64, Location::Imm64(imm), Location::GPR(dst)) if is_logical_imm64(imm) => {
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; tst X(dst), imm);
+            }
+            _ => panic!("singlepass can't emit TST {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+
+    fn emit_udiv(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; udiv W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; udiv X(dst), X(src1), X(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit UDIV {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_sdiv(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sdiv W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sdiv X(dst), X(src1), X(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit SDIV {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_msub(&mut self, sz: Size, a: Location, b: Location, c: Location, dst: Location) {
+        match (sz, a, b, c, dst) {
+            (
+                Size::S32,
+                Location::GPR(a),
+                Location::GPR(b),
+                Location::GPR(c),
+                Location::GPR(dst),
+            ) => {
+                let a = a.into_index() as u32;
+                let b = b.into_index() as u32;
+                let c = c.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; msub W(dst), W(a), W(b), W(c));
+            }
+            (
+                Size::S64,
+                Location::GPR(a),
+                Location::GPR(b),
+                Location::GPR(c),
+                Location::GPR(dst),
+            ) => {
+                let a = a.into_index() as u32;
+                let b = b.into_index() as u32;
+                let c = c.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; msub X(dst), X(a), X(b), X(c));
+            }
+            _ => panic!(
+                "singlepass can't emit MSUB {:?} {:?} {:?} {:?} {:?}",
+                sz, a, b, c, dst
+            ),
+        }
+    }
+
+    fn emit_lsl(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; lsl W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; lsl X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 32 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; lsl W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 64 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; lsl X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit LSL {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_lsr(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; lsr W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; lsr X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 32 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; lsr W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 64 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; lsr X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit LSR {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_asr(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; asr W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; asr X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 32 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; asr W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 64 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; asr X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit ASR {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_ror(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ror W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ror X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 32 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; ror W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm8(imm), Location::GPR(dst))
+                if imm < 64 =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u32;
+                dynasm!(self ; ror X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit ROR {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_and(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; and W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; and X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm32(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; and W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm64(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u64;
+                dynasm!(self ; and X(dst), X(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm64(imm), Location::GPR(dst))
+                if is_logical_imm64(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; and X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit AND {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_ands(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ands W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ands X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm32(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ands W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm64(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u64;
+                dynasm!(self ; ands X(dst), X(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm64(imm), Location::GPR(dst))
+                if is_logical_imm64(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ands X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit ANDS {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_orr(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; orr W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; orr X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm32(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; orr W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm64(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u64;
+                dynasm!(self ; orr X(dst), X(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm64(imm), Location::GPR(dst))
+                if is_logical_imm64(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; orr X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit ORR {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_eor(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; eor W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; eor X(dst), X(src1), X(src2));
+            }
+            (Size::S32, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm32(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; eor W(dst), W(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm32(imm), Location::GPR(dst))
+                if is_logical_imm64(imm as u64) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                let imm = imm as u64;
+                dynasm!(self ; eor X(dst), X(src1), imm);
+            }
+            (Size::S64, Location::GPR(src1), Location::Imm64(imm), Location::GPR(dst))
+                if is_logical_imm64(imm) =>
+            {
+                let src1 = src1.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; eor X(dst), X(src1), imm);
+            }
+            _ => panic!(
+                "singlepass can't emit EOR {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_bic(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; bic W(dst), W(src1), W(src2));
+            }
+            (Size::S64, Location::GPR(src1), Location::GPR(src2), Location::GPR(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; bic X(dst), X(src1), X(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit BIC {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_sxtb(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sxtb W(dst), W(src));
+            }
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sxtb X(dst), W(src));
+            }
+            _ => panic!("singlepass can't emit SXTB {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_sxth(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sxth W(dst), W(src));
+            }
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sxth X(dst), W(src));
+            }
+            _ => panic!("singlepass can't emit SXTH {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_sxtw(&mut self, _sz: Size, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; sxtw X(dst), W(src));
+            }
+            _ => panic!("singlepass can't emit SXTW {:?} {:?}", src, dst),
+        }
+    }
+    fn emit_uxtb(&mut self, _sz: Size, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; uxtb W(dst), W(src));
+            }
+            _ => panic!("singlepass can't emit UXTB {:?} {:?}", src, dst),
+        }
+    }
+    fn emit_uxth(&mut self, _sz: Size, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; uxth W(dst), W(src));
+            }
+            _ => panic!("singlepass can't emit UXTH {:?} {:?}", src, dst),
+        }
+    }
+
+    fn emit_ubfx(&mut self, sz: Size, src: Location, lsb: u32, width: u32, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ubfx W(dst), W(src), lsb, width);
+            }
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ubfx X(dst), X(src), lsb, width);
+            }
+            _ => panic!(
+                "singlepass can't emit UBFX {:?} {:?} {:?} {:?} {:?}",
+                sz, src, lsb, width, dst
+            ),
+        }
+    }
+    fn emit_clz(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; clz W(dst), W(src));
+            }
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; clz X(dst), X(src));
+            }
+            _ => panic!("singlepass can't emit CLZ {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_rbit(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; rbit W(dst), W(src));
+            }
+            (Size::S64, Location::GPR(src), Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; rbit X(dst), X(src));
+            }
+            _ => panic!("singlepass can't emit RBIT {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_cset(&mut self, sz: Size, reg: Location, cond: Condition) {
+        match (sz, reg) {
+            (Size::S32, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                match cond {
+                    Condition::Eq => dynasm!(self ; cset W(reg), eq),
+                    Condition::Ne => dynasm!(self ; cset W(reg), ne),
+                    Condition::Cs => dynasm!(self ; cset W(reg), cs),
+                    Condition::Cc => dynasm!(self ; cset W(reg), cc),
+                    Condition::Mi => dynasm!(self ; cset W(reg), mi),
+                    Condition::Pl => dynasm!(self ; cset W(reg), pl),
+                    Condition::Vs => dynasm!(self ; cset W(reg), vs),
+                    Condition::Vc => dynasm!(self ; cset W(reg), vc),
+                    Condition::Hi => dynasm!(self ; cset W(reg), hi),
+                    Condition::Ls => dynasm!(self ; cset W(reg), ls),
+                    Condition::Ge => dynasm!(self ; cset W(reg), ge),
+                    Condition::Lt => dynasm!(self ; cset W(reg), lt),
+                    Condition::Gt => dynasm!(self ; cset W(reg), gt),
+                    Condition::Le => dynasm!(self ; cset W(reg), le),
+                    Condition::Al => dynasm!(self ; movz W(reg), 1),
+                }
+            }
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                match cond {
+                    Condition::Eq => dynasm!(self ; cset X(reg), eq),
+                    Condition::Ne => dynasm!(self ; cset X(reg), ne),
+                    Condition::Cs => dynasm!(self ; cset X(reg), cs),
+                    Condition::Cc => dynasm!(self ; cset X(reg), cc),
+                    Condition::Mi => dynasm!(self ; cset X(reg), mi),
+                    Condition::Pl => dynasm!(self ; cset X(reg), pl),
+                    Condition::Vs => dynasm!(self ; cset X(reg), vs),
+                    Condition::Vc => dynasm!(self ; cset X(reg), vc),
+                    Condition::Hi => dynasm!(self ; cset X(reg), hi),
+                    Condition::Ls => dynasm!(self ; cset X(reg), ls),
+                    Condition::Ge => dynasm!(self ; cset X(reg), ge),
+                    Condition::Lt => dynasm!(self ; cset X(reg), lt),
+                    Condition::Gt => dynasm!(self ; cset X(reg), gt),
+                    Condition::Le => dynasm!(self ; cset X(reg), le),
+                    Condition::Al => dynasm!(self ; movz X(reg), 1),
+                }
+            }
+            _ => panic!("singlepass can't emit CSET {:?} {:?} {:?}", sz, reg, cond),
+        }
+    }
+
+    fn emit_b_label(&mut self, label: Label) {
+        dynasm!(self ; b =>label);
+    }
+    fn emit_bcond_label(&mut self, condition: Condition, label: Label) {
+        match condition {
+            Condition::Eq => dynasm!(self ; b.eq =>label),
+            Condition::Ne => dynasm!(self ; b.ne =>label),
+            Condition::Cs => dynasm!(self ; b.cs =>label),
+            Condition::Cc => dynasm!(self ; b.cc =>label),
+            Condition::Mi => dynasm!(self ; b.mi =>label),
+            Condition::Pl => dynasm!(self ; b.pl =>label),
+            Condition::Vs => dynasm!(self ; b.vs =>label),
+            Condition::Vc => dynasm!(self ; b.vc =>label),
+            Condition::Hi => dynasm!(self ; b.hi =>label),
+            Condition::Ls => dynasm!(self ; b.ls =>label),
+            Condition::Ge => dynasm!(self ; b.ge =>label),
+            Condition::Lt => dynasm!(self ; b.lt =>label),
+            Condition::Gt => dynasm!(self ; b.gt =>label),
+            Condition::Le => dynasm!(self ; b.le =>label),
+            Condition::Al => dynasm!(self ; b =>label),
+        }
+    }
+    fn emit_cbz_label(&mut self, sz: Size, reg: Location, label: Label) {
+        match (sz, reg) {
+            (Size::S32, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; cbz W(reg), =>label);
+            }
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; cbz X(reg), =>label);
+            }
+            _ => panic!("singlepass can't emit CBZ {:?} {:?} {:?}", sz, reg, label),
+        }
+    }
+    fn emit_cbnz_label(&mut self, sz: Size, reg: Location, label: Label) {
+        match (sz, reg) {
+            (Size::S32, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; cbnz W(reg), =>label);
+            }
+            (Size::S64, Location::GPR(reg)) => {
+                let reg = reg.into_index() as u32;
+                dynasm!(self ; cbnz X(reg), =>label);
+            }
+            _ => panic!("singlepass can't emit CBNZ {:?} {:?} {:?}", sz, reg, label),
+        }
+    }
+    fn emit_b_register(&mut self, reg: GPR) {
+        dynasm!(self ; br X(reg.into_index() as u32));
+    }
+    fn emit_call_label(&mut self, label: Label) {
+        dynasm!(self ; bl =>label);
+    }
+    fn emit_call_register(&mut self, reg: GPR) {
+        dynasm!(self ; blr X(reg.into_index() as u32));
+    }
+    fn emit_ret(&mut self) {
+        dynasm!(self ; ret);
+    }
+    fn emit_load_label(&mut self, reg: GPR, label: Label) {
+        let reg = reg.into_index() as u32;
+        dynasm!(self ; adr X(reg), =>label);
+    }
+    /// Emits `adrp reg, 0`, for the page to be filled in by a relocation
+    fn emit_adrp(&mut self, reg: GPR) {
+        let reg = reg.into_index() as u32;
+        dynasm!(self ; adrp X(reg), 0);
+    }
+
+    fn emit_read_fpcr(&mut self, reg: GPR) {
+        let reg = reg.into_index() as u32;
+        // 0x5a20 is the system register encoding of FPCR (op0=3, op1=3, CRn=4, CRm=4, op2=0)
+        dynasm!(self ; mrs X(reg), 0x5a20);
+    }
+    fn emit_write_fpcr(&mut self, reg: GPR) {
+        let reg = reg.into_index() as u32;
+        dynasm!(self ; msr 0x5a20, X(reg));
+    }
+
+    fn emit_read_ctr(&mut self, reg: GPR) {
+        let reg = reg.into_index() as u32;
+        // 0x5801 is the system register encoding of CTR_EL0 (op0=3, op1=3, CRn=0, CRm=0, op2=1)
+        dynasm!(self ; mrs X(reg), 0x5801);
+    }
+    fn emit_fneg(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fneg S(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fneg D(dst), D(src));
+            }
+            _ => panic!("singlepass can't emit FNEG {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_fabs(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fabs S(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fabs D(dst), D(src));
+            }
+            _ => panic!("singlepass can't emit FABS {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_frintp(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintp S(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintp D(dst), D(src));
+            }
+            _ => panic!("singlepass can't emit FRINTP {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_frintm(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintm S(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintm D(dst), D(src));
+            }
+            _ => panic!("singlepass can't emit FRINTM {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_frintz(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintz S(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintz D(dst), D(src));
+            }
+            _ => panic!("singlepass can't emit FRINTZ {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_frintn(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintn S(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; frintn D(dst), D(src));
+            }
+            _ => panic!("singlepass can't emit FRINTN {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_fsqrt(&mut self, sz: Size, src: Location, dst: Location) {
+        match (sz, src, dst) {
+            (Size::S32, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fsqrt S(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fsqrt D(dst), D(src));
+            }
+            _ => panic!("singlepass can't emit FSQRT {:?} {:?} {:?}", sz, src, dst),
+        }
+    }
+    fn emit_fadd(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fadd S(dst), S(src1), S(src2));
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fadd D(dst), D(src1), D(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit FADD {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_fsub(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fsub S(dst), S(src1), S(src2));
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fsub D(dst), D(src1), D(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit FSUB {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_fmul(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmul S(dst), S(src1), S(src2));
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmul D(dst), D(src1), D(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit FMUL {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_fdiv(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fdiv S(dst), S(src1), S(src2));
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fdiv D(dst), D(src1), D(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit FDIV {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_fmin(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmin S(dst), S(src1), S(src2));
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmin D(dst), D(src1), D(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit FMIN {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+    fn emit_fmax(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmax S(dst), S(src1), S(src2));
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fmax D(dst), D(src1), D(src2));
+            }
+            _ => panic!(
+                "singlepass can't emit FMAX {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+
+    fn emit_fcmp(&mut self, sz: Size, src1: Location, src2: Location) {
+        match (sz, src1, src2) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                dynasm!(self ; fcmp S(src1), S(src2));
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                dynasm!(self ; fcmp D(src1), D(src2));
+            }
+            _ => panic!("singlepass can't emit FCMP {:?} {:?} {:?}", sz, src1, src2),
+        }
+    }
+    fn emit_fcsel(
+        &mut self,
+        sz: Size,
+        src1: Location,
+        src2: Location,
+        dst: Location,
+        cond: Condition,
+    ) {
+        match (sz, src1, src2, dst) {
+            (Size::S32, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                match cond {
+                    Condition::Eq => dynasm!(self ; fcsel S(dst), S(src1), S(src2), eq),
+                    Condition::Ne => dynasm!(self ; fcsel S(dst), S(src1), S(src2), ne),
+                    Condition::Cs => dynasm!(self ; fcsel S(dst), S(src1), S(src2), cs),
+                    Condition::Cc => dynasm!(self ; fcsel S(dst), S(src1), S(src2), cc),
+                    Condition::Mi => dynasm!(self ; fcsel S(dst), S(src1), S(src2), mi),
+                    Condition::Pl => dynasm!(self ; fcsel S(dst), S(src1), S(src2), pl),
+                    Condition::Vs => dynasm!(self ; fcsel S(dst), S(src1), S(src2), vs),
+                    Condition::Vc => dynasm!(self ; fcsel S(dst), S(src1), S(src2), vc),
+                    Condition::Hi => dynasm!(self ; fcsel S(dst), S(src1), S(src2), hi),
+                    Condition::Ls => dynasm!(self ; fcsel S(dst), S(src1), S(src2), ls),
+                    Condition::Ge => dynasm!(self ; fcsel S(dst), S(src1), S(src2), ge),
+                    Condition::Lt => dynasm!(self ; fcsel S(dst), S(src1), S(src2), lt),
+                    Condition::Gt => dynasm!(self ; fcsel S(dst), S(src1), S(src2), gt),
+                    Condition::Le => dynasm!(self ; fcsel S(dst), S(src1), S(src2), le),
+                    Condition::Al => dynasm!(self ; fcsel S(dst), S(src1), S(src2), al),
+                }
+            }
+            (Size::S64, Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                match cond {
+                    Condition::Eq => dynasm!(self ; fcsel D(dst), D(src1), D(src2), eq),
+                    Condition::Ne => dynasm!(self ; fcsel D(dst), D(src1), D(src2), ne),
+                    Condition::Cs => dynasm!(self ; fcsel D(dst), D(src1), D(src2), cs),
+                    Condition::Cc => dynasm!(self ; fcsel D(dst), D(src1), D(src2), cc),
+                    Condition::Mi => dynasm!(self ; fcsel D(dst), D(src1), D(src2), mi),
+                    Condition::Pl => dynasm!(self ; fcsel D(dst), D(src1), D(src2), pl),
+                    Condition::Vs => dynasm!(self ; fcsel D(dst), D(src1), D(src2), vs),
+                    Condition::Vc => dynasm!(self ; fcsel D(dst), D(src1), D(src2), vc),
+                    Condition::Hi => dynasm!(self ; fcsel D(dst), D(src1), D(src2), hi),
+                    Condition::Ls => dynasm!(self ; fcsel D(dst), D(src1), D(src2), ls),
+                    Condition::Ge => dynasm!(self ; fcsel D(dst), D(src1), D(src2), ge),
+                    Condition::Lt => dynasm!(self ; fcsel D(dst), D(src1), D(src2), lt),
+                    Condition::Gt => dynasm!(self ; fcsel D(dst), D(src1), D(src2), gt),
+                    Condition::Le => dynasm!(self ; fcsel D(dst), D(src1), D(src2), le),
+                    Condition::Al => dynasm!(self ; fcsel D(dst), D(src1), D(src2), al),
+                }
+            }
+            _ => panic!(
+                "singlepass can't emit FCSEL {:?} {:?} {:?} {:?}",
+                sz, src1, src2, dst
+            ),
+        }
+    }
+
+    fn emit_fcvt(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location) {
+        match (sz_in, src, sz_out, dst) {
+            (Size::S32, Location::SIMD(src), Size::S64, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvt D(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Size::S32, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvt S(dst), D(src));
+            }
+            _ => panic!(
+                "singlepass can't emit FCVT {:?} {:?} {:?} {:?}",
+                sz_in, src, sz_out, dst
+            ),
+        }
+    }
+    fn emit_fcvtzs(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location) {
+        match (sz_in, src, sz_out, dst) {
+            (Size::S32, Location::SIMD(src), Size::S32, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzs W(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Size::S32, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzs W(dst), D(src));
+            }
+            (Size::S32, Location::SIMD(src), Size::S64, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzs X(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Size::S64, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzs X(dst), D(src));
+            }
+            _ => panic!(
+                "singlepass can't emit FCVTZS {:?} {:?} {:?} {:?}",
+                sz_in, src, sz_out, dst
+            ),
+        }
+    }
+    fn emit_fcvtzu(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location) {
+        match (sz_in, src, sz_out, dst) {
+            (Size::S32, Location::SIMD(src), Size::S32, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzu W(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Size::S32, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzu W(dst), D(src));
+            }
+            (Size::S32, Location::SIMD(src), Size::S64, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzu X(dst), S(src));
+            }
+            (Size::S64, Location::SIMD(src), Size::S64, Location::GPR(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtzu X(dst), D(src));
+            }
+            _ => panic!(
+                "singlepass can't emit FCVTZU {:?} {:?} {:?} {:?}",
+                sz_in, src, sz_out, dst
+            ),
+        }
+    }
+    fn emit_scvtf(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location) {
+        match (sz_in, src, sz_out, dst) {
+            (Size::S32, Location::GPR(src), Size::S32, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; scvtf S(dst), W(src));
+            }
+            (Size::S32, Location::GPR(src), Size::S64, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; scvtf D(dst), W(src));
+            }
+            (Size::S64, Location::GPR(src), Size::S32, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; scvtf S(dst), X(src));
+            }
+            (Size::S64, Location::GPR(src), Size::S64, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; scvtf D(dst), X(src));
+            }
+            _ => panic!(
+                "singlepass can't emit SCVTF {:?} {:?} {:?} {:?}",
+                sz_in, src, sz_out, dst
+            ),
+        }
+    }
+    fn emit_ucvtf(&mut self, sz_in: Size, src: Location, sz_out: Size, dst: Location) {
+        match (sz_in, src, sz_out, dst) {
+            (Size::S32, Location::GPR(src), Size::S32, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ucvtf S(dst), W(src));
+            }
+            (Size::S32, Location::GPR(src), Size::S64, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ucvtf D(dst), W(src));
+            }
+            (Size::S64, Location::GPR(src), Size::S32, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ucvtf S(dst), X(src));
+            }
+            (Size::S64, Location::GPR(src), Size::S64, Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; ucvtf D(dst), X(src));
+            }
+            _ => panic!(
+                "singlepass can't emit UCVTF {:?} {:?} {:?} {:?}",
+                sz_in, src, sz_out, dst
+            ),
+        }
+    }
+
+    fn emit_vmvn(&mut self, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; mvn V(dst).B16, V(src).B16);
+            }
+            _ => panic!("singlepass can't emit MVN {:?} {:?}", src, dst),
+        }
+    }
+    fn emit_vand(&mut self, src1: Location, src2: Location, dst: Location) {
+        match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; and V(dst).B16, V(src1).B16, V(src2).B16);
+            }
+            _ => panic!("singlepass can't emit AND {:?} {:?} {:?}", src1, src2, dst),
+        }
+    }
+    fn emit_vorr(&mut self, src1: Location, src2: Location, dst: Location) {
+        match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; orr V(dst).B16, V(src1).B16, V(src2).B16);
+            }
+            _ => panic!("singlepass can't emit ORR {:?} {:?} {:?}", src1, src2, dst),
+        }
+    }
+    fn emit_veor(&mut self, src1: Location, src2: Location, dst: Location) {
+        match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; eor V(dst).B16, V(src1).B16, V(src2).B16);
+            }
+            _ => panic!("singlepass can't emit EOR {:?} {:?} {:?}", src1, src2, dst),
+        }
+    }
+    fn emit_vbic(&mut self, src1: Location, src2: Location, dst: Location) {
+        match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => {
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; bic V(dst).B16, V(src1).B16, V(src2).B16);
+            }
+            _ => panic!("singlepass can't emit BIC {:?} {:?} {:?}", src1, src2, dst),
+        }
+    }
+    fn emit_vmul(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit MUL {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; mul V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; mul V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; mul V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit MUL for lane {:?}", lane),
+        }
+    }
+    fn emit_smull(
+        &mut self,
+        lane: Size,
+        high: bool,
+        src1: Location,
+        src2: Location,
+        dst: Location,
+    ) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit SMULL {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match (lane, high) {
+            (Size::S8, false) => {
+                dynasm!(self ; smull V(dst).H8, V(src1).B8, V(src2).B8);
+            }
+            (Size::S8, true) => {
+                dynasm!(self ; smull2 V(dst).H8, V(src1).B16, V(src2).B16);
+            }
+            (Size::S16, false) => {
+                dynasm!(self ; smull V(dst).S4, V(src1).H4, V(src2).H4);
+            }
+            (Size::S16, true) => {
+                dynasm!(self ; smull2 V(dst).S4, V(src1).H8, V(src2).H8);
+            }
+            (Size::S32, false) => {
+                dynasm!(self ; smull V(dst).D2, V(src1).S2, V(src2).S2);
+            }
+            (Size::S32, true) => {
+                dynasm!(self ; smull2 V(dst).D2, V(src1).S4, V(src2).S4);
+            }
+            _ => panic!("singlepass can't emit SMULL for lane {:?}", lane),
+        }
+    }
+    fn emit_umull(
+        &mut self,
+        lane: Size,
+        high: bool,
+        src1: Location,
+        src2: Location,
+        dst: Location,
+    ) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit UMULL {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match (lane, high) {
+            (Size::S8, false) => {
+                dynasm!(self ; umull V(dst).H8, V(src1).B8, V(src2).B8);
+            }
+            (Size::S8, true) => {
+                dynasm!(self ; umull2 V(dst).H8, V(src1).B16, V(src2).B16);
+            }
+            (Size::S16, false) => {
+                dynasm!(self ; umull V(dst).S4, V(src1).H4, V(src2).H4);
+            }
+            (Size::S16, true) => {
+                dynasm!(self ; umull2 V(dst).S4, V(src1).H8, V(src2).H8);
+            }
+            (Size::S32, false) => {
+                dynasm!(self ; umull V(dst).D2, V(src1).S2, V(src2).S2);
+            }
+            (Size::S32, true) => {
+                dynasm!(self ; umull2 V(dst).D2, V(src1).S4, V(src2).S4);
+            }
+            _ => panic!("singlepass can't emit UMULL for lane {:?}", lane),
+        }
+    }
+    fn emit_urhadd(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit URHADD {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; urhadd V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; urhadd V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; urhadd V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit URHADD for lane {:?}", lane),
+        }
+    }
+    fn emit_sshll(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit SSHLL {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; sshll V(dst).H8, V(src).B8, 0),
+            Size::S16 => dynasm!(self ; sshll V(dst).S4, V(src).H4, 0),
+            Size::S32 => dynasm!(self ; sshll V(dst).D2, V(src).S2, 0),
+            _ => panic!("singlepass can't emit SSHLL for lane {:?}", lane),
+        }
+    }
+    fn emit_ushll(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit USHLL {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; ushll V(dst).H8, V(src).B8, 0),
+            Size::S16 => dynasm!(self ; ushll V(dst).S4, V(src).H4, 0),
+            Size::S32 => dynasm!(self ; ushll V(dst).D2, V(src).S2, 0),
+            _ => panic!("singlepass can't emit USHLL for lane {:?}", lane),
+        }
+    }
+    fn emit_addp(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit ADDP {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; addp V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; addp V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; addp V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; addp V(dst).D2, V(src1).D2, V(src2).D2),
+            Size::S128 => panic!("singlepass can't emit ADDP {:?}", lane),
+        }
+    }
+    fn emit_dup(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::GPR(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit DUP {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; dup V(dst).B16, W(src)),
+            Size::S16 => dynasm!(self ; dup V(dst).H8, W(src)),
+            Size::S32 => dynasm!(self ; dup V(dst).S4, W(src)),
+            Size::S64 => dynasm!(self ; dup V(dst).D2, X(src)),
+            Size::S128 => panic!("singlepass can't emit DUP {:?}", lane),
+        }
+    }
+    fn emit_dup_element(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit DUP {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; dup V(dst).B16, V(src).B[0]),
+            Size::S16 => dynasm!(self ; dup V(dst).H8, V(src).H[0]),
+            Size::S32 => dynasm!(self ; dup V(dst).S4, V(src).S[0]),
+            Size::S64 => dynasm!(self ; dup V(dst).D2, V(src).D[0]),
+            Size::S128 => panic!("singlepass can't emit DUP {:?}", lane),
+        }
+    }
+    fn emit_movi(&mut self, lane: Size, imm: u8, lsl: u32, dst: Location) {
+        let dst = match dst {
+            Location::SIMD(dst) => dst.into_index() as u32,
+            _ => panic!("singlepass can't emit MOVI {:?}", dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; movi V(dst).B16, imm as u32),
+            Size::S16 => dynasm!(self ; movi V(dst).H8, imm as u32, LSL lsl),
+            Size::S32 => dynasm!(self ; movi V(dst).S4, imm as u32, LSL lsl),
+            Size::S64 => {
+                let imm = (0..8).fold(0u64, |acc, i| {
+                    if imm & (1 << i) != 0 {
+                        acc | (0xff << (i * 8))
+                    } else {
+                        acc
+                    }
+                });
+                dynasm!(self ; movi V(dst).D2, imm)
+            }
+            Size::S128 => panic!("singlepass can't emit MOVI {:?}", lane),
+        }
+    }
+    fn emit_vfneg(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit FNEG {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; fneg V(dst).S4, V(src).S4),
+            Size::S64 => dynasm!(self ; fneg V(dst).D2, V(src).D2),
+            _ => panic!("singlepass can't emit FNEG for lane {:?}", lane),
+        }
+    }
+    fn emit_bsl(&mut self, dst: Location, src1: Location, src2: Location) {
+        match (dst, src1, src2) {
+            (Location::SIMD(dst), Location::SIMD(src1), Location::SIMD(src2)) => {
+                let dst = dst.into_index() as u32;
+                let src1 = src1.into_index() as u32;
+                let src2 = src2.into_index() as u32;
+                dynasm!(self ; bsl V(dst).B16, V(src1).B16, V(src2).B16);
+            }
+            _ => panic!("singlepass can't emit BSL {:?} {:?} {:?}", dst, src1, src2),
+        }
+    }
+    fn emit_sqrdmulh(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit SQRDMULH {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match lane {
+            Size::S16 => dynasm!(self ; sqrdmulh V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; sqrdmulh V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit SQRDMULH for lane {:?}", lane),
+        }
+    }
+    fn emit_umaxp(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit UMAXP {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; umaxp V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; umaxp V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; umaxp V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit UMAXP for lane {:?}", lane),
+        }
+    }
+    fn emit_cnt(&mut self, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; cnt V(dst).B16, V(src).B16);
+            }
+            _ => panic!("singlepass can't emit CNT {:?} {:?}", src, dst),
+        }
+    }
+    fn emit_addv(&mut self, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; addv B(dst), V(src).B16);
+            }
+            _ => panic!("singlepass can't emit ADDV {:?} {:?}", src, dst),
+        }
+    }
+    fn emit_vfrintp(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit FRINTP {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; frintp V(dst).S4, V(src).S4),
+            Size::S64 => dynasm!(self ; frintp V(dst).D2, V(src).D2),
+            _ => panic!("singlepass can't emit FRINTP for lane {:?}", lane),
+        }
+    }
+    fn emit_vfrintm(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit FRINTM {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; frintm V(dst).S4, V(src).S4),
+            Size::S64 => dynasm!(self ; frintm V(dst).D2, V(src).D2),
+            _ => panic!("singlepass can't emit FRINTM for lane {:?}", lane),
+        }
+    }
+    fn emit_vfrintz(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit FRINTZ {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; frintz V(dst).S4, V(src).S4),
+            Size::S64 => dynasm!(self ; frintz V(dst).D2, V(src).D2),
+            _ => panic!("singlepass can't emit FRINTZ for lane {:?}", lane),
+        }
+    }
+    fn emit_vfrintn(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit FRINTN {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; frintn V(dst).S4, V(src).S4),
+            Size::S64 => dynasm!(self ; frintn V(dst).D2, V(src).D2),
+            _ => panic!("singlepass can't emit FRINTN for lane {:?}", lane),
+        }
+    }
+    fn emit_saddlp(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit SADDLP {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; saddlp V(dst).H8, V(src).B16),
+            Size::S16 => dynasm!(self ; saddlp V(dst).S4, V(src).H8),
+            Size::S32 => dynasm!(self ; saddlp V(dst).D2, V(src).S4),
+            _ => panic!("singlepass can't emit SADDLP for lane {:?}", lane),
+        }
+    }
+    fn emit_uaddlp(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit UADDLP {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; uaddlp V(dst).H8, V(src).B16),
+            Size::S16 => dynasm!(self ; uaddlp V(dst).S4, V(src).H8),
+            Size::S32 => dynasm!(self ; uaddlp V(dst).D2, V(src).S4),
+            _ => panic!("singlepass can't emit UADDLP for lane {:?}", lane),
+        }
+    }
+    fn emit_ld1_lane(&mut self, lane: Size, dst: Location, index: u8, addr: Location) {
+        let (dst, addr) = match (dst, addr) {
+            (Location::SIMD(dst), Location::GPR(addr)) => {
+                (dst.into_index() as u32, addr.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit LD1 {:?} {:?}", dst, addr),
+        };
+        let index = index as u32;
+        match lane {
+            Size::S8 => dynasm!(self ; ld1 {V(dst).B * 1}[index], [X(addr)]),
+            Size::S16 => dynasm!(self ; ld1 {V(dst).H * 1}[index], [X(addr)]),
+            Size::S32 => dynasm!(self ; ld1 {V(dst).S * 1}[index], [X(addr)]),
+            Size::S64 => dynasm!(self ; ld1 {V(dst).D * 1}[index], [X(addr)]),
+            Size::S128 => panic!("singlepass can't emit LD1 {:?}", lane),
+        }
+    }
+    fn emit_st1_lane(&mut self, lane: Size, src: Location, index: u8, addr: Location) {
+        let (src, addr) = match (src, addr) {
+            (Location::SIMD(src), Location::GPR(addr)) => {
+                (src.into_index() as u32, addr.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit ST1 {:?} {:?}", src, addr),
+        };
+        let index = index as u32;
+        match lane {
+            Size::S8 => dynasm!(self ; st1 {V(src).B * 1}[index], [X(addr)]),
+            Size::S16 => dynasm!(self ; st1 {V(src).H * 1}[index], [X(addr)]),
+            Size::S32 => dynasm!(self ; st1 {V(src).S * 1}[index], [X(addr)]),
+            Size::S64 => dynasm!(self ; st1 {V(src).D * 1}[index], [X(addr)]),
+            Size::S128 => panic!("singlepass can't emit ST1 {:?}", lane),
+        }
+    }
+    fn emit_vfcmeq(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit FCMEQ {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; fcmeq V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; fcmeq V(dst).D2, V(src1).D2, V(src2).D2),
+            _ => panic!("singlepass can't emit FCMEQ for lane {:?}", lane),
+        }
+    }
+    fn emit_vfcmgt(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit FCMGT {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; fcmgt V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; fcmgt V(dst).D2, V(src1).D2, V(src2).D2),
+            _ => panic!("singlepass can't emit FCMGT for lane {:?}", lane),
+        }
+    }
+    fn emit_vfcmge(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!(
+                "singlepass can't emit FCMGE {:?} {:?} {:?}",
+                src1, src2, dst
+            ),
+        };
+        match lane {
+            Size::S32 => dynasm!(self ; fcmge V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; fcmge V(dst).D2, V(src1).D2, V(src2).D2),
+            _ => panic!("singlepass can't emit FCMGE for lane {:?}", lane),
+        }
+    }
+    fn emit_vcmeq(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit CMEQ {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; cmeq V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; cmeq V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; cmeq V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; cmeq V(dst).D2, V(src1).D2, V(src2).D2),
+            Size::S128 => panic!("singlepass can't emit CMEQ {:?}", lane),
+        }
+    }
+    fn emit_vcmgt(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit CMGT {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; cmgt V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; cmgt V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; cmgt V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; cmgt V(dst).D2, V(src1).D2, V(src2).D2),
+            Size::S128 => panic!("singlepass can't emit CMGT {:?}", lane),
+        }
+    }
+    fn emit_vcmge(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit CMGE {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; cmge V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; cmge V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; cmge V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; cmge V(dst).D2, V(src1).D2, V(src2).D2),
+            Size::S128 => panic!("singlepass can't emit CMGE {:?}", lane),
+        }
+    }
+    fn emit_vcmhi(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit CMHI {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; cmhi V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; cmhi V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; cmhi V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; cmhi V(dst).D2, V(src1).D2, V(src2).D2),
+            Size::S128 => panic!("singlepass can't emit CMHI {:?}", lane),
+        }
+    }
+    fn emit_vcmhs(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit CMHS {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; cmhs V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; cmhs V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; cmhs V(dst).S4, V(src1).S4, V(src2).S4),
+            Size::S64 => dynasm!(self ; cmhs V(dst).D2, V(src1).D2, V(src2).D2),
+            Size::S128 => panic!("singlepass can't emit CMHS {:?}", lane),
+        }
+    }
+    fn emit_vsmin(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit SMIN {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; smin V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; smin V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; smin V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit SMIN for lane {:?}", lane),
+        }
+    }
+    fn emit_vumin(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit UMIN {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; umin V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; umin V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; umin V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit UMIN for lane {:?}", lane),
+        }
+    }
+    fn emit_vsmax(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit SMAX {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; smax V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; smax V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; smax V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit SMAX for lane {:?}", lane),
+        }
+    }
+    fn emit_vumax(&mut self, lane: Size, src1: Location, src2: Location, dst: Location) {
+        let (src1, src2, dst) = match (src1, src2, dst) {
+            (Location::SIMD(src1), Location::SIMD(src2), Location::SIMD(dst)) => (
+                src1.into_index() as u32,
+                src2.into_index() as u32,
+                dst.into_index() as u32,
+            ),
+            _ => panic!("singlepass can't emit UMAX {:?} {:?} {:?}", src1, src2, dst),
+        };
+        match lane {
+            Size::S8 => dynasm!(self ; umax V(dst).B16, V(src1).B16, V(src2).B16),
+            Size::S16 => dynasm!(self ; umax V(dst).H8, V(src1).H8, V(src2).H8),
+            Size::S32 => dynasm!(self ; umax V(dst).S4, V(src1).S4, V(src2).S4),
+            _ => panic!("singlepass can't emit UMAX for lane {:?}", lane),
+        }
+    }
+    fn emit_sqxtun(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit SQXTUN {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S16 => dynasm!(self ; sqxtun V(dst).B8, V(src).H8),
+            Size::S32 => dynasm!(self ; sqxtun V(dst).H4, V(src).S4),
+            Size::S64 => dynasm!(self ; sqxtun V(dst).S2, V(src).D2),
+            _ => panic!("singlepass can't emit SQXTUN for lane {:?}", lane),
+        }
+    }
+    fn emit_sqxtun2(&mut self, lane: Size, src: Location, dst: Location) {
+        let (src, dst) = match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                (src.into_index() as u32, dst.into_index() as u32)
+            }
+            _ => panic!("singlepass can't emit SQXTUN2 {:?} {:?}", src, dst),
+        };
+        match lane {
+            Size::S16 => dynasm!(self ; sqxtun2 V(dst).B16, V(src).H8),
+            Size::S32 => dynasm!(self ; sqxtun2 V(dst).H8, V(src).S4),
+            Size::S64 => dynasm!(self ; sqxtun2 V(dst).S4, V(src).D2),
+            _ => panic!("singlepass can't emit SQXTUN2 for lane {:?}", lane),
+        }
+    }
+    fn emit_fcvtl(&mut self, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtl V(dst).D2, V(src).S2);
+            }
+            _ => panic!("singlepass can't emit FCVTL {:?} {:?}", src, dst),
+        }
+    }
+    fn emit_fcvtn(&mut self, src: Location, dst: Location) {
+        match (src, dst) {
+            (Location::SIMD(src), Location::SIMD(dst)) => {
+                let src = src.into_index() as u32;
+                let dst = dst.into_index() as u32;
+                dynasm!(self ; fcvtn V(dst).S2, V(src).D2);
+            }
+            _ => panic!("singlepass can't emit FCVTN {:?} {:?}", src, dst),
+        }
+    }
+}
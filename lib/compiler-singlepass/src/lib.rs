@@ -9,13 +9,16 @@
 //! runtime performance.
 
 mod address_map;
+mod arm64_decl;
 mod codegen;
 mod common_decl;
 mod compiler;
 mod config;
+mod emitter_arm64;
 mod emitter_x64;
 mod location;
 mod machine;
+mod machine_arm64;
 mod machine_x64;
 mod x64_decl;
 
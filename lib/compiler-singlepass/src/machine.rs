@@ -1,5 +1,6 @@
 use crate::common_decl::*;
 use crate::location::{Location, Reg};
+use crate::machine_arm64::MachineARM64;
 use crate::machine_x64::MachineX86_64;
 use dynasmrt::{AssemblyOffset, DynamicLabel};
 use std::collections::BTreeMap;
@@ -31,6 +32,15 @@ pub struct CodegenError {
     pub message: String,
 }
 
+impl CodegenError {
+    /// Error for an operation the target backend has no lowering for.
+    pub fn unsupported(op: &str) -> Self {
+        Self {
+            message: format!("{} is not supported on this target", op),
+        }
+    }
+}
+
 pub trait MaybeImmediate {
     fn imm_value(&self) -> Option<Value>;
     fn is_imm(&self) -> bool {
@@ -48,6 +58,52 @@ pub struct TrapTable {
 // all machine seems to have a page this size, so not per arch for now
 pub const NATIVE_PAGE_SIZE: usize = 4096;
 
+/// Bit pattern of the canonical f32 quiet NaN, shared by every backend
+pub const CANONICAL_NAN_F32: u32 = 0x7FC0_0000;
+/// Bit pattern of the canonical f64 quiet NaN, shared by every backend
+pub const CANONICAL_NAN_F64: u64 = 0x7FF8_0000_0000_0000;
+
+// Constants for the bounds of truncation operations. These are the least or
+// greatest exact floats in either f32 or f64 representation less-than (for
+// least) or greater-than (for greatest) the i32 or i64 or u32 or u64
+// min (for least) or max (for greatest), when rounding towards zero.
+// A float converts without trapping only if it is strictly between the two
+// bounds, so both must be compared exclusively.
+
+/// Greatest Exact Float (32 bits) less-than i32::MIN when rounding towards zero.
+pub const GEF32_LT_I32_MIN: f32 = -2147483904.0;
+/// Least Exact Float (32 bits) greater-than i32::MAX when rounding towards zero.
+pub const LEF32_GT_I32_MAX: f32 = 2147483648.0;
+/// Greatest Exact Float (32 bits) less-than i64::MIN when rounding towards zero.
+pub const GEF32_LT_I64_MIN: f32 = -9223373136366403584.0;
+/// Least Exact Float (32 bits) greater-than i64::MAX when rounding towards zero.
+pub const LEF32_GT_I64_MAX: f32 = 9223372036854775808.0;
+/// Greatest Exact Float (32 bits) less-than u32::MIN when rounding towards zero.
+pub const GEF32_LT_U32_MIN: f32 = -1.0;
+/// Least Exact Float (32 bits) greater-than u32::MAX when rounding towards zero.
+pub const LEF32_GT_U32_MAX: f32 = 4294967296.0;
+/// Greatest Exact Float (32 bits) less-than u64::MIN when rounding towards zero.
+pub const GEF32_LT_U64_MIN: f32 = -1.0;
+/// Least Exact Float (32 bits) greater-than u64::MAX when rounding towards zero.
+pub const LEF32_GT_U64_MAX: f32 = 18446744073709551616.0;
+
+/// Greatest Exact Float (64 bits) less-than i32::MIN when rounding towards zero.
+pub const GEF64_LT_I32_MIN: f64 = -2147483649.0;
+/// Least Exact Float (64 bits) greater-than i32::MAX when rounding towards zero.
+pub const LEF64_GT_I32_MAX: f64 = 2147483648.0;
+/// Greatest Exact Float (64 bits) less-than i64::MIN when rounding towards zero.
+pub const GEF64_LT_I64_MIN: f64 = -9223372036854777856.0;
+/// Least Exact Float (64 bits) greater-than i64::MAX when rounding towards zero.
+pub const LEF64_GT_I64_MAX: f64 = 9223372036854775808.0;
+/// Greatest Exact Float (64 bits) less-than u32::MIN when rounding towards zero.
+pub const GEF64_LT_U32_MIN: f64 = -1.0;
+/// Least Exact Float (64 bits) greater-than u32::MAX when rounding towards zero.
+pub const LEF64_GT_U32_MAX: f64 = 4294967296.0;
+/// Greatest Exact Float (64 bits) less-than u64::MIN when rounding towards zero.
+pub const GEF64_LT_U64_MIN: f64 = -1.0;
+/// Least Exact Float (64 bits) greater-than u64::MAX when rounding towards zero.
+pub const LEF64_GT_U64_MAX: f64 = 18446744073709551616.0;
+
 pub struct MachineStackOffset(pub usize);
 
 pub trait Machine {
@@ -111,6 +167,8 @@ pub trait Machine {
     fn mark_address_with_trap_code(&mut self, code: TrapCode);
     /// Marks the instruction as trappable with trap code `code`. return "begin" offset
     fn mark_instruction_with_trap_code(&mut self, code: TrapCode) -> usize;
+    /// Get the trap code recorded at `offset`, if any
+    fn trap_code_at(&self, offset: usize) -> Option<TrapCode>;
     /// Pushes the instruction to the address map, calculating the offset from a
     /// provided beginning address.
     fn mark_instruction_address_end(&mut self, begin: usize);
@@ -136,12 +194,19 @@ pub trait Machine {
     /// Pop stack of locals
     /// Like assembler.emit_add(Size::S64, Location::Imm32(delta_stack_offset as u32), Location::GPR(GPR::RSP))
     fn pop_stack_locals(&mut self, delta_stack_offset: u32);
+    /// Rounds a stack adjustment up to the alignment the stack pointer is always kept at
+    fn round_stack_adjust(&self, value: usize) -> usize;
     /// Zero a location taht is 32bits
     fn zero_location(&mut self, size: Size, location: Location<Self::GPR, Self::SIMD>);
     /// GPR Reg used for local pointer on the stack
     fn local_pointer(&self) -> Self::GPR;
-    /// push a value on the stack for a native call
-    fn push_location_for_native(&mut self, loc: Location<Self::GPR, Self::SIMD>);
+    /// Push the stack parameters of a native call, last parameter first, below an empty
+    /// 8-byte slot aligning the stack if `padding` is set
+    fn push_params_for_native(
+        &mut self,
+        params: &[Location<Self::GPR, Self::SIMD>],
+        padding: bool,
+    );
     /// Determine whether a local should be allocated on the stack.
     fn is_local_on_stack(&self, idx: usize) -> bool;
     /// Determine a local's location.
@@ -153,6 +218,21 @@ pub trait Machine {
     /// Move a local to the stack
     /// Like emit_mov(Size::S64, location, Location::Memory(GPR::RBP, -(self.stack_offset.0 as i32)));
     fn move_local(&mut self, stack_offset: i32, location: Location<Self::GPR, Self::SIMD>);
+    /// Save the floating-point control state to the local slot at stack_offset,
+    /// then switch to the canonical state (round to nearest, no flush-to-zero, exceptions masked)
+    fn canonicalize_fp_control(&mut self, _stack_offset: i32) -> Result<(), CodegenError> {
+        Err(CodegenError::unsupported("canonicalize_fp_control"))
+    }
+    /// Restore the floating-point control state saved by canonicalize_fp_control
+    /// to the local slot at stack_offset
+    fn restore_fp_control(&mut self, _stack_offset: i32) -> Result<(), CodegenError> {
+        Err(CodegenError::unsupported("restore_fp_control"))
+    }
+    /// Increment the call depth counter at counter_offset in the vmctx,
+    /// trapping with a stack overflow if limit calls are already active
+    fn emit_call_depth_check(&mut self, counter_offset: i32, limit: u32);
+    /// Decrement the call depth counter at counter_offset in the vmctx
+    fn emit_call_depth_release(&mut self, counter_offset: i32);
     /// List of register to save, depending on the CallingConvention
     fn list_to_save(
         &self,
@@ -194,15 +274,20 @@ pub trait Machine {
         init_stack_loc_cnt: u64,
         last_stack_loc: Location<Self::GPR, Self::SIMD>,
     );
-    /// Restore save_area
-    fn restore_saved_area(&mut self, saved_area_offset: i32);
-    /// Pop a location
-    fn pop_location(&mut self, location: Location<Self::GPR, Self::SIMD>);
+    /// Restore save_area: reload `locations`, last saved first, from the save area
+    /// whose last slot is at saved_area_offset
+    fn restore_saved_area(
+        &mut self,
+        saved_area_offset: i32,
+        locations: &[Location<Self::GPR, Self::SIMD>],
+    );
     /// Create a new `MachineState` with default values.
     fn new_machine_state(&self) -> MachineState;
 
     /// Finalize the assembler
     fn assembler_finalize(self) -> Vec<u8>;
+    /// Finalize the assembler, also handing over the trap table and the instructions address map
+    fn finalize_with_metadata(self) -> (Vec<u8>, Vec<TrapInformation>, Vec<InstructionAddressMap>);
 
     /// get_offset of Assembler
     fn get_offset(&self) -> Offset;
@@ -255,6 +340,11 @@ pub trait Machine {
     );
     /// emit a call to a location
     fn emit_call_location(&mut self, location: Location<Self::GPR, Self::SIMD>);
+    /// tear down the current frame and jump to the function in register,
+    /// so the callee returns directly to the caller of the current function
+    fn emit_return_call(&mut self, register: Self::GPR);
+    /// tear down the current frame and jump to the function whose address is at location
+    fn emit_return_call_indirect(&mut self, location: Location<Self::GPR, Self::SIMD>);
     /// get the gpr for the return of generic values
     fn get_gpr_for_ret(&self) -> Self::GPR;
     /// get the simd for the return of float/double values
@@ -2137,19 +2227,27 @@ pub trait Machine {
         ret: Location<Self::GPR, Self::SIMD>,
     );
 
+    /// Make the instructions written to the len bytes at start visible to
+    /// instruction fetch, for code patched at runtime
+    fn emit_icache_sync(
+        &mut self,
+        start: Location<Self::GPR, Self::SIMD>,
+        len: Location<Self::GPR, Self::SIMD>,
+    );
+
     /// Standard function Trampoline generation
     fn gen_std_trampoline(
         &self,
         sig: &FunctionType,
         calling_convention: CallingConvention,
-    ) -> FunctionBody;
+    ) -> Result<FunctionBody, CodegenError>;
     /// Generates dynamic import function call trampoline for a function type.
     fn gen_std_dynamic_import_trampoline(
         &self,
         vmoffsets: &VMOffsets,
         sig: &FunctionType,
         calling_convention: CallingConvention,
-    ) -> FunctionBody;
+    ) -> Result<FunctionBody, CodegenError>;
     /// Singlepass calls import functions through a trampoline.
     fn gen_import_call_trampoline(
         &self,
@@ -2157,7 +2255,7 @@ pub trait Machine {
         index: FunctionIndex,
         sig: &FunctionType,
         calling_convention: CallingConvention,
-    ) -> CustomSection;
+    ) -> Result<CustomSection, CodegenError>;
 }
 
 /// Standard entry trampoline generation
@@ -2165,12 +2263,20 @@ pub fn gen_std_trampoline(
     sig: &FunctionType,
     target: &Target,
     calling_convention: CallingConvention,
-) -> FunctionBody {
-    let machine = match target.triple().architecture {
-        Architecture::X86_64 => MachineX86_64::new(),
-        _ => unimplemented!(),
-    };
-    machine.gen_std_trampoline(sig, calling_convention)
+) -> Result<FunctionBody, CodegenError> {
+    match target.triple().architecture {
+        Architecture::X86_64 => {
+            let machine = MachineX86_64::new();
+            machine.gen_std_trampoline(sig, calling_convention)
+        }
+        Architecture::Aarch64(_) => {
+            let machine = MachineARM64::new();
+            machine.gen_std_trampoline(sig, calling_convention)
+        }
+        _ => Err(CodegenError {
+            message: format!("unsupported architecture: {}", target.triple().architecture),
+        }),
+    }
 }
 /// Generates dynamic import function call trampoline for a function type.
 pub fn gen_std_dynamic_import_trampoline(
@@ -2178,12 +2284,20 @@ pub fn gen_std_dynamic_import_trampoline(
     sig: &FunctionType,
     target: &Target,
     calling_convention: CallingConvention,
-) -> FunctionBody {
-    let machine = match target.triple().architecture {
-        Architecture::X86_64 => MachineX86_64::new(),
-        _ => unimplemented!(),
-    };
-    machine.gen_std_dynamic_import_trampoline(vmoffsets, sig, calling_convention)
+) -> Result<FunctionBody, CodegenError> {
+    match target.triple().architecture {
+        Architecture::X86_64 => {
+            let machine = MachineX86_64::new();
+            machine.gen_std_dynamic_import_trampoline(vmoffsets, sig, calling_convention)
+        }
+        Architecture::Aarch64(_) => {
+            let machine = MachineARM64::new();
+            machine.gen_std_dynamic_import_trampoline(vmoffsets, sig, calling_convention)
+        }
+        _ => Err(CodegenError {
+            message: format!("unsupported architecture: {}", target.triple().architecture),
+        }),
+    }
 }
 /// Singlepass calls import functions through a trampoline.
 pub fn gen_import_call_trampoline(
@@ -2192,10 +2306,18 @@ pub fn gen_import_call_trampoline(
     sig: &FunctionType,
     target: &Target,
     calling_convention: CallingConvention,
-) -> CustomSection {
-    let machine = match target.triple().architecture {
-        Architecture::X86_64 => MachineX86_64::new(),
-        _ => unimplemented!(),
-    };
-    machine.gen_import_call_trampoline(vmoffsets, index, sig, calling_convention)
+) -> Result<CustomSection, CodegenError> {
+    match target.triple().architecture {
+        Architecture::X86_64 => {
+            let machine = MachineX86_64::new();
+            machine.gen_import_call_trampoline(vmoffsets, index, sig, calling_convention)
+        }
+        Architecture::Aarch64(_) => {
+            let machine = MachineARM64::new();
+            machine.gen_import_call_trampoline(vmoffsets, index, sig, calling_convention)
+        }
+        _ => Err(CodegenError {
+            message: format!("unsupported architecture: {}", target.triple().architecture),
+        }),
+    }
 }
@@ -0,0 +1,9362 @@
+use crate::arm64_decl::new_machine_state;
+use crate::arm64_decl::{ARM64Register, ArgumentRegisterAllocator, GPR, NEON};
+use crate::common_decl::*;
+use crate::emitter_arm64::*;
+use crate::location::Location as AbstractLocation;
+use crate::machine::{CodegenError, Machine};
+use crate::machine::{MemoryImmediate, TrapTable, CANONICAL_NAN_F32, CANONICAL_NAN_F64};
+use crate::machine::{
+    GEF32_LT_I32_MIN, GEF32_LT_I64_MIN, GEF32_LT_U32_MIN, GEF32_LT_U64_MIN, GEF64_LT_I32_MIN,
+    GEF64_LT_I64_MIN, GEF64_LT_U32_MIN, GEF64_LT_U64_MIN, LEF32_GT_I32_MAX, LEF32_GT_I64_MAX,
+    LEF32_GT_U32_MAX, LEF32_GT_U64_MAX, LEF64_GT_I32_MAX, LEF64_GT_I64_MAX, LEF64_GT_U32_MAX,
+    LEF64_GT_U64_MAX,
+};
+use dynasmrt::{aarch64::Aarch64Relocation, VecAssembler};
+use std::collections::HashSet;
+use wasmer_compiler::wasmparser::Type as WpType;
+use wasmer_compiler::{
+    CallingConvention, CustomSection, CustomSectionProtection, FunctionBody, InstructionAddressMap,
+    Relocation, RelocationKind, RelocationTarget, SectionBody, SourceLoc, TrapInformation,
+};
+use wasmer_types::{FunctionIndex, FunctionType, Type};
+use wasmer_vm::{TrapCode, VMOffsets};
+
+type Assembler = VecAssembler<Aarch64Relocation>;
+type Location = AbstractLocation<GPR, NEON>;
+
+pub struct MachineARM64 {
+    assembler: Assembler,
+    used_gprs: HashSet<GPR>,
+    used_simd: HashSet<NEON>,
+    trap_table: TrapTable,
+    /// Map from byte offset into wasm function to range of native instructions.
+    ///
+    // Ordered by increasing InstructionAddressMap::srcloc.
+    instructions_address_map: Vec<InstructionAddressMap>,
+    /// The source location for the current operator.
+    src_loc: u32,
+    /// Landing pad for misaligned atomic accesses, created on first use.
+    unaligned_atomic: Option<Label>,
+}
+
+#[allow(dead_code)]
+#[derive(PartialEq)]
+enum ImmType {
+    None,
+    NoneXzr,
+    Bits8,
+    Bits12,
+    Shift32,
+    Shift32No0,
+    Shift64,
+    Shift64No0,
+    Logical32,
+    Logical64,
+    UnscaledOffset,
+    OffsetByte,
+    OffsetHWord,
+    OffsetWord,
+    OffsetDWord,
+    OffsetQWord,
+}
+
+#[allow(dead_code)]
+impl MachineARM64 {
+    pub fn new() -> Self {
+        MachineARM64 {
+            assembler: Assembler::new(0),
+            used_gprs: HashSet::new(),
+            used_simd: HashSet::new(),
+            trap_table: TrapTable::default(),
+            instructions_address_map: vec![],
+            src_loc: 0,
+            unaligned_atomic: None,
+        }
+    }
+    fn compatible_imm(&self, imm: i64, ty: ImmType) -> bool {
+        match ty {
+            ImmType::None => false,
+            ImmType::NoneXzr => false,
+            ImmType::Bits8 => (0..256).contains(&imm),
+            ImmType::Bits12 => (0..0x1000).contains(&imm),
+            ImmType::Shift32 => (0..32).contains(&imm),
+            ImmType::Shift32No0 => (1..32).contains(&imm),
+            ImmType::Shift64 => (0..64).contains(&imm),
+            ImmType::Shift64No0 => (1..64).contains(&imm),
+            ImmType::Logical32 => (imm as u64) >> 32 == 0 && is_logical_imm32(imm as u32),
+            ImmType::Logical64 => is_logical_imm64(imm as u64),
+            ImmType::UnscaledOffset => (-256..256).contains(&imm),
+            ImmType::OffsetByte => (0..0x1000).contains(&imm),
+            ImmType::OffsetHWord => (imm & 1 == 0) && (0..0x2000).contains(&imm),
+            ImmType::OffsetWord => (imm & 3 == 0) && (0..0x4000).contains(&imm),
+            ImmType::OffsetDWord => (imm & 7 == 0) && (0..0x8000).contains(&imm),
+            ImmType::OffsetQWord => (imm & 15 == 0) && (0..0x10000).contains(&imm),
+        }
+    }
+    /// The bitmask immediate type matching a logical operation of size `sz`.
+    fn logical_imm_type(sz: Size) -> ImmType {
+        match sz {
+            Size::S64 => ImmType::Logical64,
+            _ => ImmType::Logical32,
+        }
+    }
+
+    /// Returns `src` as a register (or as an immediate when `allow_imm` accepts it),
+    /// materializing it into a temporary GPR otherwise. Temporaries are pushed to `temps`
+    /// and must be released by the caller.
+    fn location_to_reg(
+        &mut self,
+        sz: Size,
+        src: Location,
+        temps: &mut Vec<GPR>,
+        allow_imm: ImmType,
+        read_val: bool,
+        wanted: Option<GPR>,
+    ) -> Location {
+        match src {
+            Location::GPR(_) => src,
+            Location::Imm8(val) => {
+                if allow_imm == ImmType::NoneXzr && val == 0 {
+                    Location::GPR(GPR::XzrSp)
+                } else if self.compatible_imm(val as i64, allow_imm) {
+                    src
+                } else {
+                    let tmp = if let Some(wanted) = wanted {
+                        wanted
+                    } else {
+                        let tmp = self.acquire_temp_gpr().unwrap();
+                        temps.push(tmp);
+                        tmp
+                    };
+                    self.assembler.emit_mov_imm(Location::GPR(tmp), val as u64);
+                    Location::GPR(tmp)
+                }
+            }
+            Location::Imm32(val) => {
+                if allow_imm == ImmType::NoneXzr && val == 0 {
+                    Location::GPR(GPR::XzrSp)
+                } else if self.compatible_imm(val as i64, allow_imm) {
+                    src
+                } else {
+                    let tmp = if let Some(wanted) = wanted {
+                        wanted
+                    } else {
+                        let tmp = self.acquire_temp_gpr().unwrap();
+                        temps.push(tmp);
+                        tmp
+                    };
+                    self.assembler.emit_mov_imm(Location::GPR(tmp), val as u64);
+                    Location::GPR(tmp)
+                }
+            }
+            Location::Imm64(val) => {
+                if allow_imm == ImmType::NoneXzr && val == 0 {
+                    Location::GPR(GPR::XzrSp)
+                } else if self.compatible_imm(val as i64, allow_imm) {
+                    // a 64-bit bitmask immediate may not fit in 32 bits
+                    if val <= u32::MAX as u64 {
+                        Location::Imm32(val as u32)
+                    } else {
+                        src
+                    }
+                } else {
+                    let tmp = if let Some(wanted) = wanted {
+                        wanted
+                    } else {
+                        let tmp = self.acquire_temp_gpr().unwrap();
+                        temps.push(tmp);
+                        tmp
+                    };
+                    self.assembler.emit_mov_imm(Location::GPR(tmp), val);
+                    Location::GPR(tmp)
+                }
+            }
+            Location::Memory(_, _) => {
+                let tmp = if let Some(wanted) = wanted {
+                    wanted
+                } else {
+                    let tmp = self.acquire_temp_gpr().unwrap();
+                    temps.push(tmp);
+                    tmp
+                };
+                if read_val {
+                    self.emit_relaxed_ldr(sz, Location::GPR(tmp), src);
+                }
+                Location::GPR(tmp)
+            }
+            _ => panic!("singlepass can't emit location_to_reg {:?} {:?}", sz, src),
+        }
+    }
+    /// Same as `location_to_reg`, for NEON registers.
+    fn location_to_neon(
+        &mut self,
+        sz: Size,
+        src: Location,
+        temps: &mut Vec<NEON>,
+        allow_imm: ImmType,
+        read_val: bool,
+    ) -> Location {
+        match src {
+            Location::SIMD(_) => src,
+            Location::GPR(_) => {
+                let tmp = self.acquire_temp_simd().unwrap();
+                temps.push(tmp);
+                if read_val {
+                    self.assembler.emit_mov(sz, src, Location::SIMD(tmp));
+                }
+                Location::SIMD(tmp)
+            }
+            Location::Imm8(val) => {
+                if self.compatible_imm(val as i64, allow_imm) {
+                    src
+                } else {
+                    let gpr = self.acquire_temp_gpr().unwrap();
+                    let tmp = self.acquire_temp_simd().unwrap();
+                    temps.push(tmp);
+                    self.assembler.emit_mov_imm(Location::GPR(gpr), val as u64);
+                    self.assembler
+                        .emit_mov(sz, Location::GPR(gpr), Location::SIMD(tmp));
+                    self.release_gpr(gpr);
+                    Location::SIMD(tmp)
+                }
+            }
+            Location::Imm32(val) => {
+                if self.compatible_imm(val as i64, allow_imm) {
+                    src
+                } else {
+                    let gpr = self.acquire_temp_gpr().unwrap();
+                    let tmp = self.acquire_temp_simd().unwrap();
+                    temps.push(tmp);
+                    self.assembler.emit_mov_imm(Location::GPR(gpr), val as u64);
+                    self.assembler
+                        .emit_mov(sz, Location::GPR(gpr), Location::SIMD(tmp));
+                    self.release_gpr(gpr);
+                    Location::SIMD(tmp)
+                }
+            }
+            Location::Imm64(val) => {
+                if self.compatible_imm(val as i64, allow_imm) {
+                    src
+                } else {
+                    let gpr = self.acquire_temp_gpr().unwrap();
+                    let tmp = self.acquire_temp_simd().unwrap();
+                    temps.push(tmp);
+                    self.assembler.emit_mov_imm(Location::GPR(gpr), val);
+                    self.assembler
+                        .emit_mov(sz, Location::GPR(gpr), Location::SIMD(tmp));
+                    self.release_gpr(gpr);
+                    Location::SIMD(tmp)
+                }
+            }
+            Location::Memory(_, _) => {
+                let tmp = self.acquire_temp_simd().unwrap();
+                temps.push(tmp);
+                if read_val {
+                    self.emit_relaxed_ldr(sz, Location::SIMD(tmp), src);
+                }
+                Location::SIMD(tmp)
+            }
+            _ => panic!("singlepass can't emit location_to_neon {:?} {:?}", sz, src),
+        }
+    }
+
+    /// Emits a 2-operand instruction `op(src, dst)`, moving operands through temporary
+    /// registers as needed. `src` may stay an immediate if `allow_imm` accepts it.
+    /// If `putback` is set, the result is written back to `dst`.
+    fn emit_relaxed_binop(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location),
+        sz: Size,
+        src: Location,
+        dst: Location,
+        allow_imm: ImmType,
+        putback: bool,
+    ) {
+        let mut temps = vec![];
+        let src = self.location_to_reg(sz, src, &mut temps, allow_imm, true, None);
+        let dest = self.location_to_reg(sz, dst, &mut temps, ImmType::None, true, None);
+        op(&mut self.assembler, sz, src, dest);
+        if dst != dest && putback {
+            self.move_location(sz, dest, dst);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    /// Emits a 3-operand instruction `op(src1, src2, dst)`, moving operands through
+    /// temporary registers as needed. `src2` may stay an immediate if `allow_imm` accepts it.
+    fn emit_relaxed_binop3(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location, Location),
+        sz: Size,
+        src1: Location,
+        src2: Location,
+        dst: Location,
+        allow_imm: ImmType,
+    ) {
+        let mut temps = vec![];
+        let src1 = self.location_to_reg(sz, src1, &mut temps, ImmType::None, true, None);
+        let src2 = self.location_to_reg(sz, src2, &mut temps, allow_imm, true, None);
+        let dest = self.location_to_reg(sz, dst, &mut temps, ImmType::None, false, None);
+        op(&mut self.assembler, sz, src1, src2, dest);
+        if dst != dest {
+            self.move_location(sz, dest, dst);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    /// Same as `emit_relaxed_binop`, for instructions working on NEON registers.
+    fn emit_relaxed_binop_neon(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location),
+        sz: Size,
+        src: Location,
+        dst: Location,
+        putback: bool,
+    ) {
+        let mut temps = vec![];
+        let src = self.location_to_neon(sz, src, &mut temps, ImmType::None, true);
+        let dest = self.location_to_neon(sz, dst, &mut temps, ImmType::None, false);
+        op(&mut self.assembler, sz, src, dest);
+        if dst != dest && putback {
+            self.move_location(sz, dest, dst);
+        }
+        for r in temps {
+            self.release_simd(r);
+        }
+    }
+    /// Same as `emit_relaxed_binop3`, for instructions working on NEON registers.
+    fn emit_relaxed_binop3_neon(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location, Location),
+        sz: Size,
+        src1: Location,
+        src2: Location,
+        dst: Location,
+        allow_imm: ImmType,
+    ) {
+        let mut temps = vec![];
+        let src1 = self.location_to_neon(sz, src1, &mut temps, ImmType::None, true);
+        let src2 = self.location_to_neon(sz, src2, &mut temps, allow_imm, true);
+        let dest = self.location_to_neon(sz, dst, &mut temps, ImmType::None, false);
+        op(&mut self.assembler, sz, src1, src2, dest);
+        if dst != dest {
+            self.move_location(sz, dest, dst);
+        }
+        for r in temps {
+            self.release_simd(r);
+        }
+    }
+    /// Copies the sign of `src2` to `src1` with a bitwise select. The sign mask is
+    /// built with immediate forms (MOVI, plus FNEG of +0.0 for f64) instead of a
+    /// literal pool load.
+    fn emit_neon_copysign(&mut self, sz: Size, src1: Location, src2: Location, dst: Location) {
+        let mut temps = vec![];
+        let src1 = self.location_to_neon(sz, src1, &mut temps, ImmType::None, true);
+        let src2 = self.location_to_neon(sz, src2, &mut temps, ImmType::None, true);
+        let mask = self.acquire_temp_simd().unwrap();
+        match sz {
+            Size::S32 => self
+                .assembler
+                .emit_movi(Size::S32, 0x80, 24, Location::SIMD(mask)),
+            Size::S64 => {
+                self.assembler
+                    .emit_movi(Size::S64, 0, 0, Location::SIMD(mask));
+                self.assembler
+                    .emit_vfneg(Size::S64, Location::SIMD(mask), Location::SIMD(mask));
+            }
+            _ => unreachable!(),
+        }
+        // Sign bit from src2, everything else from src1
+        self.assembler.emit_bsl(Location::SIMD(mask), src2, src1);
+        self.move_location(sz, Location::SIMD(mask), dst);
+        self.release_simd(mask);
+        for r in temps {
+            self.release_simd(r);
+        }
+    }
+    /// Folds the V128 value `src` to 64 bits with UMAXP and moves them to a temporary
+    /// GPR, which is non zero iff any bit of `src` is set. The caller releases it.
+    fn emit_v128_reduce_to_gpr(&mut self, src: Location) -> GPR {
+        let tmp_simd = self.acquire_temp_simd().unwrap();
+        self.assembler
+            .emit_umaxp(Size::S32, src, src, Location::SIMD(tmp_simd));
+        let tmp = self.acquire_temp_gpr().unwrap();
+        self.assembler
+            .emit_mov(Size::S64, Location::SIMD(tmp_simd), Location::GPR(tmp));
+        self.release_simd(tmp_simd);
+        tmp
+    }
+
+    /// Returns true if `offset` can be encoded directly in a `size` wide load or store.
+    fn compatible_offset(&self, sz: Size, offset: i32) -> bool {
+        let scaled = match sz {
+            Size::S8 => ImmType::OffsetByte,
+            Size::S16 => ImmType::OffsetHWord,
+            Size::S32 => ImmType::OffsetWord,
+            Size::S64 => ImmType::OffsetDWord,
+            Size::S128 => ImmType::OffsetQWord,
+        };
+        self.compatible_imm(offset as i64, scaled)
+            || self.compatible_imm(offset as i64, ImmType::UnscaledOffset)
+    }
+    /// Lane-wise integer min/max. NEON has no 64-bit lane form of SMIN/UMIN/SMAX/UMAX,
+    /// so for those lanes a compare mask selects between the operands with BSL.
+    fn emit_v128_minmax(
+        &mut self,
+        lane: Size,
+        signed: bool,
+        max: bool,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) {
+        let mut temps = vec![];
+        let loc_a = self.location_to_neon(Size::S128, loc_a, &mut temps, ImmType::None, true);
+        let loc_b = self.location_to_neon(Size::S128, loc_b, &mut temps, ImmType::None, true);
+        let dest = self.location_to_neon(Size::S128, ret, &mut temps, ImmType::None, false);
+        match (lane, signed, max) {
+            (Size::S64, _, _) => {
+                let mask = Location::SIMD(self.acquire_temp_simd().unwrap());
+                // mask = a > b for max, b > a for min, so that it selects `a`
+                let (src1, src2) = if max { (loc_a, loc_b) } else { (loc_b, loc_a) };
+                if signed {
+                    self.assembler.emit_vcmgt(Size::S64, src1, src2, mask);
+                } else {
+                    self.assembler.emit_vcmhi(Size::S64, src1, src2, mask);
+                }
+                self.assembler.emit_bsl(mask, loc_a, loc_b);
+                self.assembler.emit_mov(Size::S64, mask, dest);
+                if let Location::SIMD(mask) = mask {
+                    self.release_simd(mask);
+                }
+            }
+            (_, true, false) => self.assembler.emit_vsmin(lane, loc_a, loc_b, dest),
+            (_, false, false) => self.assembler.emit_vumin(lane, loc_a, loc_b, dest),
+            (_, true, true) => self.assembler.emit_vsmax(lane, loc_a, loc_b, dest),
+            (_, false, true) => self.assembler.emit_vumax(lane, loc_a, loc_b, dest),
+        }
+        if ret != dest {
+            self.move_location(Size::S128, dest, ret);
+        }
+        for r in temps {
+            self.release_simd(r);
+        }
+    }
+    /// Acquires a temporary GPR that is none of the registers in `live`, which may hold
+    /// values without being reserved, like arguments being set up for a call.
+    fn acquire_temp_gpr_besides(&mut self, live: &[Location]) -> GPR {
+        let unreserved: Vec<GPR> = live
+            .iter()
+            .filter_map(|loc| match loc {
+                Location::GPR(r) if !self.used_gprs.contains(r) => Some(*r),
+                _ => None,
+            })
+            .collect();
+        for r in &unreserved {
+            self.used_gprs.insert(*r);
+        }
+        let tmp = self.acquire_temp_gpr().unwrap();
+        for r in &unreserved {
+            self.used_gprs.remove(r);
+        }
+        tmp
+    }
+    /// Emits a load through `op` into `dst`. When the offset of `addr` is out of the
+    /// encodable range and `dst` is a GPR other than the base, the address is computed in
+    /// `dst` itself, so no other register is touched while arguments are being set up.
+    fn emit_relaxed_ld(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location),
+        sz: Size,
+        dst: Location,
+        addr: Location,
+    ) {
+        match (dst, addr) {
+            (Location::GPR(reg), Location::Memory(base, offset))
+                if !self.compatible_offset(sz, offset) && reg != base && reg != GPR::XzrSp =>
+            {
+                self.assembler.emit_mov_imm(dst, (offset as i64) as u64);
+                self.assembler
+                    .emit_add(Size::S64, Location::GPR(base), dst, dst);
+                op(&mut self.assembler, sz, dst, Location::Memory(reg, 0));
+            }
+            _ => self.emit_relaxed_ldst(op, sz, dst, addr, &[]),
+        }
+    }
+    /// Emits a load or store through `op`, computing the address in a temporary register
+    /// when the offset of `addr` is out of the encodable range. The temporary is none of
+    /// `live`, the registers the caller has written without reserving them.
+    fn emit_relaxed_ldst(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location),
+        sz: Size,
+        reg: Location,
+        addr: Location,
+        live: &[Location],
+    ) {
+        match addr {
+            Location::Memory(base, offset) if !self.compatible_offset(sz, offset) => {
+                let mut besides = vec![reg, Location::GPR(base)];
+                besides.extend_from_slice(live);
+                let tmp = self.acquire_temp_gpr_besides(&besides);
+                self.assembler
+                    .emit_mov_imm(Location::GPR(tmp), (offset as i64) as u64);
+                self.assembler.emit_add(
+                    Size::S64,
+                    Location::GPR(base),
+                    Location::GPR(tmp),
+                    Location::GPR(tmp),
+                );
+                op(&mut self.assembler, sz, reg, Location::Memory(tmp, 0));
+                self.release_gpr(tmp);
+            }
+            _ => op(&mut self.assembler, sz, reg, addr),
+        }
+    }
+    /// Adds or subtracts `delta` to SP with `op`. Deltas under 16MiB take a shifted and an
+    /// unshifted immediate, larger ones go through X17, so no argument register is touched.
+    fn emit_sp_delta(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location, Location),
+        delta: u32,
+    ) {
+        debug_assert!(delta % 16 == 0, "SP must stay 16-byte aligned");
+        let sp = Location::GPR(GPR::XzrSp);
+        if is_add_sub_imm_lsl12(delta as u64 & !0xfff) {
+            let (high, low) = (delta & !0xfff, delta & 0xfff);
+            if high != 0 {
+                op(
+                    &mut self.assembler,
+                    Size::S64,
+                    sp,
+                    Location::Imm32(high),
+                    sp,
+                );
+            }
+            if low != 0 || high == 0 {
+                op(&mut self.assembler, Size::S64, sp, Location::Imm32(low), sp);
+            }
+        } else {
+            self.assembler
+                .emit_mov_imm(Location::GPR(GPR::X17), delta as u64);
+            op(
+                &mut self.assembler,
+                Size::S64,
+                sp,
+                Location::GPR(GPR::X17),
+                sp,
+            );
+        }
+    }
+    /// Pushes two registers into one 16-byte slot, `first` at the lower address.
+    fn emit_double_push(&mut self, first: Location, second: Location) {
+        self.assembler
+            .emit_stpbd(Size::S64, first, second, GPR::XzrSp, 16);
+    }
+    /// Pops the slot pushed by `emit_double_push` with the same registers.
+    fn emit_double_pop(&mut self, first: Location, second: Location) {
+        self.assembler
+            .emit_ldpai(Size::S64, first, second, GPR::XzrSp, 16);
+    }
+    /// Pushes `first` then `second` into one 16-byte slot, like two 8-byte pushes would,
+    /// going through temporaries for the values that aren't in a GPR.
+    fn emit_pair_push(&mut self, first: Location, second: Location) {
+        let mut temps = vec![];
+        let first = self.pair_operand(first, second, true, &mut temps);
+        let second = self.pair_operand(second, first, true, &mut temps);
+        self.emit_double_push(second, first);
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    /// Gets one of the values of a pair of pushes or pops in a GPR, through a temporary that
+    /// is not the other one if `loc` isn't a GPR. The value is only read for a push.
+    fn pair_operand(
+        &mut self,
+        loc: Location,
+        other: Location,
+        read_val: bool,
+        temps: &mut Vec<GPR>,
+    ) -> Location {
+        if let Location::GPR(_) = loc {
+            return loc;
+        }
+        let tmp = self.acquire_temp_gpr_besides(&[other]);
+        temps.push(tmp);
+        if read_val {
+            self.move_location(Size::S64, loc, Location::GPR(tmp));
+        }
+        Location::GPR(tmp)
+    }
+    fn emit_relaxed_ldr(&mut self, sz: Size, dst: Location, src: Location) {
+        self.emit_relaxed_ld(Assembler::emit_ldr, sz, dst, src);
+    }
+    fn emit_relaxed_str(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_relaxed_ldst(Assembler::emit_str, sz, src, dst, &[]);
+    }
+    /// Computes `base + offset` into `dst`, through a temporary register if the offset
+    /// does not fit in an ADD or SUB immediate.
+    fn emit_memory_address(&mut self, base: GPR, offset: i32, dst: GPR) {
+        let magnitude = (offset as i64).unsigned_abs();
+        if is_add_sub_imm(magnitude) {
+            if offset < 0 {
+                self.assembler.emit_sub(
+                    Size::S64,
+                    Location::GPR(base),
+                    Location::Imm32(magnitude as u32),
+                    Location::GPR(dst),
+                );
+            } else {
+                self.assembler.emit_add(
+                    Size::S64,
+                    Location::GPR(base),
+                    Location::Imm32(magnitude as u32),
+                    Location::GPR(dst),
+                );
+            }
+        } else {
+            let tmp = self.acquire_temp_gpr_besides(&[Location::GPR(base)]);
+            self.assembler
+                .emit_mov_imm(Location::GPR(tmp), (offset as i64) as u64);
+            self.assembler.emit_add(
+                Size::S64,
+                Location::GPR(base),
+                Location::GPR(tmp),
+                Location::GPR(dst),
+            );
+            self.release_gpr(tmp);
+        }
+    }
+    /// Loads `addr` with `op` into `dst`, going through a temporary register if `dst` is in memory.
+    fn emit_relaxed_load(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location),
+        sz: Size,
+        dst: Location,
+        addr: Location,
+    ) {
+        match dst {
+            Location::GPR(_) | Location::SIMD(_) => self.emit_relaxed_ld(op, sz, dst, addr),
+            Location::Memory(_, _) => {
+                let tmp = self.acquire_temp_gpr().unwrap();
+                self.emit_relaxed_ld(op, sz, Location::GPR(tmp), addr);
+                self.emit_relaxed_str(sz, Location::GPR(tmp), dst);
+                self.release_gpr(tmp);
+            }
+            _ => panic!("singlepass can't emit relaxed load {:?} {:?}", sz, dst),
+        }
+    }
+    /// Stores `src` to `addr` with `op`, going through a temporary register if `src` is not one.
+    fn emit_relaxed_store(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location),
+        sz: Size,
+        src: Location,
+        addr: Location,
+    ) {
+        let mut temps = vec![];
+        let src = match src {
+            Location::SIMD(_) => src,
+            _ => self.location_to_reg(sz, src, &mut temps, ImmType::NoneXzr, true, None),
+        };
+        self.emit_relaxed_ldst(op, sz, src, addr, &[]);
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+
+    /// Emits a division, branching to `integer_division_by_zero` on a zero divisor.
+    /// A signed division also traps on `MIN / -1`. Returns the offset of the DIV opcode.
+    fn emit_binop_div(
+        &mut self,
+        sz: Size,
+        signed: bool,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        let mut temps = vec![];
+        let src1 = self.location_to_reg(sz, loc_a, &mut temps, ImmType::None, true, None);
+        let src2 = self.location_to_reg(sz, loc_b, &mut temps, ImmType::None, true, None);
+        let dest = self.location_to_reg(sz, ret, &mut temps, ImmType::None, false, None);
+
+        self.assembler
+            .emit_cbz_label(sz, src2, integer_division_by_zero);
+        if signed {
+            let no_overflow = self.get_label();
+            // `src1 - 1` only overflows for MIN, which then traps if `src2` is -1
+            self.assembler.emit_cmp(sz, Location::Imm32(1), src1);
+            self.assembler.emit_bcond_label(Condition::Vc, no_overflow);
+            let tmp = self.acquire_temp_gpr().unwrap();
+            let minus_one = match sz {
+                Size::S32 => 0xffff_ffff,
+                _ => u64::MAX,
+            };
+            self.assembler.emit_mov_imm(Location::GPR(tmp), minus_one);
+            self.assembler.emit_cmp(sz, Location::GPR(tmp), src2);
+            self.release_gpr(tmp);
+            self.assembler.emit_bcond_label(Condition::Ne, no_overflow);
+            let offset = self.mark_instruction_with_trap_code(TrapCode::IntegerOverflow);
+            self.emit_illegal_op();
+            self.mark_instruction_address_end(offset);
+            self.emit_label(no_overflow);
+        }
+        let offset = self.mark_instruction_with_trap_code(TrapCode::IntegerOverflow);
+        if signed {
+            self.assembler.emit_sdiv(sz, src1, src2, dest);
+        } else {
+            self.assembler.emit_udiv(sz, src1, src2, dest);
+        }
+        if ret != dest {
+            self.move_location(sz, dest, ret);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+        offset
+    }
+    /// Emits a remainder, branching to `integer_division_by_zero` on a zero divisor.
+    /// Returns the offset of the DIV opcode.
+    fn emit_binop_rem(
+        &mut self,
+        sz: Size,
+        signed: bool,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        let mut temps = vec![];
+        let src1 = self.location_to_reg(sz, loc_a, &mut temps, ImmType::None, true, None);
+        let src2 = self.location_to_reg(sz, loc_b, &mut temps, ImmType::None, true, None);
+        let dest = self.location_to_reg(sz, ret, &mut temps, ImmType::None, false, None);
+        let dest = if dest == src1 || dest == src2 {
+            let tmp = self.acquire_temp_gpr().unwrap();
+            temps.push(tmp);
+            Location::GPR(tmp)
+        } else {
+            dest
+        };
+
+        self.assembler
+            .emit_cbz_label(sz, src2, integer_division_by_zero);
+        let offset = self.mark_instruction_with_trap_code(TrapCode::IntegerOverflow);
+        if signed {
+            // `sdiv` gives MIN for MIN / -1, and the remainder computed below is then 0.
+            self.assembler.emit_sdiv(sz, src1, src2, dest);
+        } else {
+            self.assembler.emit_udiv(sz, src1, src2, dest);
+        }
+        // remainder : src1 - (src1/src2)*src2
+        self.assembler.emit_msub(sz, dest, src2, src1, dest);
+        if ret != dest {
+            self.move_location(sz, dest, ret);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+        offset
+    }
+
+    fn emit_binop_i32(
+        &mut self,
+        f: fn(&mut Assembler, Size, Location, Location),
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        allow_imm: ImmType,
+    ) {
+        if loc_a != ret {
+            let tmp = self.acquire_temp_gpr().unwrap();
+            self.emit_relaxed_mov(Size::S32, loc_a, Location::GPR(tmp));
+            self.emit_relaxed_binop(f, Size::S32, loc_b, Location::GPR(tmp), allow_imm, true);
+            self.emit_relaxed_mov(Size::S32, Location::GPR(tmp), ret);
+            self.release_gpr(tmp);
+        } else {
+            self.emit_relaxed_binop(f, Size::S32, loc_b, ret, allow_imm, true);
+        }
+    }
+    /// Emits a 32-bit shift or rotation. The register forms already use the count
+    /// modulo 32, as wasm requires, so only immediate counts are masked here.
+    fn emit_shift_i32(
+        &mut self,
+        f: fn(&mut Assembler, Size, Location, Location, Location),
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) {
+        let loc_b = match loc_b {
+            Location::Imm32(imm) => Location::Imm8((imm & 31) as u8),
+            _ => loc_b,
+        };
+        self.emit_relaxed_binop3(f, Size::S32, loc_a, loc_b, ret, ImmType::Shift32);
+    }
+    /// Emits a left rotation, as a right rotation by `width - count`.
+    fn emit_rol(&mut self, sz: Size, loc_a: Location, loc_b: Location, ret: Location) {
+        let (bits, allow_imm) = match sz {
+            Size::S32 => (32, ImmType::Shift32),
+            _ => (64, ImmType::Shift64),
+        };
+        let imm = match loc_b {
+            Location::Imm32(imm) => Some(imm as u64),
+            Location::Imm64(imm) => Some(imm),
+            _ => None,
+        };
+        if let Some(imm) = imm {
+            // `(width - count) % width` keeps a zero rotation from becoming a rotation by the width
+            let count = Location::Imm8(((bits - imm % bits) % bits) as u8);
+            self.emit_relaxed_binop3(Assembler::emit_ror, sz, loc_a, count, ret, allow_imm);
+        } else {
+            // RORV uses `-count` modulo the width, which is 0 for a zero rotation
+            let mut temps = vec![];
+            let src = self.location_to_reg(sz, loc_b, &mut temps, ImmType::None, true, None);
+            let tmp = self.acquire_temp_gpr().unwrap();
+            self.assembler.emit_neg(sz, src, Location::GPR(tmp));
+            self.emit_relaxed_binop3(
+                Assembler::emit_ror,
+                sz,
+                loc_a,
+                Location::GPR(tmp),
+                ret,
+                ImmType::None,
+            );
+            self.release_gpr(tmp);
+            for r in temps {
+                self.release_gpr(r);
+            }
+        }
+    }
+    /// Counts the leading zeros of `loc`, or the trailing zeros if `trailing` is set.
+    /// CLZ of a zero value is the register width, as wasm requires.
+    fn emit_count_zeros(&mut self, sz: Size, trailing: bool, loc: Location, ret: Location) {
+        let mut temps = vec![];
+        let src = self.location_to_reg(sz, loc, &mut temps, ImmType::None, true, None);
+        let dest = self.location_to_reg(sz, ret, &mut temps, ImmType::None, false, None);
+        if trailing {
+            // the trailing zeros are the leading zeros of the reversed bits
+            self.assembler.emit_rbit(sz, src, dest);
+            self.assembler.emit_clz(sz, dest, dest);
+        } else {
+            self.assembler.emit_clz(sz, src, dest);
+        }
+        if ret != dest {
+            self.move_location(sz, dest, ret);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    /// Counts the set bits of `loc`. There is no scalar popcount, so the bytes are
+    /// counted with CNT in a NEON register and summed with ADDV.
+    fn emit_popcnt(&mut self, sz: Size, loc: Location, ret: Location) {
+        let mut temps = vec![];
+        let src = self.location_to_reg(sz, loc, &mut temps, ImmType::None, true, None);
+        let tmp = self.acquire_temp_simd().unwrap();
+        // the FMOV zeroes the rest of the vector, so only the bytes of `src` are counted
+        self.assembler.emit_mov(sz, src, Location::SIMD(tmp));
+        self.assembler
+            .emit_cnt(Location::SIMD(tmp), Location::SIMD(tmp));
+        self.assembler
+            .emit_addv(Location::SIMD(tmp), Location::SIMD(tmp));
+        let dest = self.location_to_reg(sz, ret, &mut temps, ImmType::None, false, None);
+        self.assembler
+            .emit_mov(Size::S32, Location::SIMD(tmp), dest);
+        if ret != dest {
+            self.move_location(sz, dest, ret);
+        }
+        self.release_simd(tmp);
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    fn emit_cmpop_i32_dynamic_b(
+        &mut self,
+        c: Condition,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) {
+        match ret {
+            Location::GPR(_) => {
+                self.emit_relaxed_cmp(Size::S32, loc_b, loc_a);
+                self.assembler.emit_cset(Size::S32, ret, c);
+            }
+            Location::Memory(_, _) => {
+                let tmp = self.acquire_temp_gpr().unwrap();
+                self.emit_relaxed_cmp(Size::S32, loc_b, loc_a);
+                self.assembler.emit_cset(Size::S32, Location::GPR(tmp), c);
+                self.move_location(Size::S32, Location::GPR(tmp), ret);
+                self.release_gpr(tmp);
+            }
+            _ => {
+                unreachable!();
+            }
+        }
+    }
+
+    /// I64 comparison with. The 64-bit operands are compared, the result is a 0/1 i32.
+    fn emit_cmpop_i64_dynamic_b(
+        &mut self,
+        c: Condition,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) {
+        match ret {
+            Location::GPR(_) => {
+                self.emit_relaxed_cmp(Size::S64, loc_b, loc_a);
+                self.assembler.emit_cset(Size::S32, ret, c);
+            }
+            Location::Memory(_, _) => {
+                let tmp = self.acquire_temp_gpr().unwrap();
+                self.emit_relaxed_cmp(Size::S64, loc_b, loc_a);
+                self.assembler.emit_cset(Size::S32, Location::GPR(tmp), c);
+                self.move_location(Size::S32, Location::GPR(tmp), ret);
+                self.release_gpr(tmp);
+            }
+            _ => {
+                unreachable!();
+            }
+        }
+    }
+    /// Compares the floats of size `sz` in `loc_a` and `loc_b`, setting `ret` to 1 if `c`
+    /// holds. FCMP flags unordered operands as C and V set, N and Z clear: `Mi`, `Ls`,
+    /// `Gt` and `Ge` are then false, and `Ne` is true.
+    fn emit_cmpop_f_dynamic_b(
+        &mut self,
+        sz: Size,
+        c: Condition,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) {
+        let mut temps = vec![];
+        let loc_a = self.location_to_neon(sz, loc_a, &mut temps, ImmType::None, true);
+        let loc_b = self.location_to_neon(sz, loc_b, &mut temps, ImmType::None, true);
+        self.assembler.emit_fcmp(sz, loc_a, loc_b);
+        for r in temps {
+            self.release_simd(r);
+        }
+        match ret {
+            Location::GPR(_) => {
+                self.assembler.emit_cset(Size::S32, ret, c);
+            }
+            Location::Memory(_, _) => {
+                let tmp = self.acquire_temp_gpr().unwrap();
+                self.assembler.emit_cset(Size::S32, Location::GPR(tmp), c);
+                self.move_location(Size::S32, Location::GPR(tmp), ret);
+                self.release_gpr(tmp);
+            }
+            _ => {
+                unreachable!();
+            }
+        }
+    }
+    /// Emits a 64-bit shift or rotation. The register forms already use the count
+    /// modulo 64, as wasm requires, so only immediate counts are masked here.
+    fn emit_shift_i64(
+        &mut self,
+        f: fn(&mut Assembler, Size, Location, Location, Location),
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) {
+        let loc_b = match loc_b {
+            Location::Imm64(imm) => Location::Imm8((imm & 63) as u8),
+            _ => loc_b,
+        };
+        self.emit_relaxed_binop3(f, Size::S64, loc_a, loc_b, ret, ImmType::Shift64);
+    }
+
+    /// Converts the float in `loc` to an integer of size `sz_out` in `ret`, saturating.
+    fn emit_relaxed_fcvt_to_int(
+        &mut self,
+        sz_in: Size,
+        loc: Location,
+        sz_out: Size,
+        ret: Location,
+        signed: bool,
+    ) {
+        let mut gprs = vec![];
+        let mut neons = vec![];
+        let src = self.location_to_neon(sz_in, loc, &mut neons, ImmType::None, true);
+        let dest = self.location_to_reg(sz_out, ret, &mut gprs, ImmType::None, false, None);
+        if signed {
+            self.assembler.emit_fcvtzs(sz_in, src, sz_out, dest);
+        } else {
+            self.assembler.emit_fcvtzu(sz_in, src, sz_out, dest);
+        }
+        if ret != dest {
+            self.move_location(sz_out, dest, ret);
+        }
+        for r in gprs {
+            self.release_gpr(r);
+        }
+        for r in neons {
+            self.release_simd(r);
+        }
+    }
+
+    /// Extends the low `sz_src` bits of `src` to `sz_dst` in `dst`. Sources in memory
+    /// are loaded at full register width first, the extension only looks at the low bits.
+    fn emit_relaxed_extension(
+        &mut self,
+        sz_src: Size,
+        src: Location,
+        sz_dst: Size,
+        dst: Location,
+        signed: bool,
+    ) {
+        let mut temps = vec![];
+        let load_sz = if sz_src == Size::S64 {
+            Size::S64
+        } else {
+            Size::S32
+        };
+        let src = self.location_to_reg(load_sz, src, &mut temps, ImmType::None, true, None);
+        let dest = self.location_to_reg(sz_dst, dst, &mut temps, ImmType::None, false, None);
+        match (sz_src, sz_dst, signed) {
+            (Size::S8, _, false) => self.assembler.emit_uxtb(sz_dst, src, dest),
+            (Size::S16, _, false) => self.assembler.emit_uxth(sz_dst, src, dest),
+            (Size::S8, _, true) => self.assembler.emit_sxtb(sz_dst, src, dest),
+            (Size::S16, _, true) => self.assembler.emit_sxth(sz_dst, src, dest),
+            (Size::S32, Size::S64, true) => self.assembler.emit_sxtw(sz_dst, src, dest),
+            // MOV (32-bit) clears the upper half of the register
+            (Size::S32, _, _) => self.assembler.emit_mov(Size::S32, src, dest),
+            (Size::S64, Size::S64, _) => self.assembler.emit_mov(Size::S64, src, dest),
+            _ => panic!(
+                "singlepass can't emit extension {:?} {:?} {:?}",
+                sz_src, sz_dst, signed
+            ),
+        }
+        if dst != dest {
+            self.move_location(sz_dst, dest, dst);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+
+    /// Converts the integer of size `sz_in` in `loc` to a float of size `sz_out` in `ret`.
+    fn emit_relaxed_int_to_fcvt(
+        &mut self,
+        sz_in: Size,
+        loc: Location,
+        sz_out: Size,
+        ret: Location,
+        signed: bool,
+    ) {
+        let mut gprs = vec![];
+        let mut neons = vec![];
+        let src = self.location_to_reg(sz_in, loc, &mut gprs, ImmType::NoneXzr, true, None);
+        let dest = self.location_to_neon(sz_out, ret, &mut neons, ImmType::None, false);
+        if signed {
+            self.assembler.emit_scvtf(sz_in, src, sz_out, dest);
+        } else {
+            self.assembler.emit_ucvtf(sz_in, src, sz_out, dest);
+        }
+        if ret != dest {
+            self.move_location(sz_out, dest, ret);
+        }
+        for r in gprs {
+            self.release_gpr(r);
+        }
+        for r in neons {
+            self.release_simd(r);
+        }
+    }
+
+    /// Converts the float in `loc` to an integer of size `sz_out` in `ret`, trapping
+    /// on NaN and on values outside of the range of the integer.
+    fn emit_relaxed_fcvt_to_int_trap(
+        &mut self,
+        sz_in: Size,
+        loc: Location,
+        sz_out: Size,
+        ret: Location,
+        signed: bool,
+    ) {
+        let mut neons = vec![];
+        let src = self.location_to_neon(sz_in, loc, &mut neons, ImmType::None, true);
+        match (sz_in, sz_out, signed) {
+            (Size::S32, Size::S32, true) => {
+                self.emit_f32_int_conv_check_trap(src, GEF32_LT_I32_MIN, LEF32_GT_I32_MAX)
+            }
+            (Size::S32, Size::S32, false) => {
+                self.emit_f32_int_conv_check_trap(src, GEF32_LT_U32_MIN, LEF32_GT_U32_MAX)
+            }
+            (Size::S64, Size::S32, true) => {
+                self.emit_f64_int_conv_check_trap(src, GEF64_LT_I32_MIN, LEF64_GT_I32_MAX)
+            }
+            (Size::S64, Size::S32, false) => {
+                self.emit_f64_int_conv_check_trap(src, GEF64_LT_U32_MIN, LEF64_GT_U32_MAX)
+            }
+            (Size::S32, Size::S64, true) => {
+                self.emit_f32_int_conv_check_trap(src, GEF32_LT_I64_MIN, LEF32_GT_I64_MAX)
+            }
+            (Size::S32, Size::S64, false) => {
+                self.emit_f32_int_conv_check_trap(src, GEF32_LT_U64_MIN, LEF32_GT_U64_MAX)
+            }
+            (Size::S64, Size::S64, true) => {
+                self.emit_f64_int_conv_check_trap(src, GEF64_LT_I64_MIN, LEF64_GT_I64_MAX)
+            }
+            // 2^64 is a power of two, so the bound is exact in both float sizes.
+            (Size::S64, Size::S64, false) => {
+                self.emit_f64_int_conv_check_trap(src, GEF64_LT_U64_MIN, LEF64_GT_U64_MAX)
+            }
+            _ => unreachable!(),
+        }
+        self.emit_relaxed_fcvt_to_int(sz_in, src, sz_out, ret, signed);
+        for r in neons {
+            self.release_simd(r);
+        }
+    }
+
+    // Checks for underflow/overflow/nan.
+    fn emit_f32_int_conv_check(
+        &mut self,
+        reg: Location,
+        lower_bound: f32,
+        upper_bound: f32,
+        underflow_label: Label,
+        overflow_label: Label,
+        nan_label: Label,
+        succeed_label: Label,
+    ) {
+        self.emit_int_conv_check(
+            Size::S32,
+            reg,
+            Location::Imm32(lower_bound.to_bits()),
+            Location::Imm32(upper_bound.to_bits()),
+            underflow_label,
+            overflow_label,
+            nan_label,
+            succeed_label,
+        );
+    }
+    // Checks for underflow/overflow/nan.
+    fn emit_f64_int_conv_check(
+        &mut self,
+        reg: Location,
+        lower_bound: f64,
+        upper_bound: f64,
+        underflow_label: Label,
+        overflow_label: Label,
+        nan_label: Label,
+        succeed_label: Label,
+    ) {
+        self.emit_int_conv_check(
+            Size::S64,
+            reg,
+            Location::Imm64(lower_bound.to_bits()),
+            Location::Imm64(upper_bound.to_bits()),
+            underflow_label,
+            overflow_label,
+            nan_label,
+            succeed_label,
+        );
+    }
+    /// Branches on how the float of size `sz` in `reg` compares to the bounds, which
+    /// hold the bits of floats of the same size. Values on a bound are out of range.
+    fn emit_int_conv_check(
+        &mut self,
+        sz: Size,
+        reg: Location,
+        lower_bound: Location,
+        upper_bound: Location,
+        underflow_label: Label,
+        overflow_label: Label,
+        nan_label: Label,
+        succeed_label: Label,
+    ) {
+        let tmp_x = self.acquire_temp_simd().unwrap();
+
+        // NaN. Unordered compares set V, and would also pass the LE test below.
+        self.assembler.emit_fcmp(sz, reg, reg);
+        self.assembler.emit_bcond_label(Condition::Vs, nan_label);
+
+        // Underflow.
+        self.move_location(sz, lower_bound, Location::SIMD(tmp_x));
+        self.assembler.emit_fcmp(sz, reg, Location::SIMD(tmp_x));
+        self.assembler
+            .emit_bcond_label(Condition::Le, underflow_label);
+
+        // Overflow.
+        self.move_location(sz, upper_bound, Location::SIMD(tmp_x));
+        self.assembler.emit_fcmp(sz, reg, Location::SIMD(tmp_x));
+        self.assembler
+            .emit_bcond_label(Condition::Ge, overflow_label);
+
+        self.assembler.emit_b_label(succeed_label);
+
+        self.release_simd(tmp_x);
+    }
+
+    // Checks for underflow/overflow/nan before a trapping float to int conversion.
+    fn emit_f32_int_conv_check_trap(&mut self, reg: Location, lower_bound: f32, upper_bound: f32) {
+        let trap_overflow = self.get_label();
+        let trap_badconv = self.get_label();
+        let end = self.get_label();
+
+        self.emit_f32_int_conv_check(
+            reg,
+            lower_bound,
+            upper_bound,
+            trap_overflow,
+            trap_overflow,
+            trap_badconv,
+            end,
+        );
+        self.emit_int_conv_traps(trap_overflow, trap_badconv, end);
+    }
+    fn emit_f64_int_conv_check_trap(&mut self, reg: Location, lower_bound: f64, upper_bound: f64) {
+        let trap_overflow = self.get_label();
+        let trap_badconv = self.get_label();
+        let end = self.get_label();
+
+        self.emit_f64_int_conv_check(
+            reg,
+            lower_bound,
+            upper_bound,
+            trap_overflow,
+            trap_overflow,
+            trap_badconv,
+            end,
+        );
+        self.emit_int_conv_traps(trap_overflow, trap_badconv, end);
+    }
+    /// Emits the trap pads an int conversion check branches to, followed by `end`.
+    fn emit_int_conv_traps(&mut self, trap_overflow: Label, trap_badconv: Label, end: Label) {
+        self.emit_label(trap_overflow);
+        let offset = self.mark_instruction_with_trap_code(TrapCode::IntegerOverflow);
+        self.emit_illegal_op();
+        self.mark_instruction_address_end(offset);
+
+        self.emit_label(trap_badconv);
+        let offset = self.mark_instruction_with_trap_code(TrapCode::BadConversionToInteger);
+        self.emit_illegal_op();
+        self.mark_instruction_address_end(offset);
+
+        self.emit_label(end);
+    }
+
+    fn memory_op<F: FnOnce(&mut Self, GPR)>(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        check_alignment: bool,
+        value_size: usize,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+        cb: F,
+    ) {
+        // Imported memories require one level of indirection.
+        let tmp_definition = if imported_memories {
+            let tmp = self.acquire_temp_gpr().unwrap();
+            self.emit_relaxed_ldr(
+                Size::S64,
+                Location::GPR(tmp),
+                Location::Memory(self.get_vmctx_reg(), offset),
+            );
+            Some(tmp)
+        } else {
+            None
+        };
+        let (base_loc, bound_loc) = match tmp_definition {
+            Some(tmp) => (Location::Memory(tmp, 0), Location::Memory(tmp, 8)),
+            None => (
+                Location::Memory(self.get_vmctx_reg(), offset),
+                Location::Memory(self.get_vmctx_reg(), offset + 8),
+            ),
+        };
+
+        self.bounded_memory_op(
+            addr,
+            memarg,
+            check_alignment,
+            value_size,
+            base_loc,
+            if need_check { Some(bound_loc) } else { None },
+            TrapCode::HeapAccessOutOfBounds,
+            heap_access_oob,
+            cb,
+        );
+
+        if let Some(tmp) = tmp_definition {
+            self.release_gpr(tmp);
+        }
+    }
+
+    /// Computes the effective address of a wasm memory access relative to `base_loc`
+    /// and passes it to `cb` in a temporary register.
+    ///
+    /// `bound_loc` holds the size in bytes of the region starting at the base. When
+    /// it is given, accesses past the end of the region branch to `oob`, as do offset
+    /// overflows. Misaligned addresses trap with `UnalignedAtomic` when `check_alignment`
+    /// is set. Faults raised by the code emitted by `cb` are reported with `trap_code`.
+    pub fn bounded_memory_op<F: FnOnce(&mut Self, GPR)>(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        check_alignment: bool,
+        value_size: usize,
+        base_loc: Location,
+        bound_loc: Option<Location>,
+        trap_code: TrapCode,
+        oob: Label,
+        cb: F,
+    ) {
+        let tmp_base = self.acquire_temp_gpr().unwrap();
+        let tmp_bound = self.acquire_temp_gpr().unwrap();
+
+        // Load base into temporary register.
+        self.move_location(Size::S64, base_loc, Location::GPR(tmp_base));
+
+        // Load bound into temporary register, if needed.
+        if let Some(bound_loc) = bound_loc {
+            self.move_location(Size::S64, bound_loc, Location::GPR(tmp_bound));
+
+            // Wasm -> Effective.
+            // Assuming we never underflow - should always be true on Linux/macOS and Windows >=8,
+            // since the first page from 0x0 to 0x1000 is not accepted by mmap.
+
+            // Since the upper bound of the memory is (exclusively) `tmp_bound + tmp_base`,
+            // the maximum allowed beginning of word is (inclusively)
+            // `tmp_bound + tmp_base - value_size`.
+            self.assembler.emit_add(
+                Size::S64,
+                Location::GPR(tmp_bound),
+                Location::GPR(tmp_base),
+                Location::GPR(tmp_bound),
+            );
+            self.assembler.emit_sub(
+                Size::S64,
+                Location::GPR(tmp_bound),
+                Location::Imm32(value_size as u32),
+                Location::GPR(tmp_bound),
+            );
+        }
+
+        // Load effective address.
+        let tmp_addr = self.acquire_temp_gpr().unwrap();
+        self.move_location(Size::S32, addr, Location::GPR(tmp_addr));
+
+        // Add offset to memory address.
+        if memarg.offset != 0 {
+            if is_add_sub_imm(memarg.offset as u64) {
+                self.assembler.emit_adds(
+                    Size::S32,
+                    Location::Imm32(memarg.offset),
+                    Location::GPR(tmp_addr),
+                    Location::GPR(tmp_addr),
+                );
+            } else {
+                let tmp_offset = self.acquire_temp_gpr().unwrap();
+                self.assembler
+                    .emit_mov_imm(Location::GPR(tmp_offset), memarg.offset as u64);
+                self.assembler.emit_adds(
+                    Size::S32,
+                    Location::GPR(tmp_offset),
+                    Location::GPR(tmp_addr),
+                    Location::GPR(tmp_addr),
+                );
+                self.release_gpr(tmp_offset);
+            }
+
+            // Trap if offset calculation overflowed.
+            self.assembler.emit_bcond_label(Condition::Cs, oob);
+        }
+
+        // Wasm linear memory -> real memory
+        self.assembler.emit_add(
+            Size::S64,
+            Location::GPR(tmp_base),
+            Location::GPR(tmp_addr),
+            Location::GPR(tmp_addr),
+        );
+
+        if bound_loc.is_some() {
+            // Trap if the end address of the requested area is above that of the linear memory.
+            self.assembler
+                .emit_cmp(Size::S64, Location::GPR(tmp_bound), Location::GPR(tmp_addr));
+
+            // `tmp_bound` is inclusive. So trap only if `tmp_addr > tmp_bound`.
+            self.assembler.emit_bcond_label(Condition::Hi, oob);
+        }
+
+        self.release_gpr(tmp_bound);
+        self.release_gpr(tmp_base);
+
+        // `memarg.align` is a log2 hint, atomics must be aligned to their full width.
+        let align = value_size as u64;
+        if check_alignment && align != 1 {
+            let tmp_aligncheck = self.acquire_temp_gpr().unwrap();
+            self.assembler
+                .emit_mov_imm(Location::GPR(tmp_aligncheck), align - 1);
+            self.assembler.emit_tst(
+                Size::S64,
+                Location::GPR(tmp_addr),
+                Location::GPR(tmp_aligncheck),
+            );
+            let unaligned = self.unaligned_atomic_label();
+            self.assembler.emit_bcond_label(Condition::Ne, unaligned);
+            self.release_gpr(tmp_aligncheck);
+        }
+        let begin = self.assembler.get_offset().0;
+        cb(self, tmp_addr);
+        let end = self.assembler.get_offset().0;
+        self.mark_address_range_with_trap_code(trap_code, begin, end);
+
+        self.release_gpr(tmp_addr);
+    }
+
+    /// The label misaligned atomic accesses branch to, emitted by `finalize_function`.
+    fn unaligned_atomic_label(&mut self) -> Label {
+        match self.unaligned_atomic {
+            Some(label) => label,
+            None => {
+                let label = self.assembler.get_label();
+                self.unaligned_atomic = Some(label);
+                label
+            }
+        }
+    }
+
+    /// Load-acquire exclusive of `memory_sz` bytes from `addr`, zero extended into `dst`.
+    fn emit_load_exclusive(&mut self, memory_sz: Size, dst: Location, addr: Location) {
+        match memory_sz {
+            Size::S8 => self.assembler.emit_ldaxrb(Size::S32, dst, addr),
+            Size::S16 => self.assembler.emit_ldaxrh(Size::S32, dst, addr),
+            _ => self.assembler.emit_ldaxr(memory_sz, dst, addr),
+        }
+    }
+    /// Store-release exclusive of the low `memory_sz` bytes of `src` to `addr`,
+    /// setting `status` to 0 on success.
+    fn emit_store_exclusive(
+        &mut self,
+        memory_sz: Size,
+        status: Location,
+        src: Location,
+        addr: Location,
+    ) {
+        match memory_sz {
+            Size::S8 => self.assembler.emit_stlxrb(Size::S32, status, src, addr),
+            Size::S16 => self.assembler.emit_stlxrh(Size::S32, status, src, addr),
+            _ => self.assembler.emit_stlxr(memory_sz, status, src, addr),
+        }
+    }
+
+    /// Emits a `LDAXR`/`STLXR` loop on the address computed by `memory_op` that replaces
+    /// the memory value with `op(old, loc)`, storing the previous memory value in `ret`.
+    fn emit_atomic_binop(
+        &mut self,
+        op: fn(&mut Assembler, Size, Location, Location, Location),
+        sz: Size,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        memory_sz: Size,
+        value_size: usize,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        let mut temps = vec![];
+        let value = self.location_to_reg(sz, loc, &mut temps, ImmType::None, true, None);
+
+        self.memory_op(
+            target,
+            memarg,
+            true,
+            value_size,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                let old = this.acquire_temp_gpr().unwrap();
+                let new = this.acquire_temp_gpr().unwrap();
+                let status = this.acquire_temp_gpr().unwrap();
+                let retry = this.assembler.get_label();
+
+                this.emit_label(retry);
+                this.emit_load_exclusive(memory_sz, Location::GPR(old), Location::GPR(addr));
+                op(
+                    &mut this.assembler,
+                    sz,
+                    Location::GPR(old),
+                    value,
+                    Location::GPR(new),
+                );
+                this.emit_store_exclusive(
+                    memory_sz,
+                    Location::GPR(status),
+                    Location::GPR(new),
+                    Location::GPR(addr),
+                );
+                this.assembler
+                    .emit_cbnz_label(Size::S32, Location::GPR(status), retry);
+                this.move_location(sz, Location::GPR(old), ret);
+
+                this.release_gpr(status);
+                this.release_gpr(new);
+                this.release_gpr(old);
+            },
+        );
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+
+    /// Emits a `LDAXR`/`STLXR` compare-and-exchange loop on the address computed by
+    /// `memory_op`, storing the previous memory value in `ret`.
+    fn emit_atomic_cmpxchg(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        sz: Size,
+        memory_sz: Size,
+        value_size: usize,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        let mut temps = vec![];
+        let compare = self.location_to_reg(sz, cmp, &mut temps, ImmType::None, true, None);
+        let value = self.location_to_reg(sz, new, &mut temps, ImmType::None, true, None);
+        // The value in memory is zero extended, so narrow the comparand the same way.
+        let compare = match memory_sz {
+            Size::S8 | Size::S16 => {
+                let tmp = self.acquire_temp_gpr().unwrap();
+                temps.push(tmp);
+                if memory_sz == Size::S8 {
+                    self.assembler
+                        .emit_uxtb(Size::S32, compare, Location::GPR(tmp));
+                } else {
+                    self.assembler
+                        .emit_uxth(Size::S32, compare, Location::GPR(tmp));
+                }
+                Location::GPR(tmp)
+            }
+            _ => compare,
+        };
+        // Both sides are zero extended from `memory_sz`, only compare the bits that can differ.
+        let cmp_sz = if memory_sz == Size::S64 {
+            Size::S64
+        } else {
+            Size::S32
+        };
+
+        self.memory_op(
+            target,
+            memarg,
+            true,
+            value_size,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                let old = this.acquire_temp_gpr().unwrap();
+                let status = this.acquire_temp_gpr().unwrap();
+                let retry = this.assembler.get_label();
+                let done = this.assembler.get_label();
+
+                this.emit_label(retry);
+                this.emit_load_exclusive(memory_sz, Location::GPR(old), Location::GPR(addr));
+                this.assembler.emit_cmp(cmp_sz, compare, Location::GPR(old));
+                this.assembler.emit_bcond_label(Condition::Ne, done);
+                this.emit_store_exclusive(
+                    memory_sz,
+                    Location::GPR(status),
+                    value,
+                    Location::GPR(addr),
+                );
+                this.assembler
+                    .emit_cbnz_label(Size::S32, Location::GPR(status), retry);
+                this.emit_label(done);
+                this.move_location(sz, Location::GPR(old), ret);
+
+                this.release_gpr(status);
+                this.release_gpr(old);
+            },
+        );
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+}
+
+impl Machine for MachineARM64 {
+    type GPR = GPR;
+    type SIMD = NEON;
+    fn assembler_get_offset(&self) -> Offset {
+        self.assembler.get_offset()
+    }
+    fn index_from_gpr(&self, x: GPR) -> RegisterIndex {
+        RegisterIndex(x as usize)
+    }
+    fn index_from_simd(&self, x: NEON) -> RegisterIndex {
+        RegisterIndex(x as usize + 32)
+    }
+    fn get_vmctx_reg(&self) -> GPR {
+        GPR::X28
+    }
+    fn pick_gpr(&self) -> Option<GPR> {
+        use GPR::*;
+        static REGS: &[GPR] = &[X9, X10, X11, X12, X13, X14, X15];
+        for r in REGS {
+            if !self.used_gprs.contains(r) {
+                return Some(*r);
+            }
+        }
+        None
+    }
+    // Picks an unused general purpose register for internal temporary use.
+    fn pick_temp_gpr(&self) -> Option<GPR> {
+        use GPR::*;
+        static REGS: &[GPR] = &[X1, X2, X3, X4, X5, X6, X7, X8];
+        for r in REGS {
+            if !self.used_gprs.contains(r) {
+                return Some(*r);
+            }
+        }
+        None
+    }
+    fn get_used_gprs(&self) -> Vec<GPR> {
+        self.used_gprs.iter().cloned().collect()
+    }
+    fn get_used_simd(&self) -> Vec<NEON> {
+        self.used_simd.iter().cloned().collect()
+    }
+    fn acquire_temp_gpr(&mut self) -> Option<GPR> {
+        let gpr = self.pick_temp_gpr();
+        if let Some(x) = gpr {
+            self.used_gprs.insert(x);
+        }
+        gpr
+    }
+    fn release_gpr(&mut self, gpr: GPR) {
+        assert!(self.used_gprs.remove(&gpr));
+    }
+    fn reserve_unused_temp_gpr(&mut self, gpr: GPR) -> GPR {
+        assert!(!self.used_gprs.contains(&gpr));
+        self.used_gprs.insert(gpr);
+        gpr
+    }
+    fn reserve_gpr(&mut self, gpr: GPR) {
+        self.used_gprs.insert(gpr);
+    }
+    // Used GPRs are pushed in pairs, the odd one out along with XZR.
+    fn push_used_gpr(&mut self) {
+        let used_gprs = self.get_used_gprs();
+        for pair in used_gprs.chunks(2) {
+            let first = Location::GPR(pair[0]);
+            let second = pair
+                .get(1)
+                .map_or(Location::GPR(GPR::XzrSp), |r| Location::GPR(*r));
+            self.emit_double_push(second, first);
+        }
+    }
+    fn pop_used_gpr(&mut self) {
+        let used_gprs = self.get_used_gprs();
+        for pair in used_gprs.chunks(2).rev() {
+            let first = Location::GPR(pair[0]);
+            let second = pair
+                .get(1)
+                .map_or(Location::GPR(GPR::XzrSp), |r| Location::GPR(*r));
+            self.emit_double_pop(second, first);
+        }
+    }
+    // Picks an unused NEON register.
+    fn pick_simd(&self) -> Option<NEON> {
+        use NEON::*;
+        static REGS: &[NEON] = &[V8, V9, V10, V11, V12, V13, V14, V15];
+        for r in REGS {
+            if !self.used_simd.contains(r) {
+                return Some(*r);
+            }
+        }
+        None
+    }
+    // Picks an unused NEON register for internal temporary use.
+    fn pick_temp_simd(&self) -> Option<NEON> {
+        use NEON::*;
+        static REGS: &[NEON] = &[V0, V1, V2, V3, V4, V5, V6, V7];
+        for r in REGS {
+            if !self.used_simd.contains(r) {
+                return Some(*r);
+            }
+        }
+        None
+    }
+    // Acquires a temporary NEON register.
+    fn acquire_temp_simd(&mut self) -> Option<NEON> {
+        let simd = self.pick_temp_simd();
+        if let Some(x) = simd {
+            self.used_simd.insert(x);
+        }
+        simd
+    }
+    fn reserve_simd(&mut self, simd: NEON) {
+        self.used_simd.insert(simd);
+    }
+    // Releases a temporary NEON register.
+    fn release_simd(&mut self, simd: NEON) {
+        assert!(self.used_simd.remove(&simd));
+    }
+    fn push_used_simd(&mut self) {
+        let used_neons = self.get_used_simd();
+        self.adjust_stack(self.round_stack_adjust(used_neons.len() * 8) as u32);
+
+        for (i, r) in used_neons.iter().enumerate() {
+            self.assembler.emit_str(
+                Size::S64,
+                Location::SIMD(*r),
+                Location::Memory(GPR::XzrSp, (i * 8) as i32),
+            );
+        }
+    }
+    fn pop_used_simd(&mut self) {
+        let used_neons = self.get_used_simd();
+        for (i, r) in used_neons.iter().enumerate() {
+            self.assembler.emit_ldr(
+                Size::S64,
+                Location::SIMD(*r),
+                Location::Memory(GPR::XzrSp, (i * 8) as i32),
+            );
+        }
+        self.restore_stack(self.round_stack_adjust(used_neons.len() * 8) as u32);
+    }
+    /// Set the source location of the Wasm to the given offset.
+    fn set_srcloc(&mut self, offset: u32) {
+        self.src_loc = offset;
+    }
+    /// Marks each address in the code range emitted by `f` with the trap code `code`.
+    fn mark_address_range_with_trap_code(&mut self, code: TrapCode, begin: usize, end: usize) {
+        for i in begin..end {
+            self.trap_table.offset_to_code.insert(i, code);
+        }
+        self.mark_instruction_address_end(begin);
+    }
+    /// Marks one address as trappable with trap code `code`.
+    fn mark_address_with_trap_code(&mut self, code: TrapCode) {
+        let offset = self.assembler.get_offset().0;
+        self.trap_table.offset_to_code.insert(offset, code);
+        self.mark_instruction_address_end(offset);
+    }
+    /// Marks the instruction as trappable with trap code `code`. return "begin" offset
+    fn mark_instruction_with_trap_code(&mut self, code: TrapCode) -> usize {
+        let offset = self.assembler.get_offset().0;
+        self.trap_table.offset_to_code.insert(offset, code);
+        offset
+    }
+    /// Get the trap code recorded at `offset`, if any
+    fn trap_code_at(&self, offset: usize) -> Option<TrapCode> {
+        self.trap_table.offset_to_code.get(&offset).copied()
+    }
+    /// Pushes the instruction to the address map, calculating the offset from a
+    /// provided beginning address.
+    fn mark_instruction_address_end(&mut self, begin: usize) {
+        self.instructions_address_map.push(InstructionAddressMap {
+            srcloc: SourceLoc::new(self.src_loc),
+            code_offset: begin,
+            code_len: self.assembler.get_offset().0 - begin,
+        });
+    }
+    /// Insert a StackOverflow (at offset 0)
+    fn insert_stackoverflow(&mut self) {
+        let offset = 0;
+        self.trap_table
+            .offset_to_code
+            .insert(offset, TrapCode::StackOverflow);
+        self.mark_instruction_address_end(offset);
+    }
+    /// Get all current TrapInformation
+    fn collect_trap_information(&self) -> Vec<TrapInformation> {
+        self.trap_table
+            .offset_to_code
+            .clone()
+            .into_iter()
+            .map(|(offset, code)| TrapInformation {
+                code_offset: offset as u32,
+                trap_code: code,
+            })
+            .collect()
+    }
+    fn instructions_address_map(&self) -> Vec<InstructionAddressMap> {
+        self.instructions_address_map.clone()
+    }
+    // Memory location for a local on the stack
+    fn local_on_stack(&mut self, stack_offset: i32) -> Location {
+        Location::Memory(GPR::X29, -stack_offset)
+    }
+    // Adjust stack for locals
+    fn adjust_stack(&mut self, delta_stack_offset: u32) {
+        self.emit_sp_delta(Assembler::emit_sub, delta_stack_offset);
+    }
+    // restore stack
+    fn restore_stack(&mut self, delta_stack_offset: u32) {
+        self.emit_sp_delta(Assembler::emit_add, delta_stack_offset);
+    }
+    fn push_callee_saved(&mut self) {}
+    fn pop_callee_saved(&mut self) {}
+    fn pop_stack_locals(&mut self, delta_stack_offset: u32) {
+        self.restore_stack(delta_stack_offset);
+    }
+    // SP must stay 16-byte aligned, it is checked by every access through it
+    fn round_stack_adjust(&self, value: usize) -> usize {
+        (value + 15) & !15
+    }
+    // Zero a location: a register, a NEON register as a whole, or `size` bytes of memory
+    fn zero_location(&mut self, size: Size, location: Location) {
+        match location {
+            Location::GPR(_) => self.assembler.emit_movz(location, 0),
+            Location::SIMD(_) => self.assembler.emit_movi(Size::S8, 0, 0, location),
+            Location::Memory(base, offset) if size == Size::S128 => {
+                let zero = Location::GPR(GPR::XzrSp);
+                self.emit_relaxed_str(Size::S64, zero, location);
+                self.emit_relaxed_str(Size::S64, zero, Location::Memory(base, offset + 8));
+            }
+            Location::Memory(_, _) => {
+                self.emit_relaxed_str(size, Location::GPR(GPR::XzrSp), location)
+            }
+            _ => unreachable!(),
+        }
+    }
+    // Zero a stack region 16 bytes at a time, then clear what is left with narrower stores
+    // GPR Reg used for local pointer on the stack
+    fn local_pointer(&self) -> GPR {
+        GPR::X29
+    }
+    // SP stays 16-byte aligned, so the parameters are pushed in pairs, the padding being
+    // XZR along with the last one.
+    fn push_params_for_native(&mut self, params: &[Location], padding: bool) {
+        let mut values = vec![];
+        if padding {
+            values.push(Location::GPR(GPR::XzrSp));
+        }
+        values.extend_from_slice(params);
+        debug_assert!(values.len() % 2 == 0, "unpaired stack parameter");
+        for pair in values.chunks(2) {
+            self.emit_pair_push(pair[0], pair[1]);
+        }
+    }
+    // Determine whether a local should be allocated on the stack.
+    fn is_local_on_stack(&self, idx: usize) -> bool {
+        idx > 6
+    }
+    // Determine a local's location.
+    fn get_local_location(&self, idx: usize, callee_saved_regs_size: usize) -> Location {
+        // Use callee-saved registers for the first locals.
+        match idx {
+            0 => Location::GPR(GPR::X19),
+            1 => Location::GPR(GPR::X20),
+            2 => Location::GPR(GPR::X21),
+            3 => Location::GPR(GPR::X22),
+            4 => Location::GPR(GPR::X23),
+            5 => Location::GPR(GPR::X24),
+            6 => Location::GPR(GPR::X25),
+            _ => Location::Memory(GPR::X29, -(((idx - 6) * 8 + callee_saved_regs_size) as i32)),
+        }
+    }
+    // Move a local to the stack
+    fn move_local(&mut self, stack_offset: i32, location: Location) {
+        self.emit_relaxed_str(
+            Size::S64,
+            location,
+            Location::Memory(GPR::X29, -stack_offset),
+        );
+    }
+    // X17 is used as scratch as parameters can be live in X0..X7 on both entry and exit
+    fn canonicalize_fp_control(&mut self, stack_offset: i32) -> Result<(), CodegenError> {
+        self.assembler.emit_read_fpcr(GPR::X17);
+        self.move_local(stack_offset, Location::GPR(GPR::X17));
+        self.assembler.emit_write_fpcr(GPR::XzrSp);
+        Ok(())
+    }
+    fn restore_fp_control(&mut self, stack_offset: i32) -> Result<(), CodegenError> {
+        self.emit_relaxed_ldr(
+            Size::S64,
+            Location::GPR(GPR::X17),
+            Location::Memory(GPR::X29, -stack_offset),
+        );
+        self.assembler.emit_write_fpcr(GPR::X17);
+        Ok(())
+    }
+    // X16 and X17 are used as scratch for the same reason
+    fn emit_call_depth_check(&mut self, counter_offset: i32, limit: u32) {
+        let counter = Location::Memory(self.get_vmctx_reg(), counter_offset);
+        let ok = self.get_label();
+        self.emit_relaxed_ldr(Size::S32, Location::GPR(GPR::X17), counter);
+        self.assembler
+            .emit_mov_imm(Location::GPR(GPR::X16), limit as u64);
+        self.assembler
+            .emit_cmp(Size::S32, Location::GPR(GPR::X16), Location::GPR(GPR::X17));
+        self.assembler.emit_bcond_label(Condition::Cc, ok);
+        let offset = self.mark_instruction_with_trap_code(TrapCode::StackOverflow);
+        self.emit_illegal_op();
+        self.mark_instruction_address_end(offset);
+        self.emit_label(ok);
+        self.assembler.emit_add(
+            Size::S32,
+            Location::GPR(GPR::X17),
+            Location::Imm8(1),
+            Location::GPR(GPR::X17),
+        );
+        self.emit_relaxed_str(Size::S32, Location::GPR(GPR::X17), counter);
+    }
+    fn emit_call_depth_release(&mut self, counter_offset: i32) {
+        let counter = Location::Memory(self.get_vmctx_reg(), counter_offset);
+        self.emit_relaxed_ldr(Size::S32, Location::GPR(GPR::X17), counter);
+        self.assembler.emit_sub(
+            Size::S32,
+            Location::GPR(GPR::X17),
+            Location::Imm8(1),
+            Location::GPR(GPR::X17),
+        );
+        self.emit_relaxed_str(Size::S32, Location::GPR(GPR::X17), counter);
+    }
+    // List of register to save, depending on the CallingConvention
+    // Values are allocated to V8-V15, whose low halves are callee-saved on AAPCS64 and by
+    // Apple's variant of it alike. X19-X25 and X28 are saved with the locals, and X26/X27
+    // by the trampolines: X27 can't be restored here as it holds the target of tail calls.
+    fn list_to_save(&self, _calling_convention: CallingConvention) -> Vec<Location> {
+        use NEON::*;
+        [V8, V9, V10, V11, V12, V13, V14, V15]
+            .iter()
+            .map(|&r| Location::SIMD(r))
+            .collect()
+    }
+    // Get param location
+    // Stack arguments are pushed with `push_location_for_native`, in 8-byte slots.
+    // Floats are passed in GPRs like every other argument between singlepass functions:
+    // `gen_import_call_trampoline` moves them to V0-V7 when calling a host function.
+    fn get_param_location(&self, idx: usize, _calling_convention: CallingConvention) -> Location {
+        match idx {
+            0 => Location::GPR(GPR::X0),
+            1 => Location::GPR(GPR::X1),
+            2 => Location::GPR(GPR::X2),
+            3 => Location::GPR(GPR::X3),
+            4 => Location::GPR(GPR::X4),
+            5 => Location::GPR(GPR::X5),
+            6 => Location::GPR(GPR::X6),
+            7 => Location::GPR(GPR::X7),
+            _ => Location::Memory(GPR::X29, (16 + (idx - 8) * 8) as i32),
+        }
+    }
+    // move a location to another
+    fn move_location(&mut self, size: Size, source: Location, dest: Location) {
+        match source {
+            Location::GPR(_) | Location::SIMD(_) => match dest {
+                Location::GPR(_) | Location::SIMD(_) => self.assembler.emit_mov(size, source, dest),
+                Location::Memory(_, _) | Location::Memory2(_, _, _, _) => {
+                    self.emit_relaxed_str(size, source, dest)
+                }
+                _ => unreachable!(),
+            },
+            Location::Memory(_, _) | Location::Memory2(_, _, _, _) => match dest {
+                Location::GPR(_) | Location::SIMD(_) => self.emit_relaxed_ldr(size, dest, source),
+                Location::Memory(_, _) | Location::Memory2(_, _, _, _) => {
+                    let tmp = self.acquire_temp_gpr().unwrap();
+                    self.emit_relaxed_ldr(size, Location::GPR(tmp), source);
+                    self.emit_relaxed_str(size, Location::GPR(tmp), dest);
+                    self.release_gpr(tmp);
+                }
+                _ => unreachable!(),
+            },
+            Location::Imm8(_) | Location::Imm32(_) | Location::Imm64(_) => {
+                let val = match source {
+                    Location::Imm8(val) => val as u64,
+                    Location::Imm32(val) => val as u64,
+                    Location::Imm64(val) => val,
+                    _ => unreachable!(),
+                };
+                match dest {
+                    Location::GPR(_) => self.assembler.emit_mov_imm(dest, val),
+                    Location::SIMD(_) => {
+                        let tmp = self.acquire_temp_gpr().unwrap();
+                        self.assembler.emit_mov_imm(Location::GPR(tmp), val);
+                        self.assembler.emit_mov(size, Location::GPR(tmp), dest);
+                        self.release_gpr(tmp);
+                    }
+                    Location::Memory(_, _) | Location::Memory2(_, _, _, _) => {
+                        if val == 0 {
+                            self.emit_relaxed_str(size, Location::GPR(GPR::XzrSp), dest);
+                        } else {
+                            let tmp = self.acquire_temp_gpr().unwrap();
+                            self.assembler.emit_mov_imm(Location::GPR(tmp), val);
+                            self.emit_relaxed_str(size, Location::GPR(tmp), dest);
+                            self.release_gpr(tmp);
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    // move a location to another
+    fn move_location_extend(
+        &mut self,
+        size_val: Size,
+        signed: bool,
+        source: Location,
+        size_op: Size,
+        dest: Location,
+    ) {
+        if size_op != Size::S64 && size_op != Size::S32 {
+            unreachable!();
+        }
+        let mut temps = vec![];
+        let dst = self.location_to_reg(size_op, dest, &mut temps, ImmType::None, false, None);
+        let src = match (size_val, signed, source) {
+            (Size::S64, _, _) => source,
+            (Size::S32, false, Location::GPR(_)) => source,
+            (Size::S32, true, Location::GPR(_)) => {
+                self.assembler.emit_sxtw(size_op, source, dst);
+                dst
+            }
+            (Size::S16, false, Location::GPR(_)) => {
+                self.assembler.emit_uxth(size_op, source, dst);
+                dst
+            }
+            (Size::S16, true, Location::GPR(_)) => {
+                self.assembler.emit_sxth(size_op, source, dst);
+                dst
+            }
+            (Size::S8, false, Location::GPR(_)) => {
+                self.assembler.emit_uxtb(size_op, source, dst);
+                dst
+            }
+            (Size::S8, true, Location::GPR(_)) => {
+                self.assembler.emit_sxtb(size_op, source, dst);
+                dst
+            }
+            (Size::S32, false, Location::Memory(_, _)) => {
+                self.emit_relaxed_ldr(Size::S32, dst, source);
+                dst
+            }
+            (Size::S32, true, Location::Memory(_, _)) => {
+                self.emit_relaxed_ld(Assembler::emit_ldrsw, size_op, dst, source);
+                dst
+            }
+            (Size::S16, false, Location::Memory(_, _)) => {
+                self.emit_relaxed_ld(Assembler::emit_ldrh, size_op, dst, source);
+                dst
+            }
+            (Size::S16, true, Location::Memory(_, _)) => {
+                self.emit_relaxed_ld(Assembler::emit_ldrsh, size_op, dst, source);
+                dst
+            }
+            (Size::S8, false, Location::Memory(_, _)) => {
+                self.emit_relaxed_ld(Assembler::emit_ldrb, size_op, dst, source);
+                dst
+            }
+            (Size::S8, true, Location::Memory(_, _)) => {
+                self.emit_relaxed_ld(Assembler::emit_ldrsb, size_op, dst, source);
+                dst
+            }
+            _ => panic!(
+                "singlepass can't emit move_location_extend {:?} {:?} {:?} {:?} {:?}",
+                size_val, signed, source, size_op, dest
+            ),
+        };
+        if src != dst {
+            self.move_location(size_op, src, dst);
+        }
+        if dst != dest {
+            self.move_location(size_op, dst, dest);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    fn load_address(&mut self, size: Size, reg: Location, mem: Location) {
+        match (reg, mem) {
+            // All the loads below zero extend to the full register
+            (Location::GPR(_), Location::Memory(_, _))
+            | (Location::GPR(_), Location::Memory2(_, _, _, _)) => {
+                let op = match size {
+                    Size::S8 => Assembler::emit_ldrb,
+                    Size::S16 => Assembler::emit_ldrh,
+                    Size::S32 | Size::S64 => Assembler::emit_ldr,
+                    Size::S128 => unreachable!(),
+                };
+                self.emit_relaxed_ld(op, size, reg, mem);
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn init_stack_loc(&mut self, init_stack_loc_cnt: u64, last_stack_loc: Location) {
+        let (base, offset) = match last_stack_loc {
+            Location::Memory(base, offset) => (base, offset),
+            _ => unreachable!(),
+        };
+        // A few slots are cleared one by one, more than that with a loop from the lowest one up.
+        if init_stack_loc_cnt <= 8 {
+            for i in 0..init_stack_loc_cnt as i32 {
+                self.emit_relaxed_str(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::Memory(base, offset + i * 8),
+                );
+            }
+            return;
+        }
+        let addr = self.acquire_temp_gpr().unwrap();
+        let cnt = self.acquire_temp_gpr().unwrap();
+        self.emit_memory_address(base, offset, addr);
+        self.assembler
+            .emit_mov_imm(Location::GPR(cnt), init_stack_loc_cnt);
+        let label = self.get_label();
+        self.emit_label(label);
+        self.assembler
+            .emit_stria(Size::S64, Location::GPR(GPR::XzrSp), addr, 8);
+        self.assembler.emit_sub(
+            Size::S64,
+            Location::GPR(cnt),
+            Location::Imm8(1),
+            Location::GPR(cnt),
+        );
+        self.assembler
+            .emit_cbnz_label(Size::S64, Location::GPR(cnt), label);
+        self.release_gpr(cnt);
+        self.release_gpr(addr);
+    }
+    // Restore save_area
+    // The save area is read back relative to X29, as it was written by `move_local`, so SP is
+    // left alone: the epilog resets it from X29 anyway.
+    fn restore_saved_area(&mut self, saved_area_offset: i32, locations: &[Location]) {
+        for (i, loc) in locations.iter().enumerate() {
+            self.emit_relaxed_ldr(
+                Size::S64,
+                *loc,
+                Location::Memory(GPR::X29, -saved_area_offset + (i * 8) as i32),
+            );
+        }
+    }
+    // Create a new `MachineState` with default values.
+    fn new_machine_state(&self) -> MachineState {
+        new_machine_state()
+    }
+    // assembler finalize
+    fn assembler_finalize(self) -> Vec<u8> {
+        self.assembler.finalize().unwrap()
+    }
+    fn finalize_with_metadata(self) -> (Vec<u8>, Vec<TrapInformation>, Vec<InstructionAddressMap>) {
+        let traps = self.collect_trap_information();
+        (
+            self.assembler.finalize().unwrap(),
+            traps,
+            self.instructions_address_map,
+        )
+    }
+    fn get_offset(&self) -> Offset {
+        self.assembler.get_offset()
+    }
+    fn finalize_function(&mut self) {
+        if let Some(label) = self.unaligned_atomic.take() {
+            self.emit_label(label);
+            self.mark_address_with_trap_code(TrapCode::UnalignedAtomic);
+            self.emit_illegal_op();
+        }
+        self.assembler.finalize_function();
+    }
+    fn emit_function_prolog(&mut self) {
+        self.assembler.emit_stpbd(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::X30),
+            GPR::XzrSp,
+            16,
+        );
+        self.assembler.emit_mov(
+            Size::S64,
+            Location::GPR(GPR::XzrSp),
+            Location::GPR(GPR::X29),
+        );
+    }
+    fn emit_function_epilog(&mut self) {
+        self.assembler.emit_mov(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::XzrSp),
+        );
+        self.assembler.emit_ldpai(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::X30),
+            GPR::XzrSp,
+            16,
+        );
+    }
+    fn emit_function_return_value(&mut self, ty: WpType, canonicalize: bool, loc: Location) {
+        // Floats are returned in V0, like AAPCS64 does
+        let ret = if ty == WpType::F32 || ty == WpType::F64 {
+            Location::SIMD(NEON::V0)
+        } else {
+            Location::GPR(GPR::X0)
+        };
+        if canonicalize {
+            self.canonicalize_nan(
+                match ty {
+                    WpType::F32 => Size::S32,
+                    WpType::F64 => Size::S64,
+                    _ => unreachable!(),
+                },
+                loc,
+                ret,
+            );
+        } else if loc != ret {
+            self.emit_relaxed_mov(Size::S64, loc, ret);
+        }
+    }
+    fn emit_function_return_float(&mut self) {
+        // `emit_function_return_value` already put the result in V0
+    }
+    fn arch_supports_canonicalize_nan(&self) -> bool {
+        self.assembler.arch_supports_canonicalize_nan()
+    }
+    fn canonicalize_nan(&mut self, sz: Size, input: Location, output: Location) {
+        let mut temps = vec![];
+        let src = self.location_to_neon(sz, input, &mut temps, ImmType::None, true);
+
+        let canonical = match sz {
+            Size::S32 => CANONICAL_NAN_F32 as u64,
+            Size::S64 => CANONICAL_NAN_F64,
+            _ => unreachable!(),
+        };
+        let tmp = self.acquire_temp_simd().unwrap();
+        temps.push(tmp);
+        let gpr = self.acquire_temp_gpr().unwrap();
+        self.assembler.emit_mov_imm(Location::GPR(gpr), canonical);
+        self.assembler
+            .emit_mov(sz, Location::GPR(gpr), Location::SIMD(tmp));
+        self.release_gpr(gpr);
+
+        // Comparing a value with itself is unordered (V set) only for NaN.
+        self.assembler.emit_fcmp(sz, src, src);
+        self.assembler.emit_fcsel(
+            sz,
+            Location::SIMD(tmp),
+            src,
+            Location::SIMD(tmp),
+            Condition::Vs,
+        );
+        self.move_location(sz, Location::SIMD(tmp), output);
+
+        for r in temps {
+            self.release_simd(r);
+        }
+    }
+    fn emit_illegal_op(&mut self) {
+        self.assembler.emit_udf();
+    }
+    fn get_label(&mut self) -> Label {
+        self.assembler.new_dynamic_label()
+    }
+    fn emit_label(&mut self, label: Label) {
+        self.assembler.emit_label(label);
+    }
+    fn get_grp_for_call(&self) -> GPR {
+        GPR::X27
+    }
+    fn emit_call_register(&mut self, reg: GPR) {
+        self.assembler.emit_call_register(reg);
+    }
+    fn emit_call_label(&mut self, label: Label) {
+        self.assembler.emit_call_label(label);
+    }
+    fn arch_requires_indirect_call_trampoline(&self) -> bool {
+        self.assembler.arch_requires_indirect_call_trampoline()
+    }
+    fn arch_emit_indirect_call_with_trampoline(&mut self, location: Location) {
+        self.assembler
+            .arch_emit_indirect_call_with_trampoline(location);
+    }
+    // X26 is free at call sites, unlike the temporaries that double as parameter registers
+    fn emit_call_location(&mut self, location: Location) {
+        match location {
+            Location::GPR(reg) => self.assembler.emit_call_register(reg),
+            Location::Memory(_, _) => {
+                self.move_location(Size::S64, location, Location::GPR(GPR::X26));
+                self.assembler.emit_call_register(GPR::X26);
+            }
+            _ => panic!("singlepass can't emit CALL {:?}", location),
+        }
+    }
+    fn emit_return_call(&mut self, reg: GPR) {
+        // LR is restored to the return address of the current function, so the callee
+        // returns directly to our caller.
+        self.emit_function_epilog();
+        self.assembler.emit_b_register(reg);
+    }
+    fn emit_return_call_indirect(&mut self, location: Location) {
+        self.move_location(Size::S64, location, Location::GPR(GPR::X27));
+        self.emit_return_call(GPR::X27);
+    }
+    fn get_gpr_for_ret(&self) -> GPR {
+        GPR::X0
+    }
+    fn get_simd_for_ret(&self) -> NEON {
+        NEON::V0
+    }
+    fn location_address(&mut self, size: Size, source: Location, dest: Location) {
+        let (base, offset) = match source {
+            Location::Memory(base, offset) => (base, offset),
+            _ => panic!("singlepass can't emit location_address {:?}", source),
+        };
+        let mut temps = vec![];
+        let dst = self.location_to_reg(Size::S64, dest, &mut temps, ImmType::None, false, None);
+        let dst_reg = match dst {
+            Location::GPR(reg) => reg,
+            _ => unreachable!(),
+        };
+        self.emit_memory_address(base, offset, dst_reg);
+        if size == Size::S32 {
+            // a 32-bit address only keeps the low half
+            self.assembler.emit_mov(Size::S32, dst, dst);
+        }
+        if dst != dest {
+            self.move_location(size, dst, dest);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    fn location_and(&mut self, size: Size, source: Location, dest: Location, flags: bool) {
+        let op = if flags {
+            Assembler::emit_ands
+        } else {
+            Assembler::emit_and
+        };
+        let allow_imm = Self::logical_imm_type(size);
+        self.emit_relaxed_binop3(op, size, dest, source, dest, allow_imm);
+    }
+    // There is no flag setting ORR/EOR, so the flags come from a TST of the result, leaving
+    // C and V cleared like ANDS does.
+    fn location_xor(&mut self, size: Size, source: Location, dest: Location, flags: bool) {
+        let op: fn(&mut Assembler, Size, Location, Location, Location) = if flags {
+            |a, sz, src1, src2, dst| {
+                a.emit_eor(sz, src1, src2, dst);
+                a.emit_tst(sz, dst, dst);
+            }
+        } else {
+            Assembler::emit_eor
+        };
+        let allow_imm = Self::logical_imm_type(size);
+        self.emit_relaxed_binop3(op, size, dest, source, dest, allow_imm);
+    }
+    fn location_or(&mut self, size: Size, source: Location, dest: Location, flags: bool) {
+        let op: fn(&mut Assembler, Size, Location, Location, Location) = if flags {
+            |a, sz, src1, src2, dst| {
+                a.emit_orr(sz, src1, src2, dst);
+                a.emit_tst(sz, dst, dst);
+            }
+        } else {
+            Assembler::emit_orr
+        };
+        let allow_imm = Self::logical_imm_type(size);
+        self.emit_relaxed_binop3(op, size, dest, source, dest, allow_imm);
+    }
+    fn location_add(&mut self, size: Size, source: Location, dest: Location, flags: bool) {
+        let op = if flags {
+            Assembler::emit_adds
+        } else {
+            Assembler::emit_add
+        };
+        self.emit_relaxed_binop3(op, size, dest, source, dest, ImmType::Bits12);
+    }
+    fn location_sub(&mut self, size: Size, source: Location, dest: Location, flags: bool) {
+        let op = if flags {
+            Assembler::emit_subs
+        } else {
+            Assembler::emit_sub
+        };
+        self.emit_relaxed_binop3(op, size, dest, source, dest, ImmType::Bits12);
+    }
+    fn location_neg(
+        &mut self,
+        size_val: Size,
+        signed: bool,
+        source: Location,
+        size_op: Size,
+        dest: Location,
+    ) {
+        let mut temps = vec![];
+        let src = self.location_to_reg(size_op, source, &mut temps, ImmType::None, true, None);
+        let dst = self.location_to_reg(size_op, dest, &mut temps, ImmType::None, false, None);
+        self.assembler.emit_neg(size_op, src, dst);
+        // only the low `size_val` bits of the negation are kept, extended back to `size_op`
+        if size_val != size_op {
+            self.emit_relaxed_extension(size_val, dst, size_op, dst, signed);
+        }
+        if dst != dest {
+            self.move_location(size_op, dst, dest);
+        }
+        for r in temps {
+            self.release_gpr(r);
+        }
+    }
+    fn location_cmp(&mut self, size: Size, source: Location, dest: Location) {
+        self.emit_relaxed_binop(
+            Assembler::emit_cmp,
+            size,
+            source,
+            dest,
+            ImmType::Bits12,
+            false,
+        );
+    }
+    fn location_test(&mut self, size: Size, source: Location, dest: Location) {
+        let allow_imm = Self::logical_imm_type(size);
+        self.emit_relaxed_binop(Assembler::emit_tst, size, source, dest, allow_imm, false);
+    }
+    // (un)conditionnal jmp
+    fn jmp_unconditionnal(&mut self, label: Label) {
+        self.assembler.emit_b_label(label);
+    }
+    fn jmp_on_equal(&mut self, label: Label) {
+        self.assembler.emit_bcond_label(Condition::Eq, label);
+    }
+    fn jmp_on_different(&mut self, label: Label) {
+        self.assembler.emit_bcond_label(Condition::Ne, label);
+    }
+    fn jmp_on_above(&mut self, label: Label) {
+        self.assembler.emit_bcond_label(Condition::Hi, label);
+    }
+    fn jmp_on_aboveequal(&mut self, label: Label) {
+        self.assembler.emit_bcond_label(Condition::Cs, label);
+    }
+    fn jmp_on_belowequal(&mut self, label: Label) {
+        self.assembler.emit_bcond_label(Condition::Ls, label);
+    }
+    fn jmp_on_overflow(&mut self, label: Label) {
+        self.assembler.emit_bcond_label(Condition::Cs, label);
+    }
+    // jmp table
+    fn emit_jmp_to_jumptable(&mut self, label: Label, cond: Location) {
+        let tmp1 = self.acquire_temp_gpr().unwrap();
+        let tmp2 = self.acquire_temp_gpr().unwrap();
+
+        self.assembler.emit_load_label(tmp1, label);
+        self.move_location(Size::S32, cond, Location::GPR(tmp2));
+
+        // Every entry of the table is a single `b` instruction.
+        self.assembler.emit_add_lsl(
+            Size::S64,
+            Location::GPR(tmp1),
+            Location::GPR(tmp2),
+            2,
+            Location::GPR(tmp2),
+        );
+        self.assembler.emit_b_register(tmp2);
+        self.release_gpr(tmp2);
+        self.release_gpr(tmp1);
+    }
+    fn align_for_loop(&mut self) {
+        // Pad with NOPs to the next 16-byte boundary.
+        match self.assembler.get_offset().0 % 16 {
+            0 => {}
+            x => {
+                for _ in 0..(16 - x) / 4 {
+                    self.assembler.emit_nop();
+                }
+            }
+        }
+        assert_eq!(self.assembler.get_offset().0 % 16, 0);
+    }
+    fn emit_ret(&mut self) {
+        self.assembler.emit_ret();
+    }
+    // SP must stay 16-byte aligned, so a value pushed alone fills a 16-byte slot, going
+    // through a temporary register if it is in memory. Pairs of 8-byte values share a slot
+    // with `emit_double_push`.
+    fn emit_push(&mut self, size: Size, loc: Location) {
+        match (size, loc) {
+            (_, Location::GPR(_)) | (_, Location::SIMD(_)) => {
+                self.assembler.emit_strdb(size, loc, GPR::XzrSp, 16);
+            }
+            (Size::S128, _) => {
+                let tmp = self.acquire_temp_simd().unwrap();
+                self.emit_relaxed_ldr(Size::S128, Location::SIMD(tmp), loc);
+                self.assembler
+                    .emit_strdb(Size::S128, Location::SIMD(tmp), GPR::XzrSp, 16);
+                self.release_simd(tmp);
+            }
+            _ => {
+                let tmp = self.acquire_temp_gpr().unwrap();
+                self.move_location(Size::S64, loc, Location::GPR(tmp));
+                self.assembler
+                    .emit_strdb(Size::S64, Location::GPR(tmp), GPR::XzrSp, 16);
+                self.release_gpr(tmp);
+            }
+        }
+    }
+    fn emit_pop(&mut self, size: Size, loc: Location) {
+        match (size, loc) {
+            (_, Location::GPR(_)) | (_, Location::SIMD(_)) => {
+                self.assembler.emit_ldria(size, loc, GPR::XzrSp, 16);
+            }
+            (Size::S128, _) => {
+                let tmp = self.acquire_temp_simd().unwrap();
+                self.assembler
+                    .emit_ldria(Size::S128, Location::SIMD(tmp), GPR::XzrSp, 16);
+                self.emit_relaxed_str(Size::S128, Location::SIMD(tmp), loc);
+                self.release_simd(tmp);
+            }
+            _ => {
+                let tmp = self.acquire_temp_gpr().unwrap();
+                self.assembler
+                    .emit_ldria(Size::S64, Location::GPR(tmp), GPR::XzrSp, 16);
+                self.move_location(Size::S64, Location::GPR(tmp), loc);
+                self.release_gpr(tmp);
+            }
+        }
+    }
+    fn emit_relaxed_mov(&mut self, sz: Size, src: Location, dst: Location) {
+        self.move_location(sz, src, dst);
+    }
+    fn emit_relaxed_cmp(&mut self, sz: Size, src: Location, dst: Location) {
+        self.emit_relaxed_binop(Assembler::emit_cmp, sz, src, dst, ImmType::Bits12, false);
+    }
+    fn emit_memory_fence(&mut self) {
+        // Orders all memory accesses across the inner shareable domain, which holds every core
+        self.assembler.emit_dmb_ish();
+    }
+    fn emit_relaxed_zero_extension(
+        &mut self,
+        sz_src: Size,
+        src: Location,
+        sz_dst: Size,
+        dst: Location,
+    ) {
+        self.emit_relaxed_extension(sz_src, src, sz_dst, dst, false);
+    }
+    fn emit_relaxed_sign_extension(
+        &mut self,
+        sz_src: Size,
+        src: Location,
+        sz_dst: Size,
+        dst: Location,
+    ) {
+        self.emit_relaxed_extension(sz_src, src, sz_dst, dst, true);
+    }
+    // A power of two is a single LSL, and a sum of two powers of two
+    // `(2^n + 1) << m` is an ADD of the register with itself shifted by `n`,
+    // followed by an LSL by `m`. Any other constant is moved to a temporary
+    // register for a MUL, as MUL has no immediate form.
+    fn emit_imul_imm32(&mut self, size: Size, imm32: u32, gpr: GPR) {
+        let reg = Location::GPR(gpr);
+        let shift = imm32.trailing_zeros();
+        let odd = imm32.checked_shr(shift).unwrap_or(0);
+        if imm32 == 0 {
+            self.assembler.emit_mov_imm(reg, 0);
+        } else if odd == 1 || (odd - 1).is_power_of_two() {
+            if odd != 1 {
+                self.assembler
+                    .emit_add_lsl(size, reg, reg, (odd - 1).trailing_zeros(), reg);
+            }
+            if shift != 0 {
+                self.assembler
+                    .emit_lsl(size, reg, Location::Imm8(shift as u8), reg);
+            }
+        } else {
+            let tmp = self.acquire_temp_gpr().unwrap();
+            self.assembler
+                .emit_mov_imm(Location::GPR(tmp), imm32 as u64);
+            self.assembler.emit_mul(size, reg, Location::GPR(tmp), reg);
+            self.release_gpr(tmp);
+        }
+    }
+    fn emit_binop_add32(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_binop_i32(Assembler::emit_add2, loc_a, loc_b, ret, ImmType::None);
+    }
+    fn emit_binop_sub32(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_binop_i32(Assembler::emit_sub2, loc_a, loc_b, ret, ImmType::None);
+    }
+    fn emit_binop_mul32(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        // MUL has no immediate form, an immediate `loc_b` is moved to a register first
+        self.emit_binop_i32(Assembler::emit_mul2, loc_a, loc_b, ret, ImmType::None);
+    }
+    fn emit_binop_udiv32(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_div(
+            Size::S32,
+            false,
+            loc_a,
+            loc_b,
+            ret,
+            integer_division_by_zero,
+        )
+    }
+    fn emit_binop_sdiv32(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_div(Size::S32, true, loc_a, loc_b, ret, integer_division_by_zero)
+    }
+    fn emit_binop_urem32(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_rem(
+            Size::S32,
+            false,
+            loc_a,
+            loc_b,
+            ret,
+            integer_division_by_zero,
+        )
+    }
+    fn emit_binop_srem32(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_rem(Size::S32, true, loc_a, loc_b, ret, integer_division_by_zero)
+    }
+    fn emit_binop_and32(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_binop_i32(Assembler::emit_and2, loc_a, loc_b, ret, ImmType::Logical32);
+    }
+    fn emit_binop_or32(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_binop_i32(Assembler::emit_orr2, loc_a, loc_b, ret, ImmType::Logical32);
+    }
+    fn emit_binop_xor32(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_binop_i32(Assembler::emit_eor2, loc_a, loc_b, ret, ImmType::Logical32);
+    }
+    fn i32_cmp_ge_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Ge, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_gt_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Gt, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_le_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Le, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_lt_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Lt, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_ge_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Cs, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_gt_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Hi, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_le_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Ls, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_lt_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Cc, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_ne(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Ne, loc_a, loc_b, ret);
+    }
+    fn i32_cmp_eq(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i32_dynamic_b(Condition::Eq, loc_a, loc_b, ret);
+    }
+    fn i32_clz(&mut self, loc: Location, ret: Location) {
+        self.emit_count_zeros(Size::S32, false, loc, ret);
+    }
+    fn i32_ctz(&mut self, loc: Location, ret: Location) {
+        self.emit_count_zeros(Size::S32, true, loc, ret);
+    }
+    fn i32_popcnt(&mut self, loc: Location, ret: Location) {
+        self.emit_popcnt(Size::S32, loc, ret);
+    }
+    fn i32_shl(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i32(Assembler::emit_lsl, loc_a, loc_b, ret);
+    }
+    fn i32_shr(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i32(Assembler::emit_lsr, loc_a, loc_b, ret);
+    }
+    fn i32_sar(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i32(Assembler::emit_asr, loc_a, loc_b, ret);
+    }
+    fn i32_rol(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_rol(Size::S32, loc_a, loc_b, ret);
+    }
+    fn i32_ror(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i32(Assembler::emit_ror, loc_a, loc_b, ret);
+    }
+    fn i32_load(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldr,
+                    Size::S32,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_load_8u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrb,
+                    Size::S32,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_load_8s(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrsb,
+                    Size::S32,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_load_16u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrh,
+                    Size::S32,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_load_16s(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrsh,
+                    Size::S32,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_atomic_load(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            true,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(Assembler::emit_ldar, Size::S32, ret, Location::GPR(addr));
+            },
+        );
+    }
+    fn i32_atomic_load_8u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            true,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(Assembler::emit_ldarb, Size::S32, ret, Location::GPR(addr));
+            },
+        );
+    }
+    fn i32_atomic_load_16u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            true,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(Assembler::emit_ldarh, Size::S32, ret, Location::GPR(addr));
+            },
+        );
+    }
+    fn i32_save(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_str,
+                    Size::S32,
+                    target_value,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_save_8(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_strb,
+                    Size::S32,
+                    target_value,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_save_16(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_strh,
+                    Size::S32,
+                    target_value,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i32_atomic_save(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            true,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_stlr,
+                    Size::S32,
+                    value,
+                    Location::GPR(addr),
+                );
+            },
+        );
+    }
+    fn i32_atomic_save_8(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            true,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_stlrb,
+                    Size::S32,
+                    value,
+                    Location::GPR(addr),
+                );
+            },
+        );
+    }
+    fn i32_atomic_save_16(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            true,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_stlrh,
+                    Size::S32,
+                    value,
+                    Location::GPR(addr),
+                );
+            },
+        );
+    }
+    fn i32_atomic_add(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_add,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_add_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_add,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_add_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_add,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_sub(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_sub,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_sub_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_sub,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_sub_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_sub,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_and(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_and,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_and_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_and,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_and_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_and,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_or(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_orr,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_or_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_orr,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_or_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_orr,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_xor(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_eor,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_xor_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_eor,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_xor_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_eor,
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_xchg(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            |a, sz, _old, value, new| a.emit_mov(sz, value, new),
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_xchg_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            |a, sz, _old, value, new| a.emit_mov(sz, value, new),
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_xchg_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            |a, sz, _old, value, new| a.emit_mov(sz, value, new),
+            Size::S32,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_cmpxchg(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_cmpxchg(
+            new,
+            cmp,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_cmpxchg_8u(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_cmpxchg(
+            new,
+            cmp,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i32_atomic_cmpxchg_16u(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_cmpxchg(
+            new,
+            cmp,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn move_with_reloc(
+        &mut self,
+        reloc_target: RelocationTarget,
+        relocations: &mut Vec<Relocation>,
+    ) {
+        // The universal engine copies all the function bodies of a module into one code memory
+        // allocation (see `CodeMemory::allocate`), so a local function is within the +/-4GiB
+        // reach of `adrp` unless the module has more than 4GiB of code, in which case the
+        // linker panics on the out of range page rather than patching a wrong address. The
+        // address of a local function is thus built relative to the PC with `adrp`/`add`.
+        if let RelocationTarget::LocalFunc(_) = reloc_target {
+            relocations.push(Relocation {
+                kind: RelocationKind::Arm64Adrp,
+                reloc_target,
+                offset: self.assembler.get_offset().0 as u32,
+                addend: 0,
+            });
+            self.assembler.emit_adrp(GPR::X27);
+            relocations.push(Relocation {
+                kind: RelocationKind::Arm64AddLo12,
+                reloc_target,
+                offset: self.assembler.get_offset().0 as u32,
+                addend: 0,
+            });
+            self.assembler.emit_add(
+                Size::S64,
+                Location::GPR(GPR::X27),
+                Location::Imm8(0),
+                Location::GPR(GPR::X27),
+            );
+            return;
+        }
+        // Anything else may be anywhere in the address space: the 64-bit address is built
+        // with a `movz`/`movk` sequence, each instruction being patched by the JIT linker with
+        // a 16-bit slice of the address.
+        let reloc_kinds = [
+            RelocationKind::Arm64Movw0,
+            RelocationKind::Arm64Movw1,
+            RelocationKind::Arm64Movw2,
+            RelocationKind::Arm64Movw3,
+        ];
+        for (i, kind) in reloc_kinds.iter().enumerate() {
+            relocations.push(Relocation {
+                kind: *kind,
+                reloc_target,
+                offset: self.assembler.get_offset().0 as u32,
+                addend: 0,
+            });
+            if i == 0 {
+                self.assembler.emit_movz(Location::GPR(GPR::X27), 0);
+            } else {
+                self.assembler
+                    .emit_movk(Location::GPR(GPR::X27), 0, (i * 16) as u32);
+            }
+        }
+    }
+    fn emit_binop_add64(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3(
+            Assembler::emit_add,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::Bits12,
+        );
+    }
+    fn emit_binop_sub64(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3(
+            Assembler::emit_sub,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::Bits12,
+        );
+    }
+    fn emit_binop_mul64(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3(
+            Assembler::emit_mul,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn emit_binop_udiv64(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_div(
+            Size::S64,
+            false,
+            loc_a,
+            loc_b,
+            ret,
+            integer_division_by_zero,
+        )
+    }
+    fn emit_binop_sdiv64(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_div(Size::S64, true, loc_a, loc_b, ret, integer_division_by_zero)
+    }
+    fn emit_binop_urem64(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_rem(
+            Size::S64,
+            false,
+            loc_a,
+            loc_b,
+            ret,
+            integer_division_by_zero,
+        )
+    }
+    fn emit_binop_srem64(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+        integer_division_by_zero: Label,
+    ) -> usize {
+        self.emit_binop_rem(Size::S64, true, loc_a, loc_b, ret, integer_division_by_zero)
+    }
+    fn emit_binop_and64(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3(
+            Assembler::emit_and,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::Logical64,
+        );
+    }
+    fn emit_binop_or64(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3(
+            Assembler::emit_orr,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::Logical64,
+        );
+    }
+    fn emit_binop_xor64(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3(
+            Assembler::emit_eor,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::Logical64,
+        );
+    }
+    fn i64_cmp_ge_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Ge, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_gt_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Gt, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_le_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Le, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_lt_s(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Lt, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_ge_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Cs, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_gt_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Hi, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_le_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Ls, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_lt_u(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Cc, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_ne(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Ne, loc_a, loc_b, ret);
+    }
+    fn i64_cmp_eq(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_i64_dynamic_b(Condition::Eq, loc_a, loc_b, ret);
+    }
+    fn i64_clz(&mut self, loc: Location, ret: Location) {
+        self.emit_count_zeros(Size::S64, false, loc, ret);
+    }
+    fn i64_ctz(&mut self, loc: Location, ret: Location) {
+        self.emit_count_zeros(Size::S64, true, loc, ret);
+    }
+    fn i64_popcnt(&mut self, loc: Location, ret: Location) {
+        self.emit_popcnt(Size::S64, loc, ret);
+    }
+    fn i64_shl(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i64(Assembler::emit_lsl, loc_a, loc_b, ret);
+    }
+    fn i64_shr(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i64(Assembler::emit_lsr, loc_a, loc_b, ret);
+    }
+    fn i64_sar(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i64(Assembler::emit_asr, loc_a, loc_b, ret);
+    }
+    fn i64_rol(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_rol(Size::S64, loc_a, loc_b, ret);
+    }
+    fn i64_ror(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_shift_i64(Assembler::emit_ror, loc_a, loc_b, ret);
+    }
+    fn i64_load(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldr,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_load_8u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrb,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_load_8s(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrsb,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_load_32u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| match ret {
+                // LDR (32-bit) clears the upper half of the register, store all of it
+                Location::Memory(_, _) => {
+                    let tmp = this.acquire_temp_gpr().unwrap();
+                    this.emit_relaxed_ld(
+                        Assembler::emit_ldr,
+                        Size::S32,
+                        Location::GPR(tmp),
+                        Location::Memory(addr, 0),
+                    );
+                    this.emit_relaxed_str(Size::S64, Location::GPR(tmp), ret);
+                    this.release_gpr(tmp);
+                }
+                _ => this.emit_relaxed_load(
+                    Assembler::emit_ldr,
+                    Size::S32,
+                    ret,
+                    Location::Memory(addr, 0),
+                ),
+            },
+        );
+    }
+    fn i64_load_32s(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrsw,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_load_16u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrh,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_load_16s(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldrsh,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_atomic_load(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            true,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(Assembler::emit_ldar, Size::S64, ret, Location::GPR(addr));
+            },
+        );
+    }
+    fn i64_atomic_load_8u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            true,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(Assembler::emit_ldarb, Size::S64, ret, Location::GPR(addr));
+            },
+        );
+    }
+    fn i64_atomic_load_16u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            true,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(Assembler::emit_ldarh, Size::S64, ret, Location::GPR(addr));
+            },
+        );
+    }
+    fn i64_atomic_load_32u(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            true,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| match ret {
+                // LDAR (32-bit) clears the upper half of the register, store all of it
+                Location::Memory(_, _) => {
+                    let tmp = this.acquire_temp_gpr().unwrap();
+                    this.assembler
+                        .emit_ldar(Size::S32, Location::GPR(tmp), Location::GPR(addr));
+                    this.emit_relaxed_str(Size::S64, Location::GPR(tmp), ret);
+                    this.release_gpr(tmp);
+                }
+                _ => this.emit_relaxed_load(
+                    Assembler::emit_ldar,
+                    Size::S32,
+                    ret,
+                    Location::GPR(addr),
+                ),
+            },
+        );
+    }
+    fn i64_save(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_str,
+                    Size::S64,
+                    target_value,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_save_8(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_strb,
+                    Size::S64,
+                    target_value,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_save_16(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_strh,
+                    Size::S64,
+                    target_value,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_save_32(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_str,
+                    Size::S32,
+                    target_value,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn i64_atomic_save(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            true,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_stlr,
+                    Size::S64,
+                    value,
+                    Location::GPR(addr),
+                );
+            },
+        );
+    }
+    fn i64_atomic_save_8(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            true,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_stlrb,
+                    Size::S64,
+                    value,
+                    Location::GPR(addr),
+                );
+            },
+        );
+    }
+    fn i64_atomic_save_16(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            true,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_stlrh,
+                    Size::S64,
+                    value,
+                    Location::GPR(addr),
+                );
+            },
+        );
+    }
+    fn i64_atomic_save_32(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            target_addr,
+            memarg,
+            true,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_store(
+                    Assembler::emit_stlr,
+                    Size::S32,
+                    value,
+                    Location::GPR(addr),
+                );
+            },
+        );
+    }
+    fn i64_atomic_add(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_add,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_add_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_add,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_add_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_add,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_add_32u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_add,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_sub(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_sub,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_sub_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_sub,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_sub_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_sub,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_sub_32u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_sub,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_and(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_and,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_and_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_and,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_and_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_and,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_and_32u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_and,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_or(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_orr,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_or_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_orr,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_or_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_orr,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_or_32u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_orr,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xor(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_eor,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xor_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_eor,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xor_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_eor,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xor_32u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            Assembler::emit_eor,
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xchg(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            |a, sz, _old, value, new| a.emit_mov(sz, value, new),
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xchg_8u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            |a, sz, _old, value, new| a.emit_mov(sz, value, new),
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xchg_16u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            |a, sz, _old, value, new| a.emit_mov(sz, value, new),
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_xchg_32u(
+        &mut self,
+        loc: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_binop(
+            |a, sz, _old, value, new| a.emit_mov(sz, value, new),
+            Size::S64,
+            loc,
+            target,
+            memarg,
+            ret,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_cmpxchg(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_cmpxchg(
+            new,
+            cmp,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            Size::S64,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_cmpxchg_8u(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_cmpxchg(
+            new,
+            cmp,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            Size::S8,
+            1,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_cmpxchg_16u(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_cmpxchg(
+            new,
+            cmp,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            Size::S16,
+            2,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn i64_atomic_cmpxchg_32u(
+        &mut self,
+        new: Location,
+        cmp: Location,
+        target: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.emit_atomic_cmpxchg(
+            new,
+            cmp,
+            target,
+            memarg,
+            ret,
+            Size::S64,
+            Size::S32,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+        );
+    }
+    fn f32_load(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldr,
+                    Size::S32,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn f32_save(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        canonicalize: bool,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        let canonicalize = canonicalize && self.arch_supports_canonicalize_nan();
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            4,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                if !canonicalize {
+                    this.emit_relaxed_store(
+                        Assembler::emit_str,
+                        Size::S32,
+                        target_value,
+                        Location::Memory(addr, 0),
+                    );
+                } else {
+                    this.canonicalize_nan(Size::S32, target_value, Location::Memory(addr, 0));
+                }
+            },
+        );
+    }
+    fn f64_load(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldr,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+            },
+        );
+    }
+    fn f64_save(
+        &mut self,
+        target_value: Location,
+        memarg: &MemoryImmediate,
+        target_addr: Location,
+        canonicalize: bool,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) {
+        let canonicalize = canonicalize && self.arch_supports_canonicalize_nan();
+        self.memory_op(
+            target_addr,
+            memarg,
+            false,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                if !canonicalize {
+                    this.emit_relaxed_store(
+                        Assembler::emit_str,
+                        Size::S64,
+                        target_value,
+                        Location::Memory(addr, 0),
+                    );
+                } else {
+                    this.canonicalize_nan(Size::S64, target_value, Location::Memory(addr, 0));
+                }
+            },
+        );
+    }
+    fn convert_f64_i64(&mut self, loc: Location, signed: bool, ret: Location) {
+        self.emit_relaxed_int_to_fcvt(Size::S64, loc, Size::S64, ret, signed);
+    }
+    fn convert_f64_i32(&mut self, loc: Location, signed: bool, ret: Location) {
+        self.emit_relaxed_int_to_fcvt(Size::S32, loc, Size::S64, ret, signed);
+    }
+    fn convert_f32_i64(&mut self, loc: Location, signed: bool, ret: Location) {
+        self.emit_relaxed_int_to_fcvt(Size::S64, loc, Size::S32, ret, signed);
+    }
+    fn convert_f32_i32(&mut self, loc: Location, signed: bool, ret: Location) {
+        self.emit_relaxed_int_to_fcvt(Size::S32, loc, Size::S32, ret, signed);
+    }
+    fn convert_i64_f64(&mut self, loc: Location, ret: Location, signed: bool, sat: bool) {
+        if sat {
+            self.emit_relaxed_fcvt_to_int(Size::S64, loc, Size::S64, ret, signed);
+        } else {
+            self.emit_relaxed_fcvt_to_int_trap(Size::S64, loc, Size::S64, ret, signed);
+        }
+    }
+    fn convert_i32_f64(&mut self, loc: Location, ret: Location, signed: bool, sat: bool) {
+        if sat {
+            self.emit_relaxed_fcvt_to_int(Size::S64, loc, Size::S32, ret, signed);
+        } else {
+            self.emit_relaxed_fcvt_to_int_trap(Size::S64, loc, Size::S32, ret, signed);
+        }
+    }
+    fn convert_i64_f32(&mut self, loc: Location, ret: Location, signed: bool, sat: bool) {
+        if sat {
+            self.emit_relaxed_fcvt_to_int(Size::S32, loc, Size::S64, ret, signed);
+        } else {
+            self.emit_relaxed_fcvt_to_int_trap(Size::S32, loc, Size::S64, ret, signed);
+        }
+    }
+    fn convert_i32_f32(&mut self, loc: Location, ret: Location, signed: bool, sat: bool) {
+        if sat {
+            self.emit_relaxed_fcvt_to_int(Size::S32, loc, Size::S32, ret, signed);
+        } else {
+            self.emit_relaxed_fcvt_to_int_trap(Size::S32, loc, Size::S32, ret, signed);
+        }
+    }
+    fn convert_f64_f32(&mut self, loc: Location, ret: Location) {
+        let mut temps = vec![];
+        let src = self.location_to_neon(Size::S32, loc, &mut temps, ImmType::None, true);
+        let dest = self.location_to_neon(Size::S64, ret, &mut temps, ImmType::None, false);
+        self.assembler.emit_fcvt(Size::S32, src, Size::S64, dest);
+        if ret != dest {
+            self.move_location(Size::S64, dest, ret);
+        }
+        for r in temps {
+            self.release_simd(r);
+        }
+    }
+    fn convert_f32_f64(&mut self, loc: Location, ret: Location) {
+        let mut temps = vec![];
+        let src = self.location_to_neon(Size::S64, loc, &mut temps, ImmType::None, true);
+        let dest = self.location_to_neon(Size::S32, ret, &mut temps, ImmType::None, false);
+        self.assembler.emit_fcvt(Size::S64, src, Size::S32, dest);
+        if ret != dest {
+            self.move_location(Size::S32, dest, ret);
+        }
+        for r in temps {
+            self.release_simd(r);
+        }
+    }
+    fn f64_neg(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_fneg, Size::S64, loc, ret, true);
+    }
+    fn f64_abs(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_fabs, Size::S64, loc, ret, true);
+    }
+    fn emit_i64_copysign(&mut self, tmp1: GPR, tmp2: GPR) {
+        // Both masks are encodable as logical immediates.
+        self.assembler.emit_and(
+            Size::S64,
+            Location::GPR(tmp1),
+            Location::Imm64(0x7fffffffffffffffu64),
+            Location::GPR(tmp1),
+        );
+        self.assembler.emit_and(
+            Size::S64,
+            Location::GPR(tmp2),
+            Location::Imm64(0x8000000000000000u64),
+            Location::GPR(tmp2),
+        );
+        self.assembler.emit_orr(
+            Size::S64,
+            Location::GPR(tmp1),
+            Location::GPR(tmp2),
+            Location::GPR(tmp1),
+        );
+    }
+    fn f64_sqrt(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_fsqrt, Size::S64, loc, ret, true);
+    }
+    fn f64_trunc(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintz, Size::S64, loc, ret, true);
+    }
+    fn f64_ceil(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintp, Size::S64, loc, ret, true);
+    }
+    fn f64_floor(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintm, Size::S64, loc, ret, true);
+    }
+    fn f64_nearest(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintn, Size::S64, loc, ret, true);
+    }
+    fn f64_cmp_ge(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S64, Condition::Ge, loc_a, loc_b, ret);
+    }
+    fn f64_cmp_gt(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S64, Condition::Gt, loc_a, loc_b, ret);
+    }
+    fn f64_cmp_le(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S64, Condition::Ls, loc_a, loc_b, ret);
+    }
+    fn f64_cmp_lt(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S64, Condition::Mi, loc_a, loc_b, ret);
+    }
+    fn f64_cmp_ne(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S64, Condition::Ne, loc_a, loc_b, ret);
+    }
+    fn f64_cmp_eq(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S64, Condition::Eq, loc_a, loc_b, ret);
+    }
+    fn f64_min(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fmin,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+        // FMIN propagates a quieted input NaN, only the canonical one is deterministic.
+        self.canonicalize_nan(Size::S64, ret, ret);
+    }
+    fn f64_max(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fmax,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+        // FMAX propagates a quieted input NaN, only the canonical one is deterministic.
+        self.canonicalize_nan(Size::S64, ret, ret);
+    }
+    fn f64_add(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fadd,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn f64_sub(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fsub,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn f64_mul(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fmul,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn f64_div(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fdiv,
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn f32_neg(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_fneg, Size::S32, loc, ret, true);
+    }
+    fn f32_abs(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_fabs, Size::S32, loc, ret, true);
+    }
+    fn emit_i32_copysign(&mut self, tmp1: GPR, tmp2: GPR) {
+        // Both masks are encodable as logical immediates.
+        self.assembler.emit_and(
+            Size::S32,
+            Location::GPR(tmp1),
+            Location::Imm32(0x7fffffffu32),
+            Location::GPR(tmp1),
+        );
+        self.assembler.emit_and(
+            Size::S32,
+            Location::GPR(tmp2),
+            Location::Imm32(0x80000000u32),
+            Location::GPR(tmp2),
+        );
+        self.assembler.emit_orr(
+            Size::S32,
+            Location::GPR(tmp1),
+            Location::GPR(tmp2),
+            Location::GPR(tmp1),
+        );
+    }
+    fn f32_sqrt(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_fsqrt, Size::S32, loc, ret, true);
+    }
+    fn f32_trunc(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintz, Size::S32, loc, ret, true);
+    }
+    fn f32_ceil(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintp, Size::S32, loc, ret, true);
+    }
+    fn f32_floor(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintm, Size::S32, loc, ret, true);
+    }
+    fn f32_nearest(&mut self, loc: Location, ret: Location) {
+        self.emit_relaxed_binop_neon(Assembler::emit_frintn, Size::S32, loc, ret, true);
+    }
+    fn f32_cmp_ge(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S32, Condition::Ge, loc_a, loc_b, ret);
+    }
+    fn f32_cmp_gt(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S32, Condition::Gt, loc_a, loc_b, ret);
+    }
+    fn f32_cmp_le(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S32, Condition::Ls, loc_a, loc_b, ret);
+    }
+    fn f32_cmp_lt(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S32, Condition::Mi, loc_a, loc_b, ret);
+    }
+    fn f32_cmp_ne(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S32, Condition::Ne, loc_a, loc_b, ret);
+    }
+    fn f32_cmp_eq(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_cmpop_f_dynamic_b(Size::S32, Condition::Eq, loc_a, loc_b, ret);
+    }
+    fn f32_min(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fmin,
+            Size::S32,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+        // FMIN propagates a quieted input NaN, only the canonical one is deterministic.
+        self.canonicalize_nan(Size::S32, ret, ret);
+    }
+    fn f32_max(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fmax,
+            Size::S32,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+        // FMAX propagates a quieted input NaN, only the canonical one is deterministic.
+        self.canonicalize_nan(Size::S32, ret, ret);
+    }
+    fn f32_add(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fadd,
+            Size::S32,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn f32_sub(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fsub,
+            Size::S32,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn f32_mul(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fmul,
+            Size::S32,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    fn f32_div(&mut self, loc_a: Location, loc_b: Location, ret: Location) {
+        self.emit_relaxed_binop3_neon(
+            Assembler::emit_fdiv,
+            Size::S32,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+    }
+    // BIC computes loc_a & !loc_b
+    // SMULL/UMULL widen the low half lanes, SMULL2/UMULL2 the high half
+    // URHADD computes the rounded average without intermediate overflow
+    // Only the 8 loaded bytes are bounds checked, the widening happens in registers
+    // SMULL/SMULL2 give the products of lanes 0..3 and 4..7, ADDP then sums
+    // adjacent pairs so lanes 0 and 1 land in result lane 0
+    // Floats live in the low lane of a NEON register and are broadcast from there,
+    // anything else goes through a GPR (the bits are the same either way)
+    // SQRDMULH rounds, doubles and saturates (only -32768 * -32768 does) in one go
+    // SADDLP/UADDLP extend each lane before summing, so the pairs never overflow
+    // LDUR/STUR of a Q register never fault on misalignment, even with strict
+    // alignment checking enabled for normal memory
+    // SQXTUN clamps negative lanes to 0, unlike UQXTN which would see them as large values
+    // Cleans the data cache to the point of unification, then invalidates the
+    // instruction cache, line by line over the range, with the line sizes read from
+    // CTR_EL0 as they can differ between the cores of a big.LITTLE system
+    fn emit_icache_sync(&mut self, start: Location, len: Location) {
+        let base = self.acquire_temp_gpr().unwrap();
+        let end = self.acquire_temp_gpr().unwrap();
+        let ctr = self.acquire_temp_gpr().unwrap();
+        let line = self.acquire_temp_gpr().unwrap();
+        let cur = self.acquire_temp_gpr().unwrap();
+
+        self.move_location(Size::S64, start, Location::GPR(base));
+        self.move_location(Size::S64, len, Location::GPR(end));
+        self.assembler.emit_add(
+            Size::S64,
+            Location::GPR(base),
+            Location::GPR(end),
+            Location::GPR(end),
+        );
+        self.assembler.emit_read_ctr(ctr);
+
+        // DminLine in bits 16..20 for the data cache, IminLine in bits 0..4 for
+        // the instruction cache, both as log2 of the number of words in a line
+        for lsb in [16, 0] {
+            let again = self.get_label();
+            self.assembler
+                .emit_ubfx(Size::S64, Location::GPR(ctr), lsb, 4, Location::GPR(line));
+            self.assembler.emit_mov_imm(Location::GPR(cur), 4);
+            self.assembler.emit_lsl(
+                Size::S64,
+                Location::GPR(cur),
+                Location::GPR(line),
+                Location::GPR(line),
+            );
+            // Start from the line containing the first byte
+            self.assembler.emit_sub(
+                Size::S64,
+                Location::GPR(line),
+                Location::Imm8(1),
+                Location::GPR(cur),
+            );
+            self.assembler.emit_bic(
+                Size::S64,
+                Location::GPR(base),
+                Location::GPR(cur),
+                Location::GPR(cur),
+            );
+            self.emit_label(again);
+            if lsb == 16 {
+                self.assembler.emit_dc_cvau(cur);
+            } else {
+                self.assembler.emit_ic_ivau(cur);
+            }
+            self.assembler.emit_add(
+                Size::S64,
+                Location::GPR(cur),
+                Location::GPR(line),
+                Location::GPR(cur),
+            );
+            self.assembler
+                .emit_cmp(Size::S64, Location::GPR(end), Location::GPR(cur));
+            self.assembler.emit_bcond_label(Condition::Cc, again);
+            self.assembler.emit_dsb_ish();
+        }
+        self.assembler.emit_isb();
+
+        self.release_gpr(cur);
+        self.release_gpr(line);
+        self.release_gpr(ctr);
+        self.release_gpr(end);
+        self.release_gpr(base);
+    }
+    // FCVTN writes a 64 bits vector, which clears the high half of the register
+    fn gen_std_trampoline(
+        &self,
+        sig: &FunctionType,
+        calling_convention: CallingConvention,
+    ) -> Result<FunctionBody, CodegenError> {
+        let mut a = Assembler::new(0);
+
+        // Calculate stack offset.
+        let mut stack_offset: u32 = 0;
+        for (i, _param) in sig.params().iter().enumerate() {
+            if let Location::Memory(_, _) = self.get_param_location(1 + i, calling_convention) {
+                stack_offset += 8;
+            }
+        }
+        let stack_offset = self.round_stack_adjust(stack_offset as usize) as u32;
+
+        // Frame record, and the callee-saved registers used here or left unsaved by the callee.
+        a.emit_stpbd(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::X30),
+            GPR::XzrSp,
+            16,
+        );
+        a.emit_mov(
+            Size::S64,
+            Location::GPR(GPR::XzrSp),
+            Location::GPR(GPR::X29),
+        );
+        a.emit_stpbd(
+            Size::S64,
+            Location::GPR(GPR::X28),
+            Location::GPR(GPR::X20),
+            GPR::XzrSp,
+            16,
+        );
+        a.emit_stpbd(
+            Size::S64,
+            Location::GPR(GPR::X26),
+            Location::GPR(GPR::X27),
+            GPR::XzrSp,
+            16,
+        );
+
+        // Prepare stack space.
+        if stack_offset > 0 {
+            if is_add_sub_imm(stack_offset as u64) {
+                a.emit_sub(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::Imm32(stack_offset),
+                    Location::GPR(GPR::XzrSp),
+                );
+            } else {
+                a.emit_mov_imm(Location::GPR(GPR::X16), stack_offset as u64);
+                a.emit_sub(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::GPR(GPR::X16),
+                    Location::GPR(GPR::XzrSp),
+                );
+            }
+        }
+
+        // Arguments
+        a.emit_mov(
+            Size::S64,
+            self.get_param_location(1, calling_convention),
+            Location::GPR(GPR::X28),
+        ); // func_ptr, in the vmctx register so the callee sees it is entered from outside
+        a.emit_mov(
+            Size::S64,
+            self.get_param_location(2, calling_convention),
+            Location::GPR(GPR::X20),
+        ); // args_rets
+
+        // Move arguments to their locations.
+        // `callee_vmctx` is already in the first argument register, so no need to move.
+        {
+            let mut n_stack_args: usize = 0;
+            for (i, _param) in sig.params().iter().enumerate() {
+                let src_loc = Location::Memory(GPR::X20, (i * 16) as _); // args_rets[i]
+                let dst_loc = self.get_param_location(1 + i, calling_convention);
+
+                match dst_loc {
+                    Location::GPR(_) => {
+                        a.emit_ldr(Size::S64, dst_loc, src_loc);
+                    }
+                    Location::Memory(_, _) => {
+                        // This location is for reading arguments but we are writing arguments here.
+                        // So recalculate it.
+                        a.emit_ldr(Size::S64, Location::GPR(GPR::X16), src_loc);
+                        a.emit_str(
+                            Size::S64,
+                            Location::GPR(GPR::X16),
+                            Location::Memory(GPR::XzrSp, (n_stack_args * 8) as _),
+                        );
+                        n_stack_args += 1;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        // Call.
+        a.emit_call_register(GPR::X28);
+
+        // Restore stack.
+        if stack_offset > 0 {
+            if is_add_sub_imm(stack_offset as u64) {
+                a.emit_add(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::Imm32(stack_offset),
+                    Location::GPR(GPR::XzrSp),
+                );
+            } else {
+                a.emit_mov_imm(Location::GPR(GPR::X16), stack_offset as u64);
+                a.emit_add(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::GPR(GPR::X16),
+                    Location::GPR(GPR::XzrSp),
+                );
+            }
+        }
+
+        // Write return value, floats are returned in V0.
+        if let Some(ty) = sig.results().first() {
+            let ret = match ty {
+                Type::F32 | Type::F64 => Location::SIMD(NEON::V0),
+                _ => Location::GPR(GPR::X0),
+            };
+            a.emit_str(Size::S64, ret, Location::Memory(GPR::X20, 0));
+        }
+
+        // Restore callee-saved registers.
+        a.emit_ldpai(
+            Size::S64,
+            Location::GPR(GPR::X26),
+            Location::GPR(GPR::X27),
+            GPR::XzrSp,
+            16,
+        );
+        a.emit_ldpai(
+            Size::S64,
+            Location::GPR(GPR::X28),
+            Location::GPR(GPR::X20),
+            GPR::XzrSp,
+            16,
+        );
+        a.emit_ldpai(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::X30),
+            GPR::XzrSp,
+            16,
+        );
+
+        a.emit_ret();
+
+        Ok(FunctionBody {
+            body: a.finalize().unwrap().to_vec(),
+            unwind_info: None,
+        })
+    }
+    // Generates dynamic import function call trampoline for a function type.
+    fn gen_std_dynamic_import_trampoline(
+        &self,
+        vmoffsets: &VMOffsets,
+        sig: &FunctionType,
+        calling_convention: CallingConvention,
+    ) -> Result<FunctionBody, CodegenError> {
+        let mut a = Assembler::new(0);
+
+        // Allocate argument array.
+        let stack_offset: usize = 16 * std::cmp::max(sig.params().len(), sig.results().len());
+        let stack_offset = (stack_offset + 15) & !15;
+        a.emit_stpbd(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::X30),
+            GPR::XzrSp,
+            16,
+        );
+        a.emit_mov(
+            Size::S64,
+            Location::GPR(GPR::XzrSp),
+            Location::GPR(GPR::X29),
+        );
+        if stack_offset > 0 {
+            if is_add_sub_imm(stack_offset as u64) {
+                a.emit_sub(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::Imm32(stack_offset as u32),
+                    Location::GPR(GPR::XzrSp),
+                );
+            } else {
+                a.emit_mov_imm(Location::GPR(GPR::X16), stack_offset as u64);
+                a.emit_sub(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::GPR(GPR::X16),
+                    Location::GPR(GPR::XzrSp),
+                );
+            }
+        }
+
+        // Copy arguments, which follow the native calling convention of the import.
+        let mut argalloc = ArgumentRegisterAllocator::default();
+        argalloc.next(Type::I64, calling_convention)?; // skip VMContext
+        for (i, ty) in sig.params().iter().enumerate() {
+            let source_loc = match argalloc.next(*ty, calling_convention)? {
+                Some(ARM64Register::GPR(gpr)) => Location::GPR(gpr),
+                Some(ARM64Register::NEON(neon)) => Location::SIMD(neon),
+                None => {
+                    let (offset, sz) = argalloc.next_stack(*ty, calling_convention);
+                    a.emit_ldr(
+                        sz,
+                        Location::GPR(GPR::X16),
+                        Location::Memory(GPR::X29, (16 + offset) as _),
+                    );
+                    Location::GPR(GPR::X16)
+                }
+            };
+            a.emit_str(
+                Size::S64,
+                source_loc,
+                Location::Memory(GPR::XzrSp, (i * 16) as _),
+            );
+
+            // Zero upper 64 bits.
+            a.emit_str(
+                Size::S64,
+                Location::GPR(GPR::XzrSp),
+                Location::Memory(GPR::XzrSp, (i * 16 + 8) as _),
+            );
+        }
+
+        // Load target address.
+        a.emit_ldr(
+            Size::S64,
+            Location::GPR(GPR::X16),
+            Location::Memory(
+                GPR::X0,
+                vmoffsets.vmdynamicfunction_import_context_address() as i32,
+            ),
+        );
+        // Load values array.
+        a.emit_mov(Size::S64, Location::GPR(GPR::XzrSp), Location::GPR(GPR::X1));
+
+        // Call target.
+        a.emit_call_register(GPR::X16);
+
+        // Fetch return value, floats are returned in V0.
+        if !sig.results().is_empty() {
+            assert_eq!(sig.results().len(), 1);
+            let ret = match sig.results()[0] {
+                Type::F32 => (Size::S32, Location::SIMD(NEON::V0)),
+                Type::F64 => (Size::S64, Location::SIMD(NEON::V0)),
+                _ => (Size::S64, Location::GPR(GPR::X0)),
+            };
+            a.emit_ldr(ret.0, ret.1, Location::Memory(GPR::XzrSp, 0));
+        }
+
+        // Release values array.
+        a.emit_mov(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::XzrSp),
+        );
+        a.emit_ldpai(
+            Size::S64,
+            Location::GPR(GPR::X29),
+            Location::GPR(GPR::X30),
+            GPR::XzrSp,
+            16,
+        );
+
+        // Return.
+        a.emit_ret();
+
+        Ok(FunctionBody {
+            body: a.finalize().unwrap().to_vec(),
+            unwind_info: None,
+        })
+    }
+    // Singlepass calls import functions through a trampoline.
+    fn gen_import_call_trampoline(
+        &self,
+        vmoffsets: &VMOffsets,
+        index: FunctionIndex,
+        sig: &FunctionType,
+        calling_convention: CallingConvention,
+    ) -> Result<CustomSection, CodegenError> {
+        let mut a = Assembler::new(0);
+
+        // Singlepass internally treats all arguments as integers, and pushes stack arguments
+        // in 8-byte slots, while AAPCS64 wants floating point arguments in NEON registers
+        // and Apple's variant of it packs stack arguments by their size.
+        // Translation is expensive, so only do it if needed.
+        if sig
+            .params()
+            .iter()
+            .any(|&x| x == Type::F32 || x == Type::F64)
+            || sig.params().len() > 7
+        {
+            let mut param_locations: Vec<Location> = vec![];
+
+            // Allocate stack space for arguments.
+            let stack_offset: i32 = if sig.params().len() > 7 {
+                7 * 8
+            } else {
+                (sig.params().len() as i32) * 8
+            };
+            let stack_offset = (stack_offset + 15) & !15;
+            if stack_offset > 0 {
+                a.emit_sub(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::Imm32(stack_offset as u32),
+                    Location::GPR(GPR::XzrSp),
+                );
+            }
+
+            // Store all arguments to the stack to prevent overwrite.
+            for i in 0..sig.params().len() {
+                let loc = match i {
+                    0..=6 => {
+                        static PARAM_REGS: &[GPR] = &[
+                            GPR::X1,
+                            GPR::X2,
+                            GPR::X3,
+                            GPR::X4,
+                            GPR::X5,
+                            GPR::X6,
+                            GPR::X7,
+                        ];
+                        let loc = Location::Memory(GPR::XzrSp, (i * 8) as i32);
+                        a.emit_str(Size::S64, Location::GPR(PARAM_REGS[i]), loc);
+                        loc
+                    }
+                    _ => Location::Memory(GPR::XzrSp, stack_offset + ((i - 7) * 8) as i32),
+                };
+                param_locations.push(loc);
+            }
+
+            // Copy arguments.
+            let mut argalloc = ArgumentRegisterAllocator::default();
+            argalloc.next(Type::I64, calling_convention)?; // skip VMContext
+            for (i, ty) in sig.params().iter().enumerate() {
+                let prev_loc = param_locations[i];
+                let targ = match argalloc.next(*ty, calling_convention)? {
+                    Some(ARM64Register::GPR(gpr)) => Location::GPR(gpr),
+                    Some(ARM64Register::NEON(neon)) => Location::SIMD(neon),
+                    None => {
+                        // No register can be allocated. Put this argument on the stack.
+                        //
+                        // Native stack arguments are packed as close as ours or closer, so the
+                        // copy "backward" between slots of the caller argument region always works.
+                        let (caller_stack_offset, sz) =
+                            argalloc.next_stack(*ty, calling_convention);
+                        a.emit_ldr(Size::S64, Location::GPR(GPR::X16), prev_loc);
+                        a.emit_str(
+                            sz,
+                            Location::GPR(GPR::X16),
+                            Location::Memory(GPR::XzrSp, stack_offset + caller_stack_offset as i32),
+                        );
+                        continue;
+                    }
+                };
+                a.emit_ldr(Size::S64, targ, prev_loc);
+            }
+
+            // Restore stack pointer.
+            if stack_offset > 0 {
+                a.emit_add(
+                    Size::S64,
+                    Location::GPR(GPR::XzrSp),
+                    Location::Imm32(stack_offset as u32),
+                    Location::GPR(GPR::XzrSp),
+                );
+            }
+        }
+
+        // Emits a tail call trampoline that loads the address of the target import function
+        // from Ctx and jumps to it.
+
+        let offset = vmoffsets.vmctx_vmfunction_import(index);
+
+        a.emit_ldr(
+            Size::S64,
+            Location::GPR(GPR::X16),
+            Location::Memory(GPR::X0, offset as i32), // function pointer
+        );
+        a.emit_ldr(
+            Size::S64,
+            Location::GPR(GPR::X0),
+            Location::Memory(GPR::X0, offset as i32 + 8), // target vmctx
+        );
+        a.emit_b_register(GPR::X16);
+
+        let section_body = SectionBody::new_with_vec(a.finalize().unwrap().to_vec());
+
+        Ok(CustomSection {
+            protection: CustomSectionProtection::ReadExecute,
+            bytes: section_body,
+            relocations: vec![],
+        })
+    }
+}
+
+// Lowerings that no operator in codegen reaches yet: the `v128` ones wait for
+// singlepass to have SIMD values, the others for embedders to call them.
+#[allow(dead_code)]
+impl MachineARM64 {
+    /// Zero `byte_len` bytes of stack, starting at the address of `base` and going up
+    fn emit_zero_region(&mut self, base: Location, byte_len: u64) -> Result<(), CodegenError> {
+        let (reg, offset) = match base {
+            Location::Memory(reg, offset) => (reg, offset),
+            _ => unreachable!(),
+        };
+        let addr = self.acquire_temp_gpr().unwrap();
+        let magnitude = (offset as i64).unsigned_abs();
+        if is_add_sub_imm(magnitude) {
+            let op = if offset < 0 {
+                Assembler::emit_sub
+            } else {
+                Assembler::emit_add
+            };
+            op(
+                &mut self.assembler,
+                Size::S64,
+                Location::GPR(reg),
+                Location::Imm32(magnitude as u32),
+                Location::GPR(addr),
+            );
+        } else {
+            self.assembler
+                .emit_mov_imm(Location::GPR(addr), (offset as i64) as u64);
+            self.assembler.emit_add(
+                Size::S64,
+                Location::GPR(reg),
+                Location::GPR(addr),
+                Location::GPR(addr),
+            );
+        }
+        let pairs = byte_len / 16;
+        if pairs != 0 {
+            let cnt = self.acquire_temp_gpr().unwrap();
+            self.assembler.emit_mov_imm(Location::GPR(cnt), pairs);
+            let label = self.get_label();
+            self.emit_label(label);
+            self.assembler.emit_stpia(
+                Size::S64,
+                Location::GPR(GPR::XzrSp),
+                Location::GPR(GPR::XzrSp),
+                addr,
+                16,
+            );
+            self.assembler.emit_sub(
+                Size::S64,
+                Location::GPR(cnt),
+                Location::Imm8(1),
+                Location::GPR(cnt),
+            );
+            self.assembler
+                .emit_cbnz_label(Size::S64, Location::GPR(cnt), label);
+            self.release_gpr(cnt);
+        }
+        let mut tail = 0;
+        for (sz, width) in [
+            (Size::S64, 8),
+            (Size::S32, 4),
+            (Size::S16, 2),
+            (Size::S8, 1),
+        ] {
+            if byte_len & width == 0 {
+                continue;
+            }
+            let op = match sz {
+                Size::S64 | Size::S32 => Assembler::emit_str,
+                Size::S16 => Assembler::emit_strh,
+                _ => Assembler::emit_strb,
+            };
+            op(
+                &mut self.assembler,
+                sz,
+                Location::GPR(GPR::XzrSp),
+                Location::Memory(addr, tail),
+            );
+            tail += width as i32;
+        }
+        self.release_gpr(addr);
+        Ok(())
+    }
+    /// Emit a signal waking up the cores waiting for an event, like SEV on ARM64
+    fn emit_event_signal(&mut self) -> Result<(), CodegenError> {
+        self.assembler.emit_sev();
+        Ok(())
+    }
+    /// Emit a wait for an event, like WFE on ARM64
+    fn emit_event_wait(&mut self) -> Result<(), CodegenError> {
+        self.assembler.emit_wfe();
+        Ok(())
+    }
+    /// High 64 bits of the 128-bit product of two 64-bit values, for embedder intrinsics
+    fn emit_mul_high(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        signed: bool,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.emit_relaxed_binop3(
+            if signed {
+                Assembler::emit_smulh
+            } else {
+                Assembler::emit_umulh
+            },
+            Size::S64,
+            loc_a,
+            loc_b,
+            ret,
+            ImmType::None,
+        );
+        Ok(())
+    }
+    /// Copy the sign of loc_b to the F64 loc_a, in SIMD registers
+    fn f64_copysign(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.emit_neon_copysign(Size::S64, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Copy the sign of loc_b to the F32 loc_a, in SIMD registers
+    fn f32_copysign(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.emit_neon_copysign(Size::S32, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Bitwise Not of a V128
+    fn v128_not(&mut self, loc: Location, ret: Location) -> Result<(), CodegenError> {
+        self.assembler.emit_vmvn(loc, ret);
+        Ok(())
+    }
+    /// Bitwise And of 2 V128 values
+    fn v128_and(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vand(loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Bitwise Or of 2 V128 values
+    fn v128_or(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vorr(loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Bitwise Xor of 2 V128 values
+    fn v128_xor(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_veor(loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Bitwise And of a V128 with the complement of another (a & !b)
+    fn v128_andnot(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vbic(loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise multiply of 2 V128 values, lane being the size of each lane
+    fn v128_mul(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vmul(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Widening multiply of the low (or high) half lanes of 2 V128 values,
+    /// lane being the size of the source lanes
+    fn v128_extmul(
+        &mut self,
+        lane: Size,
+        signed: bool,
+        high: bool,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        if signed {
+            self.assembler.emit_smull(lane, high, loc_a, loc_b, ret);
+        } else {
+            self.assembler.emit_umull(lane, high, loc_a, loc_b, ret);
+        }
+        Ok(())
+    }
+    /// Lane-wise unsigned rounding average (a + b + 1) >> 1 of 2 V128 values,
+    /// lane being the size of each lane
+    fn emit_simd_avgr_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_urhadd(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Load 8 bytes and sign or zero extend each lane to twice its width,
+    /// lane being the size of the loaded lanes
+    fn v128_load_extend(
+        &mut self,
+        lane: Size,
+        signed: bool,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) -> Result<(), CodegenError> {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            8,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.emit_relaxed_load(
+                    Assembler::emit_ldr,
+                    Size::S64,
+                    ret,
+                    Location::Memory(addr, 0),
+                );
+                if signed {
+                    this.assembler.emit_sshll(lane, ret, ret);
+                } else {
+                    this.assembler.emit_ushll(lane, ret, ret);
+                }
+            },
+        );
+        Ok(())
+    }
+    /// Multiply the signed i16 lanes of 2 V128 values and add adjacent
+    /// products into i32 lanes
+    fn emit_i32x4_dot_i16x8_s(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        let tmp_low = self.acquire_temp_simd().unwrap();
+        let tmp_high = self.acquire_temp_simd().unwrap();
+        self.assembler
+            .emit_smull(Size::S16, false, loc_a, loc_b, Location::SIMD(tmp_low));
+        self.assembler
+            .emit_smull(Size::S16, true, loc_a, loc_b, Location::SIMD(tmp_high));
+        self.assembler.emit_addp(
+            Size::S32,
+            Location::SIMD(tmp_low),
+            Location::SIMD(tmp_high),
+            ret,
+        );
+        self.release_simd(tmp_high);
+        self.release_simd(tmp_low);
+        Ok(())
+    }
+    /// Broadcast a scalar to every lane of a V128 value,
+    /// lane being the size of each lane
+    fn emit_splat(&mut self, src: Location, lane: Size, ret: Location) -> Result<(), CodegenError> {
+        match src {
+            Location::SIMD(_) => self.assembler.emit_dup_element(lane, src, ret),
+            _ => {
+                let mut temps = vec![];
+                let src = self.location_to_reg(lane, src, &mut temps, ImmType::None, true, None);
+                self.assembler.emit_dup(lane, src, ret);
+                for r in temps {
+                    self.release_gpr(r);
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Q15 fixed-point rounding multiply of the i16 lanes of 2 V128 values,
+    /// saturating to i16
+    fn emit_i16x8_q15mulr_sat_s(
+        &mut self,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_sqrdmulh(Size::S16, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Set ret to 1 if any bit of the V128 value is set, 0 otherwise
+    fn emit_v128_any_true(&mut self, loc: Location, ret: Location) -> Result<(), CodegenError> {
+        let tmp = self.emit_v128_reduce_to_gpr(loc);
+        self.assembler
+            .emit_cmp(Size::S64, Location::Imm8(0), Location::GPR(tmp));
+        match ret {
+            Location::GPR(_) => self.assembler.emit_cset(Size::S32, ret, Condition::Ne),
+            _ => {
+                self.assembler
+                    .emit_cset(Size::S32, Location::GPR(tmp), Condition::Ne);
+                self.move_location(Size::S32, Location::GPR(tmp), ret);
+            }
+        }
+        self.release_gpr(tmp);
+        Ok(())
+    }
+    /// Jump to label if any bit of the V128 value is set
+    fn emit_v128_any_true_branch(
+        &mut self,
+        loc: Location,
+        label: Label,
+    ) -> Result<(), CodegenError> {
+        let tmp = self.emit_v128_reduce_to_gpr(loc);
+        self.assembler
+            .emit_cbnz_label(Size::S64, Location::GPR(tmp), label);
+        self.release_gpr(tmp);
+        Ok(())
+    }
+    /// Count the set bits of each i8 lane of a V128 value
+    fn emit_i8x16_popcnt(&mut self, loc: Location, ret: Location) -> Result<(), CodegenError> {
+        self.assembler.emit_cnt(loc, ret);
+        Ok(())
+    }
+    /// Round up each float lane of a V128 value, lane being the size of each lane
+    fn v128_ceil(&mut self, lane: Size, loc: Location, ret: Location) -> Result<(), CodegenError> {
+        self.assembler.emit_vfrintp(lane, loc, ret);
+        Ok(())
+    }
+    /// Round down each float lane of a V128 value, lane being the size of each lane
+    fn v128_floor(&mut self, lane: Size, loc: Location, ret: Location) -> Result<(), CodegenError> {
+        self.assembler.emit_vfrintm(lane, loc, ret);
+        Ok(())
+    }
+    /// Round toward zero each float lane of a V128 value, lane being the size of each lane
+    fn v128_trunc(&mut self, lane: Size, loc: Location, ret: Location) -> Result<(), CodegenError> {
+        self.assembler.emit_vfrintz(lane, loc, ret);
+        Ok(())
+    }
+    /// Round to nearest, ties to even, each float lane of a V128 value, lane being the size of each lane
+    fn v128_nearest(
+        &mut self,
+        lane: Size,
+        loc: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vfrintn(lane, loc, ret);
+        Ok(())
+    }
+    /// Add adjacent lanes of a V128 value into lanes of twice the width,
+    /// lane being the size of the source lanes
+    fn v128_extadd_pairwise(
+        &mut self,
+        lane: Size,
+        signed: bool,
+        loc: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        if signed {
+            self.assembler.emit_saddlp(lane, loc, ret);
+        } else {
+            self.assembler.emit_uaddlp(lane, loc, ret);
+        }
+        Ok(())
+    }
+    /// Load one lane of a V128 value from memory, keeping the other lanes of loc,
+    /// lane being the size of the lane
+    fn v128_load_lane(
+        &mut self,
+        lane: Size,
+        lane_index: u8,
+        loc: Location,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) -> Result<(), CodegenError> {
+        // A move between NEON registers copies all 128 bits
+        if loc != ret {
+            self.move_location(Size::S64, loc, ret);
+        }
+        let value_size = match lane {
+            Size::S8 => 1,
+            Size::S16 => 2,
+            Size::S32 => 4,
+            Size::S64 => 8,
+            Size::S128 => unreachable!(),
+        };
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            value_size,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.assembler
+                    .emit_ld1_lane(lane, ret, lane_index, Location::GPR(addr));
+            },
+        );
+        Ok(())
+    }
+    /// Store one lane of a V128 value to memory, lane being the size of the lane
+    fn v128_store_lane(
+        &mut self,
+        lane: Size,
+        lane_index: u8,
+        value: Location,
+        memarg: &MemoryImmediate,
+        addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) -> Result<(), CodegenError> {
+        let value_size = match lane {
+            Size::S8 => 1,
+            Size::S16 => 2,
+            Size::S32 => 4,
+            Size::S64 => 8,
+            Size::S128 => unreachable!(),
+        };
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            value_size,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| {
+                this.assembler
+                    .emit_st1_lane(lane, value, lane_index, Location::GPR(addr));
+            },
+        );
+        Ok(())
+    }
+    /// Lane-wise equal comparison of the float lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_fcmp_eq(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vfcmeq(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise not equal comparison of the float lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_fcmp_ne(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        // NaN lanes compare not equal, so the inverted FCMEQ sets them
+        self.assembler.emit_vfcmeq(lane, loc_a, loc_b, ret);
+        self.assembler.emit_vmvn(ret, ret);
+        Ok(())
+    }
+    /// Lane-wise less than comparison of the float lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_fcmp_lt(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vfcmgt(lane, loc_b, loc_a, ret);
+        Ok(())
+    }
+    /// Lane-wise less or equal comparison of the float lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_fcmp_le(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vfcmge(lane, loc_b, loc_a, ret);
+        Ok(())
+    }
+    /// Lane-wise greater than comparison of the float lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_fcmp_gt(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vfcmgt(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise greater or equal comparison of the float lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_fcmp_ge(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vfcmge(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise equal comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_eq(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmeq(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise not equal comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_ne(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmeq(lane, loc_a, loc_b, ret);
+        self.assembler.emit_vmvn(ret, ret);
+        Ok(())
+    }
+    /// Lane-wise signed less than comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_lt_s(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmgt(lane, loc_b, loc_a, ret);
+        Ok(())
+    }
+    /// Lane-wise unsigned less than comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_lt_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmhi(lane, loc_b, loc_a, ret);
+        Ok(())
+    }
+    /// Lane-wise signed less or equal comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_le_s(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmge(lane, loc_b, loc_a, ret);
+        Ok(())
+    }
+    /// Lane-wise unsigned less or equal comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_le_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmhs(lane, loc_b, loc_a, ret);
+        Ok(())
+    }
+    /// Lane-wise signed greater than comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_gt_s(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmgt(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise unsigned greater than comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_gt_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmhi(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise signed greater or equal comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_ge_s(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmge(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise unsigned greater or equal comparison of the integer lanes of 2 V128 values,
+    /// lane being the size of each lane. Each lane of ret is set to all ones or all zeros
+    fn v128_cmp_ge_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_vcmhs(lane, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise signed minimum of the integer lanes of 2 V128 values, lane being the size of each lane
+    fn v128_min_s(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.emit_v128_minmax(lane, true, false, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise unsigned minimum of the integer lanes of 2 V128 values, lane being the size of each lane
+    fn v128_min_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.emit_v128_minmax(lane, false, false, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise signed maximum of the integer lanes of 2 V128 values, lane being the size of each lane
+    fn v128_max_s(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.emit_v128_minmax(lane, true, true, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// Lane-wise unsigned maximum of the integer lanes of 2 V128 values, lane being the size of each lane
+    fn v128_max_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.emit_v128_minmax(lane, false, true, loc_a, loc_b, ret);
+        Ok(())
+    }
+    /// v128 load. The alignment hint of memarg is never enforced
+    fn v128_load(
+        &mut self,
+        addr: Location,
+        memarg: &MemoryImmediate,
+        ret: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) -> Result<(), CodegenError> {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            16,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| this.assembler.emit_ldur_q(ret, addr, 0),
+        );
+        Ok(())
+    }
+    /// v128 save. The alignment hint of memarg is never enforced
+    fn v128_save(
+        &mut self,
+        value: Location,
+        memarg: &MemoryImmediate,
+        addr: Location,
+        need_check: bool,
+        imported_memories: bool,
+        offset: i32,
+        heap_access_oob: Label,
+    ) -> Result<(), CodegenError> {
+        self.memory_op(
+            addr,
+            memarg,
+            false,
+            16,
+            need_check,
+            imported_memories,
+            offset,
+            heap_access_oob,
+            |this, addr| this.assembler.emit_stur_q(value, addr, 0),
+        );
+        Ok(())
+    }
+    /// Narrow the signed lanes of 2 V128 values to half their width with unsigned
+    /// saturation, lane being the size of the input lanes. loc_a gives the low half of ret
+    fn emit_simd_narrow_u(
+        &mut self,
+        lane: Size,
+        loc_a: Location,
+        loc_b: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        // The low half of ret is written first, so it must not be loc_b
+        let dst = if ret == loc_b {
+            Location::SIMD(self.acquire_temp_simd().unwrap())
+        } else {
+            ret
+        };
+        self.assembler.emit_sqxtun(lane, loc_a, dst);
+        self.assembler.emit_sqxtun2(lane, loc_b, dst);
+        if dst != ret {
+            self.assembler.emit_mov(Size::S64, dst, ret);
+            if let Location::SIMD(dst) = dst {
+                self.release_simd(dst);
+            }
+        }
+        Ok(())
+    }
+    /// Convert the 2 low f32 lanes of a V128 value to f64 lanes
+    fn emit_f64x2_promote_low_f32x4(
+        &mut self,
+        loc: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_fcvtl(loc, ret);
+        Ok(())
+    }
+    /// Convert the f64 lanes of a V128 value to the 2 low f32 lanes, zeroing the 2 high lanes
+    fn emit_f32x4_demote_f64x2_zero(
+        &mut self,
+        loc: Location,
+        ret: Location,
+    ) -> Result<(), CodegenError> {
+        self.assembler.emit_fcvtn(loc, ret);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::Reg;
+    use wasmer_types::entity::EntityRef;
+    use wasmer_types::LocalFunctionIndex;
+    use wasmer_vm::libcalls::LibCall;
+
+    /// Splits `body` into its instruction words.
+    fn words(body: &[u8]) -> Vec<u32> {
+        body.chunks(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect()
+    }
+
+    /// Runs the code emitted by `emit` as a function and returns its X0.
+    ///
+    /// The code is entered with X0 pointing at `data`, and with a vmctx whose
+    /// memory at offset 0 is `data`, so `memory_op` accesses reach the buffer.
+    fn execute(data: &mut [u8], emit: impl FnOnce(&mut MachineARM64)) -> u64 {
+        let mut machine = MachineARM64::new();
+        machine.emit_function_prolog();
+        machine.assembler.emit_stpbd(
+            Size::S64,
+            Location::GPR(GPR::X27),
+            Location::GPR(GPR::X28),
+            GPR::XzrSp,
+            16,
+        );
+        machine
+            .assembler
+            .emit_mov(Size::S64, Location::GPR(GPR::X1), Location::GPR(GPR::X28));
+        emit(&mut machine);
+        machine.assembler.emit_ldpai(
+            Size::S64,
+            Location::GPR(GPR::X27),
+            Location::GPR(GPR::X28),
+            GPR::XzrSp,
+            16,
+        );
+        machine.emit_function_epilog();
+        machine.emit_ret();
+        let code = machine.assembler_finalize();
+        let vmctx = [data.as_mut_ptr() as u64, data.len() as u64];
+        unsafe { call_native(&code, data.as_mut_ptr(), vmctx.as_ptr()) }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    unsafe fn call_native(code: &[u8], data: *mut u8, vmctx: *const u64) -> u64 {
+        extern "C" {
+            fn __clear_cache(start: *mut u8, end: *mut u8);
+        }
+        let mut buffer = dynasmrt::mmap::MutableBuffer::new(code.len()).unwrap();
+        buffer.set_len(code.len());
+        buffer.copy_from_slice(code);
+        let buffer = buffer.make_exec().unwrap();
+        let start = buffer.as_ptr() as *mut u8;
+        __clear_cache(start, start.add(code.len()));
+        let function: extern "C" fn(*mut u8, *const u64) -> u64 = std::mem::transmute(start);
+        function(data, vmctx)
+    }
+
+    #[cfg(not(target_arch = "aarch64"))]
+    unsafe fn call_native(_code: &[u8], _data: *mut u8, _vmctx: *const u64) -> u64 {
+        unreachable!("ARM64 code can only run on an aarch64 host")
+    }
+
+    /// Loads `args` into V16 onwards, runs `emit` on their locations with V24 as
+    /// the result location, and returns the value left in V24.
+    fn execute_v128(
+        args: &[u128],
+        emit: impl FnOnce(&mut MachineARM64, &[Location], Location),
+    ) -> u128 {
+        let mut data: Vec<u8> = args
+            .iter()
+            .chain(std::iter::once(&0))
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let locs: Vec<Location> = (0..args.len())
+            .map(|i| Location::SIMD(NEON::from_index(16 + i).unwrap()))
+            .collect();
+        let ret = Location::SIMD(NEON::V24);
+        let ret_offset = (args.len() * 16) as i32;
+        execute(&mut data, |machine| {
+            for (i, loc) in locs.iter().enumerate() {
+                machine.assembler.emit_ldr(
+                    Size::S128,
+                    *loc,
+                    Location::Memory(GPR::X0, (i * 16) as i32),
+                );
+            }
+            emit(machine, &locs, ret);
+            machine
+                .assembler
+                .emit_str(Size::S128, ret, Location::Memory(GPR::X0, ret_offset));
+        });
+        let mut result = [0; 16];
+        result.copy_from_slice(&data[ret_offset as usize..]);
+        u128::from_le_bytes(result)
+    }
+
+    /// Runs `emit` with `memory` as the wasm memory, V24 as the result location
+    /// and a label to branch to on out-of-bounds accesses. Returns the value left
+    /// in V24 and the contents of the memory afterwards.
+    fn execute_memory_v128(
+        memory: &[u8],
+        emit: impl FnOnce(&mut MachineARM64, Location, Label),
+    ) -> (u128, Vec<u8>) {
+        let mut data = memory.to_vec();
+        data.extend_from_slice(&[0; 16]);
+        let ret = Location::SIMD(NEON::V24);
+        execute(&mut data, |machine| {
+            let oob = machine.get_label();
+            let end = machine.get_label();
+            emit(machine, ret, oob);
+            machine.assembler.emit_str(
+                Size::S128,
+                ret,
+                Location::Memory(GPR::X0, memory.len() as i32),
+            );
+            machine.jmp_unconditionnal(end);
+            machine.emit_label(oob);
+            machine.emit_illegal_op();
+            machine.emit_label(end);
+        });
+        let mut result = [0; 16];
+        result.copy_from_slice(&data[memory.len()..]);
+        data.truncate(memory.len());
+        (u128::from_le_bytes(result), data)
+    }
+
+    /// Splits a v128 value into lanes of `bits` bits, lowest lane first.
+    fn lanes(value: u128, bits: u32) -> Vec<u128> {
+        let mask = u128::MAX >> (128 - bits);
+        (0..128 / bits)
+            .map(|i| (value >> (i * bits)) & mask)
+            .collect()
+    }
+
+    /// Builds a v128 value from lanes of `bits` bits, lowest lane first.
+    fn from_lanes(lanes: impl IntoIterator<Item = u128>, bits: u32) -> u128 {
+        let mask = u128::MAX >> (128 - bits);
+        lanes
+            .into_iter()
+            .enumerate()
+            .fold(0, |acc, (i, lane)| acc | (lane & mask) << (i as u32 * bits))
+    }
+
+    /// Sign-extends the low `bits` bits of `lane`.
+    fn sign_extend(lane: u128, bits: u32) -> i128 {
+        ((lane << (128 - bits)) as i128) >> (128 - bits)
+    }
+
+    #[test]
+    fn finalize_with_metadata_traps_within_code() {
+        let mut machine = MachineARM64::new();
+        machine.insert_stackoverflow();
+        machine.emit_function_prolog();
+        machine.mark_address_with_trap_code(TrapCode::UnreachableCodeReached);
+        machine.emit_illegal_op();
+        machine.emit_function_epilog();
+        machine.emit_ret();
+
+        let (body, traps, address_map) = machine.finalize_with_metadata();
+        assert_eq!(traps.len(), 2);
+        for trap in traps {
+            assert!((trap.code_offset as usize) < body.len());
+        }
+        for map in address_map {
+            assert!(map.code_offset + map.code_len <= body.len());
+        }
+    }
+
+    #[test]
+    fn copysign_builds_sign_mask_from_immediates() {
+        for sz in [Size::S32, Size::S64] {
+            let mut machine = MachineARM64::new();
+            let (a, b) = (Location::SIMD(NEON::V8), Location::SIMD(NEON::V9));
+            match sz {
+                Size::S32 => machine.f32_copysign(a, b, a),
+                _ => machine.f64_copysign(a, b, a),
+            }
+            .unwrap();
+            let body = machine.assembler_finalize();
+            let insns = words(&body);
+            // MOVI (vector), and no LDR (literal)
+            assert!(insns.iter().any(|i| i & 0x9ff8_0c00 == 0x0f00_0400));
+            assert!(insns.iter().all(|i| i & 0x3b00_0000 != 0x1800_0000));
+        }
+    }
+
+    #[test]
+    fn mul_high_picks_umulh_or_smulh() {
+        let mut machine = MachineARM64::new();
+        let (a, b, ret) = (
+            Location::GPR(GPR::X9),
+            Location::GPR(GPR::X11),
+            Location::GPR(GPR::X10),
+        );
+        machine.emit_mul_high(a, b, false, ret).unwrap();
+        machine.emit_mul_high(a, b, true, ret).unwrap();
+        let body = machine.assembler_finalize();
+        // UMULH X10, X9, X11, then SMULH X10, X9, X11
+        assert_eq!(body, [0x2a, 0x7d, 0xcb, 0x9b, 0x2a, 0x7d, 0x4b, 0x9b]);
+    }
+
+    #[test]
+    fn mul_high_of_immediates() {
+        let mut machine = MachineARM64::new();
+        let ret = Location::GPR(GPR::X10);
+        // u64::MAX * u64::MAX = 0xffff_ffff_ffff_fffe_0000_0000_0000_0001
+        machine
+            .emit_mul_high(
+                Location::Imm64(u64::MAX),
+                Location::Imm64(u64::MAX),
+                false,
+                ret,
+            )
+            .unwrap();
+        // -3 * 5 = -15, whose high part is all ones
+        machine
+            .emit_mul_high(Location::Imm64(-3i64 as u64), Location::Imm64(5), true, ret)
+            .unwrap();
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        // both operands go through temporaries before UMULH X10, X1, X2 and SMULH X10, X1, X2
+        assert!(insns.contains(&0x9bc2_7c2a));
+        assert_eq!(insns.last(), Some(&0x9b42_7c2a));
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn mul_high_returns_the_top_of_the_full_product() {
+        let pairs = [
+            (u64::MAX, u64::MAX),
+            (-3i64 as u64, 5),
+            (1 << 63, 2),
+            (0x1234_5678_9abc_def0, 0x0fed_cba9_8765_4321),
+        ];
+        for (a, b) in pairs {
+            for signed in [false, true] {
+                let expected = if signed {
+                    ((a as i64 as i128 * b as i64 as i128) >> 64) as u64
+                } else {
+                    ((a as u128 * b as u128) >> 64) as u64
+                };
+                let ret = Location::GPR(GPR::X0);
+                let from_regs = execute(&mut [], |machine| {
+                    machine.assembler.emit_mov_imm(Location::GPR(GPR::X9), a);
+                    machine.assembler.emit_mov_imm(Location::GPR(GPR::X11), b);
+                    machine
+                        .emit_mul_high(Location::GPR(GPR::X9), Location::GPR(GPR::X11), signed, ret)
+                        .unwrap();
+                });
+                assert_eq!(from_regs, expected, "{:#x} * {:#x} signed={}", a, b, signed);
+                let from_imms = execute(&mut [], |machine| {
+                    machine
+                        .emit_mul_high(Location::Imm64(a), Location::Imm64(b), signed, ret)
+                        .unwrap();
+                });
+                assert_eq!(from_imms, expected, "{:#x} * {:#x} signed={}", a, b, signed);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_region_loops_over_pairs_then_clears_the_tail() {
+        let mut machine = MachineARM64::new();
+        machine
+            .emit_zero_region(Location::Memory(GPR::X29, -256), 256)
+            .unwrap();
+        machine
+            .emit_zero_region(Location::Memory(GPR::X29, -64), 47)
+            .unwrap();
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        // SUB x1, x29, #256 / MOV w2, #16 / STP xzr, xzr, [x1], #16 / SUB x2, x2, #1 / CBNZ x2, <loop>
+        assert_eq!(
+            insns[..5],
+            [
+                0xd104_03a1,
+                0x5280_0202,
+                0xa881_7c3f,
+                0xd100_0442,
+                0xb5ff_ffc2
+            ]
+        );
+        // two pairs, then STR xzr, [x1] / STR wzr, [x1, #8] / STRH wzr, [x1, #12] / STRB wzr, [x1, #14]
+        assert_eq!(insns[6], 0x5280_0042);
+        assert_eq!(
+            insns[10..],
+            [0xf900_003f, 0xb900_083f, 0x7900_183f, 0x3900_383f]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn zero_region_clears_exactly_the_region() {
+        for (offset, len) in [(8usize, 256usize), (3, 47), (16, 15), (0, 1)] {
+            let mut data = vec![0xaa; 320];
+            execute(&mut data, |machine| {
+                machine
+                    .emit_zero_region(Location::Memory(GPR::X0, offset as i32), len as u64)
+                    .unwrap();
+            });
+            for (i, byte) in data.iter().enumerate() {
+                let zeroed = (offset..offset + len).contains(&i);
+                assert_eq!(
+                    *byte,
+                    if zeroed { 0 } else { 0xaa },
+                    "{} {} at {}",
+                    offset,
+                    len,
+                    i
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn memory_fence_is_dmb_ish() {
+        let mut machine = MachineARM64::new();
+        machine.emit_memory_fence();
+        assert_eq!(machine.assembler_finalize(), 0xd503_3bbfu32.to_le_bytes());
+    }
+
+    #[test]
+    fn init_stack_loc_loops_over_many_slots() {
+        let mut machine = MachineARM64::new();
+        machine.init_stack_loc(20, Location::Memory(GPR::X29, -160));
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        // STR xzr, [addr], #8 / SUB cnt, cnt, #1 / CBNZ cnt, <loop>
+        let n = insns.len();
+        assert_eq!(insns[n - 3] & 0xffff_fc1f, 0xf800_841f);
+        assert_eq!(insns[n - 2] & 0xffff_fc00, 0xd100_0400);
+        assert_eq!(insns[n - 1] & 0xffff_ffe0, 0xb5ff_ffc0);
+    }
+
+    #[test]
+    fn location_add_sub_only_set_flags_when_asked() {
+        let (x9, x10) = (Location::GPR(GPR::X9), Location::GPR(GPR::X10));
+        let cases = [
+            (
+                false,
+                Size::S32,
+                Location::Imm32(1),
+                x9,
+                false,
+                0x5100_0529u32,
+            ), // SUB w9, w9, #1
+            (false, Size::S32, Location::Imm32(1), x9, true, 0x7100_0529), // SUBS w9, w9, #1
+            (true, Size::S64, x9, x10, false, 0x8b09_014a),                // ADD x10, x10, x9
+            (true, Size::S64, x9, x10, true, 0xab09_014a),                 // ADDS x10, x10, x9
+        ];
+        for (add, sz, src, dst, flags, insn) in cases {
+            let mut machine = MachineARM64::new();
+            if add {
+                machine.location_add(sz, src, dst, flags);
+            } else {
+                machine.location_sub(sz, src, dst, flags);
+            }
+            assert_eq!(machine.assembler_finalize(), insn.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn location_sub_with_flags_feeds_a_branch() {
+        let mut machine = MachineARM64::new();
+        let done = machine.get_label();
+        machine.location_sub(Size::S64, Location::Imm8(1), Location::GPR(GPR::X9), true);
+        machine.jmp_on_equal(done);
+        machine.emit_label(done);
+        let code = machine.assembler_finalize();
+        // SUBS x9, x9, #1 / B.EQ done
+        assert_eq!(code[..4], 0xf100_0529u32.to_le_bytes());
+        assert_eq!(code[4..], 0x5400_0020u32.to_le_bytes());
+    }
+
+    #[test]
+    fn location_and_with_flags_feeds_a_branch() {
+        let mut machine = MachineARM64::new();
+        let done = machine.get_label();
+        machine.location_and(
+            Size::S32,
+            Location::Imm32(0xff),
+            Location::GPR(GPR::X9),
+            true,
+        );
+        machine.jmp_on_different(done);
+        machine.emit_label(done);
+        let code = machine.assembler_finalize();
+        // ANDS w9, w9, #0xff / B.NE done
+        assert_eq!(code[..4], 0x7200_1d29u32.to_le_bytes());
+        assert_eq!(code[4..], 0x5400_0021u32.to_le_bytes());
+    }
+
+    #[test]
+    fn location_test_feeds_a_branch() {
+        let mut machine = MachineARM64::new();
+        let done = machine.get_label();
+        machine.location_test(Size::S64, Location::GPR(GPR::X9), Location::GPR(GPR::X10));
+        machine.jmp_on_equal(done);
+        machine.emit_label(done);
+        let code = machine.assembler_finalize();
+        // TST x10, x9 / B.EQ done
+        assert_eq!(code[..4], 0xea09_015fu32.to_le_bytes());
+        assert_eq!(code[4..], 0x5400_0020u32.to_le_bytes());
+    }
+
+    #[test]
+    fn location_or_with_flags_tests_the_result() {
+        let mut machine = MachineARM64::new();
+        machine.location_or(
+            Size::S32,
+            Location::GPR(GPR::X10),
+            Location::GPR(GPR::X9),
+            true,
+        );
+        let code = machine.assembler_finalize();
+        // ORR w9, w9, w10 / TST w9, w9
+        assert_eq!(code[..4], 0x2a0a_0129u32.to_le_bytes());
+        assert_eq!(code[4..], 0x6a09_013fu32.to_le_bytes());
+    }
+
+    #[test]
+    fn location_neg_widens_a_byte() {
+        let (x9, x10) = (Location::GPR(GPR::X9), Location::GPR(GPR::X10));
+        let cases = [
+            (false, 0x5300_1d4au32), // UXTB w10, w10
+            (true, 0x1300_1d4a),     // SXTB w10, w10
+        ];
+        for (signed, ext) in cases {
+            let mut machine = MachineARM64::new();
+            machine.location_neg(Size::S8, signed, x9, Size::S32, x10);
+            let code = machine.assembler_finalize();
+            // NEG w10, w9
+            assert_eq!(code[..4], 0x4b09_03eau32.to_le_bytes());
+            assert_eq!(code[4..], ext.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn location_cmp_compares_dest_against_source() {
+        let x10 = Location::GPR(GPR::X10);
+        let cases = [
+            (Size::S32, Location::GPR(GPR::X9), 0x6b09_015fu32), // CMP w10, w9
+            (Size::S64, Location::Imm32(42), 0xf100_a95f),       // CMP x10, #42
+        ];
+        for (sz, src, insn) in cases {
+            let mut machine = MachineARM64::new();
+            machine.location_cmp(sz, src, x10);
+            assert_eq!(machine.assembler_finalize(), insn.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn location_address_adds_the_offset_to_the_base() {
+        let x10 = Location::GPR(GPR::X10);
+        let cases = [
+            (Size::S64, 16, vec![0x9100_412au32]), // ADD x10, x9, #16
+            (Size::S64, -8, vec![0xd100_212a]),    // SUB x10, x9, #8
+            (Size::S32, 16, vec![0x9100_412a, 0x2a0a_03ea]), // ADD x10, x9, #16 / MOV w10, w10
+        ];
+        for (sz, offset, insns) in cases {
+            let mut machine = MachineARM64::new();
+            machine.location_address(sz, Location::Memory(GPR::X9, offset), x10);
+            let expected: Vec<u8> = insns.iter().flat_map(|i| i.to_le_bytes()).collect();
+            assert_eq!(machine.assembler_finalize(), expected);
+        }
+    }
+
+    #[test]
+    fn location_address_materializes_large_offsets() {
+        let mut machine = MachineARM64::new();
+        machine.location_address(
+            Size::S64,
+            Location::Memory(GPR::X9, 0x12345),
+            Location::GPR(GPR::X10),
+        );
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        // MOVZ wtmp, #0x2345 / MOVK xtmp, #1, LSL #16 / ADD x10, x9, xtmp
+        assert_eq!(insns.len(), 3);
+        assert_eq!(insns[0] & 0xffff_ffe0, 0x5284_68a0);
+        assert_eq!(insns[1] & 0xffff_ffe0, 0xf2a0_0020);
+        assert_eq!(insns[2] & 0xffe0_ffff, 0x8b00_012a);
+    }
+
+    #[test]
+    fn call_location_loads_the_target_from_the_stack() {
+        let mut machine = MachineARM64::new();
+        machine.emit_call_location(Location::Memory(GPR::X29, -16));
+        let code = machine.assembler_finalize();
+        // LDUR x26, [x29, #-16] / BLR x26
+        assert_eq!(code[..4], 0xf85f_03bau32.to_le_bytes());
+        assert_eq!(code[4..], 0xd63f_0340u32.to_le_bytes());
+    }
+
+    #[test]
+    fn call_location_calls_a_register_directly() {
+        let mut machine = MachineARM64::new();
+        machine.emit_call_location(Location::GPR(GPR::X9));
+        // BLR x9
+        assert_eq!(machine.assembler_finalize(), 0xd63f_0120u32.to_le_bytes());
+    }
+
+    #[test]
+    fn list_to_save_holds_the_callee_saved_value_registers() {
+        use NEON::*;
+        let machine = MachineARM64::new();
+        let expected: Vec<Location> = [V8, V9, V10, V11, V12, V13, V14, V15]
+            .iter()
+            .map(|&r| Location::SIMD(r))
+            .collect();
+        for cc in [CallingConvention::SystemV, CallingConvention::AppleAarch64] {
+            assert_eq!(machine.list_to_save(cc), expected);
+        }
+    }
+
+    #[test]
+    fn stack_arguments_follow_the_calling_convention() {
+        // vmctx and the first 7 of 10 integer arguments go in X0-X7, the rest on the stack
+        let params = [
+            Type::I32,
+            Type::I64,
+            Type::I32,
+            Type::I32,
+            Type::I64,
+            Type::I32,
+            Type::I32,
+            Type::I32,
+            Type::I32,
+            Type::I64,
+        ];
+        let cases = [
+            (
+                CallingConvention::SystemV,
+                [(0, Size::S64), (8, Size::S64), (16, Size::S64)],
+            ),
+            (
+                CallingConvention::AppleAarch64,
+                [(0, Size::S32), (4, Size::S32), (8, Size::S64)],
+            ),
+        ];
+        for (cc, expected) in cases {
+            let mut argalloc = ArgumentRegisterAllocator::default();
+            argalloc.next(Type::I64, cc).unwrap();
+            let mut stack = vec![];
+            for ty in params {
+                if argalloc.next(ty, cc).unwrap().is_none() {
+                    stack.push(argalloc.next_stack(ty, cc));
+                }
+            }
+            assert_eq!(stack, expected);
+        }
+    }
+
+    /// Decodes the unsigned offset LDR/STR of X16 or W16 in `body` that address `[rn, #imm]`,
+    /// as `(is_load, size, offset)`.
+    fn x16_memory_accesses(body: &[u8], rn: u32) -> Vec<(bool, Size, u32)> {
+        words(body)
+            .into_iter()
+            .filter(|insn| insn & 0x1f == 16 && (insn >> 5) & 0x1f == rn)
+            .filter_map(|insn| {
+                let (is_load, sz, scale) = match insn & 0xffc0_0000 {
+                    0xb900_0000 => (false, Size::S32, 4),
+                    0xf900_0000 => (false, Size::S64, 8),
+                    0xb940_0000 => (true, Size::S32, 4),
+                    0xf940_0000 => (true, Size::S64, 8),
+                    _ => return None,
+                };
+                Some((is_load, sz, ((insn >> 10) & 0xfff) * scale))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn trampolines_place_stack_arguments_by_calling_convention() {
+        // vmctx and the first 7 of 10 integer arguments go in X0-X7, the rest on the stack
+        let sig = FunctionType::new(
+            vec![
+                Type::I32,
+                Type::I64,
+                Type::I32,
+                Type::I32,
+                Type::I64,
+                Type::I32,
+                Type::I32,
+                Type::I32,
+                Type::I32,
+                Type::I64,
+            ],
+            vec![],
+        );
+        let mut vmoffsets = VMOffsets::new_for_trampolines(8);
+        vmoffsets.num_imported_functions = 1;
+        let machine = MachineARM64::new();
+        let cases = [
+            (
+                CallingConvention::SystemV,
+                [(Size::S64, 0), (Size::S64, 8), (Size::S64, 16)],
+            ),
+            (
+                CallingConvention::AppleAarch64,
+                [(Size::S32, 0), (Size::S32, 4), (Size::S64, 8)],
+            ),
+        ];
+        for (cc, native) in cases {
+            // The import call trampoline reads the wasm stack arguments above the 64 bytes
+            // it reserves, and writes each native one over that same region.
+            let section = machine
+                .gen_import_call_trampoline(&vmoffsets, FunctionIndex::new(0), &sig, cc)
+                .unwrap();
+            let expected: Vec<_> = native
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &(sz, offset))| {
+                    vec![
+                        (true, Size::S64, 64 + i as u32 * 8),
+                        (false, sz, 64 + offset),
+                    ]
+                })
+                .collect();
+            assert_eq!(
+                x16_memory_accesses(section.bytes.as_slice(), 31),
+                expected,
+                "{:?}",
+                cc
+            );
+
+            // The dynamic import trampoline reads the native stack arguments above its
+            // frame record.
+            let body = machine
+                .gen_std_dynamic_import_trampoline(&vmoffsets, &sig, cc)
+                .unwrap()
+                .body;
+            let expected: Vec<_> = native
+                .iter()
+                .map(|&(sz, offset)| (true, sz, 16 + offset))
+                .collect();
+            assert_eq!(x16_memory_accesses(&body, 29), expected, "{:?}", cc);
+        }
+
+        // The function trampoline writes the wasm stack arguments at SP, one slot each.
+        let body = machine
+            .gen_std_trampoline(&sig, CallingConvention::SystemV)
+            .unwrap()
+            .body;
+        assert_eq!(
+            x16_memory_accesses(&body, 31),
+            [
+                (false, Size::S64, 0),
+                (false, Size::S64, 8),
+                (false, Size::S64, 16)
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_argument_types_are_an_error() {
+        let mut argalloc = ArgumentRegisterAllocator::default();
+        assert!(argalloc
+            .next(Type::V128, CallingConvention::SystemV)
+            .is_err());
+
+        let machine = MachineARM64::new();
+        let sig = FunctionType::new(vec![Type::V128], vec![]);
+        assert!(machine
+            .gen_std_dynamic_import_trampoline(
+                &VMOffsets::new_for_trampolines(8),
+                &sig,
+                CallingConvention::SystemV,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn dynamic_import_trampoline_passes_floats_in_neon_registers() {
+        let machine = MachineARM64::new();
+        let sig = FunctionType::new(vec![Type::F64], vec![Type::F32]);
+        let body = machine
+            .gen_std_dynamic_import_trampoline(
+                &VMOffsets::new_for_trampolines(8),
+                &sig,
+                CallingConvention::SystemV,
+            )
+            .unwrap()
+            .body;
+        let insns = words(&body);
+        assert!(insns.contains(&0xfd00_03e0)); // STR d0, [sp]
+        assert!(insns.contains(&0xbd40_03e0)); // LDR s0, [sp]
+    }
+
+    #[test]
+    fn float_results_are_returned_in_v0() {
+        let mut machine = MachineARM64::new();
+        machine.emit_function_return_value(WpType::F64, false, Location::SIMD(NEON::V8));
+        machine.emit_function_return_value(WpType::I64, false, Location::GPR(GPR::X9));
+        machine.emit_function_return_float();
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        assert_eq!(
+            insns,
+            [
+                0x4ea8_1d00, // mov v0.16b, v8.16b
+                0xaa09_03e0, // mov x0, x9
+            ]
+        );
+
+        // and the trampoline reads them from there
+        let sig = FunctionType::new(vec![], vec![Type::F64]);
+        let body = MachineARM64::new()
+            .gen_std_trampoline(&sig, CallingConvention::SystemV)
+            .unwrap()
+            .body;
+        let insns = words(&body);
+        assert!(insns.contains(&0xfd00_0280)); // STR d0, [x20]
+    }
+
+    #[test]
+    fn move_with_reloc_uses_adrp_for_local_functions() {
+        let mut machine = MachineARM64::new();
+        let mut relocations = vec![];
+        machine.move_with_reloc(
+            RelocationTarget::LocalFunc(LocalFunctionIndex::new(0)),
+            &mut relocations,
+        );
+        let kinds: Vec<_> = relocations.iter().map(|r| (r.kind, r.offset)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (RelocationKind::Arm64Adrp, 0),
+                (RelocationKind::Arm64AddLo12, 4)
+            ]
+        );
+        let code = machine.assembler_finalize();
+        // ADRP x27, 0 / ADD x27, x27, #0
+        assert_eq!(code[..4], 0x9000_001bu32.to_le_bytes());
+        assert_eq!(code[4..], 0x9100_037bu32.to_le_bytes());
+    }
+
+    #[test]
+    fn move_with_reloc_uses_movk_for_libcalls() {
+        let mut machine = MachineARM64::new();
+        let mut relocations = vec![];
+        machine.move_with_reloc(
+            RelocationTarget::LibCall(LibCall::Memory32Size),
+            &mut relocations,
+        );
+        let kinds: Vec<_> = relocations.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                RelocationKind::Arm64Movw0,
+                RelocationKind::Arm64Movw1,
+                RelocationKind::Arm64Movw2,
+                RelocationKind::Arm64Movw3,
+            ]
+        );
+        assert_eq!(machine.assembler_finalize().len(), 16);
+    }
+
+    #[test]
+    fn indirect_call_with_trampoline_reaches_far_targets() {
+        let mut machine = MachineARM64::new();
+        // a target far outside the +/-128MiB reach of BL
+        machine
+            .assembler
+            .emit_mov_imm(Location::GPR(GPR::X9), 0x7f12_3456_7000);
+        machine.arch_emit_indirect_call_with_trampoline(Location::GPR(GPR::X9));
+        machine.arch_emit_indirect_call_with_trampoline(Location::Memory(GPR::X27, 8));
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        let n = insns.len();
+        // BLR x9 / LDR x26, [x27, #8] / BLR x26
+        assert_eq!(insns[n - 3..], [0xd63f_0120, 0xf940_077a, 0xd63f_0340]);
+        // the full address is built, and nothing is a BL with a truncated offset
+        assert!(insns.iter().all(|i| i & 0xfc00_0000 != 0x9400_0000));
+    }
+
+    #[test]
+    fn used_gprs_are_saved_in_pairs() {
+        let mut machine = MachineARM64::new();
+        for r in &[GPR::X9, GPR::X10, GPR::X19] {
+            machine.reserve_gpr(*r);
+        }
+        // the set has no fixed order, so the expected code follows the order it gives
+        let order: Vec<u32> = machine
+            .get_used_gprs()
+            .iter()
+            .map(|r| r.into_index() as u32)
+            .collect();
+        machine.push_used_gpr();
+        machine.pop_used_gpr();
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        let pair = order[0] << 10 | order[1];
+        let odd = order[2] << 10 | 31;
+        assert_eq!(
+            insns,
+            [
+                0xa9bf_03e0 | pair, // stp r1, r0, [sp, #-16]!
+                0xa9bf_03e0 | odd,  // stp xzr, r2, [sp, #-16]!
+                0xa8c1_03e0 | odd,  // ldp xzr, r2, [sp], #16
+                0xa8c1_03e0 | pair, // ldp r1, r0, [sp], #16
+            ]
+        );
+    }
+
+    #[test]
+    fn lone_pushes_and_pops_fill_a_slot() {
+        let mut machine = MachineARM64::new();
+        machine.emit_push(Size::S64, Location::GPR(GPR::X9));
+        machine.emit_pop(Size::S64, Location::GPR(GPR::X11));
+        machine.emit_push(Size::S64, Location::SIMD(NEON::V8));
+        // values in memory go through a temporary
+        machine.emit_push(Size::S64, Location::Memory(GPR::X29, -16));
+        machine.emit_pop(Size::S64, Location::Memory(GPR::X29, -24));
+        machine.emit_pop(Size::S64, Location::SIMD(NEON::V9));
+        assert!(machine.get_used_gprs().is_empty());
+        let insns = words(&machine.assembler_finalize());
+        assert_eq!(
+            insns,
+            [
+                0xf81f_0fe9, // str x9, [sp, #-16]!
+                0xf841_07eb, // ldr x11, [sp], #16
+                0xfc1f_0fe8, // str d8, [sp, #-16]!
+                0xf85f_03a1, // ldur x1, [x29, #-16]
+                0xf81f_0fe1, // str x1, [sp, #-16]!
+                0xf841_07e1, // ldr x1, [sp], #16
+                0xf81e_83a1, // stur x1, [x29, #-24]
+                0xfc41_07e9, // ldr d9, [sp], #16
+            ]
+        );
+    }
+
+    #[test]
+    fn native_params_are_pushed_in_pairs() {
+        let mut machine = MachineARM64::new();
+        // the padding goes along with the first value pushed, the last parameter
+        machine.push_params_for_native(
+            &[
+                Location::SIMD(NEON::V8),
+                Location::Memory(GPR::X29, -16),
+                Location::GPR(GPR::X9),
+            ],
+            true,
+        );
+        assert!(machine.get_used_gprs().is_empty());
+        let insns = words(&machine.assembler_finalize());
+        assert_eq!(
+            insns,
+            [
+                0x9e66_0101, // fmov x1, d8
+                0xa9bf_7fe1, // stp x1, xzr, [sp, #-16]!
+                0xf85f_03a1, // ldur x1, [x29, #-16]
+                0xa9bf_07e9, // stp x9, x1, [sp, #-16]!
+            ]
+        );
+    }
+
+    /// How far `insn` moves SP down, for the ways ARM64 code pushes and pops: SP based
+    /// pre- and post-indexed STR/LDR/STP/LDP, and ADD/SUB of an immediate to SP.
+    fn sp_push_delta(insn: u32) -> i64 {
+        let sign_extend =
+            |value: u32, bits: u32| ((value << (32 - bits)) as i32 >> (32 - bits)) as i64;
+        if (insn >> 5) & 0x1f != 31 {
+            0
+        } else if insn & 0xfe80_0000 == 0xa880_0000 {
+            -sign_extend((insn >> 15) & 0x7f, 7) * 8
+        } else if insn & 0x3b20_0400 == 0x3800_0400 {
+            -sign_extend((insn >> 12) & 0x1ff, 9)
+        } else if insn & 0x7f80_001f == 0x1100_001f {
+            let imm = (((insn >> 10) & 0xfff) << (((insn >> 22) & 1) * 12)) as i64;
+            if insn & 0x4000_0000 != 0 {
+                imm
+            } else {
+                -imm
+            }
+        } else {
+            0
+        }
+    }
+
+    #[test]
+    fn interleaved_pushes_and_pops_keep_sp_balanced() {
+        let mut machine = MachineARM64::new();
+        for r in &[GPR::X9, GPR::X10, GPR::X11] {
+            machine.reserve_gpr(*r);
+        }
+        let mut ends = vec![];
+        machine.push_used_gpr();
+        ends.push(machine.get_offset().0);
+        machine.emit_push(Size::S64, Location::GPR(GPR::X12));
+        ends.push(machine.get_offset().0);
+        machine.push_params_for_native(
+            &[
+                Location::Memory(GPR::X29, -16),
+                Location::GPR(GPR::X13),
+                Location::SIMD(NEON::V8),
+            ],
+            true,
+        );
+        ends.push(machine.get_offset().0);
+        machine.restore_stack(32);
+        ends.push(machine.get_offset().0);
+        machine.emit_push(Size::S128, Location::SIMD(NEON::V9));
+        ends.push(machine.get_offset().0);
+        machine.emit_pop(Size::S128, Location::Memory(GPR::X29, -32));
+        ends.push(machine.get_offset().0);
+        machine.emit_pop(Size::S64, Location::Memory(GPR::X29, -8));
+        ends.push(machine.get_offset().0);
+        machine.pop_used_gpr();
+        ends.push(machine.get_offset().0);
+        // only the reserved registers are left, no temporary
+        assert_eq!(machine.get_used_gprs().len(), 3);
+
+        let insns = words(&machine.assembler_finalize());
+        let mut depths = vec![0];
+        for insn in insns {
+            let depth = depths.last().unwrap() + sp_push_delta(insn);
+            assert_eq!(depth % 16, 0, "SP is misaligned by {:#010x}", insn);
+            depths.push(depth);
+        }
+        let depths: Vec<i64> = ends.iter().map(|end| depths[end / 4]).collect();
+        assert_eq!(depths, [32, 48, 80, 48, 64, 48, 32, 0]);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn interleaved_pushes_and_pops_restore_values() {
+        let mut data = [0; 64];
+        execute(&mut data, |machine| {
+            let regs = [GPR::X9, GPR::X10, GPR::X11, GPR::X12];
+            for (i, r) in regs.iter().enumerate() {
+                machine
+                    .assembler
+                    .emit_mov_imm(Location::GPR(*r), i as u64 + 1);
+            }
+            for r in &regs[..3] {
+                machine.reserve_gpr(*r);
+            }
+            machine.push_used_gpr();
+            machine.emit_push(Size::S64, Location::GPR(GPR::X12));
+            machine.push_params_for_native(
+                &[
+                    Location::GPR(GPR::X9),
+                    Location::GPR(GPR::X10),
+                    Location::GPR(GPR::X11),
+                ],
+                true,
+            );
+            // the parameters are laid out like 8-byte pushes would, the last one lowest
+            for i in 0..4 {
+                machine.assembler.emit_ldr(
+                    Size::S64,
+                    Location::GPR(GPR::X13),
+                    Location::Memory(GPR::XzrSp, i * 8),
+                );
+                machine.assembler.emit_str(
+                    Size::S64,
+                    Location::GPR(GPR::X13),
+                    Location::Memory(GPR::X0, 32 + i * 8),
+                );
+            }
+            machine.restore_stack(32);
+            for r in regs.iter() {
+                machine.assembler.emit_mov_imm(Location::GPR(*r), 0);
+            }
+            machine.emit_pop(Size::S64, Location::GPR(GPR::X12));
+            machine.pop_used_gpr();
+            for (i, r) in regs.iter().enumerate() {
+                machine.assembler.emit_str(
+                    Size::S64,
+                    Location::GPR(*r),
+                    Location::Memory(GPR::X0, i as i32 * 8),
+                );
+            }
+        });
+        let values: Vec<u64> = data
+            .chunks(8)
+            .map(|c| u64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]))
+            .collect();
+        assert_eq!(values, [1, 2, 3, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn restore_saved_area_reads_relative_to_the_frame() {
+        let mut machine = MachineARM64::new();
+        machine.restore_saved_area(
+            24,
+            &[
+                Location::SIMD(NEON::V8),
+                Location::GPR(GPR::X28),
+                Location::GPR(GPR::X19),
+            ],
+        );
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        assert_eq!(
+            insns,
+            [
+                0xfc5e_83a8, // ldur d8, [x29, #-24]
+                0xf85f_03bc, // ldur x28, [x29, #-16]
+                0xf85f_83b3, // ldur x19, [x29, #-8]
+            ]
+        );
+    }
+
+    #[test]
+    fn mov_imm_uses_the_fewest_instructions() {
+        for &(val, count) in &[
+            (0, 1),
+            (0xffff_0000, 1),
+            (0xffff_ffff_ffff_ffff, 1),
+            (0xffff_ffff_ffff_fed4, 1),
+            (0x0000_0000_ffff_1234, 1),
+            (0x5555_5555_5555_5555, 1),
+            (0x00ff_00ff, 1),
+            (0x0001_0000_0000_0000, 1),
+            (0x0012_0000_3456_0000, 2),
+            (0xffff_1234_ffff_5678, 2),
+            (0x1234_5678_9abc_def0, 4),
+        ] {
+            let mut machine = MachineARM64::new();
+            machine.assembler.emit_mov_imm(Location::GPR(GPR::X9), val);
+            let code = machine.assembler_finalize();
+            assert_eq!(code.len() / 4, count, "{:#x}", val);
+        }
+    }
+
+    #[test]
+    fn large_stack_adjustments_need_no_scratch_register() {
+        let mut machine = MachineARM64::new();
+        machine.adjust_stack(6000);
+        machine.restore_stack(6000);
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        assert_eq!(
+            insns,
+            [
+                0xd140_07ff, // sub sp, sp, #1, lsl #12
+                0xd11d_c3ff, // sub sp, sp, #1904
+                0x9140_07ff, // add sp, sp, #1, lsl #12
+                0x911d_c3ff, // add sp, sp, #1904
+            ]
+        );
+    }
+
+    #[test]
+    fn far_store_keeps_its_unreserved_source() {
+        let mut machine = MachineARM64::new();
+        // X1 holds an argument that isn't reserved, the address must go elsewhere
+        machine.emit_relaxed_str(
+            Size::S64,
+            Location::GPR(GPR::X1),
+            Location::Memory(GPR::X29, -5000),
+        );
+        assert!(machine.get_used_gprs().is_empty());
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        assert_eq!(
+            insns,
+            [
+                0x9282_70e2, // mov x2, #-5000
+                0x8b02_03a2, // add x2, x29, x2
+                0xf900_0041, // str x1, [x2]
+            ]
+        );
+    }
+
+    #[test]
+    fn far_load_computes_its_address_in_the_destination() {
+        let mut machine = MachineARM64::new();
+        // X2 may already hold the next argument, so only X1 is written
+        machine.move_location(
+            Size::S64,
+            Location::Memory(GPR::X29, -5000),
+            Location::GPR(GPR::X1),
+        );
+        machine.move_location_extend(
+            Size::S32,
+            true,
+            Location::Memory(GPR::X29, -5000),
+            Size::S64,
+            Location::GPR(GPR::X3),
+        );
+        assert!(machine.get_used_gprs().is_empty());
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        assert_eq!(
+            insns,
+            [
+                0x9282_70e1, // mov x1, #-5000
+                0x8b01_03a1, // add x1, x29, x1
+                0xf940_0021, // ldr x1, [x1]
+                0x9282_70e3, // mov x3, #-5000
+                0x8b03_03a3, // add x3, x29, x3
+                0xb980_0063, // ldrsw x3, [x3]
+            ]
+        );
+    }
+
+    #[test]
+    fn far_store_avoids_the_live_registers() {
+        let mut machine = MachineARM64::new();
+        machine.emit_relaxed_ldst(
+            Assembler::emit_str,
+            Size::S64,
+            Location::GPR(GPR::X1),
+            Location::Memory(GPR::X29, -5000),
+            &[Location::GPR(GPR::X2), Location::GPR(GPR::X3)],
+        );
+        assert!(machine.get_used_gprs().is_empty());
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        assert_eq!(
+            insns,
+            [
+                0x9282_70e4, // mov x4, #-5000
+                0x8b04_03a4, // add x4, x29, x4
+                0xf900_0081, // str x1, [x4]
+            ]
+        );
+    }
+
+    #[test]
+    fn v128_push_and_pop_keep_the_whole_register() {
+        let mut machine = MachineARM64::new();
+        machine.emit_push(Size::S128, Location::SIMD(NEON::V8));
+        machine.emit_pop(Size::S128, Location::SIMD(NEON::V9));
+        machine.emit_push(Size::S128, Location::Memory(GPR::X29, -32));
+        machine.emit_pop(Size::S128, Location::Memory(GPR::X29, -48));
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        // all Q forms, so every one of the 16 bytes is moved
+        assert_eq!(
+            insns,
+            [
+                0x3c9f_0fe8, // str q8, [sp, #-16]!
+                0x3cc1_07e9, // ldr q9, [sp], #16
+                0x3cde_03a0, // ldur q0, [x29, #-32]
+                0x3c9f_0fe0, // str q0, [sp, #-16]!
+                0x3cc1_07e0, // ldr q0, [sp], #16
+                0x3c9d_03a0, // stur q0, [x29, #-48]
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_location_clears_stack_slots_and_neon_registers() {
+        let mut machine = MachineARM64::new();
+        machine.zero_location(Size::S64, Location::Memory(GPR::X29, -16));
+        machine.zero_location(Size::S128, Location::Memory(GPR::X29, -48));
+        machine.zero_location(Size::S128, Location::SIMD(NEON::V8));
+        let code = machine.assembler_finalize();
+        let insns = words(&code);
+        assert_eq!(
+            insns,
+            [
+                0xf81f_03bf, // stur xzr, [x29, #-16]
+                0xf81d_03bf, // stur xzr, [x29, #-48]
+                0xf81d_83bf, // stur xzr, [x29, #-40]
+                0x4f00_e408, // movi v8.16b, #0
+            ]
+        );
+    }
+
+    #[test]
+    fn misaligned_atomics_trap_as_unaligned() {
+        let mut machine = MachineARM64::new();
+        let oob = machine.get_label();
+        let memarg = MemoryImmediate {
+            align: 2,
+            offset: 0,
+            memory: 0,
+        };
+        machine.i32_atomic_load(
+            Location::GPR(GPR::X9),
+            &memarg,
+            Location::GPR(GPR::X10),
+            true,
+            false,
+            0,
+            oob,
+        );
+        machine.emit_label(oob);
+        machine.mark_address_with_trap_code(TrapCode::HeapAccessOutOfBounds);
+        machine.emit_illegal_op();
+        machine.finalize_function();
+
+        let (body, traps, _) = machine.finalize_with_metadata();
+        let landing = |code| {
+            traps
+                .iter()
+                .filter(|t| t.trap_code == code)
+                .map(|t| t.code_offset as i32)
+                .max()
+                .unwrap()
+        };
+        let branches: Vec<(i32, i32)> = words(&body)
+            .into_iter()
+            .enumerate()
+            .map(|(i, insn)| (i as i32 * 4, insn))
+            .filter(|(_, insn)| insn & 0xff00_0010 == 0x5400_0000)
+            .map(|(at, insn)| (at, at + (((insn as i32) << 8) >> 13) * 4))
+            .collect();
+        // the bound check (B.HI) goes to the out of bounds pad, the alignment check
+        // (B.NE) to the unaligned atomic one
+        let bhi = branches
+            .iter()
+            .find(|(at, _)| body[*at as usize] & 0xf == 0x8);
+        let bne = branches
+            .iter()
+            .find(|(at, _)| body[*at as usize] & 0xf == 0x1);
+        assert_eq!(bhi.unwrap().1, landing(TrapCode::HeapAccessOutOfBounds));
+        assert_eq!(bne.unwrap().1, landing(TrapCode::UnalignedAtomic));
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_bitwise_ops_compute_every_bit() {
+        let a = 0x0123_4567_89ab_cdef_f0f0_f0f0_0000_ffffu128;
+        let b = 0xffff_0000_ffff_0000_0ff0_0ff0_1234_5678u128;
+        type BinOp =
+            fn(&mut MachineARM64, Location, Location, Location) -> Result<(), CodegenError>;
+        let ops: [(BinOp, u128); 4] = [
+            (MachineARM64::v128_and, a & b),
+            (MachineARM64::v128_or, a | b),
+            (MachineARM64::v128_xor, a ^ b),
+            (MachineARM64::v128_andnot, a & !b),
+        ];
+        for (op, expected) in ops {
+            let result = execute_v128(&[a, b], |machine, args, ret| {
+                op(machine, args[0], args[1], ret).unwrap()
+            });
+            assert_eq!(result, expected);
+        }
+        let result = execute_v128(&[a], |machine, args, ret| {
+            machine.v128_not(args[0], ret).unwrap()
+        });
+        assert_eq!(result, !a);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_mul_wraps_and_extmul_widens_each_half() {
+        let a = 0x8001_7fff_0102_fffe_8000_0003_00ff_ff80u128;
+        let b = 0xff02_0003_8000_7fff_0002_ffff_ff01_0081u128;
+        for (lane, bits) in [(Size::S8, 8), (Size::S16, 16), (Size::S32, 32)] {
+            let pairs: Vec<(u128, u128)> = lanes(a, bits).into_iter().zip(lanes(b, bits)).collect();
+
+            let result = execute_v128(&[a, b], |machine, args, ret| {
+                machine.v128_mul(lane, args[0], args[1], ret).unwrap()
+            });
+            let expected = from_lanes(pairs.iter().map(|(x, y)| x.wrapping_mul(*y)), bits);
+            assert_eq!(result, expected, "mul {:?}", lane);
+
+            let half = (64 / bits) as usize;
+            for signed in [false, true] {
+                for high in [false, true] {
+                    let result = execute_v128(&[a, b], |machine, args, ret| {
+                        machine
+                            .v128_extmul(lane, signed, high, args[0], args[1], ret)
+                            .unwrap()
+                    });
+                    let widen = |x: u128| {
+                        if signed {
+                            sign_extend(x, bits) as u128
+                        } else {
+                            x
+                        }
+                    };
+                    let products = pairs
+                        .iter()
+                        .skip(if high { half } else { 0 })
+                        .take(half)
+                        .map(|(x, y)| widen(*x).wrapping_mul(widen(*y)));
+                    assert_eq!(
+                        result,
+                        from_lanes(products, bits * 2),
+                        "extmul {:?} signed={} high={}",
+                        lane,
+                        signed,
+                        high
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn simd_avgr_u_rounds_up_without_overflowing() {
+        // The top lanes are all ones, so a + b + 1 overflows every lane width.
+        let a = 0xffff_0001_8000_0000_ff01_00ff_0102_ff00u128;
+        let b = 0xffff_0002_7fff_0001_ff02_ffff_0101_00ffu128;
+        for (lane, bits) in [(Size::S8, 8), (Size::S16, 16)] {
+            let result = execute_v128(&[a, b], |machine, args, ret| {
+                machine
+                    .emit_simd_avgr_u(lane, args[0], args[1], ret)
+                    .unwrap()
+            });
+            let averages = lanes(a, bits)
+                .into_iter()
+                .zip(lanes(b, bits))
+                .map(|(x, y)| (x + y + 1) >> 1);
+            assert_eq!(result, from_lanes(averages, bits), "{:?}", lane);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_load_extend_widens_the_eight_loaded_bytes() {
+        let memory: Vec<u8> = (0..32).map(|i| (i as u8).wrapping_mul(0x47)).collect();
+        let memarg = MemoryImmediate {
+            align: 3,
+            offset: 4,
+            memory: 0,
+        };
+        // Address 8 plus the offset of 4 loads bytes 12..20.
+        let loaded = memory[12..20]
+            .iter()
+            .rev()
+            .fold(0, |acc, byte| acc << 8 | *byte as u128);
+        for (lane, bits) in [(Size::S8, 8), (Size::S16, 16), (Size::S32, 32)] {
+            for signed in [false, true] {
+                let (result, _) = execute_memory_v128(&memory, |machine, ret, oob| {
+                    machine
+                        .v128_load_extend(
+                            lane,
+                            signed,
+                            Location::Imm32(8),
+                            &memarg,
+                            ret,
+                            true,
+                            false,
+                            0,
+                            oob,
+                        )
+                        .unwrap()
+                });
+                let widened = lanes(loaded, bits)
+                    .into_iter()
+                    .take((64 / bits) as usize)
+                    .map(|x| {
+                        if signed {
+                            sign_extend(x, bits) as u128
+                        } else {
+                            x
+                        }
+                    });
+                assert_eq!(
+                    result,
+                    from_lanes(widened, bits * 2),
+                    "{:?} signed={}",
+                    lane,
+                    signed
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn i32x4_dot_i16x8_s_adds_adjacent_signed_products() {
+        // The top lane multiplies -32768 by itself twice, whose sum wraps to i32::MIN.
+        let a = 0x8000_8000_7fff_ffff_0003_fffe_1234_0001u128;
+        let b = 0x8000_8000_7fff_0002_fff9_0005_0002_ffffu128;
+        let result = execute_v128(&[a, b], |machine, args, ret| {
+            machine
+                .emit_i32x4_dot_i16x8_s(args[0], args[1], ret)
+                .unwrap()
+        });
+        let products: Vec<i128> = lanes(a, 16)
+            .into_iter()
+            .zip(lanes(b, 16))
+            .map(|(x, y)| sign_extend(x, 16) * sign_extend(y, 16))
+            .collect();
+        let sums = products.chunks(2).map(|pair| (pair[0] + pair[1]) as u128);
+        assert_eq!(result, from_lanes(sums, 32));
+        assert_eq!(result >> 96, 0x8000_0000);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn splat_broadcasts_the_low_bits_of_each_source() {
+        let value = 0x1122_3344_5566_7788u64;
+        for (lane, bits) in [
+            (Size::S8, 8),
+            (Size::S16, 16),
+            (Size::S32, 32),
+            (Size::S64, 64),
+        ] {
+            let expected = from_lanes(vec![value as u128; (128 / bits) as usize], bits);
+            let from_gpr = execute_v128(&[], |machine, _, ret| {
+                machine
+                    .assembler
+                    .emit_mov_imm(Location::GPR(GPR::X9), value);
+                machine
+                    .emit_splat(Location::GPR(GPR::X9), lane, ret)
+                    .unwrap()
+            });
+            assert_eq!(from_gpr, expected, "GPR {:?}", lane);
+            let from_simd = execute_v128(&[value as u128], |machine, args, ret| {
+                machine.emit_splat(args[0], lane, ret).unwrap()
+            });
+            assert_eq!(from_simd, expected, "SIMD {:?}", lane);
+        }
+        let from_imm = execute_v128(&[], |machine, _, ret| {
+            machine
+                .emit_splat(Location::Imm32(0x89ab_cdef), Size::S32, ret)
+                .unwrap()
+        });
+        assert_eq!(from_imm, 0x89ab_cdef_89ab_cdef_89ab_cdef_89ab_cdef);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn copysign_takes_only_the_sign_of_the_second_operand() {
+        let cases = [(-3.0, 2.0, 3.0), (3.0, -0.0, -3.0), (-0.0, 1.0, 0.0)];
+        for (a, b, expected) in cases {
+            let result = execute_v128(
+                &[(a as f32).to_bits() as u128, (b as f32).to_bits() as u128],
+                |machine, args, ret| machine.f32_copysign(args[0], args[1], ret).unwrap(),
+            );
+            assert_eq!(
+                result as u32,
+                (expected as f32).to_bits(),
+                "f32 {} {}",
+                a,
+                b
+            );
+            let result = execute_v128(
+                &[(a as f64).to_bits() as u128, (b as f64).to_bits() as u128],
+                |machine, args, ret| machine.f64_copysign(args[0], args[1], ret).unwrap(),
+            );
+            assert_eq!(
+                result as u64,
+                (expected as f64).to_bits(),
+                "f64 {} {}",
+                a,
+                b
+            );
+        }
+        // The payload of a NaN is kept.
+        let result = execute_v128(&[0x7fc0_1234, 0x8000_0000], |machine, args, ret| {
+            machine.f32_copysign(args[0], args[1], ret).unwrap()
+        });
+        assert_eq!(result as u32, 0xffc0_1234);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn i16x8_q15mulr_sat_s_rounds_and_saturates() {
+        // The top lane is -32768 * -32768, the only product that saturates.
+        let a = 0x8000_4000_7fff_c000_0001_ffff_8000_1234u128;
+        let b = 0x8000_4000_7fff_4000_4000_0001_7fff_5678u128;
+        let result = execute_v128(&[a, b], |machine, args, ret| {
+            machine
+                .emit_i16x8_q15mulr_sat_s(args[0], args[1], ret)
+                .unwrap()
+        });
+        let expected = lanes(a, 16).into_iter().zip(lanes(b, 16)).map(|(x, y)| {
+            let product = (sign_extend(x, 16) * sign_extend(y, 16) + 0x4000) >> 15;
+            product.min(i16::MAX as i128) as u128
+        });
+        assert_eq!(result, from_lanes(expected, 16));
+        assert_eq!(result >> 112, 0x7fff);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_any_true_sees_every_bit() {
+        let values = [0u128, 1, 1 << 40, 1 << 64, 1 << 127, u128::MAX];
+        for value in values {
+            let expected = (value != 0) as u64;
+            let mut data = value.to_le_bytes().to_vec();
+            let result = execute(&mut data, |machine| {
+                let src = Location::SIMD(NEON::V16);
+                machine
+                    .assembler
+                    .emit_ldr(Size::S128, src, Location::Memory(GPR::X0, 0));
+                machine
+                    .emit_v128_any_true(src, Location::GPR(GPR::X0))
+                    .unwrap();
+            });
+            assert_eq!(result, expected, "{:#x}", value);
+
+            let mut data = value.to_le_bytes().to_vec();
+            let result = execute(&mut data, |machine| {
+                let src = Location::SIMD(NEON::V16);
+                let taken = machine.get_label();
+                let end = machine.get_label();
+                machine
+                    .assembler
+                    .emit_ldr(Size::S128, src, Location::Memory(GPR::X0, 0));
+                machine.emit_v128_any_true_branch(src, taken).unwrap();
+                machine.assembler.emit_mov_imm(Location::GPR(GPR::X0), 0);
+                machine.jmp_unconditionnal(end);
+                machine.emit_label(taken);
+                machine.assembler.emit_mov_imm(Location::GPR(GPR::X0), 1);
+                machine.emit_label(end);
+            });
+            assert_eq!(result, expected, "branch {:#x}", value);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn i8x16_popcnt_counts_each_byte() {
+        let value = 0xff7f_8001_0f10_aa55_0000_fe02_0304_c0ffu128;
+        let result = execute_v128(&[value], |machine, args, ret| {
+            machine.emit_i8x16_popcnt(args[0], ret).unwrap()
+        });
+        let counts = lanes(value, 8).into_iter().map(|x| x.count_ones() as u128);
+        assert_eq!(result, from_lanes(counts, 8));
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_rounding_follows_each_mode() {
+        type RoundOp = fn(&mut MachineARM64, Size, Location, Location) -> Result<(), CodegenError>;
+        let input = [2.5, -2.5, 1.5, -0.4];
+        let ops: [(RoundOp, [f64; 4]); 4] = [
+            (MachineARM64::v128_ceil, [3.0, -2.0, 2.0, -0.0]),
+            (MachineARM64::v128_floor, [2.0, -3.0, 1.0, -1.0]),
+            (MachineARM64::v128_trunc, [2.0, -2.0, 1.0, -0.0]),
+            // Ties go to the even neighbour.
+            (MachineARM64::v128_nearest, [2.0, -2.0, 2.0, -0.0]),
+        ];
+        for (op, expected) in ops {
+            let f32s =
+                |v: [f64; 4]| from_lanes(v.iter().map(|x| (*x as f32).to_bits() as u128), 32);
+            let result = execute_v128(&[f32s(input)], |machine, args, ret| {
+                op(machine, Size::S32, args[0], ret).unwrap()
+            });
+            assert_eq!(result, f32s(expected));
+            for half in [0, 2] {
+                let f64s = |v: [f64; 4]| {
+                    from_lanes(v[half..half + 2].iter().map(|x| x.to_bits() as u128), 64)
+                };
+                let result = execute_v128(&[f64s(input)], |machine, args, ret| {
+                    op(machine, Size::S64, args[0], ret).unwrap()
+                });
+                assert_eq!(result, f64s(expected));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_extadd_pairwise_widens_before_adding() {
+        let value = 0xffff_ffff_8000_8000_7fff_7fff_0102_80ffu128;
+        for (lane, bits) in [(Size::S8, 8), (Size::S16, 16)] {
+            for signed in [false, true] {
+                let result = execute_v128(&[value], |machine, args, ret| {
+                    machine
+                        .v128_extadd_pairwise(lane, signed, args[0], ret)
+                        .unwrap()
+                });
+                let widened: Vec<i128> = lanes(value, bits)
+                    .into_iter()
+                    .map(|x| {
+                        if signed {
+                            sign_extend(x, bits)
+                        } else {
+                            x as i128
+                        }
+                    })
+                    .collect();
+                let sums = widened.chunks(2).map(|pair| (pair[0] + pair[1]) as u128);
+                assert_eq!(
+                    result,
+                    from_lanes(sums, bits * 2),
+                    "{:?} signed={}",
+                    lane,
+                    signed
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_load_and_store_lane_touch_only_one_lane() {
+        // The first 16 bytes of memory hold the vector whose lane is replaced or stored.
+        let vector = 0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100u128;
+        let mut memory = vector.to_le_bytes().to_vec();
+        memory.extend((0..32).map(|i| 0x80 | i as u8));
+        let memarg = MemoryImmediate {
+            align: 0,
+            offset: 4,
+            memory: 0,
+        };
+        let src = Location::SIMD(NEON::V16);
+        // Address 24 plus the offset of 4 accesses the memory from byte 28.
+        let (addr, start) = (Location::Imm32(24), 28);
+        for (lane, bits) in [
+            (Size::S8, 8),
+            (Size::S16, 16),
+            (Size::S32, 32),
+            (Size::S64, 64),
+        ] {
+            let size = (bits / 8) as usize;
+            let index = (128 / bits - 1) as u8;
+            let mut expected = lanes(vector, bits);
+            expected[index as usize] = memory[start..start + size]
+                .iter()
+                .rev()
+                .fold(0, |acc, byte| acc << 8 | *byte as u128);
+            let (result, _) = execute_memory_v128(&memory, |machine, ret, oob| {
+                machine
+                    .assembler
+                    .emit_ldr(Size::S128, src, Location::Memory(GPR::X0, 0));
+                machine
+                    .v128_load_lane(lane, index, src, addr, &memarg, ret, true, false, 0, oob)
+                    .unwrap()
+            });
+            assert_eq!(result, from_lanes(expected, bits), "load {:?}", lane);
+
+            let (_, stored) = execute_memory_v128(&memory, |machine, _, oob| {
+                machine
+                    .assembler
+                    .emit_ldr(Size::S128, src, Location::Memory(GPR::X0, 0));
+                machine
+                    .v128_store_lane(lane, index, src, &memarg, addr, true, false, 0, oob)
+                    .unwrap()
+            });
+            let mut expected = memory.clone();
+            expected[start..start + size].copy_from_slice(&memory[16 - size..16]);
+            assert_eq!(stored, expected, "store {:?}", lane);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_fcmp_matches_ieee_comparisons() {
+        type CmpOp =
+            fn(&mut MachineARM64, Size, Location, Location, Location) -> Result<(), CodegenError>;
+        let ops: [(CmpOp, fn(f64, f64) -> bool); 6] = [
+            (MachineARM64::v128_fcmp_eq, |x, y| x == y),
+            (MachineARM64::v128_fcmp_ne, |x, y| x != y),
+            (MachineARM64::v128_fcmp_lt, |x, y| x < y),
+            (MachineARM64::v128_fcmp_le, |x, y| x <= y),
+            (MachineARM64::v128_fcmp_gt, |x, y| x > y),
+            (MachineARM64::v128_fcmp_ge, |x, y| x >= y),
+        ];
+        // NaN is unordered with everything, and -0.0 equals 0.0.
+        let a = [1.0, f64::NAN, -0.0, 2.0, 3.0, f64::NAN];
+        let b = [2.0, 1.0, 0.0, 2.0, -3.0, f64::NAN];
+        for (op, cmp) in ops {
+            for (lane, bits) in [(Size::S32, 32), (Size::S64, 64)] {
+                let count = (128 / bits) as usize;
+                for start in (0..a.len() - count + 1).step_by(count) {
+                    let (a, b) = (&a[start..start + count], &b[start..start + count]);
+                    let to_bits = |v: &[f64]| {
+                        from_lanes(
+                            v.iter().map(|x| match lane {
+                                Size::S32 => (*x as f32).to_bits() as u128,
+                                _ => x.to_bits() as u128,
+                            }),
+                            bits,
+                        )
+                    };
+                    let result = execute_v128(&[to_bits(a), to_bits(b)], |machine, args, ret| {
+                        op(machine, lane, args[0], args[1], ret).unwrap()
+                    });
+                    let expected = a
+                        .iter()
+                        .zip(b)
+                        .map(|(x, y)| if cmp(*x, *y) { u128::MAX } else { 0 });
+                    assert_eq!(
+                        result,
+                        from_lanes(expected, bits),
+                        "{:?} {:?} {:?}",
+                        lane,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_minmax_matches_scalar_min_max() {
+        type MinMaxOp =
+            fn(&mut MachineARM64, Size, Location, Location, Location) -> Result<(), CodegenError>;
+        let ops: [(MinMaxOp, bool, fn(i128, i128) -> i128); 4] = [
+            (MachineARM64::v128_min_s, true, i128::min),
+            (MachineARM64::v128_min_u, false, i128::min),
+            (MachineARM64::v128_max_s, true, i128::max),
+            (MachineARM64::v128_max_u, false, i128::max),
+        ];
+        let a = 0x8000_0000_0000_0001_7fff_ffff_0000_80ffu128;
+        let b = 0x7fff_ffff_ffff_ffff_8000_0000_0000_7f01u128;
+        for (op, signed, pick) in ops {
+            for (lane, bits) in [
+                (Size::S8, 8),
+                (Size::S16, 16),
+                (Size::S32, 32),
+                (Size::S64, 64),
+            ] {
+                let expected = from_lanes(
+                    lanes(a, bits)
+                        .into_iter()
+                        .zip(lanes(b, bits))
+                        .map(|(x, y)| {
+                            if signed {
+                                pick(sign_extend(x, bits), sign_extend(y, bits)) as u128
+                            } else {
+                                pick(x as i128, y as i128) as u128
+                            }
+                        }),
+                    bits,
+                );
+                let result = execute_v128(&[a, b], |machine, args, ret| {
+                    op(machine, lane, args[0], args[1], ret).unwrap()
+                });
+                assert_eq!(result, expected, "{:?} in registers", lane);
+                // Operands and result spilled to the stack, with the result over `a`.
+                let result = execute_v128(&[a, b], |machine, _, ret| {
+                    let (mem_a, mem_b) =
+                        (Location::Memory(GPR::X0, 0), Location::Memory(GPR::X0, 16));
+                    op(machine, lane, mem_a, mem_b, mem_a).unwrap();
+                    machine.assembler.emit_ldr(Size::S128, ret, mem_a);
+                });
+                assert_eq!(result, expected, "{:?} in memory", lane);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_load_and_save_move_sixteen_unaligned_bytes() {
+        let memory: Vec<u8> = (0..48).map(|i| (i as u8).wrapping_mul(0x35)).collect();
+        let memarg = MemoryImmediate {
+            align: 0,
+            offset: 2,
+            memory: 0,
+        };
+        // Loads bytes 5..21 and saves them at 27..43.
+        let (result, after) = execute_memory_v128(&memory, |machine, ret, oob| {
+            machine
+                .v128_load(Location::Imm32(3), &memarg, ret, true, false, 0, oob)
+                .unwrap();
+            machine
+                .v128_save(ret, &memarg, Location::Imm32(25), true, false, 0, oob)
+                .unwrap();
+        });
+        let loaded = memory[5..21]
+            .iter()
+            .rev()
+            .fold(0, |acc, byte| acc << 8 | *byte as u128);
+        assert_eq!(result, loaded);
+        let mut expected = memory.clone();
+        expected.copy_within(5..21, 27);
+        assert_eq!(after, expected);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn simd_narrow_u_clamps_signed_lanes_at_run_time() {
+        let a = 0x8000_7fff_0100_00ff_ffff_0080_007f_0000u128;
+        let b = 0x0001_fffe_8000_0000_7fff_ffff_0000_ffffu128;
+        for (lane, bits) in [(Size::S16, 16), (Size::S32, 32)] {
+            let max = (1i128 << (bits / 2)) - 1;
+            let narrow = |v: u128| {
+                lanes(v, bits)
+                    .into_iter()
+                    .map(move |x| sign_extend(x, bits).max(0).min(max) as u128)
+            };
+            let expected = from_lanes(narrow(a).chain(narrow(b)), bits / 2);
+            let result = execute_v128(&[a, b], |machine, args, ret| {
+                machine
+                    .emit_simd_narrow_u(lane, args[0], args[1], ret)
+                    .unwrap()
+            });
+            assert_eq!(result, expected, "{:?}", lane);
+            // The result may overwrite the second operand.
+            let result = execute_v128(&[a, b], |machine, args, ret| {
+                machine
+                    .emit_simd_narrow_u(lane, args[0], args[1], args[1])
+                    .unwrap();
+                machine.move_location(Size::S64, args[1], ret);
+            });
+            assert_eq!(result, expected, "{:?} into the second operand", lane);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn f64x2_promote_and_f32x4_demote_convert_at_run_time() {
+        let singles = [1.5f32, -0.0, f32::MAX, 7.0];
+        let input = from_lanes(singles.iter().map(|x| x.to_bits() as u128), 32);
+        let result = execute_v128(&[input], |machine, args, ret| {
+            machine.emit_f64x2_promote_low_f32x4(args[0], ret).unwrap()
+        });
+        let promoted = singles[..2].iter().map(|x| (*x as f64).to_bits() as u128);
+        assert_eq!(result, from_lanes(promoted, 64));
+
+        // Rounds to nearest, overflows to infinity and zeroes the high lanes.
+        let doubles = [1.000_000_1f64, -1e300];
+        let input = from_lanes(doubles.iter().map(|x| x.to_bits() as u128), 64);
+        let result = execute_v128(&[input], |machine, args, ret| {
+            machine.emit_f32x4_demote_f64x2_zero(args[0], ret).unwrap()
+        });
+        let demoted = doubles.iter().map(|x| (*x as f32).to_bits() as u128);
+        assert_eq!(result, from_lanes(demoted, 32));
+    }
+
+    #[test]
+    fn relaxed_extension_picks_the_instruction_for_each_width() {
+        let (src, dst) = (Location::GPR(GPR::X9), Location::GPR(GPR::X10));
+        let cases = [
+            (Size::S8, Size::S32, false, 0x5300_1d2au32), // UXTB w10, w9
+            (Size::S8, Size::S64, false, 0x5300_1d2a),    // UXTB w10, w9
+            (Size::S16, Size::S32, false, 0x5300_3d2a),   // UXTH w10, w9
+            (Size::S16, Size::S64, false, 0x5300_3d2a),   // UXTH w10, w9
+            (Size::S32, Size::S64, false, 0x2a09_03ea),   // MOV w10, w9
+            (Size::S8, Size::S32, true, 0x1300_1d2a),     // SXTB w10, w9
+            (Size::S8, Size::S64, true, 0x9340_1d2a),     // SXTB x10, w9
+            (Size::S16, Size::S32, true, 0x1300_3d2a),    // SXTH w10, w9
+            (Size::S16, Size::S64, true, 0x9340_3d2a),    // SXTH x10, w9
+            (Size::S32, Size::S64, true, 0x9340_7d2a),    // SXTW x10, w9
+        ];
+        for (sz_src, sz_dst, signed, insn) in cases {
+            let mut machine = MachineARM64::new();
+            if signed {
+                machine.emit_relaxed_sign_extension(sz_src, src, sz_dst, dst);
+            } else {
+                machine.emit_relaxed_zero_extension(sz_src, src, sz_dst, dst);
+            }
+            assert_eq!(
+                machine.assembler_finalize(),
+                insn.to_le_bytes(),
+                "{:?} -> {:?}, signed: {}",
+                sz_src,
+                sz_dst,
+                signed
+            );
+        }
+    }
+
+    #[test]
+    fn relaxed_extension_loads_memory_sources_first() {
+        let mut machine = MachineARM64::new();
+        machine.emit_relaxed_sign_extension(
+            Size::S8,
+            Location::Memory(GPR::X29, -16),
+            Size::S64,
+            Location::Memory(GPR::X29, -24),
+        );
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        // LDUR w, then SXTB x, then STUR x of the whole destination slot
+        assert_eq!(insns.len(), 3);
+        assert_eq!(insns[0] & 0xffe0_0c00, 0xb840_0000);
+        assert_eq!(insns[1] & 0xffff_fc00, 0x9340_1c00);
+        assert_eq!(insns[2] & 0xffe0_0c00, 0xf800_0000);
+    }
+
+    #[test]
+    fn load_address_zero_extends_from_memory() {
+        // LDRB w9, [x10, #7]; LDRH w9, [x10, #6]; LDR w9, [x10, #4]; LDR x9, [x10, #8]
+        let cases = [
+            (Size::S8, 7, 0x3940_1d49u32),
+            (Size::S16, 6, 0x7940_0d49),
+            (Size::S32, 4, 0xb940_0549),
+            (Size::S64, 8, 0xf940_0549),
+        ];
+        for (size, offset, insn) in cases {
+            let mut machine = MachineARM64::new();
+            machine.load_address(
+                size,
+                Location::GPR(GPR::X9),
+                Location::Memory(GPR::X10, offset),
+            );
+            assert_eq!(machine.assembler_finalize(), insn.to_le_bytes());
+        }
+
+        // An offset out of range of the immediate goes through a temporary register.
+        let mut machine = MachineARM64::new();
+        machine.load_address(
+            Size::S64,
+            Location::GPR(GPR::X9),
+            Location::Memory(GPR::X10, 0x10_0000),
+        );
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        assert_eq!(insns.last().unwrap() & 0xffff_fc1f, 0xf940_0009);
+    }
+
+    #[test]
+    fn event_signal_and_wait() {
+        let mut machine = MachineARM64::new();
+        machine.emit_event_signal().unwrap();
+        machine.emit_event_wait().unwrap();
+        let body = machine.assembler_finalize();
+        // SEV, then WFE
+        assert_eq!(body, [0x9f, 0x20, 0x03, 0xd5, 0x5f, 0x20, 0x03, 0xd5]);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn event_wait_returns_once_an_event_was_signalled() {
+        // SEV also sets the local event register, so the WFE must not sleep.
+        let result = execute(&mut [], |machine| {
+            machine.emit_event_signal().unwrap();
+            machine.emit_event_wait().unwrap();
+            machine.assembler.emit_mov_imm(Location::GPR(GPR::X0), 1);
+        });
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn v128_int_cmp_picks_signedness_and_operand_order() {
+        let (a, b, ret) = (
+            Location::SIMD(NEON::V0),
+            Location::SIMD(NEON::V1),
+            Location::SIMD(NEON::V2),
+        );
+        // i32x4.lt_s must be a signed compare so that -1 < 1 holds: CMGT v2.4s, v1.4s, v0.4s
+        let mut machine = MachineARM64::new();
+        machine.v128_cmp_lt_s(Size::S32, a, b, ret).unwrap();
+        assert_eq!(machine.assembler_finalize(), 0x4ea0_3422u32.to_le_bytes());
+        // i8x16.gt_u must be an unsigned compare so that 0x80 > 0x7f holds: CMHI v2.16b, v0.16b, v1.16b
+        let mut machine = MachineARM64::new();
+        machine.v128_cmp_gt_u(Size::S8, a, b, ret).unwrap();
+        assert_eq!(machine.assembler_finalize(), 0x6e21_3402u32.to_le_bytes());
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "aarch64"), ignore)]
+    fn v128_int_cmp_matches_scalar_comparisons() {
+        type CmpOp =
+            fn(&mut MachineARM64, Size, Location, Location, Location) -> Result<(), CodegenError>;
+        // Each op is checked against the comparison of the unsigned lanes and,
+        // for the signed variants, of the sign-extended lanes.
+        let ops: [(CmpOp, bool, fn(i128, i128) -> bool); 10] = [
+            (MachineARM64::v128_cmp_eq, false, |x, y| x == y),
+            (MachineARM64::v128_cmp_ne, false, |x, y| x != y),
+            (MachineARM64::v128_cmp_lt_s, true, |x, y| x < y),
+            (MachineARM64::v128_cmp_lt_u, false, |x, y| x < y),
+            (MachineARM64::v128_cmp_le_s, true, |x, y| x <= y),
+            (MachineARM64::v128_cmp_le_u, false, |x, y| x <= y),
+            (MachineARM64::v128_cmp_gt_s, true, |x, y| x > y),
+            (MachineARM64::v128_cmp_gt_u, false, |x, y| x > y),
+            (MachineARM64::v128_cmp_ge_s, true, |x, y| x >= y),
+            (MachineARM64::v128_cmp_ge_u, false, |x, y| x >= y),
+        ];
+        let a = 0x8000_0000_0000_0001_7fff_ffff_0000_80ffu128;
+        let b = 0x7fff_ffff_ffff_ffff_8000_0000_0000_7f01u128;
+        for (op, signed, cmp) in ops {
+            for (lane, bits) in [
+                (Size::S8, 8),
+                (Size::S16, 16),
+                (Size::S32, 32),
+                (Size::S64, 64),
+            ] {
+                for (a, b) in [(a, b), (b, a), (a, a)] {
+                    let result = execute_v128(&[a, b], |machine, args, ret| {
+                        op(machine, lane, args[0], args[1], ret).unwrap()
+                    });
+                    let expected = lanes(a, bits)
+                        .into_iter()
+                        .zip(lanes(b, bits))
+                        .map(|(x, y)| {
+                            let (x, y) = if signed {
+                                (sign_extend(x, bits), sign_extend(y, bits))
+                            } else {
+                                (x as i128, y as i128)
+                            };
+                            if cmp(x, y) {
+                                u128::MAX
+                            } else {
+                                0
+                            }
+                        });
+                    assert_eq!(
+                        result,
+                        from_lanes(expected, bits),
+                        "{:?} {:#x} {:#x}",
+                        lane,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_memory_op_traps_with_custom_code() {
+        let mut machine = MachineARM64::new();
+        let oob = machine.get_label();
+        let memarg = MemoryImmediate {
+            align: 2,
+            offset: 0,
+            memory: 0,
+        };
+        // A 256 byte segment whose base is held in X10.
+        machine.bounded_memory_op(
+            Location::GPR(GPR::X9),
+            &memarg,
+            false,
+            4,
+            Location::GPR(GPR::X10),
+            Some(Location::Imm32(0x100)),
+            TrapCode::TableAccessOutOfBounds,
+            oob,
+            |this, addr| {
+                this.emit_relaxed_ldr(Size::S32, Location::GPR(GPR::X0), Location::Memory(addr, 0))
+            },
+        );
+        machine.emit_label(oob);
+        machine.mark_address_with_trap_code(TrapCode::TableAccessOutOfBounds);
+        machine.emit_illegal_op();
+
+        let (body, traps, _) = machine.finalize_with_metadata();
+        assert!(traps
+            .iter()
+            .all(|t| t.trap_code == TrapCode::TableAccessOutOfBounds));
+        let landing = traps.iter().map(|t| t.code_offset).max().unwrap();
+        // The bound check is a B.HI to the landing pad.
+        let bhi = words(&body)
+            .into_iter()
+            .enumerate()
+            .map(|(i, insn)| (i * 4, insn))
+            .find(|(_, insn)| insn & 0xff00_001f == 0x5400_0008)
+            .unwrap();
+        let imm19 = ((bhi.1 as i32) << 8) >> 13;
+        assert_eq!(bhi.0 as i32 + imm19 * 4, landing as i32);
+    }
+
+    #[test]
+    fn v128_minmax_picks_signedness() {
+        let (a, b, ret) = (
+            Location::SIMD(NEON::V0),
+            Location::SIMD(NEON::V1),
+            Location::SIMD(NEON::V2),
+        );
+        // i8x16.max_u must keep 0x80 over 0x7f: UMAX v2.16b, v0.16b, v1.16b
+        let mut machine = MachineARM64::new();
+        machine.v128_max_u(Size::S8, a, b, ret).unwrap();
+        assert_eq!(machine.assembler_finalize(), 0x6e21_6402u32.to_le_bytes());
+        // i32x4.min_s must keep -1 over 1: SMIN v2.4s, v0.4s, v1.4s
+        let mut machine = MachineARM64::new();
+        machine.v128_min_s(Size::S32, a, b, ret).unwrap();
+        assert_eq!(machine.assembler_finalize(), 0x4ea1_6c02u32.to_le_bytes());
+    }
+
+    #[test]
+    fn i32_trunc_f32_s_bounds_are_exclusive() {
+        let mut machine = MachineARM64::new();
+        machine.convert_i32_f32(
+            Location::SIMD(NEON::V8),
+            Location::GPR(GPR::X9),
+            true,
+            false,
+        );
+        let body = machine.assembler_finalize();
+        // Conditions of the B.cond following each FCMP: NaN, lower bound, upper bound.
+        let conds: Vec<u32> = words(&body)
+            .into_iter()
+            .filter(|insn| insn & 0xff00_0010 == 0x5400_0000)
+            .map(|insn| insn & 0xf)
+            .collect();
+        assert_eq!(conds, [0b0110, 0b1101, 0b1010]);
+
+        // NZCV as set by FCMP, and whether each branch to a trap is taken.
+        let fcmp = |a: f32, b: f32| match a.partial_cmp(&b) {
+            None => (false, false, true, true),
+            Some(std::cmp::Ordering::Less) => (true, false, false, false),
+            Some(std::cmp::Ordering::Equal) => (false, true, true, false),
+            Some(std::cmp::Ordering::Greater) => (false, false, true, false),
+        };
+        let taken = |cond: u32, (n, z, _c, v): (bool, bool, bool, bool)| match cond {
+            0b0110 => v,
+            0b1101 => z || n != v,
+            0b1010 => n == v,
+            _ => unreachable!(),
+        };
+        let traps = |x: f32| {
+            taken(conds[0], fcmp(x, x))
+                || taken(conds[1], fcmp(x, GEF32_LT_I32_MIN))
+                || taken(conds[2], fcmp(x, LEF32_GT_I32_MAX))
+        };
+        assert!(!traps(2147483520.0));
+        assert!(traps(2147483648.0));
+        assert!(!traps(-2147483648.0));
+        assert!(traps(-2147483904.0));
+        assert!(traps(f32::NAN));
+    }
+
+    #[test]
+    fn f64_int_conv_check_branches_to_each_label() {
+        let mut machine = MachineARM64::new();
+        let labels: Vec<Label> = (0..4).map(|_| machine.get_label()).collect();
+        machine.emit_f64_int_conv_check(
+            Location::SIMD(NEON::V8),
+            GEF64_LT_U64_MIN,
+            LEF64_GT_U64_MAX,
+            labels[0],
+            labels[1],
+            labels[2],
+            labels[3],
+        );
+        // One UDF per label, so that each of them lands on its own offset.
+        for label in labels.iter() {
+            machine.emit_label(*label);
+            machine.emit_illegal_op();
+        }
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        let udfs: Vec<usize> = insns
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| **i & 0xffff_0000 == 0)
+            .map(|(n, _)| n)
+            .collect();
+        // (condition, target) of each B.cond, then the target of the final B.
+        let branches: Vec<(u32, usize)> = insns
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| **i & 0xff00_0010 == 0x5400_0000)
+            .map(|(n, i)| (i & 0xf, (n as i32 + (((*i as i32) << 8) >> 13)) as usize))
+            .collect();
+        assert_eq!(
+            branches,
+            [(0b0110, udfs[2]), (0b1101, udfs[0]), (0b1010, udfs[1])]
+        );
+        let b = insns
+            .iter()
+            .position(|i| i & 0xfc00_0000 == 0x1400_0000)
+            .unwrap();
+        assert_eq!(b as i32 + (((insns[b] as i32) << 6) >> 6), udfs[3] as i32);
+
+        // NZCV as set by FCMP, and which label the value ends up on.
+        let fcmp = |a: f64, b: f64| match a.partial_cmp(&b) {
+            None => (false, false, true, true),
+            Some(std::cmp::Ordering::Less) => (true, false, false, false),
+            Some(std::cmp::Ordering::Equal) => (false, true, true, false),
+            Some(std::cmp::Ordering::Greater) => (false, false, true, false),
+        };
+        let taken = |cond: u32, (n, z, _c, v): (bool, bool, bool, bool)| match cond {
+            0b0110 => v,
+            0b1101 => z || n != v,
+            0b1010 => n == v,
+            _ => unreachable!(),
+        };
+        let land = |x: f64| {
+            if taken(branches[0].0, fcmp(x, x)) {
+                "nan"
+            } else if taken(branches[1].0, fcmp(x, GEF64_LT_U64_MIN)) {
+                "underflow"
+            } else if taken(branches[2].0, fcmp(x, LEF64_GT_U64_MAX)) {
+                "overflow"
+            } else {
+                "succeed"
+            }
+        };
+        assert_eq!(land(f64::NAN), "nan");
+        assert_eq!(land(-1.0), "underflow");
+        assert_eq!(land(-0.9), "succeed");
+        assert_eq!(land(-0.0), "succeed");
+        assert_eq!(land(18446744073709549568.0), "succeed");
+        assert_eq!(land(18446744073709551616.0), "overflow");
+        assert_eq!(land(f64::INFINITY), "overflow");
+        assert_eq!(land(f64::NEG_INFINITY), "underflow");
+    }
+
+    #[test]
+    fn i32_atomic_save_uses_store_release() {
+        let memarg = MemoryImmediate {
+            align: 1,
+            offset: 0,
+            memory: 0,
+        };
+        // STLR W, STLRB and STLRH of W9 to the address register, and never a plain STR.
+        let cases: [(
+            fn(&mut MachineARM64, Location, &MemoryImmediate, Location, bool, bool, i32, Label),
+            u32,
+        ); 3] = [
+            (MachineARM64::i32_atomic_save, 0x889f_fc09),
+            (MachineARM64::i32_atomic_save_8, 0x089f_fc09),
+            (MachineARM64::i32_atomic_save_16, 0x489f_fc09),
+        ];
+        for (save, stlr) in cases {
+            let mut machine = MachineARM64::new();
+            let oob = machine.get_label();
+            save(
+                &mut machine,
+                Location::GPR(GPR::X9),
+                &memarg,
+                Location::GPR(GPR::X10),
+                false,
+                false,
+                0,
+                oob,
+            );
+            machine.emit_label(oob);
+            machine.finalize_function();
+            let body = machine.assembler_finalize();
+            let insns = words(&body);
+            assert!(insns.iter().any(|i| i & 0xffff_fc1f == stlr));
+            assert!(insns.iter().all(|i| i & 0x3f40_0000 != 0x3900_0000));
+        }
+    }
+
+    #[test]
+    fn i32_atomic_add_retries_from_the_exclusive_load() {
+        let memarg = MemoryImmediate {
+            align: 0,
+            offset: 0,
+            memory: 0,
+        };
+        let mut machine = MachineARM64::new();
+        let oob = machine.get_label();
+        machine.i32_atomic_add_8u(
+            Location::GPR(GPR::X9),
+            Location::GPR(GPR::X10),
+            &memarg,
+            Location::GPR(GPR::X11),
+            false,
+            false,
+            0,
+            oob,
+        );
+        machine.emit_label(oob);
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        let find = |pattern: u32, mask: u32| insns.iter().position(|i| i & mask == pattern);
+        let ldaxrb = find(0x085f_fc00, 0xffff_fc00).expect("LDAXRB");
+        let add = find(0x0b00_0000, 0xff20_0000).expect("ADD");
+        let stlxrb = find(0x0800_fc00, 0xffe0_fc00).expect("STLXRB");
+        let cbnz = find(0x3500_0000, 0xff00_0000).expect("CBNZ");
+        assert!(ldaxrb < add && add < stlxrb && stlxrb < cbnz);
+        // The status register of STLXRB is the one tested, and a failure retries the load.
+        assert_eq!((insns[stlxrb] >> 16) & 0x1f, insns[cbnz] & 0x1f);
+        let imm19 = ((insns[cbnz] as i32) << 8) >> 13;
+        assert_eq!(cbnz as i32 + imm19, ldaxrb as i32);
+    }
+
+    #[test]
+    fn v128_load_ignores_alignment_hint() {
+        let mut machine = MachineARM64::new();
+        let oob = machine.get_label();
+        // An offset of 1 with a 16 byte alignment hint.
+        let memarg = MemoryImmediate {
+            align: 16,
+            offset: 1,
+            memory: 0,
+        };
+        machine
+            .v128_load(
+                Location::GPR(GPR::X9),
+                &memarg,
+                Location::SIMD(NEON::V8),
+                false,
+                false,
+                0,
+                oob,
+            )
+            .unwrap();
+        machine.emit_label(oob);
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        // LDUR Q, and no TST (ANDS to XZR) checking the address alignment
+        assert!(insns.iter().any(|i| i & 0xffe0_0c00 == 0x3cc0_0000));
+        assert!(insns.iter().all(|i| i & 0x7f20_001f != 0x6a00_001f));
+    }
+
+    #[test]
+    fn simd_narrow_u_saturates_signed_to_unsigned() {
+        let (a, b, ret) = (
+            Location::SIMD(NEON::V8),
+            Location::SIMD(NEON::V9),
+            Location::SIMD(NEON::V10),
+        );
+        // SQXTUN maps -5 to 0 and 300 to 255, where SQXTN would give -5 and 127,
+        // and UQXTN 255 for both.
+        // i8x16.narrow_i16x8_u: SQXTUN v10.8b, v8.8h; SQXTUN2 v10.16b, v9.8h
+        let mut machine = MachineARM64::new();
+        machine.emit_simd_narrow_u(Size::S16, a, b, ret).unwrap();
+        let mut expected = 0x2e21_290au32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&0x6e21_292au32.to_le_bytes());
+        assert_eq!(machine.assembler_finalize(), expected);
+        // i16x8.narrow_i32x4_u: SQXTUN v10.4h, v8.4s; SQXTUN2 v10.8h, v9.4s
+        let mut machine = MachineARM64::new();
+        machine.emit_simd_narrow_u(Size::S32, a, b, ret).unwrap();
+        let mut expected = 0x2e61_290au32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&0x6e61_292au32.to_le_bytes());
+        assert_eq!(machine.assembler_finalize(), expected);
+    }
+
+    #[test]
+    fn icache_sync_cleans_invalidates_and_synchronizes() {
+        let mut machine = MachineARM64::new();
+        machine.emit_icache_sync(Location::GPR(GPR::X9), Location::Imm32(4096));
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        let find = |pattern: u32, mask: u32| insns.iter().position(|i| i & mask == pattern);
+        let ctr = find(0xd53b_0020, 0xffff_ffe0).expect("MRS CTR_EL0");
+        let dc = find(0xd50b_7b20, 0xffff_ffe0).expect("DC CVAU");
+        let ic = find(0xd50b_7520, 0xffff_ffe0).expect("IC IVAU");
+        let dsbs: Vec<usize> = insns
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| **i == 0xd503_3b9f)
+            .map(|(n, _)| n)
+            .collect();
+        assert!(ctr < dc && dc < dsbs[0] && dsbs[0] < ic && ic < dsbs[1]);
+        // ISB last, so that this core does not run stale prefetched instructions
+        assert_eq!(*insns.last().unwrap(), 0xd503_3fdf);
+    }
+
+    #[test]
+    fn f64x2_promote_and_f32x4_demote() {
+        let (src, dst) = (Location::SIMD(NEON::V8), Location::SIMD(NEON::V9));
+        // FCVTL v9.2d, v8.2s reads the 2 low f32 lanes only
+        let mut machine = MachineARM64::new();
+        machine.emit_f64x2_promote_low_f32x4(src, dst).unwrap();
+        assert_eq!(machine.assembler_finalize(), 0x0e61_7909u32.to_le_bytes());
+        // FCVTN v9.2s, v8.2d has Q=0, so the 2 high f32 lanes are zeroed
+        let mut machine = MachineARM64::new();
+        machine.emit_f32x4_demote_f64x2_zero(src, dst).unwrap();
+        assert_eq!(machine.assembler_finalize(), 0x0e61_6909u32.to_le_bytes());
+    }
+
+    #[test]
+    fn division_offset_is_recorded_in_trap_table() {
+        type DivOp = fn(&mut MachineARM64, Location, Location, Location, Label) -> usize;
+        // Each helper with the UDIV/SDIV it emits, and the CBZ that guards it.
+        let helpers: [(DivOp, u32, u32); 8] = [
+            (MachineARM64::emit_binop_udiv32, 0x1ac0_0800, 0x3400_0000),
+            (MachineARM64::emit_binop_sdiv32, 0x1ac0_0c00, 0x3400_0000),
+            (MachineARM64::emit_binop_urem32, 0x1ac0_0800, 0x3400_0000),
+            (MachineARM64::emit_binop_srem32, 0x1ac0_0c00, 0x3400_0000),
+            (MachineARM64::emit_binop_udiv64, 0x9ac0_0800, 0xb400_0000),
+            (MachineARM64::emit_binop_sdiv64, 0x9ac0_0c00, 0xb400_0000),
+            (MachineARM64::emit_binop_urem64, 0x9ac0_0800, 0xb400_0000),
+            (MachineARM64::emit_binop_srem64, 0x9ac0_0c00, 0xb400_0000),
+        ];
+        let mut machine = MachineARM64::new();
+        let div_by_zero = machine.get_label();
+        let (a, b, ret) = (
+            Location::GPR(GPR::X9),
+            Location::GPR(GPR::X10),
+            Location::GPR(GPR::X11),
+        );
+        let offsets: Vec<(usize, usize)> = helpers
+            .iter()
+            .map(|(op, _, _)| {
+                let start = machine.get_offset().0;
+                (start, op(&mut machine, a, b, ret, div_by_zero))
+            })
+            .collect();
+        for &(_, offset) in &offsets {
+            assert_eq!(
+                machine.trap_code_at(offset),
+                Some(TrapCode::IntegerOverflow)
+            );
+        }
+        machine.emit_label(div_by_zero);
+        machine.mark_address_with_trap_code(TrapCode::IntegerDivisionByZero);
+        machine.emit_illegal_op();
+
+        let (body, traps, _) = machine.finalize_with_metadata();
+        let insns = words(&body);
+        let insn = |offset: usize| insns[offset / 4];
+        let pad = traps
+            .iter()
+            .find(|t| t.trap_code == TrapCode::IntegerDivisionByZero)
+            .unwrap()
+            .code_offset as i32;
+        for (&(start, offset), &(_, div, cbz_opcode)) in offsets.iter().zip(helpers.iter()) {
+            // The offset is the UDIV/SDIV itself...
+            assert_eq!(insn(offset) & 0xffe0_fc00, div);
+            assert!(traps.iter().any(|t| t.code_offset as usize == offset));
+            // ...guarded by a CBZ of the divisor to the divide by zero pad.
+            let cbz = insn(start);
+            assert_eq!(cbz & 0xff00_001f, cbz_opcode | 10);
+            let imm19 = ((cbz as i32) << 8) >> 13;
+            assert_eq!(start as i32 + imm19 * 4, pad);
+        }
+    }
+
+    #[test]
+    fn logical_op_imm_falls_back_to_register() {
+        let x = Location::GPR(GPR::X9);
+        // 0xff is a bitmask immediate: AND w9, w9, #0xff
+        let mut machine = MachineARM64::new();
+        machine.emit_binop_and32(x, Location::Imm32(0xff), x);
+        assert_eq!(machine.assembler_finalize(), 0x1200_1d29u32.to_le_bytes());
+        // 0x12345 is not, it goes through a temporary GPR: MOV/MOVK w1, then AND w9, w9, w1
+        let mut machine = MachineARM64::new();
+        machine.emit_binop_and32(x, Location::Imm32(0x12345), x);
+        let body = machine.assembler_finalize();
+        assert_eq!(&body[body.len() - 4..], &0x0a01_0129u32.to_le_bytes());
+        // The same holds for ORR and EOR
+        let mut machine = MachineARM64::new();
+        machine.emit_binop_or32(x, Location::Imm32(0xffff_0000), x);
+        machine.emit_binop_xor32(x, Location::Imm32(0x5555_5555), x);
+        let body = machine.assembler_finalize();
+        assert_eq!(body.len(), 8);
+        assert_eq!(&body[..4], &0x3210_3d29u32.to_le_bytes());
+        assert_eq!(&body[4..], &0x5200_f129u32.to_le_bytes());
+    }
+
+    #[test]
+    fn logical_op_imm64_keeps_full_width() {
+        let (a, ret) = (Location::GPR(GPR::X9), Location::GPR(GPR::X10));
+        // AND x10, x9, #0xffffffff00000000 is encoded directly
+        let mut machine = MachineARM64::new();
+        machine.emit_binop_and64(a, Location::Imm64(0xffff_ffff_0000_0000), ret);
+        assert_eq!(machine.assembler_finalize(), 0x9260_7d2au32.to_le_bytes());
+        // EOR x10, x9, #0x8000000000000000
+        let mut machine = MachineARM64::new();
+        machine.emit_binop_xor64(a, Location::Imm64(0x8000_0000_0000_0000), ret);
+        assert_eq!(machine.assembler_finalize(), 0xd241_012au32.to_le_bytes());
+        // 0x1234_0000_0000 is not a bitmask immediate: ORR x10, x9, x1 after MOV/MOVK x1
+        let mut machine = MachineARM64::new();
+        machine.emit_binop_or64(a, Location::Imm64(0x1234_0000_0000), ret);
+        let body = machine.assembler_finalize();
+        assert_eq!(&body[body.len() - 4..], &0xaa01_012au32.to_le_bytes());
+    }
+
+    #[test]
+    fn imul_imm32_uses_shifts_for_two_powers() {
+        let encode = |imm: u32| {
+            let mut machine = MachineARM64::new();
+            machine.emit_imul_imm32(Size::S64, imm, GPR::X9);
+            words(&machine.assembler_finalize())
+        };
+        // 3 = 2 + 1: ADD x9, x9, x9, LSL #1
+        assert_eq!(encode(3), vec![0x8b09_0529]);
+        // 10 = (4 + 1) << 1: ADD x9, x9, x9, LSL #2; LSL x9, x9, #1
+        assert_eq!(encode(10), vec![0x8b09_0929, 0xd37f_f929]);
+        // 1 << 20: LSL x9, x9, #20
+        assert_eq!(encode(1 << 20), vec![0xd36c_ad29]);
+        // 7 is not a sum of two powers of two: MUL x9, x9, x1 after MOV x1, #7
+        assert_eq!(encode(7).last(), Some(&0x9b01_7d29));
+    }
+
+    #[test]
+    fn i64_load_32u_to_stack_writes_the_whole_slot() {
+        let mut machine = MachineARM64::new();
+        let oob = machine.get_label();
+        let memarg = MemoryImmediate {
+            align: 2,
+            offset: 0,
+            memory: 0,
+        };
+        machine.i64_load_32u(
+            Location::GPR(GPR::X9),
+            &memarg,
+            Location::Memory(GPR::X29, -16),
+            false,
+            false,
+            0,
+            oob,
+        );
+        let body = machine.assembler_finalize();
+        let insns = words(&body);
+        // LDR (32-bit) into a temporary, then STUR x, [x29, #-16]
+        assert!(insns.iter().any(|i| i & 0xffc0_0000 == 0xb940_0000));
+        let stur = insns.iter().find(|i| *i & 0xffe0_0c00 == 0xf800_0000);
+        assert_eq!(stur.map(|i| (i >> 5) & 0x1f), Some(29));
+    }
+}
@@ -1,8 +1,14 @@
 use crate::common_decl::*;
 use crate::emitter_x64::*;
 use crate::location::Location as AbstractLocation;
-use crate::machine::Machine;
-use crate::machine::{MemoryImmediate, TrapTable};
+use crate::machine::{CodegenError, Machine};
+use crate::machine::{MemoryImmediate, TrapTable, CANONICAL_NAN_F32, CANONICAL_NAN_F64};
+use crate::machine::{
+    GEF32_LT_I32_MIN, GEF32_LT_I64_MIN, GEF32_LT_U32_MIN, GEF32_LT_U64_MIN, GEF64_LT_I32_MIN,
+    GEF64_LT_I64_MIN, GEF64_LT_U32_MIN, GEF64_LT_U64_MIN, LEF32_GT_I32_MAX, LEF32_GT_I64_MAX,
+    LEF32_GT_U32_MAX, LEF32_GT_U64_MAX, LEF64_GT_I32_MAX, LEF64_GT_I64_MAX, LEF64_GT_U32_MAX,
+    LEF64_GT_U64_MAX,
+};
 use crate::x64_decl::new_machine_state;
 use crate::x64_decl::{ArgumentRegisterAllocator, X64Register, GPR, XMM};
 use dynasmrt::{x64::X64Relocation, VecAssembler};
@@ -29,6 +35,8 @@ pub struct MachineX86_64 {
     instructions_address_map: Vec<InstructionAddressMap>,
     /// The source location for the current operator.
     src_loc: u32,
+    /// Landing pad for misaligned atomic accesses, created on first use.
+    unaligned_atomic: Option<Label>,
 }
 
 impl MachineX86_64 {
@@ -40,6 +48,7 @@ impl MachineX86_64 {
             trap_table: TrapTable::default(),
             instructions_address_map: vec![],
             src_loc: 0,
+            unaligned_atomic: None,
         }
     }
     pub fn emit_relaxed_binop(
@@ -267,6 +276,31 @@ impl MachineX86_64 {
 
         f(&mut self.assembler, Size::S64, Location::GPR(GPR::RCX), ret);
     }
+    /// Pushes a value on the stack for a native call.
+    fn push_location_for_native(&mut self, loc: Location) {
+        match loc {
+            Location::Imm64(_) => {
+                // Push R9 value slot to be exchange with `mov`.
+                self.assembler.emit_push(Size::S64, Location::GPR(GPR::R9));
+                self.reserve_unused_temp_gpr(GPR::R9);
+                self.move_location(Size::S64, loc, Location::GPR(GPR::R9));
+                self.assembler.emit_xchg(
+                    Size::S64,
+                    Location::GPR(GPR::R9),
+                    Location::Memory(GPR::RSP, 0),
+                );
+                self.release_gpr(GPR::R9);
+            }
+            Location::SIMD(_) => {
+                // Dummy value slot to be filled with `mov`.
+                self.assembler.emit_push(Size::S64, Location::GPR(GPR::RAX));
+
+                // XMM registers can be directly stored to memory.
+                self.move_location(Size::S64, loc, Location::Memory(GPR::RSP, 0));
+            }
+            _ => self.assembler.emit_push(Size::S64, loc),
+        }
+    }
     /// Moves `loc` to a valid location for `div`/`idiv`.
     fn emit_relaxed_xdiv(
         &mut self,
@@ -447,8 +481,8 @@ impl MachineX86_64 {
                 Location::Imm32((align - 1).into()),
                 Location::GPR(tmp_aligncheck),
             );
-            self.assembler
-                .emit_jmp(Condition::NotEqual, heap_access_oob);
+            let unaligned = self.unaligned_atomic_label();
+            self.assembler.emit_jmp(Condition::NotEqual, unaligned);
             self.release_gpr(tmp_aligncheck);
         }
         let begin = self.assembler.get_offset().0;
@@ -459,6 +493,18 @@ impl MachineX86_64 {
         self.release_gpr(tmp_addr);
     }
 
+    /// The label misaligned atomic accesses jump to, emitted by `finalize_function`.
+    fn unaligned_atomic_label(&mut self) -> Label {
+        match self.unaligned_atomic {
+            Some(label) => label,
+            None => {
+                let label = self.assembler.get_label();
+                self.unaligned_atomic = Some(label);
+                label
+            }
+        }
+    }
+
     fn emit_compare_and_swap<F: FnOnce(&mut Self, GPR, GPR)>(
         &mut self,
         loc: Location,
@@ -1734,6 +1780,10 @@ impl Machine for MachineX86_64 {
         self.trap_table.offset_to_code.insert(offset, code);
         offset
     }
+    /// Get the trap code recorded at `offset`, if any
+    fn trap_code_at(&self, offset: usize) -> Option<TrapCode> {
+        self.trap_table.offset_to_code.get(&offset).copied()
+    }
     /// Pushes the instruction to the address map, calculating the offset from a
     /// provided beginning address.
     fn mark_instruction_address_end(&mut self, begin: usize) {
@@ -1803,29 +1853,16 @@ impl Machine for MachineX86_64 {
             Location::GPR(GPR::RSP),
         );
     }
-    // push a value on the stack for a native call
-    fn push_location_for_native(&mut self, loc: Location) {
-        match loc {
-            Location::Imm64(_) => {
-                // Push R9 value slot to be exchange with `mov`.
-                self.assembler.emit_push(Size::S64, Location::GPR(GPR::R9));
-                self.reserve_unused_temp_gpr(GPR::R9);
-                self.move_location(Size::S64, loc, Location::GPR(GPR::R9));
-                self.assembler.emit_xchg(
-                    Size::S64,
-                    Location::GPR(GPR::R9),
-                    Location::Memory(GPR::RSP, 0),
-                );
-                self.release_gpr(GPR::R9);
-            }
-            Location::SIMD(_) => {
-                // Dummy value slot to be filled with `mov`.
-                self.assembler.emit_push(Size::S64, Location::GPR(GPR::RAX));
-
-                // XMM registers can be directly stored to memory.
-                self.move_location(Size::S64, loc, Location::Memory(GPR::RSP, 0));
-            }
-            _ => self.assembler.emit_push(Size::S64, loc),
+    // RSP is only aligned at calls
+    fn round_stack_adjust(&self, value: usize) -> usize {
+        value
+    }
+    fn push_params_for_native(&mut self, params: &[Location], padding: bool) {
+        if padding {
+            self.adjust_stack(8);
+        }
+        for loc in params {
+            self.push_location_for_native(*loc);
         }
     }
 
@@ -1863,6 +1900,24 @@ impl Machine for MachineX86_64 {
             Location::Memory(GPR::RBP, -stack_offset),
         );
     }
+    fn emit_call_depth_check(&mut self, counter_offset: i32, limit: u32) {
+        let counter = Location::Memory(self.get_vmctx_reg(), counter_offset);
+        let ok = self.get_label();
+        self.assembler
+            .emit_cmp(Size::S32, Location::Imm32(limit), counter);
+        self.assembler.emit_jmp(Condition::Below, ok);
+        let offset = self.mark_instruction_with_trap_code(TrapCode::StackOverflow);
+        self.emit_illegal_op();
+        self.mark_instruction_address_end(offset);
+        self.emit_label(ok);
+        self.assembler
+            .emit_add(Size::S32, Location::Imm32(1), counter);
+    }
+    fn emit_call_depth_release(&mut self, counter_offset: i32) {
+        let counter = Location::Memory(self.get_vmctx_reg(), counter_offset);
+        self.assembler
+            .emit_sub(Size::S32, Location::Imm32(1), counter);
+    }
 
     // List of register to save, depending on the CallingConvention
     fn list_to_save(&self, calling_convention: CallingConvention) -> Vec<Location> {
@@ -1960,6 +2015,7 @@ impl Machine for MachineX86_64 {
                             self.assembler.emit_movzx(size_val, source, size_op, dest)
                         }
                     }
+                    Size::S128 => unreachable!(),
                 }
             }
             _ => unreachable!(),
@@ -1997,16 +2053,15 @@ impl Machine for MachineX86_64 {
         self.assembler.emit_rep_stosq();
     }
     // Restore save_area
-    fn restore_saved_area(&mut self, saved_area_offset: i32) {
+    fn restore_saved_area(&mut self, saved_area_offset: i32, locations: &[Location]) {
         self.assembler.emit_lea(
             Size::S64,
             Location::Memory(GPR::RBP, -saved_area_offset),
             Location::GPR(GPR::RSP),
         );
-    }
-    // Pop a location
-    fn pop_location(&mut self, location: Location) {
-        self.assembler.emit_pop(Size::S64, location);
+        for loc in locations {
+            self.assembler.emit_pop(Size::S64, *loc);
+        }
     }
     // Create a new `MachineState` with default values.
     fn new_machine_state(&self) -> MachineState {
@@ -2017,12 +2072,25 @@ impl Machine for MachineX86_64 {
     fn assembler_finalize(self) -> Vec<u8> {
         self.assembler.finalize().unwrap()
     }
+    fn finalize_with_metadata(self) -> (Vec<u8>, Vec<TrapInformation>, Vec<InstructionAddressMap>) {
+        let traps = self.collect_trap_information();
+        (
+            self.assembler.finalize().unwrap(),
+            traps,
+            self.instructions_address_map,
+        )
+    }
 
     fn get_offset(&self) -> Offset {
         self.assembler.get_offset()
     }
 
     fn finalize_function(&mut self) {
+        if let Some(label) = self.unaligned_atomic.take() {
+            self.emit_label(label);
+            self.mark_address_with_trap_code(TrapCode::UnalignedAtomic);
+            self.emit_illegal_op();
+        }
         self.assembler.finalize_function();
     }
 
@@ -2077,7 +2145,7 @@ impl Machine for MachineX86_64 {
                     .emit_vcmpunordss(tmp1, XMMOrMemory::XMM(tmp1), tmp2);
                 self.move_location(
                     Size::S32,
-                    Location::Imm32(0x7FC0_0000), // Canonical NaN
+                    Location::Imm32(CANONICAL_NAN_F32),
                     Location::GPR(tmpg1),
                 );
                 self.move_location(Size::S64, Location::GPR(tmpg1), Location::SIMD(tmp3));
@@ -2089,7 +2157,7 @@ impl Machine for MachineX86_64 {
                     .emit_vcmpunordsd(tmp1, XMMOrMemory::XMM(tmp1), tmp2);
                 self.move_location(
                     Size::S64,
-                    Location::Imm64(0x7FF8_0000_0000_0000), // Canonical NaN
+                    Location::Imm64(CANONICAL_NAN_F64),
                     Location::GPR(tmpg1),
                 );
                 self.move_location(Size::S64, Location::GPR(tmpg1), Location::SIMD(tmp3));
@@ -2145,6 +2213,16 @@ impl Machine for MachineX86_64 {
         self.assembler.emit_call_location(location);
     }
 
+    fn emit_return_call(&mut self, reg: GPR) {
+        // The return address of the current function is left on the stack for the callee.
+        self.emit_function_epilog();
+        self.assembler.emit_jmp_location(Location::GPR(reg));
+    }
+    fn emit_return_call_indirect(&mut self, location: Location) {
+        self.move_location(Size::S64, location, Location::GPR(GPR::RAX));
+        self.emit_return_call(GPR::RAX);
+    }
+
     fn location_address(&mut self, size: Size, source: Location, dest: Location) {
         self.assembler.emit_lea(size, source, dest);
     }
@@ -6072,7 +6150,7 @@ impl Machine for MachineX86_64 {
             // load float canonical nan
             self.move_location(
                 Size::S64,
-                Location::Imm64(0x7FF8_0000_0000_0000), // Canonical NaN
+                Location::Imm64(CANONICAL_NAN_F64),
                 Location::GPR(tmpg1),
             );
             self.move_location(Size::S64, Location::GPR(tmpg1), Location::SIMD(src2));
@@ -6176,7 +6254,7 @@ impl Machine for MachineX86_64 {
             // load float canonical nan
             self.move_location(
                 Size::S64,
-                Location::Imm64(0x7FF8_0000_0000_0000), // Canonical NaN
+                Location::Imm64(CANONICAL_NAN_F64),
                 Location::GPR(tmpg1),
             );
             self.move_location(Size::S64, Location::GPR(tmpg1), Location::SIMD(src2));
@@ -6376,7 +6454,7 @@ impl Machine for MachineX86_64 {
             // load float canonical nan
             self.move_location(
                 Size::S64,
-                Location::Imm32(0x7FC0_0000), // Canonical NaN
+                Location::Imm32(CANONICAL_NAN_F32),
                 Location::GPR(tmpg1),
             );
             self.move_location(Size::S64, Location::GPR(tmpg1), Location::SIMD(src2));
@@ -6480,7 +6558,7 @@ impl Machine for MachineX86_64 {
             // load float canonical nan
             self.move_location(
                 Size::S64,
-                Location::Imm32(0x7FC0_0000), // Canonical NaN
+                Location::Imm32(CANONICAL_NAN_F32),
                 Location::GPR(tmpg1),
             );
             self.move_location(Size::S64, Location::GPR(tmpg1), Location::SIMD(src2));
@@ -6518,11 +6596,14 @@ impl Machine for MachineX86_64 {
         self.emit_relaxed_avx(Assembler::emit_vdivss, loc_a, loc_b, ret);
     }
 
+    // x86 keeps instruction fetch coherent with stores, so there is nothing to do
+    fn emit_icache_sync(&mut self, _start: Location, _len: Location) {}
+
     fn gen_std_trampoline(
         &self,
         sig: &FunctionType,
         calling_convention: CallingConvention,
-    ) -> FunctionBody {
+    ) -> Result<FunctionBody, CodegenError> {
         let mut a = Assembler::new(0);
 
         // Calculate stack offset.
@@ -6621,10 +6702,10 @@ impl Machine for MachineX86_64 {
 
         a.emit_ret();
 
-        FunctionBody {
+        Ok(FunctionBody {
             body: a.finalize().unwrap().to_vec(),
             unwind_info: None,
-        }
+        })
     }
     // Generates dynamic import function call trampoline for a function type.
     fn gen_std_dynamic_import_trampoline(
@@ -6632,7 +6713,7 @@ impl Machine for MachineX86_64 {
         vmoffsets: &VMOffsets,
         sig: &FunctionType,
         calling_convention: CallingConvention,
-    ) -> FunctionBody {
+    ) -> Result<FunctionBody, CodegenError> {
         let mut a = Assembler::new(0);
 
         // Allocate argument array.
@@ -6742,10 +6823,10 @@ impl Machine for MachineX86_64 {
         // Return.
         a.emit_ret();
 
-        FunctionBody {
+        Ok(FunctionBody {
             body: a.finalize().unwrap().to_vec(),
             unwind_info: None,
-        }
+        })
     }
     // Singlepass calls import functions through a trampoline.
     fn gen_import_call_trampoline(
@@ -6754,7 +6835,7 @@ impl Machine for MachineX86_64 {
         index: FunctionIndex,
         sig: &FunctionType,
         calling_convention: CallingConvention,
-    ) -> CustomSection {
+    ) -> Result<CustomSection, CodegenError> {
         let mut a = Assembler::new(0);
 
         // TODO: ARM entry trampoline is not emitted.
@@ -6909,49 +6990,10 @@ impl Machine for MachineX86_64 {
 
         let section_body = SectionBody::new_with_vec(a.finalize().unwrap().to_vec());
 
-        CustomSection {
+        Ok(CustomSection {
             protection: CustomSectionProtection::ReadExecute,
             bytes: section_body,
             relocations: vec![],
-        }
+        })
     }
 }
-
-// Constants for the bounds of truncation operations. These are the least or
-// greatest exact floats in either f32 or f64 representation less-than (for
-// least) or greater-than (for greatest) the i32 or i64 or u32 or u64
-// min (for least) or max (for greatest), when rounding towards zero.
-
-/// Greatest Exact Float (32 bits) less-than i32::MIN when rounding towards zero.
-const GEF32_LT_I32_MIN: f32 = -2147483904.0;
-/// Least Exact Float (32 bits) greater-than i32::MAX when rounding towards zero.
-const LEF32_GT_I32_MAX: f32 = 2147483648.0;
-/// Greatest Exact Float (32 bits) less-than i64::MIN when rounding towards zero.
-const GEF32_LT_I64_MIN: f32 = -9223373136366403584.0;
-/// Least Exact Float (32 bits) greater-than i64::MAX when rounding towards zero.
-const LEF32_GT_I64_MAX: f32 = 9223372036854775808.0;
-/// Greatest Exact Float (32 bits) less-than u32::MIN when rounding towards zero.
-const GEF32_LT_U32_MIN: f32 = -1.0;
-/// Least Exact Float (32 bits) greater-than u32::MAX when rounding towards zero.
-const LEF32_GT_U32_MAX: f32 = 4294967296.0;
-/// Greatest Exact Float (32 bits) less-than u64::MIN when rounding towards zero.
-const GEF32_LT_U64_MIN: f32 = -1.0;
-/// Least Exact Float (32 bits) greater-than u64::MAX when rounding towards zero.
-const LEF32_GT_U64_MAX: f32 = 18446744073709551616.0;
-
-/// Greatest Exact Float (64 bits) less-than i32::MIN when rounding towards zero.
-const GEF64_LT_I32_MIN: f64 = -2147483649.0;
-/// Least Exact Float (64 bits) greater-than i32::MAX when rounding towards zero.
-const LEF64_GT_I32_MAX: f64 = 2147483648.0;
-/// Greatest Exact Float (64 bits) less-than i64::MIN when rounding towards zero.
-const GEF64_LT_I64_MIN: f64 = -9223372036854777856.0;
-/// Least Exact Float (64 bits) greater-than i64::MAX when rounding towards zero.
-const LEF64_GT_I64_MAX: f64 = 9223372036854775808.0;
-/// Greatest Exact Float (64 bits) less-than u32::MIN when rounding towards zero.
-const GEF64_LT_U32_MIN: f64 = -1.0;
-/// Least Exact Float (64 bits) greater-than u32::MAX when rounding towards zero.
-const LEF64_GT_U32_MAX: f64 = 4294967296.0;
-/// Greatest Exact Float (64 bits) less-than u64::MIN when rounding towards zero.
-const GEF64_LT_U64_MIN: f64 = -1.0;
-/// Least Exact Float (64 bits) greater-than u64::MAX when rounding towards zero.
-const LEF64_GT_U64_MAX: f64 = 18446744073709551616.0;
@@ -64,6 +64,10 @@ pub enum RelocationKind {
     ElfX86_64TlsGd,
     // /// Mach-O x86_64 32 bit signed PC relative offset to a `__thread_vars` entry.
     // MachOX86_64Tlv,
+    /// Arm64 adrp, page of the target relative to the page of the instruction
+    Arm64Adrp,
+    /// Arm64 add, low 12 bits of the target
+    Arm64AddLo12,
 }
 
 impl fmt::Display for RelocationKind {
@@ -86,6 +90,8 @@ impl fmt::Display for RelocationKind {
             Self::Arm64Movw3 => write!(f, "Arm64MovwG3"),
             Self::ElfX86_64TlsGd => write!(f, "ElfX86_64TlsGd"),
             // Self::MachOX86_64Tlv => write!(f, "MachOX86_64Tlv"),
+            Self::Arm64Adrp => write!(f, "Arm64AdrPrelPgHi21"),
+            Self::Arm64AddLo12 => write!(f, "Arm64AddAbsLo12"),
         }
     }
 }
@@ -171,6 +177,19 @@ impl Relocation {
                     .wrapping_add(reloc_addend as u32);
                 (reloc_address, reloc_delta_u32 as u64)
             }
+            RelocationKind::Arm64Adrp => {
+                let reloc_address = start + self.offset as usize;
+                let reloc_addend = self.addend as isize;
+                let target_page = target_func_address.wrapping_add(reloc_addend as u64) & !0xfff;
+                let reloc_page = reloc_address as u64 & !0xfff;
+                (reloc_address, target_page.wrapping_sub(reloc_page))
+            }
+            RelocationKind::Arm64AddLo12 => {
+                let reloc_address = start + self.offset as usize;
+                let reloc_addend = self.addend as isize;
+                let reloc_abs = target_func_address.wrapping_add(reloc_addend as u64);
+                (reloc_address, reloc_abs & 0xfff)
+            }
             RelocationKind::Arm64Call => {
                 let reloc_address = start + self.offset as usize;
                 let reloc_addend = self.addend as isize;
@@ -189,3 +208,53 @@ impl Relocation {
 
 /// Relocations to apply to function bodies.
 pub type Relocations = PrimaryMap<LocalFunctionIndex, Vec<Relocation>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relocation(kind: RelocationKind, offset: CodeOffset, addend: Addend) -> Relocation {
+        Relocation {
+            kind,
+            reloc_target: RelocationTarget::LocalFunc(LocalFunctionIndex::from_u32(0)),
+            offset,
+            addend,
+        }
+    }
+
+    #[test]
+    fn arm64_adrp_is_a_page_delta() {
+        let r = relocation(RelocationKind::Arm64Adrp, 0x10, 0);
+        assert_eq!(r.for_address(0x1_0000, 0x3_4567), (0x1_0010, 0x2_4000));
+        // a target below the instruction gives a negative delta
+        assert_eq!(
+            r.for_address(0x3_4000, 0x1_0fff),
+            (0x3_4010, (-0x2_4000i64) as u64)
+        );
+    }
+
+    #[test]
+    fn arm64_add_lo12_is_the_page_offset() {
+        let r = relocation(RelocationKind::Arm64AddLo12, 0x14, 0);
+        assert_eq!(r.for_address(0x1_0000, 0x3_4567), (0x1_0014, 0x567));
+    }
+
+    #[test]
+    fn arm64_adrp_add_pair_with_page_crossing_addend() {
+        // 0x3_4ff0 + 0x20 crosses into the next page
+        let adrp = relocation(RelocationKind::Arm64Adrp, 0, 0x20);
+        let add = relocation(RelocationKind::Arm64AddLo12, 4, 0x20);
+        let (_, page) = adrp.for_address(0x1_0000, 0x3_4ff0);
+        let (_, lo12) = add.for_address(0x1_0000, 0x3_4ff0);
+        assert_eq!(page, 0x2_5000);
+        assert_eq!(lo12, 0x010);
+        assert_eq!(0x1_0000 + page + lo12, 0x3_5010);
+        // and a negative addend back into the previous one
+        let adrp = relocation(RelocationKind::Arm64Adrp, 0, -0x20);
+        let add = relocation(RelocationKind::Arm64AddLo12, 4, -0x20);
+        let (_, page) = adrp.for_address(0x1_0000, 0x3_5010);
+        let (_, lo12) = add.for_address(0x1_0000, 0x3_5010);
+        assert_eq!(page, 0x2_4000);
+        assert_eq!(lo12, 0xff0);
+    }
+}
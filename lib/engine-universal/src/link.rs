@@ -164,6 +164,27 @@ fn apply_relocation(
                 | read_unaligned(reloc_address as *mut u32);
             write_unaligned(reloc_address as *mut u32, reloc_delta);
         },
+        RelocationKind::Arm64Adrp => unsafe {
+            let (reloc_address, reloc_delta) = r.for_address(body, target_func_address as u64);
+            let reloc_delta = reloc_delta as i64;
+            if !(-(1 << 32)..(1 << 32)).contains(&reloc_delta) {
+                panic!(
+                    "Relocation to big for {:?} for {:?} with {:x}",
+                    r.kind, r.reloc_target, reloc_delta
+                );
+            }
+            let page = (reloc_delta >> 12) as u32;
+            let reloc_delta = ((page & 0x3) << 29)
+                | (((page >> 2) & 0x7ffff) << 5)
+                | read_unaligned(reloc_address as *mut u32);
+            write_unaligned(reloc_address as *mut u32, reloc_delta);
+        },
+        RelocationKind::Arm64AddLo12 => unsafe {
+            let (reloc_address, reloc_delta) = r.for_address(body, target_func_address as u64);
+            let reloc_delta =
+                ((reloc_delta as u32) << 10) | read_unaligned(reloc_address as *mut u32);
+            write_unaligned(reloc_address as *mut u32, reloc_delta);
+        },
         kind => panic!(
             "Relocation kind unsupported in the current architecture {}",
             kind
@@ -19,7 +19,7 @@ use crate::global::Global;
 use crate::imports::Imports;
 use crate::memory::{Memory, MemoryError};
 use crate::table::{Table, TableElement};
-use crate::trap::{catch_traps, Trap, TrapCode, TrapHandler};
+use crate::trap::{catch_traps, save_call_depth, save_fp_control, Trap, TrapCode, TrapHandler};
 use crate::vmcontext::{
     VMBuiltinFunctionsArray, VMCallerCheckedAnyfunc, VMContext, VMFunctionBody,
     VMFunctionEnvironment, VMFunctionImport, VMFunctionKind, VMGlobalDefinition, VMGlobalImport,
@@ -378,6 +378,11 @@ impl Instance {
         unsafe { self.vmctx_plus_offset(self.offsets.vmctx_builtin_functions_begin()) }
     }
 
+    /// Return a pointer to the call depth counter.
+    pub(crate) fn call_depth_ptr(&self) -> *mut u32 {
+        unsafe { self.vmctx_plus_offset(self.offsets.vmctx_call_depth()) }
+    }
+
     /// Return a reference to the vmctx used by compiled wasm code.
     fn vmctx(&self) -> &VMContext {
         &self.vmctx
@@ -425,6 +430,10 @@ impl Instance {
         // Make the call.
         unsafe {
             catch_traps(trap_handler, || {
+                // The vmctx register holds whatever was left in it, so the
+                // callee may not see that it is entered from outside.
+                save_call_depth(self.call_depth_ptr());
+                save_fp_control();
                 mem::transmute::<*const VMFunctionBody, unsafe extern "C" fn(VMFunctionEnvironment)>(
                     callee_address,
                 )(callee_vmctx)
@@ -791,6 +800,111 @@ impl Instance {
         unsafe { memory.memory_fill(dst, val, len) }
     }
 
+    /// Perform the `memory.atomic.wait32` operation on a locally defined memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned, or if
+    /// the memory isn't `shared`.
+    pub(crate) fn local_memory_atomic_wait32(
+        &self,
+        memory_index: LocalMemoryIndex,
+        dst: u64,
+        val: u32,
+        timeout: i64,
+    ) -> Result<u32, Trap> {
+        let shared = self.module.memories[self.module.memory_index(memory_index)].shared;
+        let memory = self.memory(memory_index);
+        unsafe { memory.memory_atomic_wait32(dst, val, timeout, shared) }
+    }
+
+    /// Perform the `memory.atomic.wait32` operation on an imported memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned, or if
+    /// the memory isn't `shared`.
+    pub(crate) fn imported_memory_atomic_wait32(
+        &self,
+        memory_index: MemoryIndex,
+        dst: u64,
+        val: u32,
+        timeout: i64,
+    ) -> Result<u32, Trap> {
+        let shared = self.module.memories[memory_index].shared;
+        let import = self.imported_memory(memory_index);
+        let memory = unsafe { import.definition.as_ref() };
+        unsafe { memory.memory_atomic_wait32(dst, val, timeout, shared) }
+    }
+
+    /// Perform the `memory.atomic.wait64` operation on a locally defined memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned, or if
+    /// the memory isn't `shared`.
+    pub(crate) fn local_memory_atomic_wait64(
+        &self,
+        memory_index: LocalMemoryIndex,
+        dst: u64,
+        val: u64,
+        timeout: i64,
+    ) -> Result<u32, Trap> {
+        let shared = self.module.memories[self.module.memory_index(memory_index)].shared;
+        let memory = self.memory(memory_index);
+        unsafe { memory.memory_atomic_wait64(dst, val, timeout, shared) }
+    }
+
+    /// Perform the `memory.atomic.wait64` operation on an imported memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned, or if
+    /// the memory isn't `shared`.
+    pub(crate) fn imported_memory_atomic_wait64(
+        &self,
+        memory_index: MemoryIndex,
+        dst: u64,
+        val: u64,
+        timeout: i64,
+    ) -> Result<u32, Trap> {
+        let shared = self.module.memories[memory_index].shared;
+        let import = self.imported_memory(memory_index);
+        let memory = unsafe { import.definition.as_ref() };
+        unsafe { memory.memory_atomic_wait64(dst, val, timeout, shared) }
+    }
+
+    /// Perform the `memory.atomic.notify` operation on a locally defined memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned.
+    pub(crate) fn local_memory_atomic_notify(
+        &self,
+        memory_index: LocalMemoryIndex,
+        dst: u64,
+        count: u32,
+    ) -> Result<u32, Trap> {
+        let memory = self.memory(memory_index);
+        unsafe { memory.memory_atomic_notify(dst, count) }
+    }
+
+    /// Perform the `memory.atomic.notify` operation on an imported memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned.
+    pub(crate) fn imported_memory_atomic_notify(
+        &self,
+        memory_index: MemoryIndex,
+        dst: u64,
+        count: u32,
+    ) -> Result<u32, Trap> {
+        let import = self.imported_memory(memory_index);
+        let memory = unsafe { import.definition.as_ref() };
+        unsafe { memory.memory_atomic_notify(dst, count) }
+    }
+
     /// Performs the `memory.init` operation.
     ///
     /// # Errors
@@ -994,6 +1108,7 @@ impl InstanceHandle {
             instance.builtin_functions_ptr() as *mut VMBuiltinFunctionsArray,
             VMBuiltinFunctionsArray::initialized(),
         );
+        ptr::write(instance.call_depth_ptr(), 0);
 
         // Perform infallible initialization in this constructor, while fallible
         // initialization is deferred to the `initialize` method.
@@ -40,7 +40,7 @@
 use crate::func_data_registry::VMFuncRef;
 use crate::probestack::PROBESTACK;
 use crate::table::{RawTableElement, TableElement};
-use crate::trap::{raise_lib_trap, Trap, TrapCode};
+use crate::trap::{raise_lib_trap, save_call_depth, save_fp_control, Trap, TrapCode};
 use crate::vmcontext::VMContext;
 use crate::VMExternRef;
 use loupe::MemoryUsage;
@@ -660,6 +660,191 @@ pub unsafe extern "C" fn wasmer_vm_data_drop(vmctx: *mut VMContext, data_index:
     instance.data_drop(data_index)
 }
 
+/// Implementation of `memory.atomic.wait32` for locally defined memories,
+/// `dst + offset` being the effective address.
+///
+/// # Safety
+///
+/// `vmctx` must be dereferenceable.
+#[no_mangle]
+pub unsafe extern "C" fn wasmer_vm_memory32_atomic_wait32(
+    vmctx: *mut VMContext,
+    memory_index: u32,
+    dst: u32,
+    offset: u32,
+    val: u32,
+    timeout: i64,
+) -> u32 {
+    let result = {
+        let memory_index = LocalMemoryIndex::from_u32(memory_index);
+        let instance = (&*vmctx).instance();
+        instance.local_memory_atomic_wait32(memory_index, dst as u64 + offset as u64, val, timeout)
+    };
+    match result {
+        Ok(ret) => ret,
+        Err(trap) => raise_lib_trap(trap),
+    }
+}
+
+/// Implementation of `memory.atomic.wait32` for imported memories,
+/// `dst + offset` being the effective address.
+///
+/// # Safety
+///
+/// `vmctx` must be dereferenceable.
+#[no_mangle]
+pub unsafe extern "C" fn wasmer_vm_imported_memory32_atomic_wait32(
+    vmctx: *mut VMContext,
+    memory_index: u32,
+    dst: u32,
+    offset: u32,
+    val: u32,
+    timeout: i64,
+) -> u32 {
+    let result = {
+        let memory_index = MemoryIndex::from_u32(memory_index);
+        let instance = (&*vmctx).instance();
+        instance.imported_memory_atomic_wait32(
+            memory_index,
+            dst as u64 + offset as u64,
+            val,
+            timeout,
+        )
+    };
+    match result {
+        Ok(ret) => ret,
+        Err(trap) => raise_lib_trap(trap),
+    }
+}
+
+/// Implementation of `memory.atomic.wait64` for locally defined memories,
+/// `dst + offset` being the effective address.
+///
+/// # Safety
+///
+/// `vmctx` must be dereferenceable.
+#[no_mangle]
+pub unsafe extern "C" fn wasmer_vm_memory32_atomic_wait64(
+    vmctx: *mut VMContext,
+    memory_index: u32,
+    dst: u32,
+    offset: u32,
+    val: u64,
+    timeout: i64,
+) -> u32 {
+    let result = {
+        let memory_index = LocalMemoryIndex::from_u32(memory_index);
+        let instance = (&*vmctx).instance();
+        instance.local_memory_atomic_wait64(memory_index, dst as u64 + offset as u64, val, timeout)
+    };
+    match result {
+        Ok(ret) => ret,
+        Err(trap) => raise_lib_trap(trap),
+    }
+}
+
+/// Implementation of `memory.atomic.wait64` for imported memories,
+/// `dst + offset` being the effective address.
+///
+/// # Safety
+///
+/// `vmctx` must be dereferenceable.
+#[no_mangle]
+pub unsafe extern "C" fn wasmer_vm_imported_memory32_atomic_wait64(
+    vmctx: *mut VMContext,
+    memory_index: u32,
+    dst: u32,
+    offset: u32,
+    val: u64,
+    timeout: i64,
+) -> u32 {
+    let result = {
+        let memory_index = MemoryIndex::from_u32(memory_index);
+        let instance = (&*vmctx).instance();
+        instance.imported_memory_atomic_wait64(
+            memory_index,
+            dst as u64 + offset as u64,
+            val,
+            timeout,
+        )
+    };
+    match result {
+        Ok(ret) => ret,
+        Err(trap) => raise_lib_trap(trap),
+    }
+}
+
+/// Implementation of `memory.atomic.notify` for locally defined memories,
+/// `dst + offset` being the effective address.
+///
+/// # Safety
+///
+/// `vmctx` must be dereferenceable.
+#[no_mangle]
+pub unsafe extern "C" fn wasmer_vm_memory32_atomic_notify(
+    vmctx: *mut VMContext,
+    memory_index: u32,
+    dst: u32,
+    offset: u32,
+    count: u32,
+) -> u32 {
+    let result = {
+        let memory_index = LocalMemoryIndex::from_u32(memory_index);
+        let instance = (&*vmctx).instance();
+        instance.local_memory_atomic_notify(memory_index, dst as u64 + offset as u64, count)
+    };
+    match result {
+        Ok(ret) => ret,
+        Err(trap) => raise_lib_trap(trap),
+    }
+}
+
+/// Implementation of `memory.atomic.notify` for imported memories,
+/// `dst + offset` being the effective address.
+///
+/// # Safety
+///
+/// `vmctx` must be dereferenceable.
+#[no_mangle]
+pub unsafe extern "C" fn wasmer_vm_imported_memory32_atomic_notify(
+    vmctx: *mut VMContext,
+    memory_index: u32,
+    dst: u32,
+    offset: u32,
+    count: u32,
+) -> u32 {
+    let result = {
+        let memory_index = MemoryIndex::from_u32(memory_index);
+        let instance = (&*vmctx).instance();
+        instance.imported_memory_atomic_notify(memory_index, dst as u64 + offset as u64, count)
+    };
+    match result {
+        Ok(ret) => ret,
+        Err(trap) => raise_lib_trap(trap),
+    }
+}
+
+/// Saves the call depth counter of the instance, so that a trap can put it
+/// back. Called on entry to a function compiled with a call depth limit
+/// when its caller belongs to another instance or to the host.
+///
+/// # Safety
+///
+/// `vmctx` must be dereferenceable.
+#[no_mangle]
+pub unsafe extern "C" fn wasmer_vm_call_depth_enter(vmctx: *mut VMContext) {
+    let instance = (&*vmctx).instance();
+    save_call_depth(instance.call_depth_ptr());
+}
+
+/// Saves the floating-point control state, so that a trap can put it back.
+/// Called on entry to a function compiled with FPCR canonicalization when
+/// its caller belongs to another instance or to the host.
+#[no_mangle]
+pub extern "C" fn wasmer_vm_fp_control_enter(_vmctx: *mut VMContext) {
+    save_fp_control();
+}
+
 /// Implementation for raising a trap
 ///
 /// # Safety
@@ -64,3 +64,15 @@ void wasmer_unwind(void *JmpBuf) {
   platform_jmp_buf *buf = (platform_jmp_buf*) JmpBuf;
   platform_longjmp(*buf, 1);
 }
+
+#if defined(__aarch64__)
+unsigned long wasmer_read_fpcr(void) {
+  unsigned long fpcr;
+  __asm__ volatile("mrs %0, fpcr" : "=r"(fpcr));
+  return fpcr;
+}
+
+void wasmer_write_fpcr(unsigned long fpcr) {
+  __asm__ volatile("msr fpcr, %0" : : "r"(fpcr));
+}
+#endif
@@ -12,3 +12,4 @@ pub use traphandlers::{
     TlsRestore, Trap, TrapHandler, TrapHandlerFn,
 };
 pub use traphandlers::{init_traps, resume_panic};
+pub(crate) use traphandlers::{save_call_depth, save_fp_control};
@@ -63,6 +63,10 @@ pub enum TrapCode {
 
     /// An atomic memory access was attempted with an unaligned pointer.
     UnalignedAtomic = 11,
+
+    /// A `memory.atomic.wait32` or `memory.atomic.wait64` was attempted on a
+    /// memory that isn't shared.
+    UnsharedAtomicWait = 12,
 }
 
 impl TrapCode {
@@ -81,6 +85,7 @@ impl TrapCode {
             Self::BadConversionToInteger => "invalid conversion to integer",
             Self::UnreachableCodeReached => "unreachable",
             Self::UnalignedAtomic => "unaligned atomic access",
+            Self::UnsharedAtomicWait => "expected shared memory",
         }
     }
 }
@@ -100,6 +105,7 @@ impl Display for TrapCode {
             Self::BadConversionToInteger => "bad_toint",
             Self::UnreachableCodeReached => "unreachable",
             Self::UnalignedAtomic => "unalign_atom",
+            Self::UnsharedAtomicWait => "unshared_wait",
         };
         f.write_str(identifier)
     }
@@ -122,6 +128,7 @@ impl FromStr for TrapCode {
             "bad_toint" => Ok(TrapCode::BadConversionToInteger),
             "unreachable" => Ok(TrapCode::UnreachableCodeReached),
             "unalign_atom" => Ok(TrapCode::UnalignedAtomic),
+            "unshared_wait" => Ok(TrapCode::UnsharedAtomicWait),
             _ => Err(()),
         }
     }
@@ -132,7 +139,7 @@ mod tests {
     use super::*;
 
     // Everything but user-defined codes.
-    const CODES: [TrapCode; 12] = [
+    const CODES: [TrapCode; 13] = [
         TrapCode::StackOverflow,
         TrapCode::HeapAccessOutOfBounds,
         TrapCode::HeapMisaligned,
@@ -145,6 +152,7 @@ mod tests {
         TrapCode::BadConversionToInteger,
         TrapCode::UnreachableCodeReached,
         TrapCode::UnalignedAtomic,
+        TrapCode::UnsharedAtomicWait,
     ];
 
     #[test]
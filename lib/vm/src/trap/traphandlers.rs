@@ -8,7 +8,7 @@ use super::trapcode::TrapCode;
 use crate::vmcontext::{VMFunctionBody, VMFunctionEnvironment, VMTrampoline};
 use backtrace::Backtrace;
 use std::any::Any;
-use std::cell::{Cell, UnsafeCell};
+use std::cell::{Cell, RefCell, UnsafeCell};
 use std::error::Error;
 use std::io;
 use std::mem::{self, MaybeUninit};
@@ -35,6 +35,12 @@ extern "C" {
     fn wasmer_unwind(jmp_buf: *const u8) -> !;
 }
 
+#[cfg(target_arch = "aarch64")]
+extern "C" {
+    fn wasmer_read_fpcr() -> u64;
+    fn wasmer_write_fpcr(fpcr: u64);
+}
+
 cfg_if::cfg_if! {
     if #[cfg(unix)] {
         static mut PREV_SIGSEGV: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
@@ -439,6 +445,30 @@ pub unsafe fn resume_panic(payload: Box<dyn Any + Send>) -> ! {
     tls::with(|info| info.unwrap().unwind_with(UnwindReason::Panic(payload)))
 }
 
+/// Saves the call depth counter of an instance entered during the innermost
+/// call into wasm, so that a trap can put it back.
+///
+/// # Safety
+///
+/// `counter` must stay dereferenceable until `catch_traps` returns.
+pub(crate) unsafe fn save_call_depth(counter: *mut u32) {
+    tls::with(|info| {
+        if let Some(info) = info {
+            info.save_call_depth(counter)
+        }
+    })
+}
+
+/// Saves the floating-point control state on entry to code that changes it,
+/// during the innermost call into wasm, so that a trap can put it back.
+pub(crate) fn save_fp_control() {
+    tls::with(|info| {
+        if let Some(info) = info {
+            info.save_fp_control()
+        }
+    })
+}
+
 #[cfg(target_os = "windows")]
 fn reset_guard_page() {
     extern "C" {
@@ -609,6 +639,9 @@ pub struct CallThreadState<'a> {
     prev: Cell<tls::Ptr>,
     trap_handler: &'a (dyn TrapHandler + 'a),
     handling_trap: Cell<bool>,
+    call_depths: RefCell<Vec<(*mut u32, u32)>>,
+    #[cfg(target_arch = "aarch64")]
+    fp_control: Cell<Option<u64>>,
 }
 
 /// A package of functionality needed by `catch_traps` to figure out what to do
@@ -652,6 +685,9 @@ impl<'a> CallThreadState<'a> {
             prev: Cell::new(ptr::null()),
             trap_handler,
             handling_trap: Cell::new(false),
+            call_depths: RefCell::new(Vec::new()),
+            #[cfg(target_arch = "aarch64")]
+            fp_control: Cell::new(None),
         }
     }
 
@@ -660,6 +696,15 @@ impl<'a> CallThreadState<'a> {
         if ret != 0 {
             return Ok(());
         }
+        // The trap skipped the epilogues that count the calls down.
+        for &(counter, depth) in self.call_depths.borrow().iter() {
+            unsafe { ptr::write(counter, depth) };
+        }
+        // And the ones that restore the floating-point control state.
+        #[cfg(target_arch = "aarch64")]
+        if let Some(fpcr) = self.fp_control.get() {
+            unsafe { wasmer_write_fpcr(fpcr) };
+        }
         // We will only reach this path if ret == 0. And that will
         // only happen if a trap did happen. As such, it's safe to
         // assume that the `unwind` field is already initialized
@@ -676,6 +721,23 @@ impl<'a> CallThreadState<'a> {
         }
     }
 
+    /// Only the first save of a counter is kept: it is the depth the
+    /// instance had when this call into wasm started.
+    unsafe fn save_call_depth(&self, counter: *mut u32) {
+        let mut call_depths = self.call_depths.borrow_mut();
+        if call_depths.iter().all(|&(saved, _)| saved != counter) {
+            call_depths.push((counter, ptr::read(counter)));
+        }
+    }
+
+    /// Only the first save is kept, for the same reason.
+    fn save_fp_control(&self) {
+        #[cfg(target_arch = "aarch64")]
+        if self.fp_control.get().is_none() {
+            self.fp_control.set(Some(unsafe { wasmer_read_fpcr() }));
+        }
+    }
+
     fn unwind_with(&self, reason: UnwindReason) -> ! {
         unsafe {
             (*self.unwind.get()).as_mut_ptr().write(reason);
@@ -13,11 +13,14 @@ use crate::trap::{Trap, TrapCode};
 use crate::VMExternRef;
 use loupe::{MemoryUsage, MemoryUsageTracker, POINTER_BYTE_SIZE};
 use std::any::Any;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 use std::mem;
 use std::ptr::{self, NonNull};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Once};
+use std::time::{Duration, Instant};
 use std::u32;
 
 /// Union representing the first parameter passed when calling a function.
@@ -432,6 +435,172 @@ impl VMMemoryDefinition {
 
         Ok(())
     }
+
+    /// Checks that the `size` bytes wide atomic access at `dst` is in bounds
+    /// and naturally aligned, and returns its address.
+    fn atomic_access(&self, dst: u64, size: u64) -> Result<*mut u8, Trap> {
+        if dst
+            .checked_add(size)
+            .map_or(true, |m| m > self.current_length as u64)
+        {
+            return Err(Trap::lib(TrapCode::HeapAccessOutOfBounds));
+        }
+        if dst % size != 0 {
+            return Err(Trap::lib(TrapCode::UnalignedAtomic));
+        }
+        // Bounds are checked above, by this point we know that the address is valid.
+        Ok(unsafe { self.base.add(dst as usize) })
+    }
+
+    /// Perform the `memory.atomic.wait32` operation for the memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned, or
+    /// if the memory isn't `shared`.
+    pub(crate) unsafe fn memory_atomic_wait32(
+        &self,
+        dst: u64,
+        val: u32,
+        timeout: i64,
+        shared: bool,
+    ) -> Result<u32, Trap> {
+        let dst = self.atomic_access(dst, 4)?;
+        atomic_wait(dst, shared, timeout, || {
+            (*(dst as *const AtomicU32)).load(Ordering::SeqCst) == val
+        })
+    }
+
+    /// Perform the `memory.atomic.wait64` operation for the memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned, or
+    /// if the memory isn't `shared`.
+    pub(crate) unsafe fn memory_atomic_wait64(
+        &self,
+        dst: u64,
+        val: u64,
+        timeout: i64,
+        shared: bool,
+    ) -> Result<u32, Trap> {
+        let dst = self.atomic_access(dst, 8)?;
+        atomic_wait(dst, shared, timeout, || {
+            (*(dst as *const AtomicU64)).load(Ordering::SeqCst) == val
+        })
+    }
+
+    /// Perform the `memory.atomic.notify` operation for the memory, returning
+    /// the number of woken up waiters.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Trap` error if the address is out of bounds or misaligned.
+    pub(crate) unsafe fn memory_atomic_notify(&self, dst: u64, count: u32) -> Result<u32, Trap> {
+        let dst = self.atomic_access(dst, 4)?;
+        Ok(atomic_notify(dst, count))
+    }
+}
+
+/// The threads blocked in `memory.atomic.wait32` and `memory.atomic.wait64`,
+/// by address of the value they wait on.
+struct Waiters {
+    queues: Mutex<HashMap<usize, WaitQueue>>,
+    wakeup: Condvar,
+}
+
+#[derive(Default)]
+struct WaitQueue {
+    /// Waiters not notified yet.
+    waiting: u32,
+    /// Waiters notified that didn't return yet.
+    notified: u32,
+}
+
+fn waiters() -> &'static Waiters {
+    static INIT: Once = Once::new();
+    static mut WAITERS: Option<Waiters> = None;
+    unsafe {
+        INIT.call_once(|| {
+            WAITERS = Some(Waiters {
+                queues: Mutex::new(HashMap::new()),
+                wakeup: Condvar::new(),
+            })
+        });
+        WAITERS.as_ref().unwrap()
+    }
+}
+
+/// Waits on the value at `dst` if `equal` returns true, until a notify or
+/// until `timeout` nanoseconds elapsed. A negative `timeout` waits forever.
+fn atomic_wait(
+    dst: *mut u8,
+    shared: bool,
+    timeout: i64,
+    equal: impl FnOnce() -> bool,
+) -> Result<u32, Trap> {
+    if !shared {
+        return Err(Trap::lib(TrapCode::UnsharedAtomicWait));
+    }
+    let waiters = waiters();
+    let mut queues = waiters.queues.lock().unwrap();
+    // The value is read with the lock held, so that a notify following a
+    // store to it can't be missed.
+    if !equal() {
+        return Ok(1);
+    }
+    let deadline = u64::try_from(timeout)
+        .ok()
+        .map(|nanos| Instant::now() + Duration::from_nanos(nanos));
+    let key = dst as usize;
+    queues.entry(key).or_default().waiting += 1;
+    loop {
+        let queue = queues.get_mut(&key).unwrap();
+        let ret = if queue.notified > 0 {
+            queue.notified -= 1;
+            0
+        } else {
+            match deadline {
+                Some(deadline) if Instant::now() >= deadline => {
+                    queue.waiting -= 1;
+                    2
+                }
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    queues = waiters.wakeup.wait_timeout(queues, left).unwrap().0;
+                    continue;
+                }
+                None => {
+                    queues = waiters.wakeup.wait(queues).unwrap();
+                    continue;
+                }
+            }
+        };
+        if queue.waiting == 0 && queue.notified == 0 {
+            queues.remove(&key);
+        }
+        return Ok(ret);
+    }
+}
+
+/// Wakes up at most `count` of the waiters on the value at `dst`, and returns
+/// how many there were.
+fn atomic_notify(dst: *mut u8, count: u32) -> u32 {
+    let waiters = waiters();
+    let mut queues = waiters.queues.lock().unwrap();
+    let woken = match queues.get_mut(&(dst as usize)) {
+        Some(queue) => {
+            let woken = count.min(queue.waiting);
+            queue.waiting -= woken;
+            queue.notified += woken;
+            woken
+        }
+        None => 0,
+    };
+    if woken > 0 {
+        waiters.wakeup.notify_all();
+    }
+    woken
 }
 
 #[cfg(test)]
@@ -461,6 +630,41 @@ mod test_vmmemory_definition {
     }
 }
 
+#[cfg(test)]
+mod test_atomic_wait {
+    use super::{atomic_notify, atomic_wait};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn notify_wakes_up_an_endless_wait() {
+        let value = Arc::new(AtomicU32::new(0));
+        let waiter = {
+            let value = value.clone();
+            thread::spawn(move || {
+                let dst = value.as_ref() as *const AtomicU32 as *mut u8;
+                atomic_wait(dst, true, -1, || value.load(Ordering::SeqCst) == 0).unwrap()
+            })
+        };
+        let dst = value.as_ref() as *const AtomicU32 as *mut u8;
+        while atomic_notify(dst, 1) == 0 {
+            thread::yield_now();
+        }
+        assert_eq!(waiter.join().unwrap(), 0);
+        assert_eq!(atomic_notify(dst, 1), 0);
+    }
+
+    #[test]
+    fn wait_returns_not_equal_or_timed_out() {
+        let value = AtomicU32::new(0);
+        let dst = &value as *const AtomicU32 as *mut u8;
+        assert_eq!(atomic_wait(dst, true, -1, || false).unwrap(), 1);
+        assert_eq!(atomic_wait(dst, true, 1000, || true).unwrap(), 2);
+        assert!(atomic_wait(dst, false, 0, || true).is_err());
+    }
+}
+
 /// The fields compiled code needs to access to utilize a WebAssembly table
 /// defined within the instance.
 #[derive(Debug, Clone, Copy)]
@@ -1002,9 +1206,43 @@ impl VMBuiltinFunctionIndex {
     pub const fn get_externref_dec_index() -> Self {
         Self(25)
     }
+    /// Returns an index for wasm's `memory.atomic.wait32` for locally defined memories.
+    pub const fn get_memory_atomic_wait32_index() -> Self {
+        Self(26)
+    }
+    /// Returns an index for wasm's `memory.atomic.wait32` for imported memories.
+    pub const fn get_imported_memory_atomic_wait32_index() -> Self {
+        Self(27)
+    }
+    /// Returns an index for wasm's `memory.atomic.wait64` for locally defined memories.
+    pub const fn get_memory_atomic_wait64_index() -> Self {
+        Self(28)
+    }
+    /// Returns an index for wasm's `memory.atomic.wait64` for imported memories.
+    pub const fn get_imported_memory_atomic_wait64_index() -> Self {
+        Self(29)
+    }
+    /// Returns an index for wasm's `memory.atomic.notify` for locally defined memories.
+    pub const fn get_memory_atomic_notify_index() -> Self {
+        Self(30)
+    }
+    /// Returns an index for wasm's `memory.atomic.notify` for imported memories.
+    pub const fn get_imported_memory_atomic_notify_index() -> Self {
+        Self(31)
+    }
+    /// Returns an index for a function saving the call depth of an instance
+    /// entered from another one or from the host.
+    pub const fn get_call_depth_enter_index() -> Self {
+        Self(32)
+    }
+    /// Returns an index for a function saving the floating-point control
+    /// state on entry to an instance from another one or from the host.
+    pub const fn get_fp_control_enter_index() -> Self {
+        Self(33)
+    }
     /// Returns the total number of builtin functions.
     pub const fn builtin_functions_total_number() -> u32 {
-        26
+        34
     }
 
     /// Return the index as an u32 number.
@@ -1086,6 +1324,22 @@ impl VMBuiltinFunctionsArray {
             wasmer_vm_externref_inc as usize;
         ptrs[VMBuiltinFunctionIndex::get_externref_dec_index().index() as usize] =
             wasmer_vm_externref_dec as usize;
+        ptrs[VMBuiltinFunctionIndex::get_memory_atomic_wait32_index().index() as usize] =
+            wasmer_vm_memory32_atomic_wait32 as usize;
+        ptrs[VMBuiltinFunctionIndex::get_imported_memory_atomic_wait32_index().index() as usize] =
+            wasmer_vm_imported_memory32_atomic_wait32 as usize;
+        ptrs[VMBuiltinFunctionIndex::get_memory_atomic_wait64_index().index() as usize] =
+            wasmer_vm_memory32_atomic_wait64 as usize;
+        ptrs[VMBuiltinFunctionIndex::get_imported_memory_atomic_wait64_index().index() as usize] =
+            wasmer_vm_imported_memory32_atomic_wait64 as usize;
+        ptrs[VMBuiltinFunctionIndex::get_memory_atomic_notify_index().index() as usize] =
+            wasmer_vm_memory32_atomic_notify as usize;
+        ptrs[VMBuiltinFunctionIndex::get_imported_memory_atomic_notify_index().index() as usize] =
+            wasmer_vm_imported_memory32_atomic_notify as usize;
+        ptrs[VMBuiltinFunctionIndex::get_call_depth_enter_index().index() as usize] =
+            wasmer_vm_call_depth_enter as usize;
+        ptrs[VMBuiltinFunctionIndex::get_fp_control_enter_index().index() as usize] =
+            wasmer_vm_fp_control_enter as usize;
 
         debug_assert!(ptrs.iter().cloned().all(|p| p != 0));
 
@@ -42,3 +42,85 @@ fn deterministic_table() -> Result<()> {
 
     compile_and_compare(&wasm_bytes)
 }
+
+/// Instantiates `wat` compiled with FPCR canonicalization, and calls `f` on
+/// the instance with the host rounding toward zero. Returns the result of `f`
+/// and the rounding mode the host had when `f` returned.
+#[cfg(all(feature = "singlepass", feature = "universal", target_arch = "aarch64"))]
+fn call_rounding_toward_zero<R>(
+    wat: &[u8],
+    f: impl FnOnce(&wasmer::Instance) -> R,
+) -> Result<(R, i32)> {
+    use wasmer::{imports, Instance, Store};
+    use wasmer_compiler_singlepass::Singlepass;
+    use wasmer_engine_universal::Universal;
+
+    extern "C" {
+        fn fegetround() -> i32;
+        fn fesetround(round: i32) -> i32;
+    }
+
+    let mut compiler = Singlepass::new();
+    compiler.canonicalize_fpcr(true);
+    let store = Store::new(&Universal::new(compiler).engine());
+    let module = Module::new(&store, wat2wasm(wat)?)?;
+    let instance = Instance::new(&module, &imports! {})?;
+
+    let previous = unsafe { fegetround() };
+    unsafe { fesetround(FE_TOWARDZERO) };
+    let result = f(&instance);
+    let round = unsafe { fegetround() };
+    unsafe { fesetround(previous) };
+    Ok((result, round))
+}
+
+#[cfg(all(feature = "singlepass", feature = "universal", target_arch = "aarch64"))]
+const FE_TOWARDZERO: i32 = 0x00c0_0000;
+
+/// With FPCR canonicalization enabled, float results must not depend on the
+/// rounding mode the host left in the FPCR, and the host state is restored.
+#[cfg(all(feature = "singlepass", feature = "universal", target_arch = "aarch64"))]
+#[test]
+fn deterministic_fpcr() -> Result<()> {
+    use wasmer::NativeFunc;
+
+    let wat = br#"
+(module
+  (func (export "third") (result f32)
+    (f32.div (f32.const 1) (f32.const 3))))
+"#;
+    let (results, round) = call_rounding_toward_zero(wat, |instance| {
+        let third: NativeFunc<(), f32> = instance.exports.get_native_function("third")?;
+        Ok::<_, anyhow::Error>((third.call()?, third.call()?))
+    })?;
+    let (expected, result) = results?;
+
+    // 1/3 rounds up to 0x3eaaaaab when rounding to nearest, but down to 0x3eaaaaaa toward zero.
+    assert_eq!(expected.to_bits(), 0x3eaa_aaab);
+    assert_eq!(result.to_bits(), expected.to_bits());
+    assert_eq!(round, FE_TOWARDZERO);
+    Ok(())
+}
+
+/// A trap unwinds past the FPCR restore in the function epilogue, so the
+/// runtime must put the host's FPCR back itself.
+#[cfg(all(feature = "singlepass", feature = "universal", target_arch = "aarch64"))]
+#[test]
+fn deterministic_fpcr_survives_a_trap() -> Result<()> {
+    use wasmer::NativeFunc;
+
+    let wat = br#"
+(module
+  (func (export "trap")
+    (drop (f32.div (f32.const 1) (f32.const 3)))
+    (unreachable)))
+"#;
+    let (result, round) = call_rounding_toward_zero(wat, |instance| {
+        let trap: NativeFunc<(), ()> = instance.exports.get_native_function("trap")?;
+        Ok::<_, anyhow::Error>(trap.call().is_err())
+    })?;
+
+    assert!(result?);
+    assert_eq!(round, FE_TOWARDZERO);
+    Ok(())
+}
@@ -83,6 +83,35 @@ fn dynamic_function(config: crate::Config) -> Result<()> {
     Ok(())
 }
 
+#[compiler_test(imports)]
+fn dynamic_function_with_float_signature(config: crate::Config) -> Result<()> {
+    let store = config.store();
+    let wat = r#"
+        (import "host" "0" (func (param f64) (result f32)))
+        (func (export "main") (param f64) (result f32)
+            local.get 0
+            call 0
+            f32.const 1
+            f32.add
+        )
+    "#;
+    let module = Module::new(&store, wat)?;
+    let instance = Instance::new(
+        &module,
+        &imports! {
+            "host" => {
+                "0" => Function::new(&store, FunctionType::new(vec![ValType::F64], vec![ValType::F32]), |values| {
+                    assert_eq!(values[0], Value::F64(2.5));
+                    Ok(vec![Value::F32(values[0].unwrap_f64() as f32 * 2.0)])
+                }),
+            },
+        },
+    )?;
+    let f: NativeFunc<f64, f32> = instance.exports.get_native_function("main")?;
+    assert_eq!(f.call(2.5)?, 6.0);
+    Ok(())
+}
+
 #[compiler_test(imports)]
 fn dynamic_function_with_env(config: crate::Config) -> Result<()> {
     let store = config.store();
@@ -216,6 +245,37 @@ fn static_function_with_results(config: crate::Config) -> Result<()> {
     Ok(())
 }
 
+#[compiler_test(imports)]
+fn static_function_with_float_between_ints(config: crate::Config) -> Result<()> {
+    let store = config.store();
+    let wat = r#"
+        (import "host" "0" (func (param i32 f64 i32) (result i32)))
+        (func (export "main") (result i32)
+            i32.const 100
+            f64.const 200.5
+            i32.const 300
+            call 0
+        )
+    "#;
+    let module = Module::new(&store, wat)?;
+    let instance = Instance::new(
+        &module,
+        &imports! {
+            "host" => {
+                "0" => Function::new_native(&store, |a: i32, b: f64, c: i32| -> i32 {
+                    assert_eq!(a, 100);
+                    assert_eq!(b, 200.5);
+                    assert_eq!(c, 300);
+                    a + c
+                }),
+            },
+        },
+    )?;
+    let f: NativeFunc<(), i32> = instance.exports.get_native_function("main")?;
+    assert_eq!(f.call()?, 400);
+    Ok(())
+}
+
 #[compiler_test(imports)]
 fn static_function_with_env(config: crate::Config) -> Result<()> {
     let store = config.store();
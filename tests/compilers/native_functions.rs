@@ -76,6 +76,33 @@ fn native_function_works_for_wasm(config: crate::Config) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[compiler_test(native_functions)]
+fn native_function_returns_computed_f64(config: crate::Config) -> anyhow::Result<()> {
+    let store = config.store();
+    let wat = r#"(module
+        (func (export "fma") (param f64 f64 f64) (result f64)
+           (f64.add (f64.mul (local.get 0)
+                             (local.get 1))
+                    (local.get 2)))
+)"#;
+    let module = Module::new(&store, wat).unwrap();
+    let instance = Instance::new(&module, &imports! {})?;
+
+    {
+        let f: NativeFunc<(f64, f64, f64), f64> = instance.exports.get_native_function("fma")?;
+        let result = f.call(1.5, 4.0, 0.25)?;
+        assert_eq!(result, 6.25);
+    }
+
+    {
+        let f: &Function = instance.exports.get("fma")?;
+        let result = f.call(&[Val::F64(1.5), Val::F64(4.0), Val::F64(0.25)])?;
+        assert_eq!(result[0], Val::F64(6.25));
+    }
+
+    Ok(())
+}
+
 #[should_panic(
     expected = "Closures (functions with captured environments) are currently unsupported with native functions. See: https://github.com/wasmerio/wasmer/issues/1840"
 )]
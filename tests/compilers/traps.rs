@@ -147,6 +147,123 @@ fn test_trap_stack_overflow(config: crate::Config) -> Result<()> {
     Ok(())
 }
 
+/// With a call depth limit, unbounded recursion traps after exactly `limit`
+/// nested calls, whatever the host stack size.
+#[cfg(all(feature = "singlepass", feature = "universal"))]
+#[test]
+fn singlepass_call_depth_limit() -> Result<()> {
+    use wasmer_compiler_singlepass::Singlepass;
+    use wasmer_engine_universal::Universal;
+
+    let mut compiler = Singlepass::new();
+    compiler.call_depth_limit(Some(100));
+    let store = Store::new(&Universal::new(compiler).engine());
+    let wat = r#"
+        (module
+            (global $depth (export "depth") (mut i32) (i32.const 0))
+            (func $run (export "run")
+                (global.set $depth (i32.add (global.get $depth) (i32.const 1)))
+                (call $run))
+        )
+    "#;
+
+    let module = Module::new(&store, wat)?;
+    let instance = Instance::new(&module, &imports! {})?;
+    let run_func = instance.exports.get_function("run")?;
+
+    let e = run_func.call(&[]).err().expect("error calling function");
+    assert!(e.message().contains("call stack exhausted"));
+    assert_eq!(instance.exports.get_global("depth")?.get(), Value::I32(100));
+
+    // The trap puts the depth back, so the instance gets the whole limit
+    // again, through both calling paths.
+    let e = run_func.call(&[]).err().expect("error calling function");
+    assert!(e.message().contains("call stack exhausted"));
+    assert_eq!(instance.exports.get_global("depth")?.get(), Value::I32(200));
+    let run_native = run_func.native::<(), ()>()?;
+    let e = run_native.call().err().expect("error calling function");
+    assert!(e.message().contains("call stack exhausted"));
+    assert_eq!(instance.exports.get_global("depth")?.get(), Value::I32(300));
+
+    Ok(())
+}
+
+/// A trap puts back the call depth of every instance it unwinds through,
+/// not only of the one called from the host.
+#[cfg(all(feature = "singlepass", feature = "universal"))]
+#[test]
+fn singlepass_call_depth_limit_across_instances() -> Result<()> {
+    use wasmer_compiler_singlepass::Singlepass;
+    use wasmer_engine_universal::Universal;
+
+    let mut compiler = Singlepass::new();
+    compiler.call_depth_limit(Some(100));
+    let store = Store::new(&Universal::new(compiler).engine());
+    let callee_wat = r#"
+        (module
+            (global $depth (export "depth") (mut i32) (i32.const 0))
+            (func $run (export "run")
+                (global.set $depth (i32.add (global.get $depth) (i32.const 1)))
+                (call $run))
+        )
+    "#;
+    let caller_wat = r#"
+        (module
+            (import "callee" "run" (func $run))
+            (func (export "run") (call $run))
+        )
+    "#;
+
+    let callee = Instance::new(&Module::new(&store, callee_wat)?, &imports! {})?;
+    let caller = Instance::new(
+        &Module::new(&store, caller_wat)?,
+        &imports! {
+            "callee" => {
+                "run" => callee.exports.get_function("run")?.clone(),
+            },
+        },
+    )?;
+    let run_func = caller.exports.get_function("run")?;
+    let depth = callee.exports.get_global("depth")?;
+
+    // More calls than the limit, so a counter left raised in either
+    // instance would make one of them trap early.
+    for i in 1..=101 {
+        let e = run_func.call(&[]).err().expect("error calling function");
+        assert!(e.message().contains("call stack exhausted"));
+        assert_eq!(depth.get(), Value::I32(100 * i));
+    }
+
+    Ok(())
+}
+
+#[compiler_test(traps)]
+fn integer_division_by_zero(config: crate::Config) -> Result<()> {
+    let store = config.store();
+    let wat = r#"
+        (module
+            (func (export "div_u") (param i32 i32) (result i32)
+                (i32.div_u (local.get 0) (local.get 1)))
+        )
+    "#;
+
+    let module = Module::new(&store, wat)?;
+    let instance = Instance::new(&module, &imports! {})?;
+    let div_u = instance.exports.get_function("div_u")?;
+
+    assert_eq!(
+        div_u.call(&[Value::I32(7), Value::I32(2)])?.to_vec(),
+        vec![Value::I32(3)]
+    );
+    let e = div_u
+        .call(&[Value::I32(7), Value::I32(0)])
+        .err()
+        .expect("error calling function");
+    assert_eq!(e.message(), "integer divide by zero");
+
+    Ok(())
+}
+
 #[cfg_attr(target_env = "musl", ignore)]
 #[compiler_test(traps)]
 fn trap_display_pretty(config: crate::Config) -> Result<()> {
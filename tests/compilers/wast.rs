@@ -22,12 +22,20 @@ pub fn run_wast(mut config: crate::Config, wast_path: &str) -> anyhow::Result<()
     let mut features = Features::default();
     let is_bulkmemory = wast_path.contains("bulk-memory");
     let is_simd = wast_path.contains("simd");
+    let is_tail_call = wast_path.contains("tail-call");
+    let is_threads = wast_path.contains("atomic");
     if is_bulkmemory {
         features.bulk_memory(true);
     }
     if is_simd {
         features.simd(true);
     }
+    if is_tail_call {
+        features.tail_call(true);
+    }
+    if is_threads {
+        features.threads(true);
+    }
     if config.compiler == crate::Compiler::Singlepass {
         features.multi_value(false);
     }
@@ -0,0 +1,75 @@
+;; A wait on an unshared memory traps once its address is checked, while a
+;; notify on it wakes no one. On a shared memory, a wait returns "not-equal"
+;; (1) or, as no other thread notifies it here, "timed-out" (2).
+
+(module $unshared
+  (memory 1)
+  (data (i32.const 8) "\ff\ff\ff\ff\ff\ff\ff\ff")
+
+  (func (export "notify") (param i32 i32) (result i32)
+    (memory.atomic.notify (local.get 0) (local.get 1)))
+  (func (export "notify-offset") (param i32 i32) (result i32)
+    (memory.atomic.notify offset=8 (local.get 0) (local.get 1)))
+  (func (export "wait32") (param i32 i32 i64) (result i32)
+    (memory.atomic.wait32 (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "wait64") (param i32 i64 i64) (result i32)
+    (memory.atomic.wait64 (local.get 0) (local.get 1) (local.get 2)))
+)
+
+(assert_return (invoke "notify" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "notify" (i32.const 4) (i32.const 10)) (i32.const 0))
+(assert_return (invoke "notify-offset" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "notify" (i32.const 65536) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "notify-offset" (i32.const 65532) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "notify" (i32.const 1) (i32.const 1)) "unaligned atomic access")
+
+;; Whether the value matches or not, and whatever the timeout.
+(assert_trap (invoke "wait32" (i32.const 0) (i32.const 0) (i64.const 0)) "expected shared memory")
+(assert_trap (invoke "wait32" (i32.const 0) (i32.const 1) (i64.const -1)) "expected shared memory")
+(assert_trap (invoke "wait64" (i32.const 8) (i64.const -1) (i64.const -1)) "expected shared memory")
+(assert_trap (invoke "wait64" (i32.const 8) (i64.const 0) (i64.const 0)) "expected shared memory")
+
+(assert_trap (invoke "wait32" (i32.const 65536) (i32.const 0) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "wait64" (i32.const 4) (i64.const 0) (i64.const 0)) "unaligned atomic access")
+
+(module $shared
+  (memory (export "memory") 1 1 shared)
+  (data (i32.const 8) "\ff\ff\ff\ff\ff\ff\ff\ff")
+
+  (func (export "notify") (param i32 i32) (result i32)
+    (memory.atomic.notify (local.get 0) (local.get 1)))
+  (func (export "wait32") (param i32 i32 i64) (result i32)
+    (memory.atomic.wait32 (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "wait32-offset") (param i32 i32 i64) (result i32)
+    (memory.atomic.wait32 offset=8 (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "wait64") (param i32 i64 i64) (result i32)
+    (memory.atomic.wait64 (local.get 0) (local.get 1) (local.get 2)))
+)
+(register "shared" $shared)
+
+(module $imported
+  (memory (import "shared" "memory") 1 1 shared)
+
+  (func (export "wait32") (param i32 i32 i64) (result i32)
+    (memory.atomic.wait32 (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "wait64") (param i32 i64 i64) (result i32)
+    (memory.atomic.wait64 (local.get 0) (local.get 1) (local.get 2)))
+)
+
+(assert_return (invoke $shared "notify" (i32.const 0) (i32.const 1)) (i32.const 0))
+
+;; The value does not match the expected one.
+(assert_return (invoke $shared "wait32" (i32.const 0) (i32.const 1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke $shared "wait32-offset" (i32.const 0) (i32.const 0) (i64.const -1)) (i32.const 1))
+(assert_return (invoke $shared "wait64" (i32.const 8) (i64.const 0) (i64.const -1)) (i32.const 1))
+(assert_return (invoke $imported "wait32" (i32.const 0) (i32.const 1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke $imported "wait64" (i32.const 0) (i64.const 1) (i64.const -1)) (i32.const 1))
+
+;; The value matches, and no one notifies the waiter before the timeout.
+(assert_return (invoke $shared "wait32" (i32.const 0) (i32.const 0) (i64.const 0)) (i32.const 2))
+(assert_return (invoke $shared "wait32-offset" (i32.const 0) (i32.const -1) (i64.const 1000)) (i32.const 2))
+(assert_return (invoke $shared "wait64" (i32.const 8) (i64.const -1) (i64.const 0)) (i32.const 2))
+(assert_return (invoke $imported "wait32" (i32.const 0) (i32.const 0) (i64.const 1000)) (i32.const 2))
+
+(assert_trap (invoke $shared "wait32" (i32.const 65536) (i32.const 0) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke $imported "wait64" (i32.const 4) (i64.const 0) (i64.const 0)) "unaligned atomic access")
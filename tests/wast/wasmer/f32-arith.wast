@@ -0,0 +1,53 @@
+;; f32 arithmetic. Results are compared bit for bit with what Rust's `f32`
+;; operators give for the same operands, including overflow to infinity,
+;; subnormal results and division by zero.
+
+(module
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.reinterpret_f32 (f32.add (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+  (func (export "sub") (param i32 i32) (result i32)
+    (i32.reinterpret_f32 (f32.sub (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+  (func (export "mul") (param i32 i32) (result i32)
+    (i32.reinterpret_f32 (f32.mul (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+  (func (export "div") (param i32 i32) (result i32)
+    (i32.reinterpret_f32 (f32.div (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+  ;; Operands coming from immediates and from a chain of results.
+  (func (export "chain") (param f32) (result f32)
+    (f32.div (f32.mul (f32.sub (f32.add (local.get 0) (f32.const 2.25)) (f32.const 0.5)) (local.get 0)) (f32.const 3)))
+)
+
+;; 1.5 and 2.25
+(assert_return (invoke "add" (i32.const 0x3fc00000) (i32.const 0x40100000)) (i32.const 0x40700000))
+(assert_return (invoke "sub" (i32.const 0x3fc00000) (i32.const 0x40100000)) (i32.const 0xbf400000))
+(assert_return (invoke "mul" (i32.const 0x3fc00000) (i32.const 0x40100000)) (i32.const 0x40580000))
+(assert_return (invoke "div" (i32.const 0x3fc00000) (i32.const 0x40100000)) (i32.const 0x3f2aaaab))
+;; 0.1 and 0.2, rounded to nearest
+(assert_return (invoke "add" (i32.const 0x3dcccccd) (i32.const 0x3e4ccccd)) (i32.const 0x3e99999a))
+(assert_return (invoke "sub" (i32.const 0x3dcccccd) (i32.const 0x3e4ccccd)) (i32.const 0xbdcccccd))
+(assert_return (invoke "mul" (i32.const 0x3dcccccd) (i32.const 0x3e4ccccd)) (i32.const 0x3ca3d70b))
+(assert_return (invoke "div" (i32.const 0x3dcccccd) (i32.const 0x3e4ccccd)) (i32.const 0x3f000000))
+;; f32::MAX overflows to infinity
+(assert_return (invoke "add" (i32.const 0x7f7fffff) (i32.const 0x7f7fffff)) (i32.const 0x7f800000))
+(assert_return (invoke "sub" (i32.const 0x7f7fffff) (i32.const 0x7f7fffff)) (i32.const 0x00000000))
+(assert_return (invoke "mul" (i32.const 0x7f7fffff) (i32.const 0x7f7fffff)) (i32.const 0x7f800000))
+(assert_return (invoke "div" (i32.const 0x7f7fffff) (i32.const 0x7f7fffff)) (i32.const 0x3f800000))
+;; 1 and 3
+(assert_return (invoke "div" (i32.const 0x3f800000) (i32.const 0x40400000)) (i32.const 0x3eaaaaab))
+;; The smallest subnormal and 0.5, halving it ties to an even 0
+(assert_return (invoke "add" (i32.const 0x00000001) (i32.const 0x3f000000)) (i32.const 0x3f000000))
+(assert_return (invoke "mul" (i32.const 0x00000001) (i32.const 0x3f000000)) (i32.const 0x00000000))
+(assert_return (invoke "div" (i32.const 0x00000001) (i32.const 0x3f000000)) (i32.const 0x00000002))
+;; 2^24 + 1 is not representable
+(assert_return (invoke "add" (i32.const 0x4b800000) (i32.const 0x3f800000)) (i32.const 0x4b800000))
+(assert_return (invoke "sub" (i32.const 0x4b800000) (i32.const 0x3f800000)) (i32.const 0x4b7fffff))
+
+;; Division by zero
+(assert_return (invoke "div" (i32.const 0x3f800000) (i32.const 0x00000000)) (i32.const 0x7f800000))
+(assert_return (invoke "div" (i32.const 0x3f800000) (i32.const 0x80000000)) (i32.const 0xff800000))
+(assert_return (invoke "div" (i32.const 0xc0e00000) (i32.const 0x00000000)) (i32.const 0xff800000))
+(assert_return (invoke "div" (i32.const 0x00000001) (i32.const 0x00000000)) (i32.const 0x7f800000))
+
+(assert_return (invoke "chain" (f32.const 1.5)) (f32.const 1.625))
+(assert_return (invoke "chain" (f32.const -2.25)) (f32.const 0.375))
+(assert_return (invoke "chain" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "chain" (f32.const 0)) (f32.const 0))
@@ -0,0 +1,64 @@
+;; f64 arithmetic. Results are compared bit for bit with what Rust's `f64`
+;; operators give for the same operands. Infinities and NaNs propagate.
+
+(module
+  (func (export "add") (param i64 i64) (result i64)
+    (i64.reinterpret_f64 (f64.add (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+  (func (export "sub") (param i64 i64) (result i64)
+    (i64.reinterpret_f64 (f64.sub (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+  (func (export "mul") (param i64 i64) (result i64)
+    (i64.reinterpret_f64 (f64.mul (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+  (func (export "div") (param i64 i64) (result i64)
+    (i64.reinterpret_f64 (f64.div (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+  (func (export "fadd") (param f64 f64) (result f64) (f64.add (local.get 0) (local.get 1)))
+  (func (export "fsub") (param f64 f64) (result f64) (f64.sub (local.get 0) (local.get 1)))
+  (func (export "fmul") (param f64 f64) (result f64) (f64.mul (local.get 0) (local.get 1)))
+  (func (export "fdiv") (param f64 f64) (result f64) (f64.div (local.get 0) (local.get 1)))
+  ;; Operands coming from immediates and from a chain of results.
+  (func (export "chain") (param f64) (result f64)
+    (f64.div (f64.mul (f64.sub (f64.add (local.get 0) (f64.const 2.25)) (f64.const 0.5)) (local.get 0)) (f64.const 3)))
+)
+
+;; 0.1 and 0.2, rounded to nearest
+(assert_return (invoke "add" (i64.const 0x3fb999999999999a) (i64.const 0x3fc999999999999a)) (i64.const 0x3fd3333333333334))
+(assert_return (invoke "sub" (i64.const 0x3fb999999999999a) (i64.const 0x3fc999999999999a)) (i64.const 0xbfb999999999999a))
+(assert_return (invoke "mul" (i64.const 0x3fb999999999999a) (i64.const 0x3fc999999999999a)) (i64.const 0x3f947ae147ae147c))
+(assert_return (invoke "div" (i64.const 0x3fb999999999999a) (i64.const 0x3fc999999999999a)) (i64.const 0x3fe0000000000000))
+;; 1 and 3
+(assert_return (invoke "div" (i64.const 0x3ff0000000000000) (i64.const 0x4008000000000000)) (i64.const 0x3fd5555555555555))
+;; 1e308 and 10
+(assert_return (invoke "add" (i64.const 0x7fe1ccf385ebc8a0) (i64.const 0x4024000000000000)) (i64.const 0x7fe1ccf385ebc8a0))
+(assert_return (invoke "mul" (i64.const 0x7fe1ccf385ebc8a0) (i64.const 0x4024000000000000)) (i64.const 0x7ff0000000000000))
+(assert_return (invoke "div" (i64.const 0x7fe1ccf385ebc8a0) (i64.const 0x4024000000000000)) (i64.const 0x7fac7b1f3cac7433))
+;; The smallest subnormal and 0.5
+(assert_return (invoke "mul" (i64.const 0x0000000000000001) (i64.const 0x3fe0000000000000)) (i64.const 0x0000000000000000))
+(assert_return (invoke "div" (i64.const 0x0000000000000001) (i64.const 0x3fe0000000000000)) (i64.const 0x0000000000000002))
+;; 2^53 + 1 is not representable
+(assert_return (invoke "add" (i64.const 0x4340000000000000) (i64.const 0x3ff0000000000000)) (i64.const 0x4340000000000000))
+(assert_return (invoke "sub" (i64.const 0x4340000000000000) (i64.const 0x3ff0000000000000)) (i64.const 0x433fffffffffffff))
+
+;; Infinities
+(assert_return (invoke "fadd" (f64.const inf) (f64.const -1e308)) (f64.const inf))
+(assert_return (invoke "fsub" (f64.const -inf) (f64.const inf)) (f64.const -inf))
+(assert_return (invoke "fmul" (f64.const inf) (f64.const -2)) (f64.const -inf))
+(assert_return (invoke "fdiv" (f64.const 1) (f64.const 0)) (f64.const inf))
+(assert_return (invoke "fdiv" (f64.const 1) (f64.const -0)) (f64.const -inf))
+(assert_return (invoke "fdiv" (f64.const -1) (f64.const inf)) (f64.const -0))
+(assert_return (invoke "fadd" (f64.const inf) (f64.const -inf)) (f64.const nan:arithmetic))
+(assert_return (invoke "fsub" (f64.const inf) (f64.const inf)) (f64.const nan:arithmetic))
+(assert_return (invoke "fmul" (f64.const inf) (f64.const 0)) (f64.const nan:arithmetic))
+(assert_return (invoke "fdiv" (f64.const 0) (f64.const 0)) (f64.const nan:arithmetic))
+(assert_return (invoke "fdiv" (f64.const inf) (f64.const -inf)) (f64.const nan:arithmetic))
+
+;; NaNs
+(assert_return (invoke "fadd" (f64.const nan) (f64.const 1)) (f64.const nan:canonical))
+(assert_return (invoke "fsub" (f64.const 1) (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "fmul" (f64.const -nan) (f64.const inf)) (f64.const nan:canonical))
+(assert_return (invoke "fdiv" (f64.const inf) (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "fadd" (f64.const nan:0x4) (f64.const 1)) (f64.const nan:arithmetic))
+(assert_return (invoke "fdiv" (f64.const 0) (f64.const nan:0x4)) (f64.const nan:arithmetic))
+(assert_return (invoke "chain" (f64.const nan)) (f64.const nan:canonical))
+
+(assert_return (invoke "chain" (f64.const 1.5)) (f64.const 1.625))
+(assert_return (invoke "chain" (f64.const -2.25)) (f64.const 0.375))
+(assert_return (invoke "chain" (f64.const -inf)) (f64.const inf))
@@ -0,0 +1,44 @@
+;; f64 values round-trip through memory bit for bit, subnormals and NaN
+;; payloads included. Only the result of an arithmetic operation gets its NaN
+;; canonicalized when stored, and only when canonicalization is enabled.
+
+(module
+  (memory 1)
+  (func (export "roundtrip") (param i32 i64) (result i64)
+    (f64.store (local.get 0) (f64.reinterpret_i64 (local.get 1)))
+    (i64.reinterpret_f64 (f64.load (local.get 0))))
+  (func (export "store") (param i32 f64)
+    (f64.store (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result f64)
+    (f64.load (local.get 0)))
+  (func (export "load_bits") (param i32) (result i64)
+    (i64.load (local.get 0)))
+  (func (export "store_const") (param i32)
+    (f64.store offset=8 (local.get 0) (f64.const -0x1p-1074)))
+  (func (export "store_max") (param i32 f64) (result i64)
+    (f64.store (local.get 0) (f64.max (local.get 1) (local.get 1)))
+    (i64.load (local.get 0)))
+)
+
+(assert_return (invoke "roundtrip" (i32.const 0) (i64.const 0x3ff8000000000000)) (i64.const 0x3ff8000000000000))
+(assert_return (invoke "roundtrip" (i32.const 3) (i64.const 0x0000000000000001)) (i64.const 0x0000000000000001))
+(assert_return (invoke "roundtrip" (i32.const 8) (i64.const 0x000fffffffffffff)) (i64.const 0x000fffffffffffff))
+(assert_return (invoke "roundtrip" (i32.const 8) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "roundtrip" (i32.const 8) (i64.const 0x7ff0000000000000)) (i64.const 0x7ff0000000000000))
+(assert_return (invoke "roundtrip" (i32.const 8) (i64.const 0x7ff8000000000001)) (i64.const 0x7ff8000000000001))
+(assert_return (invoke "roundtrip" (i32.const 8) (i64.const 0xfff4000000000000)) (i64.const 0xfff4000000000000))
+
+(assert_return (invoke "store" (i32.const 16) (f64.const 0x1.fffffffffffffp+1023)))
+(assert_return (invoke "load" (i32.const 16)) (f64.const 0x1.fffffffffffffp+1023))
+(assert_return (invoke "load_bits" (i32.const 16)) (i64.const 0x7fefffffffffffff))
+(assert_return (invoke "store" (i32.const 16) (f64.const 0x0.0000000000001p-1022)))
+(assert_return (invoke "load" (i32.const 16)) (f64.const 0x0.0000000000001p-1022))
+(assert_return (invoke "store_const" (i32.const 16)))
+(assert_return (invoke "load_bits" (i32.const 24)) (i64.const 0x8000000000000001))
+(assert_return (invoke "store_max" (i32.const 32) (f64.const 0x0.8p-1022)) (i64.const 0x0008000000000000))
+(assert_return (invoke "store_max" (i32.const 32) (f64.const nan)) (i64.const 0x7ff8000000000000))
+
+(assert_return (invoke "store" (i32.const 65528) (f64.const -2.5)))
+(assert_return (invoke "load" (i32.const 65528)) (f64.const -2.5))
+(assert_trap (invoke "store" (i32.const 65529) (f64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "load" (i32.const 65529)) "out of bounds memory access")
@@ -0,0 +1,171 @@
+;; f32 and f64 comparisons. A NaN operand makes every comparison false except
+;; `ne`, whichever side it is on and whatever its sign or payload.
+
+(module
+  (func (export "f32.eq") (param f32 f32) (result i32) (f32.eq (local.get 0) (local.get 1)))
+  (func (export "f32.ne") (param f32 f32) (result i32) (f32.ne (local.get 0) (local.get 1)))
+  (func (export "f32.lt") (param f32 f32) (result i32) (f32.lt (local.get 0) (local.get 1)))
+  (func (export "f32.le") (param f32 f32) (result i32) (f32.le (local.get 0) (local.get 1)))
+  (func (export "f32.gt") (param f32 f32) (result i32) (f32.gt (local.get 0) (local.get 1)))
+  (func (export "f32.ge") (param f32 f32) (result i32) (f32.ge (local.get 0) (local.get 1)))
+  (func (export "f32.lt_imm") (param f32) (result i32) (f32.lt (local.get 0) (f32.const 1)))
+  (func (export "f64.eq") (param f64 f64) (result i32) (f64.eq (local.get 0) (local.get 1)))
+  (func (export "f64.ne") (param f64 f64) (result i32) (f64.ne (local.get 0) (local.get 1)))
+  (func (export "f64.lt") (param f64 f64) (result i32) (f64.lt (local.get 0) (local.get 1)))
+  (func (export "f64.le") (param f64 f64) (result i32) (f64.le (local.get 0) (local.get 1)))
+  (func (export "f64.gt") (param f64 f64) (result i32) (f64.gt (local.get 0) (local.get 1)))
+  (func (export "f64.ge") (param f64 f64) (result i32) (f64.ge (local.get 0) (local.get 1)))
+  (func (export "f64.lt_imm") (param f64) (result i32) (f64.lt (local.get 0) (f64.const 1)))
+)
+
+;; f32
+(assert_return (invoke "f32.eq" (f32.const 1.0) (f32.const 2.0)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const 2.0) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const 1.0) (f32.const 1.0)) (i32.const 1))
+(assert_return (invoke "f32.eq" (f32.const 0.0) (f32.const -0)) (i32.const 1))
+(assert_return (invoke "f32.eq" (f32.const -inf) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const inf) (f32.const inf)) (i32.const 1))
+(assert_return (invoke "f32.eq" (f32.const nan) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const 1.0) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const nan) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const -nan) (f32.const 0.0)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const nan:0x200000) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.eq" (f32.const -inf) (f32.const nan:0x200000)) (i32.const 0))
+(assert_return (invoke "f32.ne" (f32.const 1.0) (f32.const 2.0)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const 2.0) (f32.const 1.0)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const 1.0) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.ne" (f32.const 0.0) (f32.const -0)) (i32.const 0))
+(assert_return (invoke "f32.ne" (f32.const -inf) (f32.const inf)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const inf) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.ne" (f32.const nan) (f32.const 1.0)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const 1.0) (f32.const nan)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const nan) (f32.const nan)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const -nan) (f32.const 0.0)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const nan:0x200000) (f32.const inf)) (i32.const 1))
+(assert_return (invoke "f32.ne" (f32.const -inf) (f32.const nan:0x200000)) (i32.const 1))
+(assert_return (invoke "f32.lt" (f32.const 1.0) (f32.const 2.0)) (i32.const 1))
+(assert_return (invoke "f32.lt" (f32.const 2.0) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const 1.0) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const 0.0) (f32.const -0)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const -inf) (f32.const inf)) (i32.const 1))
+(assert_return (invoke "f32.lt" (f32.const inf) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const nan) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const 1.0) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const nan) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const -nan) (f32.const 0.0)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const nan:0x200000) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.lt" (f32.const -inf) (f32.const nan:0x200000)) (i32.const 0))
+(assert_return (invoke "f32.le" (f32.const 1.0) (f32.const 2.0)) (i32.const 1))
+(assert_return (invoke "f32.le" (f32.const 2.0) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.le" (f32.const 1.0) (f32.const 1.0)) (i32.const 1))
+(assert_return (invoke "f32.le" (f32.const 0.0) (f32.const -0)) (i32.const 1))
+(assert_return (invoke "f32.le" (f32.const -inf) (f32.const inf)) (i32.const 1))
+(assert_return (invoke "f32.le" (f32.const inf) (f32.const inf)) (i32.const 1))
+(assert_return (invoke "f32.le" (f32.const nan) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.le" (f32.const 1.0) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.le" (f32.const nan) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.le" (f32.const -nan) (f32.const 0.0)) (i32.const 0))
+(assert_return (invoke "f32.le" (f32.const nan:0x200000) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.le" (f32.const -inf) (f32.const nan:0x200000)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const 1.0) (f32.const 2.0)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const 2.0) (f32.const 1.0)) (i32.const 1))
+(assert_return (invoke "f32.gt" (f32.const 1.0) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const 0.0) (f32.const -0)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const -inf) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const inf) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const nan) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const 1.0) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const nan) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const -nan) (f32.const 0.0)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const nan:0x200000) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.gt" (f32.const -inf) (f32.const nan:0x200000)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const 1.0) (f32.const 2.0)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const 2.0) (f32.const 1.0)) (i32.const 1))
+(assert_return (invoke "f32.ge" (f32.const 1.0) (f32.const 1.0)) (i32.const 1))
+(assert_return (invoke "f32.ge" (f32.const 0.0) (f32.const -0)) (i32.const 1))
+(assert_return (invoke "f32.ge" (f32.const -inf) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const inf) (f32.const inf)) (i32.const 1))
+(assert_return (invoke "f32.ge" (f32.const nan) (f32.const 1.0)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const 1.0) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const nan) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const -nan) (f32.const 0.0)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const nan:0x200000) (f32.const inf)) (i32.const 0))
+(assert_return (invoke "f32.ge" (f32.const -inf) (f32.const nan:0x200000)) (i32.const 0))
+(assert_return (invoke "f32.lt_imm" (f32.const 0.5)) (i32.const 1))
+(assert_return (invoke "f32.lt_imm" (f32.const nan)) (i32.const 0))
+
+;; f64
+(assert_return (invoke "f64.eq" (f64.const 1.0) (f64.const 2.0)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const 2.0) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const 1.0) (f64.const 1.0)) (i32.const 1))
+(assert_return (invoke "f64.eq" (f64.const 0.0) (f64.const -0)) (i32.const 1))
+(assert_return (invoke "f64.eq" (f64.const -inf) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const inf) (f64.const inf)) (i32.const 1))
+(assert_return (invoke "f64.eq" (f64.const nan) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const 1.0) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const nan) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const -nan) (f64.const 0.0)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const nan:0x4000000000000) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.eq" (f64.const -inf) (f64.const nan:0x4000000000000)) (i32.const 0))
+(assert_return (invoke "f64.ne" (f64.const 1.0) (f64.const 2.0)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const 2.0) (f64.const 1.0)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const 1.0) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.ne" (f64.const 0.0) (f64.const -0)) (i32.const 0))
+(assert_return (invoke "f64.ne" (f64.const -inf) (f64.const inf)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const inf) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.ne" (f64.const nan) (f64.const 1.0)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const 1.0) (f64.const nan)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const nan) (f64.const nan)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const -nan) (f64.const 0.0)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const nan:0x4000000000000) (f64.const inf)) (i32.const 1))
+(assert_return (invoke "f64.ne" (f64.const -inf) (f64.const nan:0x4000000000000)) (i32.const 1))
+(assert_return (invoke "f64.lt" (f64.const 1.0) (f64.const 2.0)) (i32.const 1))
+(assert_return (invoke "f64.lt" (f64.const 2.0) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const 1.0) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const 0.0) (f64.const -0)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const -inf) (f64.const inf)) (i32.const 1))
+(assert_return (invoke "f64.lt" (f64.const inf) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const nan) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const 1.0) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const nan) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const -nan) (f64.const 0.0)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const nan:0x4000000000000) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.lt" (f64.const -inf) (f64.const nan:0x4000000000000)) (i32.const 0))
+(assert_return (invoke "f64.le" (f64.const 1.0) (f64.const 2.0)) (i32.const 1))
+(assert_return (invoke "f64.le" (f64.const 2.0) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.le" (f64.const 1.0) (f64.const 1.0)) (i32.const 1))
+(assert_return (invoke "f64.le" (f64.const 0.0) (f64.const -0)) (i32.const 1))
+(assert_return (invoke "f64.le" (f64.const -inf) (f64.const inf)) (i32.const 1))
+(assert_return (invoke "f64.le" (f64.const inf) (f64.const inf)) (i32.const 1))
+(assert_return (invoke "f64.le" (f64.const nan) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.le" (f64.const 1.0) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.le" (f64.const nan) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.le" (f64.const -nan) (f64.const 0.0)) (i32.const 0))
+(assert_return (invoke "f64.le" (f64.const nan:0x4000000000000) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.le" (f64.const -inf) (f64.const nan:0x4000000000000)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const 1.0) (f64.const 2.0)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const 2.0) (f64.const 1.0)) (i32.const 1))
+(assert_return (invoke "f64.gt" (f64.const 1.0) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const 0.0) (f64.const -0)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const -inf) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const inf) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const nan) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const 1.0) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const nan) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const -nan) (f64.const 0.0)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const nan:0x4000000000000) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.gt" (f64.const -inf) (f64.const nan:0x4000000000000)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const 1.0) (f64.const 2.0)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const 2.0) (f64.const 1.0)) (i32.const 1))
+(assert_return (invoke "f64.ge" (f64.const 1.0) (f64.const 1.0)) (i32.const 1))
+(assert_return (invoke "f64.ge" (f64.const 0.0) (f64.const -0)) (i32.const 1))
+(assert_return (invoke "f64.ge" (f64.const -inf) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const inf) (f64.const inf)) (i32.const 1))
+(assert_return (invoke "f64.ge" (f64.const nan) (f64.const 1.0)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const 1.0) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const nan) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const -nan) (f64.const 0.0)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const nan:0x4000000000000) (f64.const inf)) (i32.const 0))
+(assert_return (invoke "f64.ge" (f64.const -inf) (f64.const nan:0x4000000000000)) (i32.const 0))
+(assert_return (invoke "f64.lt_imm" (f64.const 0.5)) (i32.const 1))
+(assert_return (invoke "f64.lt_imm" (f64.const nan)) (i32.const 0))
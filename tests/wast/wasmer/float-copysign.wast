@@ -0,0 +1,41 @@
+;; copysign only moves the sign bit: the magnitude operand keeps its exponent
+;; and payload bits, NaNs included, and the sign of a NaN is copied like any
+;; other.
+
+(module
+  (func (export "f32") (param i32 i32) (result i32)
+    (i32.reinterpret_f32 (f32.copysign (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+  (func (export "f64") (param i64 i64) (result i64)
+    (i64.reinterpret_f64 (f64.copysign (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+  (func (export "f32_imm") (param f32) (result f32)
+    (f32.copysign (f32.const 2.5) (local.get 0)))
+  (func (export "f64_imm") (param f64) (result f64)
+    (f64.copysign (local.get 0) (f64.const -0)))
+)
+
+;; -1.5 onto a positive sign and 1.5 onto a negative one
+(assert_return (invoke "f32" (i32.const 0xbfc00000) (i32.const 0x3f800000)) (i32.const 0x3fc00000))
+(assert_return (invoke "f32" (i32.const 0x3fc00000) (i32.const 0xbf800000)) (i32.const 0xbfc00000))
+(assert_return (invoke "f32" (i32.const 0xbfc00000) (i32.const 0xbf800000)) (i32.const 0xbfc00000))
+(assert_return (invoke "f32" (i32.const 0x00000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "f32" (i32.const 0xff800000) (i32.const 0x00000001)) (i32.const 0x7f800000))
+;; NaN magnitudes keep their payload, NaN signs are copied
+(assert_return (invoke "f32" (i32.const 0x7fa00001) (i32.const 0xbf800000)) (i32.const 0xffa00001))
+(assert_return (invoke "f32" (i32.const 0xffc00000) (i32.const 0x3f800000)) (i32.const 0x7fc00000))
+(assert_return (invoke "f32" (i32.const 0x3f800000) (i32.const 0xffc00000)) (i32.const 0xbf800000))
+(assert_return (invoke "f32" (i32.const 0xbf800000) (i32.const 0x7fa00001)) (i32.const 0x3f800000))
+
+(assert_return (invoke "f64" (i64.const 0xbff8000000000000) (i64.const 0x3ff0000000000000)) (i64.const 0x3ff8000000000000))
+(assert_return (invoke "f64" (i64.const 0x3ff8000000000000) (i64.const 0xbff0000000000000)) (i64.const 0xbff8000000000000))
+(assert_return (invoke "f64" (i64.const 0xbff8000000000000) (i64.const 0xbff0000000000000)) (i64.const 0xbff8000000000000))
+(assert_return (invoke "f64" (i64.const 0x0000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "f64" (i64.const 0xfff0000000000000) (i64.const 0x0000000000000001)) (i64.const 0x7ff0000000000000))
+(assert_return (invoke "f64" (i64.const 0x7ff4000000000001) (i64.const 0xbff0000000000000)) (i64.const 0xfff4000000000001))
+(assert_return (invoke "f64" (i64.const 0xfff8000000000000) (i64.const 0x3ff0000000000000)) (i64.const 0x7ff8000000000000))
+(assert_return (invoke "f64" (i64.const 0x3ff0000000000000) (i64.const 0xfff8000000000000)) (i64.const 0xbff0000000000000))
+(assert_return (invoke "f64" (i64.const 0xbff0000000000000) (i64.const 0x7ff4000000000001)) (i64.const 0x3ff0000000000000))
+
+(assert_return (invoke "f32_imm" (f32.const -0)) (f32.const -2.5))
+(assert_return (invoke "f32_imm" (f32.const nan)) (f32.const 2.5))
+(assert_return (invoke "f64_imm" (f64.const 7)) (f64.const -7))
+(assert_return (invoke "f64_imm" (f64.const -inf)) (f64.const -inf))
@@ -0,0 +1,43 @@
+;; f64.promote_f32 and f32.demote_f64 on subnormals, rounding ties and NaNs.
+
+(module
+  (func (export "promote") (param f32) (result f64)
+    (f64.promote_f32 (local.get 0))
+  )
+  (func (export "demote") (param f64) (result f32)
+    (f32.demote_f64 (local.get 0))
+  )
+  (func (export "promote-bits") (param i32) (result i64)
+    (i64.reinterpret_f64 (f64.promote_f32 (f32.reinterpret_i32 (local.get 0))))
+  )
+  (func (export "demote-bits") (param i64) (result i32)
+    (i32.reinterpret_f32 (f32.demote_f64 (f64.reinterpret_i64 (local.get 0))))
+  )
+)
+
+;; Subnormal f32 values are normal in f64 and promote exactly.
+(assert_return (invoke "promote-bits" (i32.const 0x00000001)) (i64.const 0x36a0000000000000))
+(assert_return (invoke "promote-bits" (i32.const 0x807fffff)) (i64.const 0xb80fffffc0000000))
+(assert_return (invoke "promote" (f32.const -0x1.fffffep+127)) (f64.const -0x1.fffffep+127))
+(assert_return (invoke "promote" (f32.const inf)) (f64.const inf))
+
+;; Demoting into the f32 subnormal range, including the ties around zero.
+(assert_return (invoke "demote-bits" (i64.const 0x36a0000000000000)) (i32.const 0x00000001))
+(assert_return (invoke "demote-bits" (i64.const 0x3690000000000000)) (i32.const 0x00000000))
+(assert_return (invoke "demote-bits" (i64.const 0x3698000000000000)) (i32.const 0x00000001))
+(assert_return (invoke "demote-bits" (i64.const 0xb690000000000001)) (i32.const 0x80000001))
+(assert_return (invoke "demote" (f64.const 0x1p-1022)) (f32.const 0))
+
+;; Round to nearest, ties to even.
+(assert_return (invoke "demote" (f64.const 0x1.000001p+0)) (f32.const 1))
+(assert_return (invoke "demote" (f64.const 0x1.000003p+0)) (f32.const 0x1.000004p+0))
+(assert_return (invoke "demote" (f64.const 0x1.0000010000001p+0)) (f32.const 0x1.000002p+0))
+(assert_return (invoke "demote" (f64.const 0x1.fffffefffffffp+127)) (f32.const 0x1.fffffep+127))
+(assert_return (invoke "demote" (f64.const 0x1.ffffffp+127)) (f32.const inf))
+(assert_return (invoke "demote" (f64.const -inf)) (f32.const -inf))
+
+;; NaNs stay NaNs, whatever their payload.
+(assert_return (invoke "promote" (f32.const nan:0x200000)) (f64.const nan:arithmetic))
+(assert_return (invoke "promote" (f32.const -nan:0x7fffff)) (f64.const nan:arithmetic))
+(assert_return (invoke "demote" (f64.const nan:0x8000000000001)) (f32.const nan:arithmetic))
+(assert_return (invoke "demote" (f64.const nan:0xfffffffffffff)) (f32.const nan:arithmetic))
@@ -0,0 +1,153 @@
+;; Rounding to an integral value. `nearest` rounds halfway cases to even, and
+;; every operation keeps the sign of a zero result.
+
+(module
+  (func (export "f32.ceil") (param f32) (result f32) (f32.ceil (local.get 0)))
+  (func (export "f32.floor") (param f32) (result f32) (f32.floor (local.get 0)))
+  (func (export "f32.trunc") (param f32) (result f32) (f32.trunc (local.get 0)))
+  (func (export "f32.nearest") (param f32) (result f32) (f32.nearest (local.get 0)))
+  (func (export "f64.ceil") (param f64) (result f64) (f64.ceil (local.get 0)))
+  (func (export "f64.floor") (param f64) (result f64) (f64.floor (local.get 0)))
+  (func (export "f64.trunc") (param f64) (result f64) (f64.trunc (local.get 0)))
+  (func (export "f64.nearest") (param f64) (result f64) (f64.nearest (local.get 0)))
+)
+
+;; f32
+(assert_return (invoke "f32.ceil" (f32.const 0.5)) (f32.const 1))
+(assert_return (invoke "f32.ceil" (f32.const 1.5)) (f32.const 2))
+(assert_return (invoke "f32.ceil" (f32.const 2.5)) (f32.const 3))
+(assert_return (invoke "f32.ceil" (f32.const -0.5)) (f32.const -0))
+(assert_return (invoke "f32.ceil" (f32.const -1.5)) (f32.const -1))
+(assert_return (invoke "f32.ceil" (f32.const -2.5)) (f32.const -2))
+(assert_return (invoke "f32.ceil" (f32.const 0.3)) (f32.const 1))
+(assert_return (invoke "f32.ceil" (f32.const -0.7)) (f32.const -0))
+(assert_return (invoke "f32.ceil" (f32.const 3.75)) (f32.const 4))
+(assert_return (invoke "f32.ceil" (f32.const -3.75)) (f32.const -3))
+(assert_return (invoke "f32.ceil" (f32.const 0x1p+23)) (f32.const 8388608))
+(assert_return (invoke "f32.ceil" (f32.const -0x1.fffffep+22)) (f32.const -8388607))
+(assert_return (invoke "f32.ceil" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "f32.ceil" (f32.const -inf)) (f32.const -inf))
+(assert_return (invoke "f32.ceil" (f32.const -0)) (f32.const -0))
+(assert_return (invoke "f32.ceil" (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "f32.ceil" (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.floor" (f32.const 0.5)) (f32.const 0))
+(assert_return (invoke "f32.floor" (f32.const 1.5)) (f32.const 1))
+(assert_return (invoke "f32.floor" (f32.const 2.5)) (f32.const 2))
+(assert_return (invoke "f32.floor" (f32.const -0.5)) (f32.const -1))
+(assert_return (invoke "f32.floor" (f32.const -1.5)) (f32.const -2))
+(assert_return (invoke "f32.floor" (f32.const -2.5)) (f32.const -3))
+(assert_return (invoke "f32.floor" (f32.const 0.3)) (f32.const 0))
+(assert_return (invoke "f32.floor" (f32.const -0.7)) (f32.const -1))
+(assert_return (invoke "f32.floor" (f32.const 3.75)) (f32.const 3))
+(assert_return (invoke "f32.floor" (f32.const -3.75)) (f32.const -4))
+(assert_return (invoke "f32.floor" (f32.const 0x1p+23)) (f32.const 8388608))
+(assert_return (invoke "f32.floor" (f32.const -0x1.fffffep+22)) (f32.const -8388608))
+(assert_return (invoke "f32.floor" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "f32.floor" (f32.const -inf)) (f32.const -inf))
+(assert_return (invoke "f32.floor" (f32.const -0)) (f32.const -0))
+(assert_return (invoke "f32.floor" (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "f32.floor" (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.trunc" (f32.const 0.5)) (f32.const 0))
+(assert_return (invoke "f32.trunc" (f32.const 1.5)) (f32.const 1))
+(assert_return (invoke "f32.trunc" (f32.const 2.5)) (f32.const 2))
+(assert_return (invoke "f32.trunc" (f32.const -0.5)) (f32.const -0))
+(assert_return (invoke "f32.trunc" (f32.const -1.5)) (f32.const -1))
+(assert_return (invoke "f32.trunc" (f32.const -2.5)) (f32.const -2))
+(assert_return (invoke "f32.trunc" (f32.const 0.3)) (f32.const 0))
+(assert_return (invoke "f32.trunc" (f32.const -0.7)) (f32.const -0))
+(assert_return (invoke "f32.trunc" (f32.const 3.75)) (f32.const 3))
+(assert_return (invoke "f32.trunc" (f32.const -3.75)) (f32.const -3))
+(assert_return (invoke "f32.trunc" (f32.const 0x1p+23)) (f32.const 8388608))
+(assert_return (invoke "f32.trunc" (f32.const -0x1.fffffep+22)) (f32.const -8388607))
+(assert_return (invoke "f32.trunc" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "f32.trunc" (f32.const -inf)) (f32.const -inf))
+(assert_return (invoke "f32.trunc" (f32.const -0)) (f32.const -0))
+(assert_return (invoke "f32.trunc" (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "f32.trunc" (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.nearest" (f32.const 0.5)) (f32.const 0))
+(assert_return (invoke "f32.nearest" (f32.const 1.5)) (f32.const 2))
+(assert_return (invoke "f32.nearest" (f32.const 2.5)) (f32.const 2))
+(assert_return (invoke "f32.nearest" (f32.const -0.5)) (f32.const -0))
+(assert_return (invoke "f32.nearest" (f32.const -1.5)) (f32.const -2))
+(assert_return (invoke "f32.nearest" (f32.const -2.5)) (f32.const -2))
+(assert_return (invoke "f32.nearest" (f32.const 0.3)) (f32.const 0))
+(assert_return (invoke "f32.nearest" (f32.const -0.7)) (f32.const -1))
+(assert_return (invoke "f32.nearest" (f32.const 3.75)) (f32.const 4))
+(assert_return (invoke "f32.nearest" (f32.const -3.75)) (f32.const -4))
+(assert_return (invoke "f32.nearest" (f32.const 0x1p+23)) (f32.const 8388608))
+(assert_return (invoke "f32.nearest" (f32.const -0x1.fffffep+22)) (f32.const -8388608))
+(assert_return (invoke "f32.nearest" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "f32.nearest" (f32.const -inf)) (f32.const -inf))
+(assert_return (invoke "f32.nearest" (f32.const -0)) (f32.const -0))
+(assert_return (invoke "f32.nearest" (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "f32.nearest" (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+
+;; f64
+(assert_return (invoke "f64.ceil" (f64.const 0.5)) (f64.const 1))
+(assert_return (invoke "f64.ceil" (f64.const 1.5)) (f64.const 2))
+(assert_return (invoke "f64.ceil" (f64.const 2.5)) (f64.const 3))
+(assert_return (invoke "f64.ceil" (f64.const -0.5)) (f64.const -0))
+(assert_return (invoke "f64.ceil" (f64.const -1.5)) (f64.const -1))
+(assert_return (invoke "f64.ceil" (f64.const -2.5)) (f64.const -2))
+(assert_return (invoke "f64.ceil" (f64.const 0.3)) (f64.const 1))
+(assert_return (invoke "f64.ceil" (f64.const -0.7)) (f64.const -0))
+(assert_return (invoke "f64.ceil" (f64.const 3.75)) (f64.const 4))
+(assert_return (invoke "f64.ceil" (f64.const -3.75)) (f64.const -3))
+(assert_return (invoke "f64.ceil" (f64.const 0x1p+23)) (f64.const 8388608))
+(assert_return (invoke "f64.ceil" (f64.const -0x1.fffffep+22)) (f64.const -8388607))
+(assert_return (invoke "f64.ceil" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "f64.ceil" (f64.const -inf)) (f64.const -inf))
+(assert_return (invoke "f64.ceil" (f64.const -0)) (f64.const -0))
+(assert_return (invoke "f64.ceil" (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "f64.ceil" (f64.const nan:0x200000)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.floor" (f64.const 0.5)) (f64.const 0))
+(assert_return (invoke "f64.floor" (f64.const 1.5)) (f64.const 1))
+(assert_return (invoke "f64.floor" (f64.const 2.5)) (f64.const 2))
+(assert_return (invoke "f64.floor" (f64.const -0.5)) (f64.const -1))
+(assert_return (invoke "f64.floor" (f64.const -1.5)) (f64.const -2))
+(assert_return (invoke "f64.floor" (f64.const -2.5)) (f64.const -3))
+(assert_return (invoke "f64.floor" (f64.const 0.3)) (f64.const 0))
+(assert_return (invoke "f64.floor" (f64.const -0.7)) (f64.const -1))
+(assert_return (invoke "f64.floor" (f64.const 3.75)) (f64.const 3))
+(assert_return (invoke "f64.floor" (f64.const -3.75)) (f64.const -4))
+(assert_return (invoke "f64.floor" (f64.const 0x1p+23)) (f64.const 8388608))
+(assert_return (invoke "f64.floor" (f64.const -0x1.fffffep+22)) (f64.const -8388608))
+(assert_return (invoke "f64.floor" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "f64.floor" (f64.const -inf)) (f64.const -inf))
+(assert_return (invoke "f64.floor" (f64.const -0)) (f64.const -0))
+(assert_return (invoke "f64.floor" (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "f64.floor" (f64.const nan:0x200000)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.trunc" (f64.const 0.5)) (f64.const 0))
+(assert_return (invoke "f64.trunc" (f64.const 1.5)) (f64.const 1))
+(assert_return (invoke "f64.trunc" (f64.const 2.5)) (f64.const 2))
+(assert_return (invoke "f64.trunc" (f64.const -0.5)) (f64.const -0))
+(assert_return (invoke "f64.trunc" (f64.const -1.5)) (f64.const -1))
+(assert_return (invoke "f64.trunc" (f64.const -2.5)) (f64.const -2))
+(assert_return (invoke "f64.trunc" (f64.const 0.3)) (f64.const 0))
+(assert_return (invoke "f64.trunc" (f64.const -0.7)) (f64.const -0))
+(assert_return (invoke "f64.trunc" (f64.const 3.75)) (f64.const 3))
+(assert_return (invoke "f64.trunc" (f64.const -3.75)) (f64.const -3))
+(assert_return (invoke "f64.trunc" (f64.const 0x1p+23)) (f64.const 8388608))
+(assert_return (invoke "f64.trunc" (f64.const -0x1.fffffep+22)) (f64.const -8388607))
+(assert_return (invoke "f64.trunc" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "f64.trunc" (f64.const -inf)) (f64.const -inf))
+(assert_return (invoke "f64.trunc" (f64.const -0)) (f64.const -0))
+(assert_return (invoke "f64.trunc" (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "f64.trunc" (f64.const nan:0x200000)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.nearest" (f64.const 0.5)) (f64.const 0))
+(assert_return (invoke "f64.nearest" (f64.const 1.5)) (f64.const 2))
+(assert_return (invoke "f64.nearest" (f64.const 2.5)) (f64.const 2))
+(assert_return (invoke "f64.nearest" (f64.const -0.5)) (f64.const -0))
+(assert_return (invoke "f64.nearest" (f64.const -1.5)) (f64.const -2))
+(assert_return (invoke "f64.nearest" (f64.const -2.5)) (f64.const -2))
+(assert_return (invoke "f64.nearest" (f64.const 0.3)) (f64.const 0))
+(assert_return (invoke "f64.nearest" (f64.const -0.7)) (f64.const -1))
+(assert_return (invoke "f64.nearest" (f64.const 3.75)) (f64.const 4))
+(assert_return (invoke "f64.nearest" (f64.const -3.75)) (f64.const -4))
+(assert_return (invoke "f64.nearest" (f64.const 0x1p+23)) (f64.const 8388608))
+(assert_return (invoke "f64.nearest" (f64.const -0x1.fffffep+22)) (f64.const -8388608))
+(assert_return (invoke "f64.nearest" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "f64.nearest" (f64.const -inf)) (f64.const -inf))
+(assert_return (invoke "f64.nearest" (f64.const -0)) (f64.const -0))
+(assert_return (invoke "f64.nearest" (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "f64.nearest" (f64.const nan:0x200000)) (f64.const nan:arithmetic))
@@ -0,0 +1,48 @@
+;; Square roots are correctly rounded. A negative operand gives a NaN, except
+;; -0 whose root is -0.
+
+(module
+  (func (export "f32.sqrt") (param f32) (result f32) (f32.sqrt (local.get 0)))
+  (func (export "f32.sqrt_bits") (param i32) (result i32)
+    (i32.reinterpret_f32 (f32.sqrt (f32.reinterpret_i32 (local.get 0)))))
+  (func (export "f32.sqrt_imm") (result f32) (f32.sqrt (f32.const 6.25)))
+  (func (export "f64.sqrt") (param f64) (result f64) (f64.sqrt (local.get 0)))
+  (func (export "f64.sqrt_bits") (param i64) (result i64)
+    (i64.reinterpret_f64 (f64.sqrt (f64.reinterpret_i64 (local.get 0)))))
+  (func (export "f64.sqrt_imm") (result f64) (f64.sqrt (f64.const 6.25)))
+)
+
+;; Perfect squares
+(assert_return (invoke "f32.sqrt" (f32.const 4)) (f32.const 2))
+(assert_return (invoke "f32.sqrt" (f32.const 0.25)) (f32.const 0.5))
+(assert_return (invoke "f32.sqrt" (f32.const 0x1p-148)) (f32.const 0x1p-74))
+(assert_return (invoke "f32.sqrt_imm") (f32.const 2.5))
+(assert_return (invoke "f64.sqrt" (f64.const 4)) (f64.const 2))
+(assert_return (invoke "f64.sqrt" (f64.const 0x1p+1000)) (f64.const 0x1p+500))
+(assert_return (invoke "f64.sqrt" (f64.const 0x1p-1074)) (f64.const 0x1p-537))
+(assert_return (invoke "f64.sqrt_imm") (f64.const 2.5))
+
+;; Non-squares, against Rust's `sqrt`
+(assert_return (invoke "f32.sqrt_bits" (i32.const 0x40000000)) (i32.const 0x3fb504f3))
+(assert_return (invoke "f32.sqrt_bits" (i32.const 0x40400000)) (i32.const 0x3fddb3d7))
+(assert_return (invoke "f32.sqrt_bits" (i32.const 0x41200000)) (i32.const 0x404a62c2))
+(assert_return (invoke "f32.sqrt_bits" (i32.const 0x00000001)) (i32.const 0x1a3504f3))
+(assert_return (invoke "f32.sqrt_bits" (i32.const 0x7f7fffff)) (i32.const 0x5f7fffff))
+(assert_return (invoke "f64.sqrt_bits" (i64.const 0x4000000000000000)) (i64.const 0x3ff6a09e667f3bcd))
+(assert_return (invoke "f64.sqrt_bits" (i64.const 0x4008000000000000)) (i64.const 0x3ffbb67ae8584caa))
+(assert_return (invoke "f64.sqrt_bits" (i64.const 0x4024000000000000)) (i64.const 0x40094c583ada5b53))
+(assert_return (invoke "f64.sqrt_bits" (i64.const 0x7fefffffffffffff)) (i64.const 0x5fefffffffffffff))
+
+;; Signed zeros, infinities and NaNs
+(assert_return (invoke "f32.sqrt" (f32.const -0)) (f32.const -0))
+(assert_return (invoke "f32.sqrt" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "f32.sqrt" (f32.const -1)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.sqrt" (f32.const -0x1p-149)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.sqrt" (f32.const -inf)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.sqrt" (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "f64.sqrt" (f64.const -0)) (f64.const -0))
+(assert_return (invoke "f64.sqrt" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "f64.sqrt" (f64.const -1)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.sqrt" (f64.const -0x1p-1074)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.sqrt" (f64.const -inf)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.sqrt" (f64.const nan)) (f64.const nan:canonical))
@@ -0,0 +1,159 @@
+;; i32 arithmetic with operands coming from locals and immediates, on both
+;; sides of the operator, including wrapping at the ends of the i32 range.
+
+(module
+  (func (export "sub") (param i32 i32) (result i32)
+    (i32.sub (local.get 0) (local.get 1)))
+  (func (export "sub_imm") (param i32) (result i32)
+    (i32.sub (local.get 0) (i32.const 1)))
+  (func (export "imm_sub") (param i32) (result i32)
+    (i32.sub (i32.const 0) (local.get 0)))
+  (func (export "mul") (param i32 i32) (result i32)
+    (i32.mul (local.get 0) (local.get 1)))
+  (func (export "mul_imm") (param i32) (result i32)
+    (i32.mul (local.get 0) (i32.const 0x10000)))
+  (func (export "div_s") (param i32 i32) (result i32)
+    (i32.div_s (local.get 0) (local.get 1)))
+  (func (export "shl") (param i32 i32) (result i32)
+    (i32.shl (local.get 0) (local.get 1)))
+  (func (export "shl_33") (param i32) (result i32)
+    (i32.shl (local.get 0) (i32.const 33)))
+  (func (export "shr_u") (param i32 i32) (result i32)
+    (i32.shr_u (local.get 0) (local.get 1)))
+  (func (export "shr_u_32") (param i32) (result i32)
+    (i32.shr_u (local.get 0) (i32.const 32)))
+  (func (export "shr_s") (param i32 i32) (result i32)
+    (i32.shr_s (local.get 0) (local.get 1)))
+  (func (export "shr_s_33") (param i32) (result i32)
+    (i32.shr_s (local.get 0) (i32.const 33)))
+  (func (export "rotl") (param i32 i32) (result i32)
+    (i32.rotl (local.get 0) (local.get 1)))
+  (func (export "rotr") (param i32 i32) (result i32)
+    (i32.rotr (local.get 0) (local.get 1)))
+  (func (export "rotl_0") (param i32) (result i32)
+    (i32.rotl (local.get 0) (i32.const 0)))
+  (func (export "rotr_0") (param i32) (result i32)
+    (i32.rotr (local.get 0) (i32.const 0)))
+  (func (export "rotl_1") (param i32) (result i32)
+    (i32.rotl (local.get 0) (i32.const 1)))
+  (func (export "rotr_1") (param i32) (result i32)
+    (i32.rotr (local.get 0) (i32.const 1)))
+  (func (export "rotl_31") (param i32) (result i32)
+    (i32.rotl (local.get 0) (i32.const 31)))
+  (func (export "rotr_31") (param i32) (result i32)
+    (i32.rotr (local.get 0) (i32.const 31)))
+  (func (export "rotl_32") (param i32) (result i32)
+    (i32.rotl (local.get 0) (i32.const 32)))
+  (func (export "rotr_32") (param i32) (result i32)
+    (i32.rotr (local.get 0) (i32.const 32)))
+  (func (export "clz") (param i32) (result i32)
+    (i32.clz (local.get 0)))
+  (func (export "ctz") (param i32) (result i32)
+    (i32.ctz (local.get 0)))
+  (func (export "popcnt") (param i32) (result i32)
+    (i32.popcnt (local.get 0)))
+  (func (export "and") (param i32 i32) (result i32)
+    (i32.and (local.get 0) (local.get 1)))
+  (func (export "and_ff") (param i32) (result i32)
+    (i32.and (local.get 0) (i32.const 0xff)))
+  (func (export "and_12345") (param i32) (result i32)
+    (i32.and (local.get 0) (i32.const 0x12345)))
+  (func (export "or_imm") (param i32) (result i32)
+    (i32.or (local.get 0) (i32.const 0xffff0000)))
+  (func (export "or_12345") (param i32) (result i32)
+    (i32.or (local.get 0) (i32.const 0x12345)))
+  (func (export "xor_imm") (param i32) (result i32)
+    (i32.xor (local.get 0) (i32.const 0x55555555)))
+  (func (export "xor_12345") (param i32) (result i32)
+    (i32.xor (local.get 0) (i32.const 0x12345)))
+)
+
+(assert_return (invoke "sub" (i32.const 10) (i32.const 7)) (i32.const 3))
+(assert_return (invoke "sub" (i32.const 7) (i32.const 10)) (i32.const -3))
+(assert_return (invoke "sub" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_return (invoke "sub" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "sub" (i32.const 0) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "sub_imm" (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "imm_sub" (i32.const 5)) (i32.const -5))
+(assert_return (invoke "imm_sub" (i32.const 0x80000000)) (i32.const 0x80000000))
+
+(assert_return (invoke "mul" (i32.const 6) (i32.const 7)) (i32.const 42))
+(assert_return (invoke "mul" (i32.const -3) (i32.const 7)) (i32.const -21))
+(assert_return (invoke "mul" (i32.const 0x10000) (i32.const 0x10000)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const 0x12345678) (i32.const 0x10)) (i32.const 0x23456780))
+(assert_return (invoke "mul" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "mul_imm" (i32.const 0x10000)) (i32.const 0))
+(assert_return (invoke "mul_imm" (i32.const 0x7fff)) (i32.const 0x7fff0000))
+
+(assert_return (invoke "and" (i32.const 0xf0f0f0f0) (i32.const 0xff00ff00)) (i32.const 0xf000f000))
+(assert_return (invoke "and_ff" (i32.const 0x12345678)) (i32.const 0x78))
+(assert_return (invoke "and_12345" (i32.const -1)) (i32.const 0x12345))
+(assert_return (invoke "and_12345" (i32.const 0xf0f0f)) (i32.const 0x10305))
+(assert_return (invoke "or_imm" (i32.const 0x1234)) (i32.const 0xffff1234))
+(assert_return (invoke "or_12345" (i32.const 0x80000000)) (i32.const 0x80012345))
+(assert_return (invoke "xor_imm" (i32.const -1)) (i32.const 0xaaaaaaaa))
+(assert_return (invoke "xor_12345" (i32.const 0x12345)) (i32.const 0))
+
+(assert_return (invoke "div_s" (i32.const 7) (i32.const 2)) (i32.const 3))
+(assert_return (invoke "div_s" (i32.const -7) (i32.const 2)) (i32.const -3))
+(assert_return (invoke "div_s" (i32.const 7) (i32.const -2)) (i32.const -3))
+(assert_return (invoke "div_s" (i32.const -7) (i32.const -2)) (i32.const 3))
+(assert_return (invoke "div_s" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "div_s" (i32.const 0x80000000) (i32.const 2)) (i32.const 0xc0000000))
+(assert_return (invoke "div_s" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x80000001))
+(assert_trap (invoke "div_s" (i32.const 0x80000000) (i32.const -1)) "integer overflow")
+(assert_trap (invoke "div_s" (i32.const 1) (i32.const 0)) "integer divide by zero")
+
+(assert_return (invoke "shl" (i32.const 3) (i32.const 33)) (i32.const 6))
+(assert_return (invoke "shl" (i32.const 3) (i32.const 1)) (i32.const 6))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 31)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 1) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "shl_33" (i32.const 3)) (i32.const 6))
+(assert_return (invoke "shl_33" (i32.const 0xc0000000)) (i32.const 0x80000000))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const 33)) (i32.const 0x40000000))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const 32)) (i32.const 0x80000000))
+(assert_return (invoke "shr_u_32" (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "shr_s" (i32.const 0x80000000) (i32.const 33)) (i32.const 0xc0000000))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 31)) (i32.const -1))
+(assert_return (invoke "shr_s_33" (i32.const -8)) (i32.const -4))
+(assert_return (invoke "shr_s_33" (i32.const 8)) (i32.const 4))
+
+(assert_return (invoke "rotl" (i32.const 0x80000001) (i32.const 0)) (i32.const 0x80000001))
+(assert_return (invoke "rotr" (i32.const 0x80000001) (i32.const 0)) (i32.const 0x80000001))
+(assert_return (invoke "rotl_0" (i32.const 0x80000001)) (i32.const 0x80000001))
+(assert_return (invoke "rotr_0" (i32.const 0x80000001)) (i32.const 0x80000001))
+(assert_return (invoke "rotl" (i32.const 0x80000001) (i32.const 1)) (i32.const 0x3))
+(assert_return (invoke "rotr" (i32.const 0x80000001) (i32.const 1)) (i32.const 0xc0000000))
+(assert_return (invoke "rotl_1" (i32.const 0x80000001)) (i32.const 0x3))
+(assert_return (invoke "rotr_1" (i32.const 0x80000001)) (i32.const 0xc0000000))
+(assert_return (invoke "rotl" (i32.const 0x80000001) (i32.const 31)) (i32.const 0xc0000000))
+(assert_return (invoke "rotr" (i32.const 0x80000001) (i32.const 31)) (i32.const 0x3))
+(assert_return (invoke "rotl_31" (i32.const 0x80000001)) (i32.const 0xc0000000))
+(assert_return (invoke "rotr_31" (i32.const 0x80000001)) (i32.const 0x3))
+(assert_return (invoke "rotl" (i32.const 0x80000001) (i32.const 32)) (i32.const 0x80000001))
+(assert_return (invoke "rotr" (i32.const 0x80000001) (i32.const 32)) (i32.const 0x80000001))
+(assert_return (invoke "rotl_32" (i32.const 0x80000001)) (i32.const 0x80000001))
+(assert_return (invoke "rotr_32" (i32.const 0x80000001)) (i32.const 0x80000001))
+
+(assert_return (invoke "clz" (i32.const 0)) (i32.const 32))
+(assert_return (invoke "ctz" (i32.const 0)) (i32.const 32))
+(assert_return (invoke "clz" (i32.const 0x1)) (i32.const 31))
+(assert_return (invoke "ctz" (i32.const 0x1)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 0x2)) (i32.const 30))
+(assert_return (invoke "ctz" (i32.const 0x2)) (i32.const 1))
+(assert_return (invoke "clz" (i32.const 0x8000)) (i32.const 16))
+(assert_return (invoke "ctz" (i32.const 0x8000)) (i32.const 15))
+(assert_return (invoke "clz" (i32.const 0x10000)) (i32.const 15))
+(assert_return (invoke "ctz" (i32.const 0x10000)) (i32.const 16))
+(assert_return (invoke "clz" (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0x80000000)) (i32.const 31))
+(assert_return (invoke "clz" (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const -1)) (i32.const 0))
+
+(assert_return (invoke "popcnt" (i32.const 0x0)) (i32.const 0))
+(assert_return (invoke "popcnt" (i32.const 0xffffffff)) (i32.const 32))
+(assert_return (invoke "popcnt" (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const 0x12345678)) (i32.const 13))
+(assert_return (invoke "popcnt" (i32.const 0xdeadbeef)) (i32.const 24))
+(assert_return (invoke "popcnt" (i32.const 0xf0f0f0f)) (i32.const 16))
+(assert_return (invoke "popcnt" (i32.const 0x7fffffff)) (i32.const 31))
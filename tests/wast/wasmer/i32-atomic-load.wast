@@ -0,0 +1,41 @@
+;; Atomic i32 loads read the same value as plain loads, zero extend the
+;; narrow widths, are bounds checked and trap on misaligned addresses.
+
+(module
+  (memory 1 1 shared)
+  (data (i32.const 0) "\f1\f2\f3\f4\05\06\07\08")
+
+  (func (export "load") (param i32) (result i32)
+    (i32.load (local.get 0)))
+  (func (export "atomic-load") (param i32) (result i32)
+    (i32.atomic.load (local.get 0)))
+  (func (export "atomic-load-offset") (param i32) (result i32)
+    (i32.atomic.load offset=4 (local.get 0)))
+  (func (export "atomic-load8_u") (param i32) (result i32)
+    (i32.atomic.load8_u (local.get 0)))
+  (func (export "atomic-load16_u") (param i32) (result i32)
+    (i32.atomic.load16_u (local.get 0)))
+  ;; Plain and atomic loads of the same address in one function.
+  (func (export "both-agree") (param i32) (result i32)
+    (i32.eq (i32.load (local.get 0)) (i32.atomic.load (local.get 0))))
+)
+
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0xf4f3f2f1))
+(assert_return (invoke "atomic-load" (i32.const 0)) (i32.const 0xf4f3f2f1))
+(assert_return (invoke "atomic-load" (i32.const 4)) (i32.const 0x08070605))
+(assert_return (invoke "atomic-load-offset" (i32.const 0)) (i32.const 0x08070605))
+(assert_return (invoke "atomic-load8_u" (i32.const 1)) (i32.const 0xf2))
+(assert_return (invoke "atomic-load16_u" (i32.const 2)) (i32.const 0xf4f3))
+(assert_return (invoke "both-agree" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "both-agree" (i32.const 65532)) (i32.const 1))
+
+(assert_trap (invoke "atomic-load" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "atomic-load" (i32.const -4)) "out of bounds memory access")
+(assert_trap (invoke "atomic-load-offset" (i32.const 65532)) "out of bounds memory access")
+(assert_trap (invoke "atomic-load8_u" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "atomic-load16_u" (i32.const 65535)) "out of bounds memory access")
+
+(assert_trap (invoke "atomic-load" (i32.const 1)) "unaligned atomic access")
+(assert_trap (invoke "atomic-load" (i32.const 2)) "unaligned atomic access")
+(assert_trap (invoke "atomic-load-offset" (i32.const 2)) "unaligned atomic access")
+(assert_trap (invoke "atomic-load16_u" (i32.const 1)) "unaligned atomic access")
@@ -0,0 +1,44 @@
+;; i32.atomic.rmw.add and its narrow variants return the old value and wrap
+;; around within their width. Two instances share the counter's memory.
+
+(module $counter
+  (memory (export "memory") 1 1 shared)
+
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.atomic.rmw.add (local.get 0) (local.get 1)))
+  (func (export "add8_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw8.add_u (local.get 0) (local.get 1)))
+  (func (export "add16_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw16.add_u offset=2 (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result i32)
+    (i32.load (local.get 0)))
+)
+(register "counter" $counter)
+
+(module $other
+  (memory (import "counter" "memory") 1 1 shared)
+
+  (func (export "increment") (result i32)
+    (i32.atomic.rmw.add (i32.const 0) (i32.const 1)))
+)
+
+(assert_return (invoke $counter "add" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke $other "increment") (i32.const 1))
+(assert_return (invoke $counter "add" (i32.const 0) (i32.const 1)) (i32.const 2))
+(assert_return (invoke $other "increment") (i32.const 3))
+(assert_return (invoke $counter "load" (i32.const 0)) (i32.const 4))
+
+(assert_return (invoke $counter "add" (i32.const 0) (i32.const -5)) (i32.const 4))
+(assert_return (invoke $counter "load" (i32.const 0)) (i32.const -1))
+
+;; The narrow variants only touch their own bytes.
+(assert_return (invoke $counter "add" (i32.const 4) (i32.const 0x000000ff)) (i32.const 0))
+(assert_return (invoke $counter "add8_u" (i32.const 4) (i32.const 0x102)) (i32.const 0xff))
+(assert_return (invoke $counter "load" (i32.const 4)) (i32.const 0x00000001))
+(assert_return (invoke $counter "add16_u" (i32.const 4) (i32.const 0x1ffff)) (i32.const 0))
+(assert_return (invoke $counter "add16_u" (i32.const 4) (i32.const 2)) (i32.const 0xffff))
+(assert_return (invoke $counter "load" (i32.const 4)) (i32.const 0x00010001))
+
+(assert_trap (invoke $counter "add" (i32.const 65536) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke $counter "add8_u" (i32.const 65536) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke $counter "add16_u" (i32.const 65534) (i32.const 1)) "out of bounds memory access")
@@ -0,0 +1,50 @@
+;; i32.atomic.rmw.and, .or and .xor with their narrow variants: the returned
+;; old value and what is left in memory.
+
+(module
+  (memory 1 1 shared)
+  (data (i32.const 0) "\f0\f0\f0\f0\f0\f0\f0\f0\f0\f0\f0\f0")
+
+  (func (export "and") (param i32 i32) (result i32)
+    (i32.atomic.rmw.and (local.get 0) (local.get 1)))
+  (func (export "and8_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw8.and_u (local.get 0) (local.get 1)))
+  (func (export "and16_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw16.and_u (local.get 0) (local.get 1)))
+  (func (export "or") (param i32 i32) (result i32)
+    (i32.atomic.rmw.or (local.get 0) (local.get 1)))
+  (func (export "or8_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw8.or_u (local.get 0) (local.get 1)))
+  (func (export "or16_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw16.or_u (local.get 0) (local.get 1)))
+  (func (export "xor") (param i32 i32) (result i32)
+    (i32.atomic.rmw.xor (local.get 0) (local.get 1)))
+  (func (export "xor8_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw8.xor_u (local.get 0) (local.get 1)))
+  (func (export "xor16_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw16.xor_u (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result i32)
+    (i32.load (local.get 0)))
+)
+
+(assert_return (invoke "and" (i32.const 0) (i32.const 0x0ff00ff0)) (i32.const 0xf0f0f0f0))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x00f000f0))
+(assert_return (invoke "and8_u" (i32.const 1) (i32.const 0xffffff3c)) (i32.const 0x00))
+(assert_return (invoke "and16_u" (i32.const 2) (i32.const 0xffff00ff)) (i32.const 0x00f0))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x00f000f0))
+
+(assert_return (invoke "or" (i32.const 4) (i32.const 0x01020304)) (i32.const 0xf0f0f0f0))
+(assert_return (invoke "load" (i32.const 4)) (i32.const 0xf1f2f3f4))
+(assert_return (invoke "or8_u" (i32.const 7) (i32.const 0xffffff0f)) (i32.const 0xf1))
+(assert_return (invoke "or16_u" (i32.const 4) (i32.const 0x00010000)) (i32.const 0xf3f4))
+(assert_return (invoke "load" (i32.const 4)) (i32.const 0xfff2f3f4))
+
+(assert_return (invoke "xor" (i32.const 8) (i32.const -1)) (i32.const 0xf0f0f0f0))
+(assert_return (invoke "load" (i32.const 8)) (i32.const 0x0f0f0f0f))
+(assert_return (invoke "xor8_u" (i32.const 8) (i32.const 0x10f)) (i32.const 0x0f))
+(assert_return (invoke "xor16_u" (i32.const 10) (i32.const 0xffff)) (i32.const 0x0f0f))
+(assert_return (invoke "load" (i32.const 8)) (i32.const 0xf0f00f00))
+
+(assert_trap (invoke "and" (i32.const 65536) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "or8_u" (i32.const 65536) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "xor16_u" (i32.const 65535) (i32.const 0)) "out of bounds memory access")
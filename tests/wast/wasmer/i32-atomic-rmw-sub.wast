@@ -0,0 +1,38 @@
+;; i32.atomic.rmw.sub and its narrow variants return the old value, zero
+;; extended, and wrap around within their width.
+
+(module
+  (memory 1 1 shared)
+  (data (i32.const 0) "\0a\00\00\00")
+
+  (func (export "sub") (param i32 i32) (result i32)
+    (i32.atomic.rmw.sub (local.get 0) (local.get 1)))
+  (func (export "sub8_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw8.sub_u (local.get 0) (local.get 1)))
+  (func (export "sub16_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw16.sub_u offset=2 (local.get 0) (local.get 1)))
+  (func (export "decrement") (result i32)
+    (i32.atomic.rmw.sub (i32.const 0) (i32.const 1)))
+  (func (export "load") (param i32) (result i32)
+    (i32.load (local.get 0)))
+)
+
+(assert_return (invoke "decrement") (i32.const 10))
+(assert_return (invoke "decrement") (i32.const 9))
+(assert_return (invoke "sub" (i32.const 0) (i32.const 8)) (i32.const 8))
+(assert_return (invoke "decrement") (i32.const 0))
+(assert_return (invoke "load" (i32.const 0)) (i32.const -1))
+(assert_return (invoke "sub" (i32.const 0) (i32.const -2)) (i32.const -1))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 1))
+
+;; The narrow variants borrow within their own bytes only.
+(assert_return (invoke "sub8_u" (i32.const 4) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "sub8_u" (i32.const 4) (i32.const 0x1ff)) (i32.const 0xff))
+(assert_return (invoke "load" (i32.const 4)) (i32.const 0x00000000))
+(assert_return (invoke "sub16_u" (i32.const 4) (i32.const 2)) (i32.const 0))
+(assert_return (invoke "sub16_u" (i32.const 4) (i32.const 0xfffe)) (i32.const 0xfffe))
+(assert_return (invoke "load" (i32.const 4)) (i32.const 0))
+
+(assert_trap (invoke "sub" (i32.const 65536) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "sub8_u" (i32.const 65536) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "sub16_u" (i32.const 65534) (i32.const 1)) "out of bounds memory access")
@@ -0,0 +1,29 @@
+;; i32.atomic.rmw.xchg and its narrow variants return the old value and leave
+;; the new one, truncated to their width, in memory.
+
+(module
+  (memory 1 1 shared)
+  (data (i32.const 0) "\11\22\33\44")
+
+  (func (export "xchg") (param i32 i32) (result i32)
+    (i32.atomic.rmw.xchg (local.get 0) (local.get 1)))
+  (func (export "xchg8_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw8.xchg_u (local.get 0) (local.get 1)))
+  (func (export "xchg16_u") (param i32 i32) (result i32)
+    (i32.atomic.rmw16.xchg_u (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result i32)
+    (i32.load (local.get 0)))
+)
+
+(assert_return (invoke "xchg" (i32.const 0) (i32.const -1)) (i32.const 0x44332211))
+(assert_return (invoke "xchg" (i32.const 0) (i32.const 0x44332211)) (i32.const -1))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x44332211))
+
+(assert_return (invoke "xchg8_u" (i32.const 3) (i32.const 0x1234)) (i32.const 0x44))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x34332211))
+(assert_return (invoke "xchg16_u" (i32.const 0) (i32.const 0xabcdef01)) (i32.const 0x2211))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x3433ef01))
+
+(assert_trap (invoke "xchg" (i32.const 65536) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "xchg8_u" (i32.const 65536) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "xchg16_u" (i32.const 65535) (i32.const 0)) "out of bounds memory access")
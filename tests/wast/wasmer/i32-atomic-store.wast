@@ -0,0 +1,37 @@
+;; Atomic i32 stores write the same bytes as plain stores, and the narrow
+;; widths only keep the low bits of the value.
+
+(module
+  (memory 1 1 shared)
+
+  (func (export "atomic-store") (param i32 i32)
+    (i32.atomic.store (local.get 0) (local.get 1)))
+  (func (export "atomic-store-offset") (param i32 i32)
+    (i32.atomic.store offset=4 (local.get 0) (local.get 1)))
+  (func (export "atomic-store8") (param i32 i32)
+    (i32.atomic.store8 (local.get 0) (local.get 1)))
+  (func (export "atomic-store16") (param i32 i32)
+    (i32.atomic.store16 (local.get 0) (local.get 1)))
+  (func (export "store-zero") (param i32)
+    (i32.atomic.store (local.get 0) (i32.const 0)))
+  (func (export "load") (param i32) (result i32)
+    (i32.load (local.get 0)))
+)
+
+(invoke "atomic-store" (i32.const 0) (i32.const 0x12345678))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x12345678))
+(invoke "atomic-store-offset" (i32.const 0) (i32.const -1))
+(assert_return (invoke "load" (i32.const 4)) (i32.const -1))
+
+;; Narrow stores leave the neighbouring bytes alone.
+(invoke "atomic-store8" (i32.const 1) (i32.const 0xabcdef))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x1234ef78))
+(invoke "atomic-store16" (i32.const 6) (i32.const 0x7fff0000))
+(assert_return (invoke "load" (i32.const 4)) (i32.const 0x0000ffff))
+(invoke "store-zero" (i32.const 0))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0))
+
+(assert_trap (invoke "atomic-store" (i32.const 65536) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "atomic-store-offset" (i32.const 65532) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "atomic-store8" (i32.const 65536) (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "atomic-store16" (i32.const 65535) (i32.const 1)) "out of bounds memory access")
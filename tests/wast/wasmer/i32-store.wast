@@ -0,0 +1,42 @@
+;; i32 stores of words, halfwords and bytes, with the value coming from a
+;; local or an immediate, reloaded right below the end of memory. A store
+;; running past the end traps and leaves memory untouched.
+
+(module
+  (memory 1)
+  (func (export "store") (param i32 i32)
+    (i32.store (local.get 0) (local.get 1)))
+  (func (export "store8") (param i32 i32)
+    (i32.store8 (local.get 0) (local.get 1)))
+  (func (export "store16") (param i32 i32)
+    (i32.store16 (local.get 0) (local.get 1)))
+  (func (export "store_imm") (param i32)
+    (i32.store offset=2 (local.get 0) (i32.const 0x12345678)))
+  (func (export "store8_zero") (param i32)
+    (i32.store8 (local.get 0) (i32.const 0)))
+  (func (export "load") (param i32) (result i32)
+    (i32.load (local.get 0)))
+  (func (export "load8") (param i32) (result i32)
+    (i32.load8_u (local.get 0)))
+  (func (export "load16") (param i32) (result i32)
+    (i32.load16_u (local.get 0)))
+)
+
+(assert_return (invoke "store" (i32.const 0xfffc) (i32.const 0xdeadbeef)))
+(assert_return (invoke "load" (i32.const 0xfffc)) (i32.const 0xdeadbeef))
+(assert_return (invoke "store16" (i32.const 0xfffe) (i32.const 0x12345)))
+(assert_return (invoke "load16" (i32.const 0xfffe)) (i32.const 0x2345))
+(assert_return (invoke "load" (i32.const 0xfffc)) (i32.const 0x2345beef))
+(assert_return (invoke "store8" (i32.const 0xffff) (i32.const 0x1ff)))
+(assert_return (invoke "load8" (i32.const 0xffff)) (i32.const 0xff))
+(assert_return (invoke "store8_zero" (i32.const 0xfffc)))
+(assert_return (invoke "load" (i32.const 0xfffc)) (i32.const 0xff45be00))
+(assert_return (invoke "store_imm" (i32.const 0xfffa)))
+(assert_return (invoke "load" (i32.const 0xfffc)) (i32.const 0x12345678))
+
+(assert_trap (invoke "store" (i32.const 0xfffd) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "store16" (i32.const 0xffff) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "store8" (i32.const 0x10000) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "store_imm" (i32.const 0xfffb)) "out of bounds memory access")
+(assert_trap (invoke "store" (i32.const -1) (i32.const 0)) "out of bounds memory access")
+(assert_return (invoke "load" (i32.const 0xfffc)) (i32.const 0x12345678))
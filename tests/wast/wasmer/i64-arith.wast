@@ -0,0 +1,180 @@
+;; i64 division and remainder, including the traps and the boundaries at the
+;; ends of the i64 range, bitwise operators with 64-bit wide immediates, and
+;; comparisons of values that only differ in their high half, and shifts and
+;; rotations with counts taken modulo 64.
+
+(module
+  (func (export "div_s") (param i64 i64) (result i64)
+    (i64.div_s (local.get 0) (local.get 1)))
+  (func (export "div_u") (param i64 i64) (result i64)
+    (i64.div_u (local.get 0) (local.get 1)))
+  (func (export "rem_s") (param i64 i64) (result i64)
+    (i64.rem_s (local.get 0) (local.get 1)))
+  (func (export "rem_u") (param i64 i64) (result i64)
+    (i64.rem_u (local.get 0) (local.get 1)))
+  (func (export "and_high") (param i64) (result i64)
+    (i64.and (local.get 0) (i64.const 0xffffffff00000000)))
+  (func (export "or_high") (param i64) (result i64)
+    (i64.or (local.get 0) (i64.const 0xffffffff00000000)))
+  (func (export "xor_sign") (param i64) (result i64)
+    (i64.xor (local.get 0) (i64.const 0x8000000000000000)))
+  (func (export "and_1234") (param i64) (result i64)
+    (i64.and (local.get 0) (i64.const 0x123400000000)))
+  (func (export "xor") (param i64 i64) (result i64)
+    (i64.xor (local.get 0) (local.get 1)))
+  (func (export "eq") (param i64 i64) (result i32)
+    (i64.eq (local.get 0) (local.get 1)))
+  (func (export "ne") (param i64 i64) (result i32)
+    (i64.ne (local.get 0) (local.get 1)))
+  (func (export "lt_s") (param i64 i64) (result i32)
+    (i64.lt_s (local.get 0) (local.get 1)))
+  (func (export "lt_u") (param i64 i64) (result i32)
+    (i64.lt_u (local.get 0) (local.get 1)))
+  (func (export "gt_s") (param i64 i64) (result i32)
+    (i64.gt_s (local.get 0) (local.get 1)))
+  (func (export "gt_u") (param i64 i64) (result i32)
+    (i64.gt_u (local.get 0) (local.get 1)))
+  (func (export "le_s") (param i64 i64) (result i32)
+    (i64.le_s (local.get 0) (local.get 1)))
+  (func (export "le_u") (param i64 i64) (result i32)
+    (i64.le_u (local.get 0) (local.get 1)))
+  (func (export "ge_s") (param i64 i64) (result i32)
+    (i64.ge_s (local.get 0) (local.get 1)))
+  (func (export "ge_u") (param i64 i64) (result i32)
+    (i64.ge_u (local.get 0) (local.get 1)))
+  (func (export "shl") (param i64 i64) (result i64)
+    (i64.shl (local.get 0) (local.get 1)))
+  (func (export "shl_63") (param i64) (result i64)
+    (i64.shl (local.get 0) (i64.const 63)))
+  (func (export "shl_65") (param i64) (result i64)
+    (i64.shl (local.get 0) (i64.const 65)))
+  (func (export "shr_u") (param i64 i64) (result i64)
+    (i64.shr_u (local.get 0) (local.get 1)))
+  (func (export "shr_s") (param i64 i64) (result i64)
+    (i64.shr_s (local.get 0) (local.get 1)))
+  (func (export "shr_s_4") (param i64) (result i64)
+    (i64.shr_s (local.get 0) (i64.const 4)))
+  (func (export "rotl") (param i64 i64) (result i64)
+    (i64.rotl (local.get 0) (local.get 1)))
+  (func (export "rotr") (param i64 i64) (result i64)
+    (i64.rotr (local.get 0) (local.get 1)))
+  (func (export "rotl_0") (param i64) (result i64)
+    (i64.rotl (local.get 0) (i64.const 0)))
+  (func (export "rotr_0") (param i64) (result i64)
+    (i64.rotr (local.get 0) (i64.const 0)))
+  (func (export "rotl_1") (param i64) (result i64)
+    (i64.rotl (local.get 0) (i64.const 1)))
+  (func (export "rotr_1") (param i64) (result i64)
+    (i64.rotr (local.get 0) (i64.const 1)))
+  (func (export "rotl_63") (param i64) (result i64)
+    (i64.rotl (local.get 0) (i64.const 63)))
+  (func (export "rotr_63") (param i64) (result i64)
+    (i64.rotr (local.get 0) (i64.const 63)))
+  (func (export "rotl_64") (param i64) (result i64)
+    (i64.rotl (local.get 0) (i64.const 64)))
+  (func (export "rotr_64") (param i64) (result i64)
+    (i64.rotr (local.get 0) (i64.const 64)))
+  (func (export "clz") (param i64) (result i64)
+    (i64.clz (local.get 0)))
+  (func (export "ctz") (param i64) (result i64)
+    (i64.ctz (local.get 0)))
+  (func (export "popcnt") (param i64) (result i64)
+    (i64.popcnt (local.get 0)))
+  (func (export "lt_s_imm") (param i64) (result i32)
+    (i64.lt_s (local.get 0) (i64.const 0x7fffffffffffffff)))
+)
+
+(assert_return (invoke "div_s" (i64.const -7) (i64.const 2)) (i64.const -3))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0xc000000000000000))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000001) (i64.const -1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "div_s" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "div_s" (i64.const 0x80000000) (i64.const -1)) (i64.const -0x80000000))
+(assert_trap (invoke "div_s" (i64.const 0x8000000000000000) (i64.const -1)) "integer overflow")
+(assert_trap (invoke "div_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+
+(assert_return (invoke "div_u" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -1) (i64.const 2)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "div_u" (i64.const 0x100000000) (i64.const 0x10)) (i64.const 0x10000000))
+(assert_trap (invoke "div_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const 3)) (i64.const -2))
+(assert_return (invoke "rem_s" (i64.const -7) (i64.const 2)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 7) (i64.const -2)) (i64.const 1))
+(assert_trap (invoke "rem_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+
+(assert_return (invoke "rem_u" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rem_u" (i64.const -1) (i64.const 0x100000000)) (i64.const 0xffffffff))
+(assert_trap (invoke "rem_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+
+(assert_return (invoke "and_high" (i64.const 0x123456789abcdef0)) (i64.const 0x1234567800000000))
+(assert_return (invoke "or_high" (i64.const 0x9abcdef0)) (i64.const 0xffffffff9abcdef0))
+(assert_return (invoke "xor_sign" (i64.const 1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "and_1234" (i64.const -1)) (i64.const 0x123400000000))
+(assert_return (invoke "xor" (i64.const 0xffffffff00000000) (i64.const -1)) (i64.const 0xffffffff))
+
+(assert_return (invoke "eq" (i64.const 0x100000000) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const 0x100000000) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const -1) (i64.const 0xffffffff)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0xffffffff) (i64.const 0x100000000)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const -1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const -1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0x100000000) (i64.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000001)) (i32.const 1))
+(assert_return (invoke "lt_s_imm" (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "lt_s_imm" (i64.const 0x7fffffffffffffff)) (i32.const 0))
+
+(assert_return (invoke "shl" (i64.const 1) (i64.const 63)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 32)) (i64.const 0x100000000))
+(assert_return (invoke "shl_63" (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl_65" (i64.const 3)) (i64.const 6))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 32)) (i64.const 0xffffffff))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -16) (i64.const 2)) (i64.const -4))
+(assert_return (invoke "shr_s" (i64.const -16) (i64.const 66)) (i64.const -4))
+(assert_return (invoke "shr_s_4" (i64.const -0x100000000)) (i64.const -0x10000000))
+(assert_return (invoke "shr_s_4" (i64.const 0x7fffffffffffffff)) (i64.const 0x07ffffffffffffff))
+
+(assert_return (invoke "rotl" (i64.const 0x8000000000000003) (i64.const 0)) (i64.const 0x8000000000000003))
+(assert_return (invoke "rotl_0" (i64.const 0x8000000000000003)) (i64.const 0x8000000000000003))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000003) (i64.const 0)) (i64.const 0x8000000000000003))
+(assert_return (invoke "rotr_0" (i64.const 0x8000000000000003)) (i64.const 0x8000000000000003))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000003) (i64.const 1)) (i64.const 0x7))
+(assert_return (invoke "rotl_1" (i64.const 0x8000000000000003)) (i64.const 0x7))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000003) (i64.const 1)) (i64.const 0xc000000000000001))
+(assert_return (invoke "rotr_1" (i64.const 0x8000000000000003)) (i64.const 0xc000000000000001))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000003) (i64.const 63)) (i64.const 0xc000000000000001))
+(assert_return (invoke "rotl_63" (i64.const 0x8000000000000003)) (i64.const 0xc000000000000001))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000003) (i64.const 63)) (i64.const 0x7))
+(assert_return (invoke "rotr_63" (i64.const 0x8000000000000003)) (i64.const 0x7))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000003) (i64.const 64)) (i64.const 0x8000000000000003))
+(assert_return (invoke "rotl_64" (i64.const 0x8000000000000003)) (i64.const 0x8000000000000003))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000003) (i64.const 64)) (i64.const 0x8000000000000003))
+(assert_return (invoke "rotr_64" (i64.const 0x8000000000000003)) (i64.const 0x8000000000000003))
+
+(assert_return (invoke "clz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "ctz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "clz" (i64.const 0x1)) (i64.const 63))
+(assert_return (invoke "ctz" (i64.const 0x1)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0x80000000)) (i64.const 32))
+(assert_return (invoke "ctz" (i64.const 0x80000000)) (i64.const 31))
+(assert_return (invoke "clz" (i64.const 0x100000000)) (i64.const 31))
+(assert_return (invoke "ctz" (i64.const 0x100000000)) (i64.const 32))
+(assert_return (invoke "clz" (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 0x8000000000000000)) (i64.const 63))
+
+(assert_return (invoke "popcnt" (i64.const 0x0)) (i64.const 0))
+(assert_return (invoke "popcnt" (i64.const 0xffffffffffffffff)) (i64.const 64))
+(assert_return (invoke "popcnt" (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const 0x123456789abcdef0)) (i64.const 32))
+(assert_return (invoke "popcnt" (i64.const 0xffffffff00000000)) (i64.const 32))
@@ -0,0 +1,49 @@
+;; i64 atomic loads and stores at every width, round tripping values through
+;; linear memory. Narrow loads zero extend into the full 64 bits.
+
+(module
+  (memory 1 1 shared)
+
+  (func (export "store") (param i32 i64)
+    (i64.atomic.store (local.get 0) (local.get 1)))
+  (func (export "store8") (param i32 i64)
+    (i64.atomic.store8 (local.get 0) (local.get 1)))
+  (func (export "store16") (param i32 i64)
+    (i64.atomic.store16 (local.get 0) (local.get 1)))
+  (func (export "store32") (param i32 i64)
+    (i64.atomic.store32 offset=4 (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result i64)
+    (i64.atomic.load (local.get 0)))
+  (func (export "load8_u") (param i32) (result i64)
+    (i64.atomic.load8_u (local.get 0)))
+  (func (export "load16_u") (param i32) (result i64)
+    (i64.atomic.load16_u (local.get 0)))
+  (func (export "load32_u") (param i32) (result i64)
+    (i64.atomic.load32_u offset=4 (local.get 0)))
+  ;; Keeps the narrow result live on the stack across a call.
+  (func $id (param i64) (result i64) (local.get 0))
+  (func (export "load32_u-spilled") (param i32) (result i64)
+    (i64.add (i64.atomic.load32_u (local.get 0)) (call $id (i64.const 0))))
+)
+
+(invoke "store" (i32.const 0) (i64.const 0x8899aabbccddeeff))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x8899aabbccddeeff))
+(assert_return (invoke "load8_u" (i32.const 7)) (i64.const 0x88))
+(assert_return (invoke "load16_u" (i32.const 0)) (i64.const 0xeeff))
+(assert_return (invoke "load32_u" (i32.const 0)) (i64.const 0x8899aabb))
+(assert_return (invoke "load32_u-spilled" (i32.const 0)) (i64.const 0xccddeeff))
+
+(invoke "store8" (i32.const 0) (i64.const -1))
+(invoke "store16" (i32.const 2) (i64.const 0x123456789abc))
+(invoke "store32" (i32.const 0) (i64.const 0x7fffffff00000001))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x000000019abceeff))
+
+(invoke "store" (i32.const 65528) (i64.const -2))
+(assert_return (invoke "load" (i32.const 65528)) (i64.const -2))
+
+(assert_trap (invoke "store" (i32.const 65536) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "store32" (i32.const 65532) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "load" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "load8_u" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "load16_u" (i32.const 65535)) "out of bounds memory access")
+(assert_trap (invoke "load32_u" (i32.const 65532)) "out of bounds memory access")
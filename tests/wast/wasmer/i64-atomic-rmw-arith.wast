@@ -0,0 +1,55 @@
+;; i64.atomic.rmw.add and .sub at every width. The narrow variants wrap
+;; within their width and return the old value zero extended to 64 bits,
+;; which is easiest to get wrong for the 32 bit one.
+
+(module
+  (memory 1 1 shared)
+
+  (func (export "add") (param i32 i64) (result i64)
+    (i64.atomic.rmw.add (local.get 0) (local.get 1)))
+  (func (export "add8_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw8.add_u (local.get 0) (local.get 1)))
+  (func (export "add16_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw16.add_u (local.get 0) (local.get 1)))
+  (func (export "add32_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw32.add_u (local.get 0) (local.get 1)))
+  (func (export "sub") (param i32 i64) (result i64)
+    (i64.atomic.rmw.sub (local.get 0) (local.get 1)))
+  (func (export "sub8_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw8.sub_u (local.get 0) (local.get 1)))
+  (func (export "sub16_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw16.sub_u (local.get 0) (local.get 1)))
+  (func (export "sub32_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw32.sub_u (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result i64)
+    (i64.load (local.get 0)))
+)
+
+(assert_return (invoke "add" (i32.const 0) (i64.const 0xffffffff)) (i64.const 0))
+(assert_return (invoke "add" (i32.const 0) (i64.const 1)) (i64.const 0xffffffff))
+(assert_return (invoke "sub" (i32.const 0) (i64.const 0x100000001)) (i64.const 0x100000000))
+(assert_return (invoke "load" (i32.const 0)) (i64.const -1))
+(assert_return (invoke "sub" (i32.const 0) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0))
+
+;; 32 bit: the carry out of the low word must not reach the high word.
+(assert_return (invoke "add32_u" (i32.const 8) (i64.const 0xfffffffe)) (i64.const 0))
+(assert_return (invoke "add32_u" (i32.const 8) (i64.const 0x1234567800000003)) (i64.const 0xfffffffe))
+(assert_return (invoke "load" (i32.const 8)) (i64.const 1))
+(assert_return (invoke "sub32_u" (i32.const 8) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "load" (i32.const 8)) (i64.const 0xffffffff))
+(assert_return (invoke "sub32_u" (i32.const 12) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "load" (i32.const 8)) (i64.const -1))
+(assert_return (invoke "add32_u" (i32.const 12) (i64.const 1)) (i64.const 0xffffffff))
+(assert_return (invoke "load" (i32.const 8)) (i64.const 0xffffffff))
+
+(assert_return (invoke "add8_u" (i32.const 16) (i64.const 0x1ff)) (i64.const 0))
+(assert_return (invoke "add8_u" (i32.const 16) (i64.const 2)) (i64.const 0xff))
+(assert_return (invoke "sub16_u" (i32.const 18) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "add16_u" (i32.const 18) (i64.const 2)) (i64.const 0xffff))
+(assert_return (invoke "sub8_u" (i32.const 17) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "load" (i32.const 16)) (i64.const 0x0000000000010001))
+
+(assert_trap (invoke "add" (i32.const 65536) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "add32_u" (i32.const 65536) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "sub16_u" (i32.const 65535) (i64.const 0)) "out of bounds memory access")
@@ -0,0 +1,61 @@
+;; i64.atomic.rmw.and, .or, .xor and .xchg: the returned old value, zero
+;; extended for the narrow variants, and the memory left behind.
+
+(module
+  (memory 1 1 shared)
+  (data (i32.const 0) "\f0\f0\f0\f0\f0\f0\f0\f0")
+
+  (func (export "and") (param i32 i64) (result i64)
+    (i64.atomic.rmw.and (local.get 0) (local.get 1)))
+  (func (export "and8_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw8.and_u (local.get 0) (local.get 1)))
+  (func (export "or") (param i32 i64) (result i64)
+    (i64.atomic.rmw.or (local.get 0) (local.get 1)))
+  (func (export "or8_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw8.or_u (local.get 0) (local.get 1)))
+  (func (export "or16_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw16.or_u (local.get 0) (local.get 1)))
+  (func (export "xor") (param i32 i64) (result i64)
+    (i64.atomic.rmw.xor (local.get 0) (local.get 1)))
+  (func (export "xor8_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw8.xor_u (local.get 0) (local.get 1)))
+  (func (export "xor32_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw32.xor_u (local.get 0) (local.get 1)))
+  (func (export "xchg") (param i32 i64) (result i64)
+    (i64.atomic.rmw.xchg (local.get 0) (local.get 1)))
+  (func (export "xchg8_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw8.xchg_u (local.get 0) (local.get 1)))
+  (func (export "xchg16_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw16.xchg_u (local.get 0) (local.get 1)))
+  (func (export "xchg32_u") (param i32 i64) (result i64)
+    (i64.atomic.rmw32.xchg_u (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result i64)
+    (i64.load (local.get 0)))
+)
+
+(assert_return (invoke "and" (i32.const 0) (i64.const 0x0ff00ff00ff00ff0)) (i64.const 0xf0f0f0f0f0f0f0f0))
+(assert_return (invoke "and8_u" (i32.const 7) (i64.const 0xffffffffffffff0f)) (i64.const 0x00))
+(assert_return (invoke "and8_u" (i32.const 2) (i64.const 0x30)) (i64.const 0xf0))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x00f000f0003000f0))
+
+(assert_return (invoke "or" (i32.const 8) (i64.const 0x8000000000000001)) (i64.const 0))
+(assert_return (invoke "or8_u" (i32.const 9) (i64.const 0x1ff)) (i64.const 0))
+(assert_return (invoke "or16_u" (i32.const 12) (i64.const 0xa5a5)) (i64.const 0))
+(assert_return (invoke "load" (i32.const 8)) (i64.const 0x8000a5a50000ff01))
+
+(assert_return (invoke "xor" (i32.const 8) (i64.const -1)) (i64.const 0x8000a5a50000ff01))
+(assert_return (invoke "xor8_u" (i32.const 8) (i64.const 0xfe)) (i64.const 0xfe))
+(assert_return (invoke "xor32_u" (i32.const 12) (i64.const 0xffffffff7fff5a5a)) (i64.const 0x7fff5a5a))
+(assert_return (invoke "load" (i32.const 8)) (i64.const 0x00000000ffff0000))
+
+(assert_return (invoke "xchg" (i32.const 16) (i64.const 0x0102030405060708)) (i64.const 0))
+(assert_return (invoke "xchg8_u" (i32.const 23) (i64.const 0xffff)) (i64.const 0x01))
+(assert_return (invoke "xchg16_u" (i32.const 16) (i64.const 0x1122)) (i64.const 0x0708))
+(assert_return (invoke "xchg32_u" (i32.const 20) (i64.const 0xaaaaaaaabbbbbbbb)) (i64.const 0xff020304))
+(assert_return (invoke "load" (i32.const 16)) (i64.const 0xbbbbbbbb05061122))
+(assert_return (invoke "xchg" (i32.const 16) (i64.const -1)) (i64.const 0xbbbbbbbb05061122))
+(assert_return (invoke "load" (i32.const 16)) (i64.const -1))
+
+(assert_trap (invoke "and" (i32.const 65536) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "xor32_u" (i32.const 65536) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "xchg8_u" (i32.const 65536) (i64.const 0)) "out of bounds memory access")
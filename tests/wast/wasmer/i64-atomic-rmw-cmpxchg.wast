@@ -0,0 +1,44 @@
+;; i64.atomic.rmw.cmpxchg at every width. The narrow variants only compare
+;; the low bits of the expected value, and always return the old value.
+
+(module
+  (memory 1 1 shared)
+
+  (func (export "cmpxchg") (param i32 i64 i64) (result i64)
+    (i64.atomic.rmw.cmpxchg (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "cmpxchg8_u") (param i32 i64 i64) (result i64)
+    (i64.atomic.rmw8.cmpxchg_u (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "cmpxchg16_u") (param i32 i64 i64) (result i64)
+    (i64.atomic.rmw16.cmpxchg_u (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "cmpxchg32_u") (param i32 i64 i64) (result i64)
+    (i64.atomic.rmw32.cmpxchg_u (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "load") (param i32) (result i64)
+    (i64.load (local.get 0)))
+)
+
+;; 64 bit: the high word takes part in the comparison.
+(assert_return (invoke "cmpxchg" (i32.const 0) (i64.const 0x100000000) (i64.const 5)) (i64.const 0))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0))
+(assert_return (invoke "cmpxchg" (i32.const 0) (i64.const 0) (i64.const 0x8000000000000005)) (i64.const 0))
+(assert_return (invoke "cmpxchg" (i32.const 0) (i64.const 5) (i64.const 6)) (i64.const 0x8000000000000005))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x8000000000000005))
+
+;; 32 bit
+(assert_return (invoke "cmpxchg32_u" (i32.const 8) (i64.const 0xffffffff00000000) (i64.const 0xaaaaaaaa87654321)) (i64.const 0))
+(assert_return (invoke "load" (i32.const 8)) (i64.const 0x87654321))
+(assert_return (invoke "cmpxchg32_u" (i32.const 8) (i64.const 0x87654320) (i64.const 1)) (i64.const 0x87654321))
+(assert_return (invoke "load" (i32.const 8)) (i64.const 0x87654321))
+
+;; 16 bit
+(assert_return (invoke "cmpxchg16_u" (i32.const 16) (i64.const 0x10000) (i64.const 0xbeef)) (i64.const 0))
+(assert_return (invoke "cmpxchg16_u" (i32.const 16) (i64.const 0xbeee) (i64.const 0)) (i64.const 0xbeef))
+(assert_return (invoke "load" (i32.const 16)) (i64.const 0xbeef))
+
+;; 8 bit
+(assert_return (invoke "cmpxchg8_u" (i32.const 23) (i64.const -256) (i64.const 0x1ff)) (i64.const 0))
+(assert_return (invoke "cmpxchg8_u" (i32.const 23) (i64.const 0xfe) (i64.const 0)) (i64.const 0xff))
+(assert_return (invoke "load" (i32.const 16)) (i64.const 0xff0000000000beef))
+
+(assert_trap (invoke "cmpxchg" (i32.const 65536) (i64.const 0) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "cmpxchg32_u" (i32.const 65536) (i64.const 0) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "cmpxchg8_u" (i32.const 65536) (i64.const 0) (i64.const 0)) "out of bounds memory access")
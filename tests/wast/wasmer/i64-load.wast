@@ -0,0 +1,61 @@
+;; i64 loads of every width. The narrow unsigned loads leave the upper bits of
+;; the result clear, also when the value was already in a register, and the
+;; signed ones fill them with the sign bit.
+
+(module
+  (memory 1)
+  (data (i32.const 0) "\ff\fe\fd\fc\fb\fa\f9\f8")
+  (data (i32.const 65528) "\01\02\03\04\05\06\07\88")
+  (func (export "load") (param i32) (result i64)
+    (i64.load (local.get 0)))
+  (func (export "load8_u") (param i32) (result i64)
+    (i64.load8_u (local.get 0)))
+  (func (export "load16_u") (param i32) (result i64)
+    (i64.load16_u (local.get 0)))
+  (func (export "load32_u") (param i32) (result i64)
+    (i64.load32_u (local.get 0)))
+  (func (export "load8_s") (param i32) (result i64)
+    (i64.load8_s (local.get 0)))
+  (func (export "load16_s") (param i32) (result i64)
+    (i64.load16_s (local.get 0)))
+  (func (export "load32_s") (param i32) (result i64)
+    (i64.load32_s (local.get 0)))
+  ;; The first load fills the whole result register before it is reused.
+  (func (export "reload32_u") (param i32) (result i64)
+    (drop (i64.load (local.get 0)))
+    (i64.load32_u (local.get 0)))
+  (func (export "sum_u") (param i32) (result i64)
+    (i64.add
+      (i64.add (i64.load8_u (local.get 0)) (i64.load16_u offset=1 (local.get 0)))
+      (i64.load32_u offset=3 (local.get 0))))
+)
+
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0xf8f9fafbfcfdfeff))
+(assert_return (invoke "load8_u" (i32.const 0)) (i64.const 0xff))
+(assert_return (invoke "load16_u" (i32.const 0)) (i64.const 0xfeff))
+(assert_return (invoke "load32_u" (i32.const 0)) (i64.const 0xfcfdfeff))
+(assert_return (invoke "load32_u" (i32.const 4)) (i64.const 0xf8f9fafb))
+(assert_return (invoke "reload32_u" (i32.const 0)) (i64.const 0xfcfdfeff))
+(assert_return (invoke "sum_u" (i32.const 0)) (i64.const 0xf9fbfaf9))
+
+(assert_return (invoke "load8_s" (i32.const 0)) (i64.const -1))
+(assert_return (invoke "load16_s" (i32.const 0)) (i64.const -2))
+(assert_return (invoke "load32_s" (i32.const 0)) (i64.const 0xfffffffffcfdfeff))
+(assert_return (invoke "load8_s" (i32.const 65528)) (i64.const 1))
+(assert_return (invoke "load16_s" (i32.const 65528)) (i64.const 0x0201))
+(assert_return (invoke "load32_s" (i32.const 65528)) (i64.const 0x04030201))
+(assert_return (invoke "load8_s" (i32.const 65535)) (i64.const 0xffffffffffffff88))
+(assert_return (invoke "load16_s" (i32.const 65534)) (i64.const 0xffffffffffff8807))
+(assert_return (invoke "load32_s" (i32.const 65532)) (i64.const 0xffffffff88070605))
+
+(assert_return (invoke "load" (i32.const 65528)) (i64.const 0x8807060504030201))
+(assert_return (invoke "load8_u" (i32.const 65535)) (i64.const 0x88))
+(assert_return (invoke "load16_u" (i32.const 65534)) (i64.const 0x8807))
+(assert_return (invoke "load32_u" (i32.const 65532)) (i64.const 0x88070605))
+(assert_trap (invoke "load" (i32.const 65529)) "out of bounds memory access")
+(assert_trap (invoke "load8_u" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "load16_u" (i32.const 65535)) "out of bounds memory access")
+(assert_trap (invoke "load32_u" (i32.const 65533)) "out of bounds memory access")
+(assert_trap (invoke "load8_s" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "load16_s" (i32.const 65535)) "out of bounds memory access")
+(assert_trap (invoke "load32_s" (i32.const 65533)) "out of bounds memory access")
@@ -0,0 +1,55 @@
+;; i64 stores of every width truncate the value, which comes from a local or
+;; an immediate. The narrower reloads check that nothing past the stored width
+;; was written.
+
+(module
+  (memory 1)
+  (func (export "store") (param i32 i64)
+    (i64.store (local.get 0) (local.get 1)))
+  (func (export "store8") (param i32 i64)
+    (i64.store8 (local.get 0) (local.get 1)))
+  (func (export "store16") (param i32 i64)
+    (i64.store16 (local.get 0) (local.get 1)))
+  (func (export "store32") (param i32 i64)
+    (i64.store32 (local.get 0) (local.get 1)))
+  (func (export "store32_imm") (param i32)
+    (i64.store32 offset=4 (local.get 0) (i64.const 0x1122334455667788)))
+  (func (export "store16_zero") (param i32)
+    (i64.store16 (local.get 0) (i64.const 0)))
+  (func (export "load") (param i32) (result i64)
+    (i64.load (local.get 0)))
+  (func (export "load8") (param i32) (result i64)
+    (i64.load8_u (local.get 0)))
+  (func (export "load16") (param i32) (result i64)
+    (i64.load16_u (local.get 0)))
+  (func (export "load32") (param i32) (result i64)
+    (i64.load32_u (local.get 0)))
+)
+
+(assert_return (invoke "store" (i32.const 0) (i64.const 0x0102030405060708)))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x0102030405060708))
+(assert_return (invoke "load8" (i32.const 0)) (i64.const 0x08))
+(assert_return (invoke "load16" (i32.const 0)) (i64.const 0x0708))
+(assert_return (invoke "load32" (i32.const 0)) (i64.const 0x05060708))
+(assert_return (invoke "load32" (i32.const 4)) (i64.const 0x01020304))
+
+(assert_return (invoke "store8" (i32.const 0) (i64.const -1)))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x01020304050607ff))
+(assert_return (invoke "store16" (i32.const 2) (i64.const 0xaaaabbbbccccdddd)))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x01020304dddd07ff))
+(assert_return (invoke "store32" (i32.const 3) (i64.const 0xffffffff00000000)))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x0100000000dd07ff))
+(assert_return (invoke "store32_imm" (i32.const 0)))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x5566778800dd07ff))
+(assert_return (invoke "store16_zero" (i32.const 0)))
+(assert_return (invoke "load" (i32.const 0)) (i64.const 0x5566778800dd0000))
+
+(assert_return (invoke "store" (i32.const 65528) (i64.const -2)))
+(assert_return (invoke "load" (i32.const 65528)) (i64.const -2))
+(assert_return (invoke "store32" (i32.const 65532) (i64.const 0x12345678)))
+(assert_return (invoke "load" (i32.const 65528)) (i64.const 0x12345678fffffffe))
+(assert_trap (invoke "store" (i32.const 65529) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "store8" (i32.const 65536) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "store16" (i32.const 65535) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "store32" (i32.const 65533) (i64.const 0)) "out of bounds memory access")
+(assert_return (invoke "load" (i32.const 65528)) (i64.const 0x12345678fffffffe))
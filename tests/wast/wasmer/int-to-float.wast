@@ -0,0 +1,62 @@
+;; Integer to float conversions, signed and unsigned, compared bit for bit with
+;; Rust's `as` casts. Values that don't fit the significand are rounded to
+;; nearest, ties to even.
+
+(module
+  (func (export "f32.convert_i32_s") (param i32) (result i32)
+    (i32.reinterpret_f32 (f32.convert_i32_s (local.get 0))))
+  (func (export "f32.convert_i32_u") (param i32) (result i32)
+    (i32.reinterpret_f32 (f32.convert_i32_u (local.get 0))))
+  (func (export "f64.convert_i32_s") (param i32) (result i64)
+    (i64.reinterpret_f64 (f64.convert_i32_s (local.get 0))))
+  (func (export "f64.convert_i32_u") (param i32) (result i64)
+    (i64.reinterpret_f64 (f64.convert_i32_u (local.get 0))))
+  (func (export "f32.convert_i64_s") (param i64) (result i32)
+    (i32.reinterpret_f32 (f32.convert_i64_s (local.get 0))))
+  (func (export "f32.convert_i64_u") (param i64) (result i32)
+    (i32.reinterpret_f32 (f32.convert_i64_u (local.get 0))))
+  (func (export "f64.convert_i64_s") (param i64) (result i64)
+    (i64.reinterpret_f64 (f64.convert_i64_s (local.get 0))))
+  (func (export "f64.convert_i64_u") (param i64) (result i64)
+    (i64.reinterpret_f64 (f64.convert_i64_u (local.get 0))))
+  (func (export "f64.convert_imm") (result f64) (f64.convert_i32_u (i32.const -1)))
+)
+
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0xffffffff)) (i32.const 0x4f800000))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 0xffffffff)) (i64.const 0x41efffffffe00000))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0x80000000)) (i32.const 0x4f000000))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 0x80000000)) (i64.const 0x41e0000000000000))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0x1000001)) (i32.const 0x4b800000))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 0x1000001)) (i64.const 0x4170000010000000))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0x1)) (i32.const 0x3f800000))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 0x1)) (i64.const 0x3ff0000000000000))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -2147483648)) (i32.const 0xcf000000))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -2147483648)) (i64.const 0xc1e0000000000000))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -1)) (i32.const 0xbf800000))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -1)) (i64.const 0xbff0000000000000))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 16777217)) (i32.const 0x4b800000))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 16777217)) (i64.const 0x4170000010000000))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 2147483647)) (i32.const 0x4f000000))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 2147483647)) (i64.const 0x41dfffffffc00000))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -9223372036854775808)) (i32.const 0xdf000000))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -9223372036854775808)) (i64.const 0xc3e0000000000000))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -1)) (i32.const 0xbf800000))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -1)) (i64.const 0xbff0000000000000))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 9223372036854775807)) (i32.const 0x5f000000))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9223372036854775807)) (i64.const 0x43e0000000000000))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 9007199254740993)) (i32.const 0x5a000000))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9007199254740993)) (i64.const 0x4340000000000000))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -9007199254740993)) (i32.const 0xda000000))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -9007199254740993)) (i64.const 0xc340000000000000))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 0xffffffffffffffff)) (i32.const 0x5f800000))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0xffffffffffffffff)) (i64.const 0x43f0000000000000))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 0x8000000000000000)) (i32.const 0x5f000000))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0x8000000000000000)) (i64.const 0x43e0000000000000))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 0x20000000000001)) (i32.const 0x5a000000))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0x20000000000001)) (i64.const 0x4340000000000000))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 0x8000008000000001)) (i32.const 0x5f000001))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0x8000008000000001)) (i64.const 0x43e0000010000000))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0x1000003)) (i32.const 0x4b800002))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "f64.convert_imm") (f64.const 4294967295))
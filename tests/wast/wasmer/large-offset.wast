@@ -0,0 +1,23 @@
+;; Memory accesses whose static offset does not fit an ADD immediate.
+
+(module
+  (memory 64)
+  (func (export "store") (param i32 i32)
+    (i32.store offset=0x3ffff0 (local.get 0) (local.get 1)))
+  (func (export "load") (param i32) (result i32)
+    (i32.load offset=0x3ffff0 (local.get 0)))
+  (func (export "load_odd") (param i32) (result i32)
+    (i32.load8_u offset=0x123457 (local.get 0)))
+  (func (export "load_max") (param i32) (result i32)
+    (i32.load8_u offset=0xffffffff (local.get 0)))
+)
+
+(assert_return (invoke "store" (i32.const 0) (i32.const 0x11223344)))
+(assert_return (invoke "load" (i32.const 0)) (i32.const 0x11223344))
+(assert_return (invoke "store" (i32.const 12) (i32.const 0x55667788)))
+(assert_return (invoke "load" (i32.const 12)) (i32.const 0x55667788))
+(assert_return (invoke "load_odd" (i32.const 0x2dcb99)) (i32.const 0x44))
+(assert_trap (invoke "load" (i32.const 13)) "out of bounds memory access")
+(assert_trap (invoke "store" (i32.const 13) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "load_max" (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "load_max" (i32.const 1)) "out of bounds memory access")
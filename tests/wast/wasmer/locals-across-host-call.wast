@@ -0,0 +1,47 @@
+;; Locals and values on the stack keep their contents across a call to an
+;; imported host function made in the middle of a computation.
+
+(module
+  (import "spectest" "print_i32" (func $print (param i32)))
+
+  (func (export "locals-survive") (param $a i64) (param $x f64) (result f64)
+    (local $l1 i64) (local $l2 i64) (local $l3 i64) (local $l4 i64)
+    (local $l5 i64) (local $l6 i64) (local $l7 i64) (local $l8 i64)
+    (local.set $l1 (i64.const 2)) (local.set $l2 (i64.const 3))
+    (local.set $l3 (i64.const 4)) (local.set $l4 (i64.const 5))
+    (local.set $l5 (i64.const 6)) (local.set $l6 (i64.const 7))
+    (local.set $l7 (i64.const 8)) (local.set $l8 (i64.const 9))
+    (f64.mul (local.get $x) (f64.const 2))
+    (call $print (i32.const 42))
+    (i64.add (local.get $a) (local.get $l1))
+    (i64.add (local.get $l2))
+    (i64.add (local.get $l3))
+    (i64.add (local.get $l4))
+    (i64.add (local.get $l5))
+    (i64.add (local.get $l6))
+    (i64.add (local.get $l7))
+    (i64.add (local.get $l8))
+    (f64.convert_i64_s)
+    (f64.add)
+  )
+
+  (func (export "stack-survives") (param $a i32) (result i32)
+    (i32.mul (local.get $a) (i32.const 3))
+    (i32.add (local.get $a) (i32.const 1))
+    (call $print (local.get $a))
+    (i32.sub)
+  )
+
+  (func (export "more-stack-survives") (param $a i64) (result i64)
+    (i64.mul (local.get $a) (i64.const 5))
+    (i64.add (local.get $a) (i64.const 7))
+    (i64.sub (local.get $a) (i64.const 2))
+    (call $print (i32.const 0))
+    (i64.mul)
+    (i64.sub)
+  )
+)
+
+(assert_return (invoke "locals-survive" (i64.const 1) (f64.const 0.25)) (f64.const 45.5))
+(assert_return (invoke "stack-survives" (i32.const 10)) (i32.const 19))
+(assert_return (invoke "more-stack-survives" (i64.const 10)) (i64.const -86))
@@ -219,3 +219,40 @@
     return
   )
 )
+
+;; NaNs produced by division, min and a float store all carry the same canonical bits.
+(module
+  (memory 1)
+  (func (export "f32-nans-agree") (result i32)
+    (f32.store (i32.const 0) (f32.div (f32.const 0) (f32.const 0)))
+    (i32.and
+      (i32.and
+        (i32.eq (i32.reinterpret_f32 (f32.div (f32.const 0) (f32.const 0))) (i32.const 0x7fc00000))
+        (i32.eq (i32.reinterpret_f32 (f32.min (f32.const nan:0x200001) (f32.const 1))) (i32.const 0x7fc00000)))
+      (i32.eq (i32.load (i32.const 0)) (i32.const 0x7fc00000)))
+  )
+  (func (export "f64-nans-agree") (result i32)
+    (f64.store (i32.const 0) (f64.div (f64.const 0) (f64.const 0)))
+    (i32.and
+      (i32.and
+        (i64.eq (i64.reinterpret_f64 (f64.div (f64.const 0) (f64.const 0))) (i64.const 0x7ff8000000000000))
+        (i64.eq (i64.reinterpret_f64 (f64.min (f64.const nan:0x4000000000001) (f64.const 1))) (i64.const 0x7ff8000000000000)))
+      (i64.eq (i64.load (i32.const 0)) (i64.const 0x7ff8000000000000)))
+  )
+)
+
+(assert_return (invoke "f32-nans-agree") (i32.const 1))
+(assert_return (invoke "f64-nans-agree") (i32.const 1))
+
+;; NaNs returned as floats, rather than reinterpreted as integers, are canonicalized too.
+(module
+  (func (export "f32-return-nan") (param f32) (result f32)
+    (f32.add (local.get 0) (f32.const 0))
+  )
+  (func (export "f64-return-nan") (param f64) (result f64)
+    (f64.add (local.get 0) (f64.const 0))
+  )
+)
+
+(assert_return (invoke "f32-return-nan" (f32.const nan:0x200001)) (f32.const nan:canonical))
+(assert_return (invoke "f64-return-nan" (f64.const nan:0x4000000000001)) (f64.const nan:canonical))
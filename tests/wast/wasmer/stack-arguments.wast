@@ -0,0 +1,129 @@
+;; Calls passing some arguments on the stack, in odd and even numbers, while other
+;; values are live in registers and spilled to the stack across the call.
+
+(module
+  (func $weigh10
+    (param i64 i64 i64 i64 i64 i64 i64 i64 i64 i64) (result i64)
+    (i64.add (local.get 0) (i64.mul (local.get 1) (i64.const 2)))
+    (i64.add (i64.mul (local.get 2) (i64.const 3)))
+    (i64.add (i64.mul (local.get 3) (i64.const 4)))
+    (i64.add (i64.mul (local.get 4) (i64.const 5)))
+    (i64.add (i64.mul (local.get 5) (i64.const 6)))
+    (i64.add (i64.mul (local.get 6) (i64.const 7)))
+    (i64.add (i64.mul (local.get 7) (i64.const 8)))
+    (i64.add (i64.mul (local.get 8) (i64.const 9)))
+    (i64.add (i64.mul (local.get 9) (i64.const 10)))
+  )
+
+  (func $weigh9
+    (param i64 i64 i64 i64 i64 i64 i64 i64 i64) (result i64)
+    (i64.mul (local.get 0) (i64.const 3))
+    (i64.add (i64.mul (local.get 1) (i64.const 6)))
+    (i64.add (i64.mul (local.get 2) (i64.const 9)))
+    (i64.add (i64.mul (local.get 3) (i64.const 12)))
+    (i64.add (i64.mul (local.get 4) (i64.const 15)))
+    (i64.add (i64.mul (local.get 5) (i64.const 18)))
+    (i64.add (i64.mul (local.get 6) (i64.const 21)))
+    (i64.add (i64.mul (local.get 7) (i64.const 24)))
+    (i64.add (i64.mul (local.get 8) (i64.const 27)))
+  )
+
+  (func $mixed
+    (param i64 f64 i64 f64 i64 f64 i64 f64 i64 f64) (result i64)
+    (local.get 0)
+    (i64.add (i64.trunc_f64_s (f64.mul (local.get 1) (f64.const 2))))
+    (i64.add (i64.mul (local.get 2) (i64.const 3)))
+    (i64.add (i64.trunc_f64_s (f64.mul (local.get 3) (f64.const 4))))
+    (i64.add (i64.mul (local.get 4) (i64.const 5)))
+    (i64.add (i64.trunc_f64_s (f64.mul (local.get 5) (f64.const 6))))
+    (i64.add (i64.mul (local.get 6) (i64.const 7)))
+    (i64.add (i64.trunc_f64_s (f64.mul (local.get 7) (f64.const 8))))
+    (i64.add (i64.mul (local.get 8) (i64.const 9)))
+    (i64.add (i64.trunc_f64_s (f64.mul (local.get 9) (f64.const 10))))
+  )
+
+  (func $weigh8
+    (param i64 i64 i64 i64 i64 i64 i64 i64) (result i64)
+    (i64.add (local.get 0) (i64.mul (local.get 7) (i64.const 1000)))
+  )
+
+  (func (export "spilled-locals") (param $a i64) (result i64)
+    (local $l1 i64) (local $l2 i64) (local $l3 i64) (local $l4 i64)
+    (local $l5 i64) (local $l6 i64) (local $l7 i64) (local $l8 i64)
+    (local $l9 i64) (local $l10 i64) (local $l11 i64) (local $l12 i64)
+    (local.set $l1 (i64.add (local.get $a) (i64.const 100)))
+    (local.set $l2 (i64.add (local.get $a) (i64.const 200)))
+    (local.set $l3 (i64.add (local.get $a) (i64.const 300)))
+    (local.set $l4 (i64.add (local.get $a) (i64.const 400)))
+    (local.set $l5 (i64.add (local.get $a) (i64.const 500)))
+    (local.set $l6 (i64.add (local.get $a) (i64.const 600)))
+    (local.set $l7 (i64.add (local.get $a) (i64.const 700)))
+    (local.set $l8 (i64.add (local.get $a) (i64.const 800)))
+    (local.set $l9 (i64.add (local.get $a) (i64.const 900)))
+    (local.set $l10 (i64.add (local.get $a) (i64.const 1000)))
+    (local.set $l11 (i64.add (local.get $a) (i64.const 1100)))
+    (local.set $l12 (i64.add (local.get $a) (i64.const 1200)))
+    (i64.add
+      (local.get $l12)
+      (i64.add
+        (local.get $l11)
+        (call $weigh10
+          (local.get $a) (local.get $l2)
+          (i64.add (local.get $a) (i64.const 2)) (local.get $l4)
+          (i64.add (local.get $a) (i64.const 4)) (local.get $l6)
+          (i64.add (local.get $a) (i64.const 6)) (local.get $l8)
+          (i64.add (local.get $a) (i64.const 8)) (local.get $l10))))
+    (i64.add
+      (local.get $l8)
+      (i64.add
+        (local.get $l7)
+        (i64.add
+          (local.get $l9)
+          (call $weigh9
+            (i64.const 0) (local.get $a)
+            (i64.mul (local.get $a) (i64.const 2))
+            (i64.mul (local.get $a) (i64.const 3))
+            (i64.mul (local.get $a) (i64.const 4))
+            (i64.mul (local.get $a) (i64.const 5))
+            (i64.mul (local.get $a) (i64.const 6))
+            (i64.mul (local.get $a) (i64.const 7))
+            (i64.mul (local.get $a) (i64.const 8))))))
+    (i64.add)
+  )
+
+  (func (export "float-arguments") (param $a i64) (result i64)
+    (i64.add (local.get $a) (i64.const 1))
+    (i64.mul (local.get $a) (i64.const 2))
+    (i64.add (local.get $a) (i64.const 3))
+    (call $mixed
+      (local.get $a)
+      (f64.add (f64.convert_i64_s (local.get $a)) (f64.const 0.5))
+      (i64.add (local.get $a) (i64.const 2))
+      (f64.add (f64.convert_i64_s (local.get $a)) (f64.const 0.5))
+      (i64.add (local.get $a) (i64.const 4))
+      (f64.add (f64.convert_i64_s (local.get $a)) (f64.const 0.5))
+      (i64.add (local.get $a) (i64.const 6))
+      (f64.add (f64.convert_i64_s (local.get $a)) (f64.const 0.5))
+      (i64.add (local.get $a) (i64.const 8))
+      (f64.add (f64.convert_i64_s (local.get $a)) (f64.const 0.5)))
+    (call $weigh8
+      (i64.const 0) (local.get $a)
+      (i64.mul (local.get $a) (i64.const 2))
+      (i64.mul (local.get $a) (i64.const 3))
+      (i64.mul (local.get $a) (i64.const 4))
+      (i64.mul (local.get $a) (i64.const 5))
+      (i64.mul (local.get $a) (i64.const 6))
+      (i64.mul (local.get $a) (i64.const 7)))
+    (i64.add)
+    (i64.add)
+    (i64.add)
+    (i64.add)
+  )
+)
+
+(assert_return (invoke "spilled-locals" (i64.const 0)) (i64.const 26840))
+(assert_return (invoke "spilled-locals" (i64.const 1)) (i64.const 27620))
+(assert_return (invoke "spilled-locals" (i64.const 12345)) (i64.const 9655940))
+(assert_return (invoke "float-arguments" (i64.const 0)) (i64.const 159))
+(assert_return (invoke "float-arguments" (i64.const 1)) (i64.const 7218))
+(assert_return (invoke "float-arguments" (i64.const 12345)) (i64.const 87143514))
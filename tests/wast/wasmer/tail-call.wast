@@ -0,0 +1,156 @@
+(module
+  (type $count-t (func (param i64 i64) (result i64)))
+  (type $rotate-t
+    (func (param i64 i64 i64 i64 i64 i64 i64 i64 i64 i64) (result i64)))
+  (table funcref (elem $count-indirect $rotate-indirect))
+
+  ;; Counts down `n` while accumulating into `acc` using direct tail calls.
+  (func $count (export "count") (param $n i64) (param $acc i64) (result i64)
+    (if (result i64) (i64.eqz (local.get $n))
+      (then (local.get $acc))
+      (else
+        (return_call $count
+          (i64.sub (local.get $n) (i64.const 1))
+          (i64.add (local.get $acc) (i64.const 1))
+        )
+      )
+    )
+  )
+
+  ;; Same as above, but going through the table.
+  (func $count-indirect (export "count-indirect") (param $n i64) (param $acc i64) (result i64)
+    (if (result i64) (i64.eqz (local.get $n))
+      (then (local.get $acc))
+      (else
+        (return_call_indirect (type $count-t)
+          (i64.sub (local.get $n) (i64.const 1))
+          (i64.add (local.get $acc) (i64.const 1))
+          (i32.const 0)
+        )
+      )
+    )
+  )
+
+  ;; Mutual recursion between two functions.
+  (func $even (export "even") (param $n i64) (result i32)
+    (if (result i32) (i64.eqz (local.get $n))
+      (then (i32.const 1))
+      (else (return_call $odd (i64.sub (local.get $n) (i64.const 1))))
+    )
+  )
+  (func $odd (export "odd") (param $n i64) (result i32)
+    (if (result i32) (i64.eqz (local.get $n))
+      (then (i32.const 0))
+      (else (return_call $even (i64.sub (local.get $n) (i64.const 1))))
+    )
+  )
+
+  ;; Enough parameters for some of them to be passed on the stack. Each call adds the first
+  ;; of the rotating parameters to `acc`, and moves it to the back.
+  (func $rotate (export "rotate")
+    (param $n i64) (param $acc i64)
+    (param $a i64) (param $b i64) (param $c i64) (param $d i64)
+    (param $e i64) (param $f i64) (param $g i64) (param $h i64)
+    (result i64)
+    (if (result i64) (i64.eqz (local.get $n))
+      (then (local.get $acc))
+      (else
+        (return_call $rotate
+          (i64.sub (local.get $n) (i64.const 1))
+          (i64.add (local.get $acc) (local.get $a))
+          (local.get $b) (local.get $c) (local.get $d) (local.get $e)
+          (local.get $f) (local.get $g) (local.get $h) (local.get $a)
+        )
+      )
+    )
+  )
+
+  ;; Same as above, but going through the table.
+  (func $rotate-indirect (export "rotate-indirect")
+    (param $n i64) (param $acc i64)
+    (param $a i64) (param $b i64) (param $c i64) (param $d i64)
+    (param $e i64) (param $f i64) (param $g i64) (param $h i64)
+    (result i64)
+    (if (result i64) (i64.eqz (local.get $n))
+      (then (local.get $acc))
+      (else
+        (return_call_indirect (type $rotate-t)
+          (i64.sub (local.get $n) (i64.const 1))
+          (i64.add (local.get $acc) (local.get $a))
+          (local.get $b) (local.get $c) (local.get $d) (local.get $e)
+          (local.get $f) (local.get $g) (local.get $h) (local.get $a)
+          (i32.const 1)
+        )
+      )
+    )
+  )
+
+  ;; Tail calls from a function taking fewer stack parameters than the callee,
+  ;; whose incoming argument area is too small for the ones of the callee.
+  (func (export "start-rotate") (param $n i64) (result i64)
+    (return_call $rotate
+      (local.get $n) (i64.const 0)
+      (i64.const 1) (i64.const 2) (i64.const 3) (i64.const 4)
+      (i64.const 5) (i64.const 6) (i64.const 7) (i64.const 8)
+    )
+  )
+  (func (export "start-rotate-indirect") (param $n i64) (result i64)
+    (return_call_indirect (type $rotate-t)
+      (local.get $n) (i64.const 0)
+      (i64.const 1) (i64.const 2) (i64.const 3) (i64.const 4)
+      (i64.const 5) (i64.const 6) (i64.const 7) (i64.const 8)
+      (i32.const 1)
+    )
+  )
+
+  (func $weigh (param i64 i64) (result i64)
+    (i64.sub (i64.mul (local.get 0) (i64.const 1000)) (local.get 1))
+  )
+
+  ;; The locals and the values below the arguments push both of them out of
+  ;; reach of a load offset, loading them must not clobber the other.
+  (func (export "far-arguments") (param $a i64) (result i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (i64.add (local.get $a) (i64.const 1))
+    (i64.add (local.get $a) (i64.const 2))
+    (i64.add (local.get $a) (i64.const 3))
+    (i64.add (local.get $a) (i64.const 4))
+    (i64.add (local.get $a) (i64.const 5))
+    (i64.add (local.get $a) (i64.const 6))
+    (i64.add (local.get $a) (i64.const 7))
+    (i64.add (local.get $a) (i64.const 8))
+    (i64.add (local.get $a) (i64.const 9))
+    (return_call $weigh
+      (i64.add (local.get $a) (i64.const 10))
+      (i64.add (local.get $a) (i64.const 20)))
+  )
+)
+
+(assert_return (invoke "count" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "count" (i64.const 1000) (i64.const 0)) (i64.const 1000))
+;; Deep enough to overflow the stack if the frames were not reused.
+(assert_return (invoke "count" (i64.const 10_000_000) (i64.const 0)) (i64.const 10_000_000))
+(assert_return (invoke "count-indirect" (i64.const 10_000_000) (i64.const 0)) (i64.const 10_000_000))
+(assert_return (invoke "even" (i64.const 1_000_001)) (i32.const 0))
+(assert_return (invoke "odd" (i64.const 1_000_001)) (i32.const 1))
+(assert_return
+  (invoke "rotate" (i64.const 10)
+    (i64.const 0) (i64.const 1) (i64.const 2) (i64.const 3) (i64.const 4)
+    (i64.const 5) (i64.const 6) (i64.const 7) (i64.const 8))
+  (i64.const 39))
+(assert_return
+  (invoke "rotate" (i64.const 1_000_000)
+    (i64.const 0) (i64.const 1) (i64.const 2) (i64.const 3) (i64.const 4)
+    (i64.const 5) (i64.const 6) (i64.const 7) (i64.const 8))
+  (i64.const 4_500_000))
+(assert_return
+  (invoke "rotate-indirect" (i64.const 1_000_000)
+    (i64.const 0) (i64.const 1) (i64.const 2) (i64.const 3) (i64.const 4)
+    (i64.const 5) (i64.const 6) (i64.const 7) (i64.const 8))
+  (i64.const 4_500_000))
+(assert_return (invoke "far-arguments" (i64.const 0)) (i64.const 9980))
+(assert_return (invoke "far-arguments" (i64.const 5)) (i64.const 14975))
+(assert_return (invoke "far-arguments" (i64.const 12345)) (i64.const 12342635))
+(assert_return (invoke "start-rotate" (i64.const 1_000_000)) (i64.const 4_500_000))
+(assert_return (invoke "start-rotate-indirect" (i64.const 1_000_000)) (i64.const 4_500_000))
@@ -0,0 +1,77 @@
+;; Trapping float to integer truncations. NaN is an invalid conversion, values
+;; whose truncation is out of range of the integer overflow.
+
+(module
+  (func (export "i32.trunc_f32_s") (param f32) (result i32) (i32.trunc_f32_s (local.get 0)))
+  (func (export "i32.trunc_f32_u") (param f32) (result i32) (i32.trunc_f32_u (local.get 0)))
+  (func (export "i32.trunc_f64_s") (param f64) (result i32) (i32.trunc_f64_s (local.get 0)))
+  (func (export "i32.trunc_f64_u") (param f64) (result i32) (i32.trunc_f64_u (local.get 0)))
+  (func (export "i64.trunc_f32_s") (param f32) (result i64) (i64.trunc_f32_s (local.get 0)))
+  (func (export "i64.trunc_f32_u") (param f32) (result i64) (i64.trunc_f32_u (local.get 0)))
+  (func (export "i64.trunc_f64_s") (param f64) (result i64) (i64.trunc_f64_s (local.get 0)))
+  (func (export "i64.trunc_f64_u") (param f64) (result i64) (i64.trunc_f64_u (local.get 0)))
+)
+
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -1.9)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x1.fffffep+30)) (i32.const 0x7fffff80))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1p+31)) (i32.const 0x80000000))
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -0x1.000002p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const nan)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -nan:0x200000)) "invalid conversion to integer")
+
+(assert_return (invoke "i32.trunc_f32_u" (f32.const -0.9)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.fffffep+31)) (i32.const 0xffffff00))
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -1)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 0x1p+32)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const nan)) "invalid conversion to integer")
+
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 2147483647.9)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -2147483648.9)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 2147483648)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -2147483649)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const nan)) "invalid conversion to integer")
+
+(assert_return (invoke "i32.trunc_f64_u" (f64.const -0.9)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 4294967295.9)) (i32.const -1))
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -1)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 4294967296)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -1e30)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const nan)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.fffffep+62)) (i64.const 9223371487098961920))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1p+63)) (i64.const 0x8000000000000000))
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const 0x1p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const -0x1.000002p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const 1e30)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const nan)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_f32_u" (f32.const -0.9)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.fffffep+63)) (i64.const 18446742974197923840))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const 0x1p+64)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -1)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -1e30)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const nan)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.fffffffffffffp+62)) (i64.const 9223372036854774784))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1p+63)) (i64.const 0x8000000000000000))
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const 0x1p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const -0x1.0000000000001p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const -1e30)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const nan)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_f64_u" (f64.const -0.9)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.fffffffffffffp+63)) (i64.const 18446744073709549568))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const 0x1p+64)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -1)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const nan)) "invalid conversion to integer")
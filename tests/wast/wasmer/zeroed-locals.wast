@@ -0,0 +1,1039 @@
+;; Locals start out as zero, even when a previous call left garbage in the
+;; stack slots they are allocated to. A few locals are cleared one by one,
+;; many of them with a loop, and the largest function spans several pages.
+
+(module
+  (func (export "dirty-6") (result i64)
+    (local i64 i64 i64 i64 i64 i64)
+    (local.set 0 (i64.const -1)) (local.set 1 (i64.const -1)) (local.set 2 (i64.const -1)) (local.set 3 (i64.const -1))
+    (local.set 4 (i64.const -1)) (local.set 5 (i64.const -1))
+    (i64.add (local.get 0) (local.get 5))
+  )
+  (func (export "read-6") (result i64)
+    (local $acc i64) (local i64 i64 i64 i64 i64 i64)
+    (local.set $acc (i64.or (local.get $acc) (local.get 1))) (local.set $acc (i64.or (local.get $acc) (local.get 2)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 3))) (local.set $acc (i64.or (local.get $acc) (local.get 4)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 5))) (local.set $acc (i64.or (local.get $acc) (local.get 6)))
+    (local.get $acc)
+  )
+  (func (export "dirty-40") (result i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64)
+    (local.set 0 (i64.const -1)) (local.set 1 (i64.const -1)) (local.set 2 (i64.const -1)) (local.set 3 (i64.const -1))
+    (local.set 4 (i64.const -1)) (local.set 5 (i64.const -1)) (local.set 6 (i64.const -1)) (local.set 7 (i64.const -1))
+    (local.set 8 (i64.const -1)) (local.set 9 (i64.const -1)) (local.set 10 (i64.const -1)) (local.set 11 (i64.const -1))
+    (local.set 12 (i64.const -1)) (local.set 13 (i64.const -1)) (local.set 14 (i64.const -1)) (local.set 15 (i64.const -1))
+    (local.set 16 (i64.const -1)) (local.set 17 (i64.const -1)) (local.set 18 (i64.const -1)) (local.set 19 (i64.const -1))
+    (local.set 20 (i64.const -1)) (local.set 21 (i64.const -1)) (local.set 22 (i64.const -1)) (local.set 23 (i64.const -1))
+    (local.set 24 (i64.const -1)) (local.set 25 (i64.const -1)) (local.set 26 (i64.const -1)) (local.set 27 (i64.const -1))
+    (local.set 28 (i64.const -1)) (local.set 29 (i64.const -1)) (local.set 30 (i64.const -1)) (local.set 31 (i64.const -1))
+    (local.set 32 (i64.const -1)) (local.set 33 (i64.const -1)) (local.set 34 (i64.const -1)) (local.set 35 (i64.const -1))
+    (local.set 36 (i64.const -1)) (local.set 37 (i64.const -1)) (local.set 38 (i64.const -1)) (local.set 39 (i64.const -1))
+    (i64.add (local.get 0) (local.get 39))
+  )
+  (func (export "read-40") (result i64)
+    (local $acc i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64)
+    (local.set $acc (i64.or (local.get $acc) (local.get 1))) (local.set $acc (i64.or (local.get $acc) (local.get 2)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 3))) (local.set $acc (i64.or (local.get $acc) (local.get 4)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 5))) (local.set $acc (i64.or (local.get $acc) (local.get 6)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 7))) (local.set $acc (i64.or (local.get $acc) (local.get 8)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 9))) (local.set $acc (i64.or (local.get $acc) (local.get 10)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 11))) (local.set $acc (i64.or (local.get $acc) (local.get 12)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 13))) (local.set $acc (i64.or (local.get $acc) (local.get 14)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 15))) (local.set $acc (i64.or (local.get $acc) (local.get 16)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 17))) (local.set $acc (i64.or (local.get $acc) (local.get 18)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 19))) (local.set $acc (i64.or (local.get $acc) (local.get 20)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 21))) (local.set $acc (i64.or (local.get $acc) (local.get 22)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 23))) (local.set $acc (i64.or (local.get $acc) (local.get 24)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 25))) (local.set $acc (i64.or (local.get $acc) (local.get 26)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 27))) (local.set $acc (i64.or (local.get $acc) (local.get 28)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 29))) (local.set $acc (i64.or (local.get $acc) (local.get 30)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 31))) (local.set $acc (i64.or (local.get $acc) (local.get 32)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 33))) (local.set $acc (i64.or (local.get $acc) (local.get 34)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 35))) (local.set $acc (i64.or (local.get $acc) (local.get 36)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 37))) (local.set $acc (i64.or (local.get $acc) (local.get 38)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 39))) (local.set $acc (i64.or (local.get $acc) (local.get 40)))
+    (local.get $acc)
+  )
+  (func (export "dirty-1100") (result i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local.set 0 (i64.const -1)) (local.set 1 (i64.const -1)) (local.set 2 (i64.const -1)) (local.set 3 (i64.const -1))
+    (local.set 4 (i64.const -1)) (local.set 5 (i64.const -1)) (local.set 6 (i64.const -1)) (local.set 7 (i64.const -1))
+    (local.set 8 (i64.const -1)) (local.set 9 (i64.const -1)) (local.set 10 (i64.const -1)) (local.set 11 (i64.const -1))
+    (local.set 12 (i64.const -1)) (local.set 13 (i64.const -1)) (local.set 14 (i64.const -1)) (local.set 15 (i64.const -1))
+    (local.set 16 (i64.const -1)) (local.set 17 (i64.const -1)) (local.set 18 (i64.const -1)) (local.set 19 (i64.const -1))
+    (local.set 20 (i64.const -1)) (local.set 21 (i64.const -1)) (local.set 22 (i64.const -1)) (local.set 23 (i64.const -1))
+    (local.set 24 (i64.const -1)) (local.set 25 (i64.const -1)) (local.set 26 (i64.const -1)) (local.set 27 (i64.const -1))
+    (local.set 28 (i64.const -1)) (local.set 29 (i64.const -1)) (local.set 30 (i64.const -1)) (local.set 31 (i64.const -1))
+    (local.set 32 (i64.const -1)) (local.set 33 (i64.const -1)) (local.set 34 (i64.const -1)) (local.set 35 (i64.const -1))
+    (local.set 36 (i64.const -1)) (local.set 37 (i64.const -1)) (local.set 38 (i64.const -1)) (local.set 39 (i64.const -1))
+    (local.set 40 (i64.const -1)) (local.set 41 (i64.const -1)) (local.set 42 (i64.const -1)) (local.set 43 (i64.const -1))
+    (local.set 44 (i64.const -1)) (local.set 45 (i64.const -1)) (local.set 46 (i64.const -1)) (local.set 47 (i64.const -1))
+    (local.set 48 (i64.const -1)) (local.set 49 (i64.const -1)) (local.set 50 (i64.const -1)) (local.set 51 (i64.const -1))
+    (local.set 52 (i64.const -1)) (local.set 53 (i64.const -1)) (local.set 54 (i64.const -1)) (local.set 55 (i64.const -1))
+    (local.set 56 (i64.const -1)) (local.set 57 (i64.const -1)) (local.set 58 (i64.const -1)) (local.set 59 (i64.const -1))
+    (local.set 60 (i64.const -1)) (local.set 61 (i64.const -1)) (local.set 62 (i64.const -1)) (local.set 63 (i64.const -1))
+    (local.set 64 (i64.const -1)) (local.set 65 (i64.const -1)) (local.set 66 (i64.const -1)) (local.set 67 (i64.const -1))
+    (local.set 68 (i64.const -1)) (local.set 69 (i64.const -1)) (local.set 70 (i64.const -1)) (local.set 71 (i64.const -1))
+    (local.set 72 (i64.const -1)) (local.set 73 (i64.const -1)) (local.set 74 (i64.const -1)) (local.set 75 (i64.const -1))
+    (local.set 76 (i64.const -1)) (local.set 77 (i64.const -1)) (local.set 78 (i64.const -1)) (local.set 79 (i64.const -1))
+    (local.set 80 (i64.const -1)) (local.set 81 (i64.const -1)) (local.set 82 (i64.const -1)) (local.set 83 (i64.const -1))
+    (local.set 84 (i64.const -1)) (local.set 85 (i64.const -1)) (local.set 86 (i64.const -1)) (local.set 87 (i64.const -1))
+    (local.set 88 (i64.const -1)) (local.set 89 (i64.const -1)) (local.set 90 (i64.const -1)) (local.set 91 (i64.const -1))
+    (local.set 92 (i64.const -1)) (local.set 93 (i64.const -1)) (local.set 94 (i64.const -1)) (local.set 95 (i64.const -1))
+    (local.set 96 (i64.const -1)) (local.set 97 (i64.const -1)) (local.set 98 (i64.const -1)) (local.set 99 (i64.const -1))
+    (local.set 100 (i64.const -1)) (local.set 101 (i64.const -1)) (local.set 102 (i64.const -1)) (local.set 103 (i64.const -1))
+    (local.set 104 (i64.const -1)) (local.set 105 (i64.const -1)) (local.set 106 (i64.const -1)) (local.set 107 (i64.const -1))
+    (local.set 108 (i64.const -1)) (local.set 109 (i64.const -1)) (local.set 110 (i64.const -1)) (local.set 111 (i64.const -1))
+    (local.set 112 (i64.const -1)) (local.set 113 (i64.const -1)) (local.set 114 (i64.const -1)) (local.set 115 (i64.const -1))
+    (local.set 116 (i64.const -1)) (local.set 117 (i64.const -1)) (local.set 118 (i64.const -1)) (local.set 119 (i64.const -1))
+    (local.set 120 (i64.const -1)) (local.set 121 (i64.const -1)) (local.set 122 (i64.const -1)) (local.set 123 (i64.const -1))
+    (local.set 124 (i64.const -1)) (local.set 125 (i64.const -1)) (local.set 126 (i64.const -1)) (local.set 127 (i64.const -1))
+    (local.set 128 (i64.const -1)) (local.set 129 (i64.const -1)) (local.set 130 (i64.const -1)) (local.set 131 (i64.const -1))
+    (local.set 132 (i64.const -1)) (local.set 133 (i64.const -1)) (local.set 134 (i64.const -1)) (local.set 135 (i64.const -1))
+    (local.set 136 (i64.const -1)) (local.set 137 (i64.const -1)) (local.set 138 (i64.const -1)) (local.set 139 (i64.const -1))
+    (local.set 140 (i64.const -1)) (local.set 141 (i64.const -1)) (local.set 142 (i64.const -1)) (local.set 143 (i64.const -1))
+    (local.set 144 (i64.const -1)) (local.set 145 (i64.const -1)) (local.set 146 (i64.const -1)) (local.set 147 (i64.const -1))
+    (local.set 148 (i64.const -1)) (local.set 149 (i64.const -1)) (local.set 150 (i64.const -1)) (local.set 151 (i64.const -1))
+    (local.set 152 (i64.const -1)) (local.set 153 (i64.const -1)) (local.set 154 (i64.const -1)) (local.set 155 (i64.const -1))
+    (local.set 156 (i64.const -1)) (local.set 157 (i64.const -1)) (local.set 158 (i64.const -1)) (local.set 159 (i64.const -1))
+    (local.set 160 (i64.const -1)) (local.set 161 (i64.const -1)) (local.set 162 (i64.const -1)) (local.set 163 (i64.const -1))
+    (local.set 164 (i64.const -1)) (local.set 165 (i64.const -1)) (local.set 166 (i64.const -1)) (local.set 167 (i64.const -1))
+    (local.set 168 (i64.const -1)) (local.set 169 (i64.const -1)) (local.set 170 (i64.const -1)) (local.set 171 (i64.const -1))
+    (local.set 172 (i64.const -1)) (local.set 173 (i64.const -1)) (local.set 174 (i64.const -1)) (local.set 175 (i64.const -1))
+    (local.set 176 (i64.const -1)) (local.set 177 (i64.const -1)) (local.set 178 (i64.const -1)) (local.set 179 (i64.const -1))
+    (local.set 180 (i64.const -1)) (local.set 181 (i64.const -1)) (local.set 182 (i64.const -1)) (local.set 183 (i64.const -1))
+    (local.set 184 (i64.const -1)) (local.set 185 (i64.const -1)) (local.set 186 (i64.const -1)) (local.set 187 (i64.const -1))
+    (local.set 188 (i64.const -1)) (local.set 189 (i64.const -1)) (local.set 190 (i64.const -1)) (local.set 191 (i64.const -1))
+    (local.set 192 (i64.const -1)) (local.set 193 (i64.const -1)) (local.set 194 (i64.const -1)) (local.set 195 (i64.const -1))
+    (local.set 196 (i64.const -1)) (local.set 197 (i64.const -1)) (local.set 198 (i64.const -1)) (local.set 199 (i64.const -1))
+    (local.set 200 (i64.const -1)) (local.set 201 (i64.const -1)) (local.set 202 (i64.const -1)) (local.set 203 (i64.const -1))
+    (local.set 204 (i64.const -1)) (local.set 205 (i64.const -1)) (local.set 206 (i64.const -1)) (local.set 207 (i64.const -1))
+    (local.set 208 (i64.const -1)) (local.set 209 (i64.const -1)) (local.set 210 (i64.const -1)) (local.set 211 (i64.const -1))
+    (local.set 212 (i64.const -1)) (local.set 213 (i64.const -1)) (local.set 214 (i64.const -1)) (local.set 215 (i64.const -1))
+    (local.set 216 (i64.const -1)) (local.set 217 (i64.const -1)) (local.set 218 (i64.const -1)) (local.set 219 (i64.const -1))
+    (local.set 220 (i64.const -1)) (local.set 221 (i64.const -1)) (local.set 222 (i64.const -1)) (local.set 223 (i64.const -1))
+    (local.set 224 (i64.const -1)) (local.set 225 (i64.const -1)) (local.set 226 (i64.const -1)) (local.set 227 (i64.const -1))
+    (local.set 228 (i64.const -1)) (local.set 229 (i64.const -1)) (local.set 230 (i64.const -1)) (local.set 231 (i64.const -1))
+    (local.set 232 (i64.const -1)) (local.set 233 (i64.const -1)) (local.set 234 (i64.const -1)) (local.set 235 (i64.const -1))
+    (local.set 236 (i64.const -1)) (local.set 237 (i64.const -1)) (local.set 238 (i64.const -1)) (local.set 239 (i64.const -1))
+    (local.set 240 (i64.const -1)) (local.set 241 (i64.const -1)) (local.set 242 (i64.const -1)) (local.set 243 (i64.const -1))
+    (local.set 244 (i64.const -1)) (local.set 245 (i64.const -1)) (local.set 246 (i64.const -1)) (local.set 247 (i64.const -1))
+    (local.set 248 (i64.const -1)) (local.set 249 (i64.const -1)) (local.set 250 (i64.const -1)) (local.set 251 (i64.const -1))
+    (local.set 252 (i64.const -1)) (local.set 253 (i64.const -1)) (local.set 254 (i64.const -1)) (local.set 255 (i64.const -1))
+    (local.set 256 (i64.const -1)) (local.set 257 (i64.const -1)) (local.set 258 (i64.const -1)) (local.set 259 (i64.const -1))
+    (local.set 260 (i64.const -1)) (local.set 261 (i64.const -1)) (local.set 262 (i64.const -1)) (local.set 263 (i64.const -1))
+    (local.set 264 (i64.const -1)) (local.set 265 (i64.const -1)) (local.set 266 (i64.const -1)) (local.set 267 (i64.const -1))
+    (local.set 268 (i64.const -1)) (local.set 269 (i64.const -1)) (local.set 270 (i64.const -1)) (local.set 271 (i64.const -1))
+    (local.set 272 (i64.const -1)) (local.set 273 (i64.const -1)) (local.set 274 (i64.const -1)) (local.set 275 (i64.const -1))
+    (local.set 276 (i64.const -1)) (local.set 277 (i64.const -1)) (local.set 278 (i64.const -1)) (local.set 279 (i64.const -1))
+    (local.set 280 (i64.const -1)) (local.set 281 (i64.const -1)) (local.set 282 (i64.const -1)) (local.set 283 (i64.const -1))
+    (local.set 284 (i64.const -1)) (local.set 285 (i64.const -1)) (local.set 286 (i64.const -1)) (local.set 287 (i64.const -1))
+    (local.set 288 (i64.const -1)) (local.set 289 (i64.const -1)) (local.set 290 (i64.const -1)) (local.set 291 (i64.const -1))
+    (local.set 292 (i64.const -1)) (local.set 293 (i64.const -1)) (local.set 294 (i64.const -1)) (local.set 295 (i64.const -1))
+    (local.set 296 (i64.const -1)) (local.set 297 (i64.const -1)) (local.set 298 (i64.const -1)) (local.set 299 (i64.const -1))
+    (local.set 300 (i64.const -1)) (local.set 301 (i64.const -1)) (local.set 302 (i64.const -1)) (local.set 303 (i64.const -1))
+    (local.set 304 (i64.const -1)) (local.set 305 (i64.const -1)) (local.set 306 (i64.const -1)) (local.set 307 (i64.const -1))
+    (local.set 308 (i64.const -1)) (local.set 309 (i64.const -1)) (local.set 310 (i64.const -1)) (local.set 311 (i64.const -1))
+    (local.set 312 (i64.const -1)) (local.set 313 (i64.const -1)) (local.set 314 (i64.const -1)) (local.set 315 (i64.const -1))
+    (local.set 316 (i64.const -1)) (local.set 317 (i64.const -1)) (local.set 318 (i64.const -1)) (local.set 319 (i64.const -1))
+    (local.set 320 (i64.const -1)) (local.set 321 (i64.const -1)) (local.set 322 (i64.const -1)) (local.set 323 (i64.const -1))
+    (local.set 324 (i64.const -1)) (local.set 325 (i64.const -1)) (local.set 326 (i64.const -1)) (local.set 327 (i64.const -1))
+    (local.set 328 (i64.const -1)) (local.set 329 (i64.const -1)) (local.set 330 (i64.const -1)) (local.set 331 (i64.const -1))
+    (local.set 332 (i64.const -1)) (local.set 333 (i64.const -1)) (local.set 334 (i64.const -1)) (local.set 335 (i64.const -1))
+    (local.set 336 (i64.const -1)) (local.set 337 (i64.const -1)) (local.set 338 (i64.const -1)) (local.set 339 (i64.const -1))
+    (local.set 340 (i64.const -1)) (local.set 341 (i64.const -1)) (local.set 342 (i64.const -1)) (local.set 343 (i64.const -1))
+    (local.set 344 (i64.const -1)) (local.set 345 (i64.const -1)) (local.set 346 (i64.const -1)) (local.set 347 (i64.const -1))
+    (local.set 348 (i64.const -1)) (local.set 349 (i64.const -1)) (local.set 350 (i64.const -1)) (local.set 351 (i64.const -1))
+    (local.set 352 (i64.const -1)) (local.set 353 (i64.const -1)) (local.set 354 (i64.const -1)) (local.set 355 (i64.const -1))
+    (local.set 356 (i64.const -1)) (local.set 357 (i64.const -1)) (local.set 358 (i64.const -1)) (local.set 359 (i64.const -1))
+    (local.set 360 (i64.const -1)) (local.set 361 (i64.const -1)) (local.set 362 (i64.const -1)) (local.set 363 (i64.const -1))
+    (local.set 364 (i64.const -1)) (local.set 365 (i64.const -1)) (local.set 366 (i64.const -1)) (local.set 367 (i64.const -1))
+    (local.set 368 (i64.const -1)) (local.set 369 (i64.const -1)) (local.set 370 (i64.const -1)) (local.set 371 (i64.const -1))
+    (local.set 372 (i64.const -1)) (local.set 373 (i64.const -1)) (local.set 374 (i64.const -1)) (local.set 375 (i64.const -1))
+    (local.set 376 (i64.const -1)) (local.set 377 (i64.const -1)) (local.set 378 (i64.const -1)) (local.set 379 (i64.const -1))
+    (local.set 380 (i64.const -1)) (local.set 381 (i64.const -1)) (local.set 382 (i64.const -1)) (local.set 383 (i64.const -1))
+    (local.set 384 (i64.const -1)) (local.set 385 (i64.const -1)) (local.set 386 (i64.const -1)) (local.set 387 (i64.const -1))
+    (local.set 388 (i64.const -1)) (local.set 389 (i64.const -1)) (local.set 390 (i64.const -1)) (local.set 391 (i64.const -1))
+    (local.set 392 (i64.const -1)) (local.set 393 (i64.const -1)) (local.set 394 (i64.const -1)) (local.set 395 (i64.const -1))
+    (local.set 396 (i64.const -1)) (local.set 397 (i64.const -1)) (local.set 398 (i64.const -1)) (local.set 399 (i64.const -1))
+    (local.set 400 (i64.const -1)) (local.set 401 (i64.const -1)) (local.set 402 (i64.const -1)) (local.set 403 (i64.const -1))
+    (local.set 404 (i64.const -1)) (local.set 405 (i64.const -1)) (local.set 406 (i64.const -1)) (local.set 407 (i64.const -1))
+    (local.set 408 (i64.const -1)) (local.set 409 (i64.const -1)) (local.set 410 (i64.const -1)) (local.set 411 (i64.const -1))
+    (local.set 412 (i64.const -1)) (local.set 413 (i64.const -1)) (local.set 414 (i64.const -1)) (local.set 415 (i64.const -1))
+    (local.set 416 (i64.const -1)) (local.set 417 (i64.const -1)) (local.set 418 (i64.const -1)) (local.set 419 (i64.const -1))
+    (local.set 420 (i64.const -1)) (local.set 421 (i64.const -1)) (local.set 422 (i64.const -1)) (local.set 423 (i64.const -1))
+    (local.set 424 (i64.const -1)) (local.set 425 (i64.const -1)) (local.set 426 (i64.const -1)) (local.set 427 (i64.const -1))
+    (local.set 428 (i64.const -1)) (local.set 429 (i64.const -1)) (local.set 430 (i64.const -1)) (local.set 431 (i64.const -1))
+    (local.set 432 (i64.const -1)) (local.set 433 (i64.const -1)) (local.set 434 (i64.const -1)) (local.set 435 (i64.const -1))
+    (local.set 436 (i64.const -1)) (local.set 437 (i64.const -1)) (local.set 438 (i64.const -1)) (local.set 439 (i64.const -1))
+    (local.set 440 (i64.const -1)) (local.set 441 (i64.const -1)) (local.set 442 (i64.const -1)) (local.set 443 (i64.const -1))
+    (local.set 444 (i64.const -1)) (local.set 445 (i64.const -1)) (local.set 446 (i64.const -1)) (local.set 447 (i64.const -1))
+    (local.set 448 (i64.const -1)) (local.set 449 (i64.const -1)) (local.set 450 (i64.const -1)) (local.set 451 (i64.const -1))
+    (local.set 452 (i64.const -1)) (local.set 453 (i64.const -1)) (local.set 454 (i64.const -1)) (local.set 455 (i64.const -1))
+    (local.set 456 (i64.const -1)) (local.set 457 (i64.const -1)) (local.set 458 (i64.const -1)) (local.set 459 (i64.const -1))
+    (local.set 460 (i64.const -1)) (local.set 461 (i64.const -1)) (local.set 462 (i64.const -1)) (local.set 463 (i64.const -1))
+    (local.set 464 (i64.const -1)) (local.set 465 (i64.const -1)) (local.set 466 (i64.const -1)) (local.set 467 (i64.const -1))
+    (local.set 468 (i64.const -1)) (local.set 469 (i64.const -1)) (local.set 470 (i64.const -1)) (local.set 471 (i64.const -1))
+    (local.set 472 (i64.const -1)) (local.set 473 (i64.const -1)) (local.set 474 (i64.const -1)) (local.set 475 (i64.const -1))
+    (local.set 476 (i64.const -1)) (local.set 477 (i64.const -1)) (local.set 478 (i64.const -1)) (local.set 479 (i64.const -1))
+    (local.set 480 (i64.const -1)) (local.set 481 (i64.const -1)) (local.set 482 (i64.const -1)) (local.set 483 (i64.const -1))
+    (local.set 484 (i64.const -1)) (local.set 485 (i64.const -1)) (local.set 486 (i64.const -1)) (local.set 487 (i64.const -1))
+    (local.set 488 (i64.const -1)) (local.set 489 (i64.const -1)) (local.set 490 (i64.const -1)) (local.set 491 (i64.const -1))
+    (local.set 492 (i64.const -1)) (local.set 493 (i64.const -1)) (local.set 494 (i64.const -1)) (local.set 495 (i64.const -1))
+    (local.set 496 (i64.const -1)) (local.set 497 (i64.const -1)) (local.set 498 (i64.const -1)) (local.set 499 (i64.const -1))
+    (local.set 500 (i64.const -1)) (local.set 501 (i64.const -1)) (local.set 502 (i64.const -1)) (local.set 503 (i64.const -1))
+    (local.set 504 (i64.const -1)) (local.set 505 (i64.const -1)) (local.set 506 (i64.const -1)) (local.set 507 (i64.const -1))
+    (local.set 508 (i64.const -1)) (local.set 509 (i64.const -1)) (local.set 510 (i64.const -1)) (local.set 511 (i64.const -1))
+    (local.set 512 (i64.const -1)) (local.set 513 (i64.const -1)) (local.set 514 (i64.const -1)) (local.set 515 (i64.const -1))
+    (local.set 516 (i64.const -1)) (local.set 517 (i64.const -1)) (local.set 518 (i64.const -1)) (local.set 519 (i64.const -1))
+    (local.set 520 (i64.const -1)) (local.set 521 (i64.const -1)) (local.set 522 (i64.const -1)) (local.set 523 (i64.const -1))
+    (local.set 524 (i64.const -1)) (local.set 525 (i64.const -1)) (local.set 526 (i64.const -1)) (local.set 527 (i64.const -1))
+    (local.set 528 (i64.const -1)) (local.set 529 (i64.const -1)) (local.set 530 (i64.const -1)) (local.set 531 (i64.const -1))
+    (local.set 532 (i64.const -1)) (local.set 533 (i64.const -1)) (local.set 534 (i64.const -1)) (local.set 535 (i64.const -1))
+    (local.set 536 (i64.const -1)) (local.set 537 (i64.const -1)) (local.set 538 (i64.const -1)) (local.set 539 (i64.const -1))
+    (local.set 540 (i64.const -1)) (local.set 541 (i64.const -1)) (local.set 542 (i64.const -1)) (local.set 543 (i64.const -1))
+    (local.set 544 (i64.const -1)) (local.set 545 (i64.const -1)) (local.set 546 (i64.const -1)) (local.set 547 (i64.const -1))
+    (local.set 548 (i64.const -1)) (local.set 549 (i64.const -1)) (local.set 550 (i64.const -1)) (local.set 551 (i64.const -1))
+    (local.set 552 (i64.const -1)) (local.set 553 (i64.const -1)) (local.set 554 (i64.const -1)) (local.set 555 (i64.const -1))
+    (local.set 556 (i64.const -1)) (local.set 557 (i64.const -1)) (local.set 558 (i64.const -1)) (local.set 559 (i64.const -1))
+    (local.set 560 (i64.const -1)) (local.set 561 (i64.const -1)) (local.set 562 (i64.const -1)) (local.set 563 (i64.const -1))
+    (local.set 564 (i64.const -1)) (local.set 565 (i64.const -1)) (local.set 566 (i64.const -1)) (local.set 567 (i64.const -1))
+    (local.set 568 (i64.const -1)) (local.set 569 (i64.const -1)) (local.set 570 (i64.const -1)) (local.set 571 (i64.const -1))
+    (local.set 572 (i64.const -1)) (local.set 573 (i64.const -1)) (local.set 574 (i64.const -1)) (local.set 575 (i64.const -1))
+    (local.set 576 (i64.const -1)) (local.set 577 (i64.const -1)) (local.set 578 (i64.const -1)) (local.set 579 (i64.const -1))
+    (local.set 580 (i64.const -1)) (local.set 581 (i64.const -1)) (local.set 582 (i64.const -1)) (local.set 583 (i64.const -1))
+    (local.set 584 (i64.const -1)) (local.set 585 (i64.const -1)) (local.set 586 (i64.const -1)) (local.set 587 (i64.const -1))
+    (local.set 588 (i64.const -1)) (local.set 589 (i64.const -1)) (local.set 590 (i64.const -1)) (local.set 591 (i64.const -1))
+    (local.set 592 (i64.const -1)) (local.set 593 (i64.const -1)) (local.set 594 (i64.const -1)) (local.set 595 (i64.const -1))
+    (local.set 596 (i64.const -1)) (local.set 597 (i64.const -1)) (local.set 598 (i64.const -1)) (local.set 599 (i64.const -1))
+    (local.set 600 (i64.const -1)) (local.set 601 (i64.const -1)) (local.set 602 (i64.const -1)) (local.set 603 (i64.const -1))
+    (local.set 604 (i64.const -1)) (local.set 605 (i64.const -1)) (local.set 606 (i64.const -1)) (local.set 607 (i64.const -1))
+    (local.set 608 (i64.const -1)) (local.set 609 (i64.const -1)) (local.set 610 (i64.const -1)) (local.set 611 (i64.const -1))
+    (local.set 612 (i64.const -1)) (local.set 613 (i64.const -1)) (local.set 614 (i64.const -1)) (local.set 615 (i64.const -1))
+    (local.set 616 (i64.const -1)) (local.set 617 (i64.const -1)) (local.set 618 (i64.const -1)) (local.set 619 (i64.const -1))
+    (local.set 620 (i64.const -1)) (local.set 621 (i64.const -1)) (local.set 622 (i64.const -1)) (local.set 623 (i64.const -1))
+    (local.set 624 (i64.const -1)) (local.set 625 (i64.const -1)) (local.set 626 (i64.const -1)) (local.set 627 (i64.const -1))
+    (local.set 628 (i64.const -1)) (local.set 629 (i64.const -1)) (local.set 630 (i64.const -1)) (local.set 631 (i64.const -1))
+    (local.set 632 (i64.const -1)) (local.set 633 (i64.const -1)) (local.set 634 (i64.const -1)) (local.set 635 (i64.const -1))
+    (local.set 636 (i64.const -1)) (local.set 637 (i64.const -1)) (local.set 638 (i64.const -1)) (local.set 639 (i64.const -1))
+    (local.set 640 (i64.const -1)) (local.set 641 (i64.const -1)) (local.set 642 (i64.const -1)) (local.set 643 (i64.const -1))
+    (local.set 644 (i64.const -1)) (local.set 645 (i64.const -1)) (local.set 646 (i64.const -1)) (local.set 647 (i64.const -1))
+    (local.set 648 (i64.const -1)) (local.set 649 (i64.const -1)) (local.set 650 (i64.const -1)) (local.set 651 (i64.const -1))
+    (local.set 652 (i64.const -1)) (local.set 653 (i64.const -1)) (local.set 654 (i64.const -1)) (local.set 655 (i64.const -1))
+    (local.set 656 (i64.const -1)) (local.set 657 (i64.const -1)) (local.set 658 (i64.const -1)) (local.set 659 (i64.const -1))
+    (local.set 660 (i64.const -1)) (local.set 661 (i64.const -1)) (local.set 662 (i64.const -1)) (local.set 663 (i64.const -1))
+    (local.set 664 (i64.const -1)) (local.set 665 (i64.const -1)) (local.set 666 (i64.const -1)) (local.set 667 (i64.const -1))
+    (local.set 668 (i64.const -1)) (local.set 669 (i64.const -1)) (local.set 670 (i64.const -1)) (local.set 671 (i64.const -1))
+    (local.set 672 (i64.const -1)) (local.set 673 (i64.const -1)) (local.set 674 (i64.const -1)) (local.set 675 (i64.const -1))
+    (local.set 676 (i64.const -1)) (local.set 677 (i64.const -1)) (local.set 678 (i64.const -1)) (local.set 679 (i64.const -1))
+    (local.set 680 (i64.const -1)) (local.set 681 (i64.const -1)) (local.set 682 (i64.const -1)) (local.set 683 (i64.const -1))
+    (local.set 684 (i64.const -1)) (local.set 685 (i64.const -1)) (local.set 686 (i64.const -1)) (local.set 687 (i64.const -1))
+    (local.set 688 (i64.const -1)) (local.set 689 (i64.const -1)) (local.set 690 (i64.const -1)) (local.set 691 (i64.const -1))
+    (local.set 692 (i64.const -1)) (local.set 693 (i64.const -1)) (local.set 694 (i64.const -1)) (local.set 695 (i64.const -1))
+    (local.set 696 (i64.const -1)) (local.set 697 (i64.const -1)) (local.set 698 (i64.const -1)) (local.set 699 (i64.const -1))
+    (local.set 700 (i64.const -1)) (local.set 701 (i64.const -1)) (local.set 702 (i64.const -1)) (local.set 703 (i64.const -1))
+    (local.set 704 (i64.const -1)) (local.set 705 (i64.const -1)) (local.set 706 (i64.const -1)) (local.set 707 (i64.const -1))
+    (local.set 708 (i64.const -1)) (local.set 709 (i64.const -1)) (local.set 710 (i64.const -1)) (local.set 711 (i64.const -1))
+    (local.set 712 (i64.const -1)) (local.set 713 (i64.const -1)) (local.set 714 (i64.const -1)) (local.set 715 (i64.const -1))
+    (local.set 716 (i64.const -1)) (local.set 717 (i64.const -1)) (local.set 718 (i64.const -1)) (local.set 719 (i64.const -1))
+    (local.set 720 (i64.const -1)) (local.set 721 (i64.const -1)) (local.set 722 (i64.const -1)) (local.set 723 (i64.const -1))
+    (local.set 724 (i64.const -1)) (local.set 725 (i64.const -1)) (local.set 726 (i64.const -1)) (local.set 727 (i64.const -1))
+    (local.set 728 (i64.const -1)) (local.set 729 (i64.const -1)) (local.set 730 (i64.const -1)) (local.set 731 (i64.const -1))
+    (local.set 732 (i64.const -1)) (local.set 733 (i64.const -1)) (local.set 734 (i64.const -1)) (local.set 735 (i64.const -1))
+    (local.set 736 (i64.const -1)) (local.set 737 (i64.const -1)) (local.set 738 (i64.const -1)) (local.set 739 (i64.const -1))
+    (local.set 740 (i64.const -1)) (local.set 741 (i64.const -1)) (local.set 742 (i64.const -1)) (local.set 743 (i64.const -1))
+    (local.set 744 (i64.const -1)) (local.set 745 (i64.const -1)) (local.set 746 (i64.const -1)) (local.set 747 (i64.const -1))
+    (local.set 748 (i64.const -1)) (local.set 749 (i64.const -1)) (local.set 750 (i64.const -1)) (local.set 751 (i64.const -1))
+    (local.set 752 (i64.const -1)) (local.set 753 (i64.const -1)) (local.set 754 (i64.const -1)) (local.set 755 (i64.const -1))
+    (local.set 756 (i64.const -1)) (local.set 757 (i64.const -1)) (local.set 758 (i64.const -1)) (local.set 759 (i64.const -1))
+    (local.set 760 (i64.const -1)) (local.set 761 (i64.const -1)) (local.set 762 (i64.const -1)) (local.set 763 (i64.const -1))
+    (local.set 764 (i64.const -1)) (local.set 765 (i64.const -1)) (local.set 766 (i64.const -1)) (local.set 767 (i64.const -1))
+    (local.set 768 (i64.const -1)) (local.set 769 (i64.const -1)) (local.set 770 (i64.const -1)) (local.set 771 (i64.const -1))
+    (local.set 772 (i64.const -1)) (local.set 773 (i64.const -1)) (local.set 774 (i64.const -1)) (local.set 775 (i64.const -1))
+    (local.set 776 (i64.const -1)) (local.set 777 (i64.const -1)) (local.set 778 (i64.const -1)) (local.set 779 (i64.const -1))
+    (local.set 780 (i64.const -1)) (local.set 781 (i64.const -1)) (local.set 782 (i64.const -1)) (local.set 783 (i64.const -1))
+    (local.set 784 (i64.const -1)) (local.set 785 (i64.const -1)) (local.set 786 (i64.const -1)) (local.set 787 (i64.const -1))
+    (local.set 788 (i64.const -1)) (local.set 789 (i64.const -1)) (local.set 790 (i64.const -1)) (local.set 791 (i64.const -1))
+    (local.set 792 (i64.const -1)) (local.set 793 (i64.const -1)) (local.set 794 (i64.const -1)) (local.set 795 (i64.const -1))
+    (local.set 796 (i64.const -1)) (local.set 797 (i64.const -1)) (local.set 798 (i64.const -1)) (local.set 799 (i64.const -1))
+    (local.set 800 (i64.const -1)) (local.set 801 (i64.const -1)) (local.set 802 (i64.const -1)) (local.set 803 (i64.const -1))
+    (local.set 804 (i64.const -1)) (local.set 805 (i64.const -1)) (local.set 806 (i64.const -1)) (local.set 807 (i64.const -1))
+    (local.set 808 (i64.const -1)) (local.set 809 (i64.const -1)) (local.set 810 (i64.const -1)) (local.set 811 (i64.const -1))
+    (local.set 812 (i64.const -1)) (local.set 813 (i64.const -1)) (local.set 814 (i64.const -1)) (local.set 815 (i64.const -1))
+    (local.set 816 (i64.const -1)) (local.set 817 (i64.const -1)) (local.set 818 (i64.const -1)) (local.set 819 (i64.const -1))
+    (local.set 820 (i64.const -1)) (local.set 821 (i64.const -1)) (local.set 822 (i64.const -1)) (local.set 823 (i64.const -1))
+    (local.set 824 (i64.const -1)) (local.set 825 (i64.const -1)) (local.set 826 (i64.const -1)) (local.set 827 (i64.const -1))
+    (local.set 828 (i64.const -1)) (local.set 829 (i64.const -1)) (local.set 830 (i64.const -1)) (local.set 831 (i64.const -1))
+    (local.set 832 (i64.const -1)) (local.set 833 (i64.const -1)) (local.set 834 (i64.const -1)) (local.set 835 (i64.const -1))
+    (local.set 836 (i64.const -1)) (local.set 837 (i64.const -1)) (local.set 838 (i64.const -1)) (local.set 839 (i64.const -1))
+    (local.set 840 (i64.const -1)) (local.set 841 (i64.const -1)) (local.set 842 (i64.const -1)) (local.set 843 (i64.const -1))
+    (local.set 844 (i64.const -1)) (local.set 845 (i64.const -1)) (local.set 846 (i64.const -1)) (local.set 847 (i64.const -1))
+    (local.set 848 (i64.const -1)) (local.set 849 (i64.const -1)) (local.set 850 (i64.const -1)) (local.set 851 (i64.const -1))
+    (local.set 852 (i64.const -1)) (local.set 853 (i64.const -1)) (local.set 854 (i64.const -1)) (local.set 855 (i64.const -1))
+    (local.set 856 (i64.const -1)) (local.set 857 (i64.const -1)) (local.set 858 (i64.const -1)) (local.set 859 (i64.const -1))
+    (local.set 860 (i64.const -1)) (local.set 861 (i64.const -1)) (local.set 862 (i64.const -1)) (local.set 863 (i64.const -1))
+    (local.set 864 (i64.const -1)) (local.set 865 (i64.const -1)) (local.set 866 (i64.const -1)) (local.set 867 (i64.const -1))
+    (local.set 868 (i64.const -1)) (local.set 869 (i64.const -1)) (local.set 870 (i64.const -1)) (local.set 871 (i64.const -1))
+    (local.set 872 (i64.const -1)) (local.set 873 (i64.const -1)) (local.set 874 (i64.const -1)) (local.set 875 (i64.const -1))
+    (local.set 876 (i64.const -1)) (local.set 877 (i64.const -1)) (local.set 878 (i64.const -1)) (local.set 879 (i64.const -1))
+    (local.set 880 (i64.const -1)) (local.set 881 (i64.const -1)) (local.set 882 (i64.const -1)) (local.set 883 (i64.const -1))
+    (local.set 884 (i64.const -1)) (local.set 885 (i64.const -1)) (local.set 886 (i64.const -1)) (local.set 887 (i64.const -1))
+    (local.set 888 (i64.const -1)) (local.set 889 (i64.const -1)) (local.set 890 (i64.const -1)) (local.set 891 (i64.const -1))
+    (local.set 892 (i64.const -1)) (local.set 893 (i64.const -1)) (local.set 894 (i64.const -1)) (local.set 895 (i64.const -1))
+    (local.set 896 (i64.const -1)) (local.set 897 (i64.const -1)) (local.set 898 (i64.const -1)) (local.set 899 (i64.const -1))
+    (local.set 900 (i64.const -1)) (local.set 901 (i64.const -1)) (local.set 902 (i64.const -1)) (local.set 903 (i64.const -1))
+    (local.set 904 (i64.const -1)) (local.set 905 (i64.const -1)) (local.set 906 (i64.const -1)) (local.set 907 (i64.const -1))
+    (local.set 908 (i64.const -1)) (local.set 909 (i64.const -1)) (local.set 910 (i64.const -1)) (local.set 911 (i64.const -1))
+    (local.set 912 (i64.const -1)) (local.set 913 (i64.const -1)) (local.set 914 (i64.const -1)) (local.set 915 (i64.const -1))
+    (local.set 916 (i64.const -1)) (local.set 917 (i64.const -1)) (local.set 918 (i64.const -1)) (local.set 919 (i64.const -1))
+    (local.set 920 (i64.const -1)) (local.set 921 (i64.const -1)) (local.set 922 (i64.const -1)) (local.set 923 (i64.const -1))
+    (local.set 924 (i64.const -1)) (local.set 925 (i64.const -1)) (local.set 926 (i64.const -1)) (local.set 927 (i64.const -1))
+    (local.set 928 (i64.const -1)) (local.set 929 (i64.const -1)) (local.set 930 (i64.const -1)) (local.set 931 (i64.const -1))
+    (local.set 932 (i64.const -1)) (local.set 933 (i64.const -1)) (local.set 934 (i64.const -1)) (local.set 935 (i64.const -1))
+    (local.set 936 (i64.const -1)) (local.set 937 (i64.const -1)) (local.set 938 (i64.const -1)) (local.set 939 (i64.const -1))
+    (local.set 940 (i64.const -1)) (local.set 941 (i64.const -1)) (local.set 942 (i64.const -1)) (local.set 943 (i64.const -1))
+    (local.set 944 (i64.const -1)) (local.set 945 (i64.const -1)) (local.set 946 (i64.const -1)) (local.set 947 (i64.const -1))
+    (local.set 948 (i64.const -1)) (local.set 949 (i64.const -1)) (local.set 950 (i64.const -1)) (local.set 951 (i64.const -1))
+    (local.set 952 (i64.const -1)) (local.set 953 (i64.const -1)) (local.set 954 (i64.const -1)) (local.set 955 (i64.const -1))
+    (local.set 956 (i64.const -1)) (local.set 957 (i64.const -1)) (local.set 958 (i64.const -1)) (local.set 959 (i64.const -1))
+    (local.set 960 (i64.const -1)) (local.set 961 (i64.const -1)) (local.set 962 (i64.const -1)) (local.set 963 (i64.const -1))
+    (local.set 964 (i64.const -1)) (local.set 965 (i64.const -1)) (local.set 966 (i64.const -1)) (local.set 967 (i64.const -1))
+    (local.set 968 (i64.const -1)) (local.set 969 (i64.const -1)) (local.set 970 (i64.const -1)) (local.set 971 (i64.const -1))
+    (local.set 972 (i64.const -1)) (local.set 973 (i64.const -1)) (local.set 974 (i64.const -1)) (local.set 975 (i64.const -1))
+    (local.set 976 (i64.const -1)) (local.set 977 (i64.const -1)) (local.set 978 (i64.const -1)) (local.set 979 (i64.const -1))
+    (local.set 980 (i64.const -1)) (local.set 981 (i64.const -1)) (local.set 982 (i64.const -1)) (local.set 983 (i64.const -1))
+    (local.set 984 (i64.const -1)) (local.set 985 (i64.const -1)) (local.set 986 (i64.const -1)) (local.set 987 (i64.const -1))
+    (local.set 988 (i64.const -1)) (local.set 989 (i64.const -1)) (local.set 990 (i64.const -1)) (local.set 991 (i64.const -1))
+    (local.set 992 (i64.const -1)) (local.set 993 (i64.const -1)) (local.set 994 (i64.const -1)) (local.set 995 (i64.const -1))
+    (local.set 996 (i64.const -1)) (local.set 997 (i64.const -1)) (local.set 998 (i64.const -1)) (local.set 999 (i64.const -1))
+    (local.set 1000 (i64.const -1)) (local.set 1001 (i64.const -1)) (local.set 1002 (i64.const -1)) (local.set 1003 (i64.const -1))
+    (local.set 1004 (i64.const -1)) (local.set 1005 (i64.const -1)) (local.set 1006 (i64.const -1)) (local.set 1007 (i64.const -1))
+    (local.set 1008 (i64.const -1)) (local.set 1009 (i64.const -1)) (local.set 1010 (i64.const -1)) (local.set 1011 (i64.const -1))
+    (local.set 1012 (i64.const -1)) (local.set 1013 (i64.const -1)) (local.set 1014 (i64.const -1)) (local.set 1015 (i64.const -1))
+    (local.set 1016 (i64.const -1)) (local.set 1017 (i64.const -1)) (local.set 1018 (i64.const -1)) (local.set 1019 (i64.const -1))
+    (local.set 1020 (i64.const -1)) (local.set 1021 (i64.const -1)) (local.set 1022 (i64.const -1)) (local.set 1023 (i64.const -1))
+    (local.set 1024 (i64.const -1)) (local.set 1025 (i64.const -1)) (local.set 1026 (i64.const -1)) (local.set 1027 (i64.const -1))
+    (local.set 1028 (i64.const -1)) (local.set 1029 (i64.const -1)) (local.set 1030 (i64.const -1)) (local.set 1031 (i64.const -1))
+    (local.set 1032 (i64.const -1)) (local.set 1033 (i64.const -1)) (local.set 1034 (i64.const -1)) (local.set 1035 (i64.const -1))
+    (local.set 1036 (i64.const -1)) (local.set 1037 (i64.const -1)) (local.set 1038 (i64.const -1)) (local.set 1039 (i64.const -1))
+    (local.set 1040 (i64.const -1)) (local.set 1041 (i64.const -1)) (local.set 1042 (i64.const -1)) (local.set 1043 (i64.const -1))
+    (local.set 1044 (i64.const -1)) (local.set 1045 (i64.const -1)) (local.set 1046 (i64.const -1)) (local.set 1047 (i64.const -1))
+    (local.set 1048 (i64.const -1)) (local.set 1049 (i64.const -1)) (local.set 1050 (i64.const -1)) (local.set 1051 (i64.const -1))
+    (local.set 1052 (i64.const -1)) (local.set 1053 (i64.const -1)) (local.set 1054 (i64.const -1)) (local.set 1055 (i64.const -1))
+    (local.set 1056 (i64.const -1)) (local.set 1057 (i64.const -1)) (local.set 1058 (i64.const -1)) (local.set 1059 (i64.const -1))
+    (local.set 1060 (i64.const -1)) (local.set 1061 (i64.const -1)) (local.set 1062 (i64.const -1)) (local.set 1063 (i64.const -1))
+    (local.set 1064 (i64.const -1)) (local.set 1065 (i64.const -1)) (local.set 1066 (i64.const -1)) (local.set 1067 (i64.const -1))
+    (local.set 1068 (i64.const -1)) (local.set 1069 (i64.const -1)) (local.set 1070 (i64.const -1)) (local.set 1071 (i64.const -1))
+    (local.set 1072 (i64.const -1)) (local.set 1073 (i64.const -1)) (local.set 1074 (i64.const -1)) (local.set 1075 (i64.const -1))
+    (local.set 1076 (i64.const -1)) (local.set 1077 (i64.const -1)) (local.set 1078 (i64.const -1)) (local.set 1079 (i64.const -1))
+    (local.set 1080 (i64.const -1)) (local.set 1081 (i64.const -1)) (local.set 1082 (i64.const -1)) (local.set 1083 (i64.const -1))
+    (local.set 1084 (i64.const -1)) (local.set 1085 (i64.const -1)) (local.set 1086 (i64.const -1)) (local.set 1087 (i64.const -1))
+    (local.set 1088 (i64.const -1)) (local.set 1089 (i64.const -1)) (local.set 1090 (i64.const -1)) (local.set 1091 (i64.const -1))
+    (local.set 1092 (i64.const -1)) (local.set 1093 (i64.const -1)) (local.set 1094 (i64.const -1)) (local.set 1095 (i64.const -1))
+    (local.set 1096 (i64.const -1)) (local.set 1097 (i64.const -1)) (local.set 1098 (i64.const -1)) (local.set 1099 (i64.const -1))
+    (i64.add (local.get 0) (local.get 1099))
+  )
+  (func (export "read-1100") (result i64)
+    (local $acc i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+    (local.set $acc (i64.or (local.get $acc) (local.get 1))) (local.set $acc (i64.or (local.get $acc) (local.get 2)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 3))) (local.set $acc (i64.or (local.get $acc) (local.get 4)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 5))) (local.set $acc (i64.or (local.get $acc) (local.get 6)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 7))) (local.set $acc (i64.or (local.get $acc) (local.get 8)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 9))) (local.set $acc (i64.or (local.get $acc) (local.get 10)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 11))) (local.set $acc (i64.or (local.get $acc) (local.get 12)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 13))) (local.set $acc (i64.or (local.get $acc) (local.get 14)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 15))) (local.set $acc (i64.or (local.get $acc) (local.get 16)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 17))) (local.set $acc (i64.or (local.get $acc) (local.get 18)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 19))) (local.set $acc (i64.or (local.get $acc) (local.get 20)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 21))) (local.set $acc (i64.or (local.get $acc) (local.get 22)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 23))) (local.set $acc (i64.or (local.get $acc) (local.get 24)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 25))) (local.set $acc (i64.or (local.get $acc) (local.get 26)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 27))) (local.set $acc (i64.or (local.get $acc) (local.get 28)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 29))) (local.set $acc (i64.or (local.get $acc) (local.get 30)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 31))) (local.set $acc (i64.or (local.get $acc) (local.get 32)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 33))) (local.set $acc (i64.or (local.get $acc) (local.get 34)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 35))) (local.set $acc (i64.or (local.get $acc) (local.get 36)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 37))) (local.set $acc (i64.or (local.get $acc) (local.get 38)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 39))) (local.set $acc (i64.or (local.get $acc) (local.get 40)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 41))) (local.set $acc (i64.or (local.get $acc) (local.get 42)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 43))) (local.set $acc (i64.or (local.get $acc) (local.get 44)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 45))) (local.set $acc (i64.or (local.get $acc) (local.get 46)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 47))) (local.set $acc (i64.or (local.get $acc) (local.get 48)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 49))) (local.set $acc (i64.or (local.get $acc) (local.get 50)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 51))) (local.set $acc (i64.or (local.get $acc) (local.get 52)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 53))) (local.set $acc (i64.or (local.get $acc) (local.get 54)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 55))) (local.set $acc (i64.or (local.get $acc) (local.get 56)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 57))) (local.set $acc (i64.or (local.get $acc) (local.get 58)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 59))) (local.set $acc (i64.or (local.get $acc) (local.get 60)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 61))) (local.set $acc (i64.or (local.get $acc) (local.get 62)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 63))) (local.set $acc (i64.or (local.get $acc) (local.get 64)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 65))) (local.set $acc (i64.or (local.get $acc) (local.get 66)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 67))) (local.set $acc (i64.or (local.get $acc) (local.get 68)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 69))) (local.set $acc (i64.or (local.get $acc) (local.get 70)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 71))) (local.set $acc (i64.or (local.get $acc) (local.get 72)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 73))) (local.set $acc (i64.or (local.get $acc) (local.get 74)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 75))) (local.set $acc (i64.or (local.get $acc) (local.get 76)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 77))) (local.set $acc (i64.or (local.get $acc) (local.get 78)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 79))) (local.set $acc (i64.or (local.get $acc) (local.get 80)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 81))) (local.set $acc (i64.or (local.get $acc) (local.get 82)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 83))) (local.set $acc (i64.or (local.get $acc) (local.get 84)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 85))) (local.set $acc (i64.or (local.get $acc) (local.get 86)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 87))) (local.set $acc (i64.or (local.get $acc) (local.get 88)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 89))) (local.set $acc (i64.or (local.get $acc) (local.get 90)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 91))) (local.set $acc (i64.or (local.get $acc) (local.get 92)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 93))) (local.set $acc (i64.or (local.get $acc) (local.get 94)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 95))) (local.set $acc (i64.or (local.get $acc) (local.get 96)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 97))) (local.set $acc (i64.or (local.get $acc) (local.get 98)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 99))) (local.set $acc (i64.or (local.get $acc) (local.get 100)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 101))) (local.set $acc (i64.or (local.get $acc) (local.get 102)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 103))) (local.set $acc (i64.or (local.get $acc) (local.get 104)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 105))) (local.set $acc (i64.or (local.get $acc) (local.get 106)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 107))) (local.set $acc (i64.or (local.get $acc) (local.get 108)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 109))) (local.set $acc (i64.or (local.get $acc) (local.get 110)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 111))) (local.set $acc (i64.or (local.get $acc) (local.get 112)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 113))) (local.set $acc (i64.or (local.get $acc) (local.get 114)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 115))) (local.set $acc (i64.or (local.get $acc) (local.get 116)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 117))) (local.set $acc (i64.or (local.get $acc) (local.get 118)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 119))) (local.set $acc (i64.or (local.get $acc) (local.get 120)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 121))) (local.set $acc (i64.or (local.get $acc) (local.get 122)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 123))) (local.set $acc (i64.or (local.get $acc) (local.get 124)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 125))) (local.set $acc (i64.or (local.get $acc) (local.get 126)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 127))) (local.set $acc (i64.or (local.get $acc) (local.get 128)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 129))) (local.set $acc (i64.or (local.get $acc) (local.get 130)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 131))) (local.set $acc (i64.or (local.get $acc) (local.get 132)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 133))) (local.set $acc (i64.or (local.get $acc) (local.get 134)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 135))) (local.set $acc (i64.or (local.get $acc) (local.get 136)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 137))) (local.set $acc (i64.or (local.get $acc) (local.get 138)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 139))) (local.set $acc (i64.or (local.get $acc) (local.get 140)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 141))) (local.set $acc (i64.or (local.get $acc) (local.get 142)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 143))) (local.set $acc (i64.or (local.get $acc) (local.get 144)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 145))) (local.set $acc (i64.or (local.get $acc) (local.get 146)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 147))) (local.set $acc (i64.or (local.get $acc) (local.get 148)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 149))) (local.set $acc (i64.or (local.get $acc) (local.get 150)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 151))) (local.set $acc (i64.or (local.get $acc) (local.get 152)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 153))) (local.set $acc (i64.or (local.get $acc) (local.get 154)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 155))) (local.set $acc (i64.or (local.get $acc) (local.get 156)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 157))) (local.set $acc (i64.or (local.get $acc) (local.get 158)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 159))) (local.set $acc (i64.or (local.get $acc) (local.get 160)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 161))) (local.set $acc (i64.or (local.get $acc) (local.get 162)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 163))) (local.set $acc (i64.or (local.get $acc) (local.get 164)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 165))) (local.set $acc (i64.or (local.get $acc) (local.get 166)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 167))) (local.set $acc (i64.or (local.get $acc) (local.get 168)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 169))) (local.set $acc (i64.or (local.get $acc) (local.get 170)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 171))) (local.set $acc (i64.or (local.get $acc) (local.get 172)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 173))) (local.set $acc (i64.or (local.get $acc) (local.get 174)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 175))) (local.set $acc (i64.or (local.get $acc) (local.get 176)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 177))) (local.set $acc (i64.or (local.get $acc) (local.get 178)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 179))) (local.set $acc (i64.or (local.get $acc) (local.get 180)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 181))) (local.set $acc (i64.or (local.get $acc) (local.get 182)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 183))) (local.set $acc (i64.or (local.get $acc) (local.get 184)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 185))) (local.set $acc (i64.or (local.get $acc) (local.get 186)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 187))) (local.set $acc (i64.or (local.get $acc) (local.get 188)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 189))) (local.set $acc (i64.or (local.get $acc) (local.get 190)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 191))) (local.set $acc (i64.or (local.get $acc) (local.get 192)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 193))) (local.set $acc (i64.or (local.get $acc) (local.get 194)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 195))) (local.set $acc (i64.or (local.get $acc) (local.get 196)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 197))) (local.set $acc (i64.or (local.get $acc) (local.get 198)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 199))) (local.set $acc (i64.or (local.get $acc) (local.get 200)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 201))) (local.set $acc (i64.or (local.get $acc) (local.get 202)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 203))) (local.set $acc (i64.or (local.get $acc) (local.get 204)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 205))) (local.set $acc (i64.or (local.get $acc) (local.get 206)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 207))) (local.set $acc (i64.or (local.get $acc) (local.get 208)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 209))) (local.set $acc (i64.or (local.get $acc) (local.get 210)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 211))) (local.set $acc (i64.or (local.get $acc) (local.get 212)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 213))) (local.set $acc (i64.or (local.get $acc) (local.get 214)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 215))) (local.set $acc (i64.or (local.get $acc) (local.get 216)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 217))) (local.set $acc (i64.or (local.get $acc) (local.get 218)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 219))) (local.set $acc (i64.or (local.get $acc) (local.get 220)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 221))) (local.set $acc (i64.or (local.get $acc) (local.get 222)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 223))) (local.set $acc (i64.or (local.get $acc) (local.get 224)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 225))) (local.set $acc (i64.or (local.get $acc) (local.get 226)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 227))) (local.set $acc (i64.or (local.get $acc) (local.get 228)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 229))) (local.set $acc (i64.or (local.get $acc) (local.get 230)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 231))) (local.set $acc (i64.or (local.get $acc) (local.get 232)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 233))) (local.set $acc (i64.or (local.get $acc) (local.get 234)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 235))) (local.set $acc (i64.or (local.get $acc) (local.get 236)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 237))) (local.set $acc (i64.or (local.get $acc) (local.get 238)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 239))) (local.set $acc (i64.or (local.get $acc) (local.get 240)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 241))) (local.set $acc (i64.or (local.get $acc) (local.get 242)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 243))) (local.set $acc (i64.or (local.get $acc) (local.get 244)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 245))) (local.set $acc (i64.or (local.get $acc) (local.get 246)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 247))) (local.set $acc (i64.or (local.get $acc) (local.get 248)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 249))) (local.set $acc (i64.or (local.get $acc) (local.get 250)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 251))) (local.set $acc (i64.or (local.get $acc) (local.get 252)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 253))) (local.set $acc (i64.or (local.get $acc) (local.get 254)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 255))) (local.set $acc (i64.or (local.get $acc) (local.get 256)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 257))) (local.set $acc (i64.or (local.get $acc) (local.get 258)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 259))) (local.set $acc (i64.or (local.get $acc) (local.get 260)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 261))) (local.set $acc (i64.or (local.get $acc) (local.get 262)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 263))) (local.set $acc (i64.or (local.get $acc) (local.get 264)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 265))) (local.set $acc (i64.or (local.get $acc) (local.get 266)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 267))) (local.set $acc (i64.or (local.get $acc) (local.get 268)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 269))) (local.set $acc (i64.or (local.get $acc) (local.get 270)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 271))) (local.set $acc (i64.or (local.get $acc) (local.get 272)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 273))) (local.set $acc (i64.or (local.get $acc) (local.get 274)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 275))) (local.set $acc (i64.or (local.get $acc) (local.get 276)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 277))) (local.set $acc (i64.or (local.get $acc) (local.get 278)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 279))) (local.set $acc (i64.or (local.get $acc) (local.get 280)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 281))) (local.set $acc (i64.or (local.get $acc) (local.get 282)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 283))) (local.set $acc (i64.or (local.get $acc) (local.get 284)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 285))) (local.set $acc (i64.or (local.get $acc) (local.get 286)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 287))) (local.set $acc (i64.or (local.get $acc) (local.get 288)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 289))) (local.set $acc (i64.or (local.get $acc) (local.get 290)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 291))) (local.set $acc (i64.or (local.get $acc) (local.get 292)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 293))) (local.set $acc (i64.or (local.get $acc) (local.get 294)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 295))) (local.set $acc (i64.or (local.get $acc) (local.get 296)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 297))) (local.set $acc (i64.or (local.get $acc) (local.get 298)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 299))) (local.set $acc (i64.or (local.get $acc) (local.get 300)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 301))) (local.set $acc (i64.or (local.get $acc) (local.get 302)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 303))) (local.set $acc (i64.or (local.get $acc) (local.get 304)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 305))) (local.set $acc (i64.or (local.get $acc) (local.get 306)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 307))) (local.set $acc (i64.or (local.get $acc) (local.get 308)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 309))) (local.set $acc (i64.or (local.get $acc) (local.get 310)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 311))) (local.set $acc (i64.or (local.get $acc) (local.get 312)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 313))) (local.set $acc (i64.or (local.get $acc) (local.get 314)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 315))) (local.set $acc (i64.or (local.get $acc) (local.get 316)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 317))) (local.set $acc (i64.or (local.get $acc) (local.get 318)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 319))) (local.set $acc (i64.or (local.get $acc) (local.get 320)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 321))) (local.set $acc (i64.or (local.get $acc) (local.get 322)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 323))) (local.set $acc (i64.or (local.get $acc) (local.get 324)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 325))) (local.set $acc (i64.or (local.get $acc) (local.get 326)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 327))) (local.set $acc (i64.or (local.get $acc) (local.get 328)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 329))) (local.set $acc (i64.or (local.get $acc) (local.get 330)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 331))) (local.set $acc (i64.or (local.get $acc) (local.get 332)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 333))) (local.set $acc (i64.or (local.get $acc) (local.get 334)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 335))) (local.set $acc (i64.or (local.get $acc) (local.get 336)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 337))) (local.set $acc (i64.or (local.get $acc) (local.get 338)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 339))) (local.set $acc (i64.or (local.get $acc) (local.get 340)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 341))) (local.set $acc (i64.or (local.get $acc) (local.get 342)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 343))) (local.set $acc (i64.or (local.get $acc) (local.get 344)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 345))) (local.set $acc (i64.or (local.get $acc) (local.get 346)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 347))) (local.set $acc (i64.or (local.get $acc) (local.get 348)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 349))) (local.set $acc (i64.or (local.get $acc) (local.get 350)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 351))) (local.set $acc (i64.or (local.get $acc) (local.get 352)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 353))) (local.set $acc (i64.or (local.get $acc) (local.get 354)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 355))) (local.set $acc (i64.or (local.get $acc) (local.get 356)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 357))) (local.set $acc (i64.or (local.get $acc) (local.get 358)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 359))) (local.set $acc (i64.or (local.get $acc) (local.get 360)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 361))) (local.set $acc (i64.or (local.get $acc) (local.get 362)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 363))) (local.set $acc (i64.or (local.get $acc) (local.get 364)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 365))) (local.set $acc (i64.or (local.get $acc) (local.get 366)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 367))) (local.set $acc (i64.or (local.get $acc) (local.get 368)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 369))) (local.set $acc (i64.or (local.get $acc) (local.get 370)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 371))) (local.set $acc (i64.or (local.get $acc) (local.get 372)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 373))) (local.set $acc (i64.or (local.get $acc) (local.get 374)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 375))) (local.set $acc (i64.or (local.get $acc) (local.get 376)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 377))) (local.set $acc (i64.or (local.get $acc) (local.get 378)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 379))) (local.set $acc (i64.or (local.get $acc) (local.get 380)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 381))) (local.set $acc (i64.or (local.get $acc) (local.get 382)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 383))) (local.set $acc (i64.or (local.get $acc) (local.get 384)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 385))) (local.set $acc (i64.or (local.get $acc) (local.get 386)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 387))) (local.set $acc (i64.or (local.get $acc) (local.get 388)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 389))) (local.set $acc (i64.or (local.get $acc) (local.get 390)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 391))) (local.set $acc (i64.or (local.get $acc) (local.get 392)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 393))) (local.set $acc (i64.or (local.get $acc) (local.get 394)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 395))) (local.set $acc (i64.or (local.get $acc) (local.get 396)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 397))) (local.set $acc (i64.or (local.get $acc) (local.get 398)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 399))) (local.set $acc (i64.or (local.get $acc) (local.get 400)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 401))) (local.set $acc (i64.or (local.get $acc) (local.get 402)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 403))) (local.set $acc (i64.or (local.get $acc) (local.get 404)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 405))) (local.set $acc (i64.or (local.get $acc) (local.get 406)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 407))) (local.set $acc (i64.or (local.get $acc) (local.get 408)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 409))) (local.set $acc (i64.or (local.get $acc) (local.get 410)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 411))) (local.set $acc (i64.or (local.get $acc) (local.get 412)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 413))) (local.set $acc (i64.or (local.get $acc) (local.get 414)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 415))) (local.set $acc (i64.or (local.get $acc) (local.get 416)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 417))) (local.set $acc (i64.or (local.get $acc) (local.get 418)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 419))) (local.set $acc (i64.or (local.get $acc) (local.get 420)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 421))) (local.set $acc (i64.or (local.get $acc) (local.get 422)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 423))) (local.set $acc (i64.or (local.get $acc) (local.get 424)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 425))) (local.set $acc (i64.or (local.get $acc) (local.get 426)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 427))) (local.set $acc (i64.or (local.get $acc) (local.get 428)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 429))) (local.set $acc (i64.or (local.get $acc) (local.get 430)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 431))) (local.set $acc (i64.or (local.get $acc) (local.get 432)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 433))) (local.set $acc (i64.or (local.get $acc) (local.get 434)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 435))) (local.set $acc (i64.or (local.get $acc) (local.get 436)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 437))) (local.set $acc (i64.or (local.get $acc) (local.get 438)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 439))) (local.set $acc (i64.or (local.get $acc) (local.get 440)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 441))) (local.set $acc (i64.or (local.get $acc) (local.get 442)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 443))) (local.set $acc (i64.or (local.get $acc) (local.get 444)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 445))) (local.set $acc (i64.or (local.get $acc) (local.get 446)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 447))) (local.set $acc (i64.or (local.get $acc) (local.get 448)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 449))) (local.set $acc (i64.or (local.get $acc) (local.get 450)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 451))) (local.set $acc (i64.or (local.get $acc) (local.get 452)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 453))) (local.set $acc (i64.or (local.get $acc) (local.get 454)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 455))) (local.set $acc (i64.or (local.get $acc) (local.get 456)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 457))) (local.set $acc (i64.or (local.get $acc) (local.get 458)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 459))) (local.set $acc (i64.or (local.get $acc) (local.get 460)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 461))) (local.set $acc (i64.or (local.get $acc) (local.get 462)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 463))) (local.set $acc (i64.or (local.get $acc) (local.get 464)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 465))) (local.set $acc (i64.or (local.get $acc) (local.get 466)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 467))) (local.set $acc (i64.or (local.get $acc) (local.get 468)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 469))) (local.set $acc (i64.or (local.get $acc) (local.get 470)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 471))) (local.set $acc (i64.or (local.get $acc) (local.get 472)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 473))) (local.set $acc (i64.or (local.get $acc) (local.get 474)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 475))) (local.set $acc (i64.or (local.get $acc) (local.get 476)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 477))) (local.set $acc (i64.or (local.get $acc) (local.get 478)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 479))) (local.set $acc (i64.or (local.get $acc) (local.get 480)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 481))) (local.set $acc (i64.or (local.get $acc) (local.get 482)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 483))) (local.set $acc (i64.or (local.get $acc) (local.get 484)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 485))) (local.set $acc (i64.or (local.get $acc) (local.get 486)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 487))) (local.set $acc (i64.or (local.get $acc) (local.get 488)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 489))) (local.set $acc (i64.or (local.get $acc) (local.get 490)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 491))) (local.set $acc (i64.or (local.get $acc) (local.get 492)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 493))) (local.set $acc (i64.or (local.get $acc) (local.get 494)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 495))) (local.set $acc (i64.or (local.get $acc) (local.get 496)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 497))) (local.set $acc (i64.or (local.get $acc) (local.get 498)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 499))) (local.set $acc (i64.or (local.get $acc) (local.get 500)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 501))) (local.set $acc (i64.or (local.get $acc) (local.get 502)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 503))) (local.set $acc (i64.or (local.get $acc) (local.get 504)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 505))) (local.set $acc (i64.or (local.get $acc) (local.get 506)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 507))) (local.set $acc (i64.or (local.get $acc) (local.get 508)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 509))) (local.set $acc (i64.or (local.get $acc) (local.get 510)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 511))) (local.set $acc (i64.or (local.get $acc) (local.get 512)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 513))) (local.set $acc (i64.or (local.get $acc) (local.get 514)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 515))) (local.set $acc (i64.or (local.get $acc) (local.get 516)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 517))) (local.set $acc (i64.or (local.get $acc) (local.get 518)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 519))) (local.set $acc (i64.or (local.get $acc) (local.get 520)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 521))) (local.set $acc (i64.or (local.get $acc) (local.get 522)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 523))) (local.set $acc (i64.or (local.get $acc) (local.get 524)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 525))) (local.set $acc (i64.or (local.get $acc) (local.get 526)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 527))) (local.set $acc (i64.or (local.get $acc) (local.get 528)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 529))) (local.set $acc (i64.or (local.get $acc) (local.get 530)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 531))) (local.set $acc (i64.or (local.get $acc) (local.get 532)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 533))) (local.set $acc (i64.or (local.get $acc) (local.get 534)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 535))) (local.set $acc (i64.or (local.get $acc) (local.get 536)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 537))) (local.set $acc (i64.or (local.get $acc) (local.get 538)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 539))) (local.set $acc (i64.or (local.get $acc) (local.get 540)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 541))) (local.set $acc (i64.or (local.get $acc) (local.get 542)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 543))) (local.set $acc (i64.or (local.get $acc) (local.get 544)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 545))) (local.set $acc (i64.or (local.get $acc) (local.get 546)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 547))) (local.set $acc (i64.or (local.get $acc) (local.get 548)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 549))) (local.set $acc (i64.or (local.get $acc) (local.get 550)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 551))) (local.set $acc (i64.or (local.get $acc) (local.get 552)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 553))) (local.set $acc (i64.or (local.get $acc) (local.get 554)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 555))) (local.set $acc (i64.or (local.get $acc) (local.get 556)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 557))) (local.set $acc (i64.or (local.get $acc) (local.get 558)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 559))) (local.set $acc (i64.or (local.get $acc) (local.get 560)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 561))) (local.set $acc (i64.or (local.get $acc) (local.get 562)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 563))) (local.set $acc (i64.or (local.get $acc) (local.get 564)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 565))) (local.set $acc (i64.or (local.get $acc) (local.get 566)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 567))) (local.set $acc (i64.or (local.get $acc) (local.get 568)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 569))) (local.set $acc (i64.or (local.get $acc) (local.get 570)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 571))) (local.set $acc (i64.or (local.get $acc) (local.get 572)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 573))) (local.set $acc (i64.or (local.get $acc) (local.get 574)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 575))) (local.set $acc (i64.or (local.get $acc) (local.get 576)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 577))) (local.set $acc (i64.or (local.get $acc) (local.get 578)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 579))) (local.set $acc (i64.or (local.get $acc) (local.get 580)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 581))) (local.set $acc (i64.or (local.get $acc) (local.get 582)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 583))) (local.set $acc (i64.or (local.get $acc) (local.get 584)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 585))) (local.set $acc (i64.or (local.get $acc) (local.get 586)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 587))) (local.set $acc (i64.or (local.get $acc) (local.get 588)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 589))) (local.set $acc (i64.or (local.get $acc) (local.get 590)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 591))) (local.set $acc (i64.or (local.get $acc) (local.get 592)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 593))) (local.set $acc (i64.or (local.get $acc) (local.get 594)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 595))) (local.set $acc (i64.or (local.get $acc) (local.get 596)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 597))) (local.set $acc (i64.or (local.get $acc) (local.get 598)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 599))) (local.set $acc (i64.or (local.get $acc) (local.get 600)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 601))) (local.set $acc (i64.or (local.get $acc) (local.get 602)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 603))) (local.set $acc (i64.or (local.get $acc) (local.get 604)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 605))) (local.set $acc (i64.or (local.get $acc) (local.get 606)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 607))) (local.set $acc (i64.or (local.get $acc) (local.get 608)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 609))) (local.set $acc (i64.or (local.get $acc) (local.get 610)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 611))) (local.set $acc (i64.or (local.get $acc) (local.get 612)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 613))) (local.set $acc (i64.or (local.get $acc) (local.get 614)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 615))) (local.set $acc (i64.or (local.get $acc) (local.get 616)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 617))) (local.set $acc (i64.or (local.get $acc) (local.get 618)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 619))) (local.set $acc (i64.or (local.get $acc) (local.get 620)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 621))) (local.set $acc (i64.or (local.get $acc) (local.get 622)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 623))) (local.set $acc (i64.or (local.get $acc) (local.get 624)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 625))) (local.set $acc (i64.or (local.get $acc) (local.get 626)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 627))) (local.set $acc (i64.or (local.get $acc) (local.get 628)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 629))) (local.set $acc (i64.or (local.get $acc) (local.get 630)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 631))) (local.set $acc (i64.or (local.get $acc) (local.get 632)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 633))) (local.set $acc (i64.or (local.get $acc) (local.get 634)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 635))) (local.set $acc (i64.or (local.get $acc) (local.get 636)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 637))) (local.set $acc (i64.or (local.get $acc) (local.get 638)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 639))) (local.set $acc (i64.or (local.get $acc) (local.get 640)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 641))) (local.set $acc (i64.or (local.get $acc) (local.get 642)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 643))) (local.set $acc (i64.or (local.get $acc) (local.get 644)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 645))) (local.set $acc (i64.or (local.get $acc) (local.get 646)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 647))) (local.set $acc (i64.or (local.get $acc) (local.get 648)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 649))) (local.set $acc (i64.or (local.get $acc) (local.get 650)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 651))) (local.set $acc (i64.or (local.get $acc) (local.get 652)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 653))) (local.set $acc (i64.or (local.get $acc) (local.get 654)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 655))) (local.set $acc (i64.or (local.get $acc) (local.get 656)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 657))) (local.set $acc (i64.or (local.get $acc) (local.get 658)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 659))) (local.set $acc (i64.or (local.get $acc) (local.get 660)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 661))) (local.set $acc (i64.or (local.get $acc) (local.get 662)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 663))) (local.set $acc (i64.or (local.get $acc) (local.get 664)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 665))) (local.set $acc (i64.or (local.get $acc) (local.get 666)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 667))) (local.set $acc (i64.or (local.get $acc) (local.get 668)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 669))) (local.set $acc (i64.or (local.get $acc) (local.get 670)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 671))) (local.set $acc (i64.or (local.get $acc) (local.get 672)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 673))) (local.set $acc (i64.or (local.get $acc) (local.get 674)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 675))) (local.set $acc (i64.or (local.get $acc) (local.get 676)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 677))) (local.set $acc (i64.or (local.get $acc) (local.get 678)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 679))) (local.set $acc (i64.or (local.get $acc) (local.get 680)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 681))) (local.set $acc (i64.or (local.get $acc) (local.get 682)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 683))) (local.set $acc (i64.or (local.get $acc) (local.get 684)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 685))) (local.set $acc (i64.or (local.get $acc) (local.get 686)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 687))) (local.set $acc (i64.or (local.get $acc) (local.get 688)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 689))) (local.set $acc (i64.or (local.get $acc) (local.get 690)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 691))) (local.set $acc (i64.or (local.get $acc) (local.get 692)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 693))) (local.set $acc (i64.or (local.get $acc) (local.get 694)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 695))) (local.set $acc (i64.or (local.get $acc) (local.get 696)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 697))) (local.set $acc (i64.or (local.get $acc) (local.get 698)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 699))) (local.set $acc (i64.or (local.get $acc) (local.get 700)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 701))) (local.set $acc (i64.or (local.get $acc) (local.get 702)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 703))) (local.set $acc (i64.or (local.get $acc) (local.get 704)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 705))) (local.set $acc (i64.or (local.get $acc) (local.get 706)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 707))) (local.set $acc (i64.or (local.get $acc) (local.get 708)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 709))) (local.set $acc (i64.or (local.get $acc) (local.get 710)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 711))) (local.set $acc (i64.or (local.get $acc) (local.get 712)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 713))) (local.set $acc (i64.or (local.get $acc) (local.get 714)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 715))) (local.set $acc (i64.or (local.get $acc) (local.get 716)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 717))) (local.set $acc (i64.or (local.get $acc) (local.get 718)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 719))) (local.set $acc (i64.or (local.get $acc) (local.get 720)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 721))) (local.set $acc (i64.or (local.get $acc) (local.get 722)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 723))) (local.set $acc (i64.or (local.get $acc) (local.get 724)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 725))) (local.set $acc (i64.or (local.get $acc) (local.get 726)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 727))) (local.set $acc (i64.or (local.get $acc) (local.get 728)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 729))) (local.set $acc (i64.or (local.get $acc) (local.get 730)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 731))) (local.set $acc (i64.or (local.get $acc) (local.get 732)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 733))) (local.set $acc (i64.or (local.get $acc) (local.get 734)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 735))) (local.set $acc (i64.or (local.get $acc) (local.get 736)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 737))) (local.set $acc (i64.or (local.get $acc) (local.get 738)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 739))) (local.set $acc (i64.or (local.get $acc) (local.get 740)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 741))) (local.set $acc (i64.or (local.get $acc) (local.get 742)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 743))) (local.set $acc (i64.or (local.get $acc) (local.get 744)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 745))) (local.set $acc (i64.or (local.get $acc) (local.get 746)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 747))) (local.set $acc (i64.or (local.get $acc) (local.get 748)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 749))) (local.set $acc (i64.or (local.get $acc) (local.get 750)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 751))) (local.set $acc (i64.or (local.get $acc) (local.get 752)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 753))) (local.set $acc (i64.or (local.get $acc) (local.get 754)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 755))) (local.set $acc (i64.or (local.get $acc) (local.get 756)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 757))) (local.set $acc (i64.or (local.get $acc) (local.get 758)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 759))) (local.set $acc (i64.or (local.get $acc) (local.get 760)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 761))) (local.set $acc (i64.or (local.get $acc) (local.get 762)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 763))) (local.set $acc (i64.or (local.get $acc) (local.get 764)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 765))) (local.set $acc (i64.or (local.get $acc) (local.get 766)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 767))) (local.set $acc (i64.or (local.get $acc) (local.get 768)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 769))) (local.set $acc (i64.or (local.get $acc) (local.get 770)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 771))) (local.set $acc (i64.or (local.get $acc) (local.get 772)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 773))) (local.set $acc (i64.or (local.get $acc) (local.get 774)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 775))) (local.set $acc (i64.or (local.get $acc) (local.get 776)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 777))) (local.set $acc (i64.or (local.get $acc) (local.get 778)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 779))) (local.set $acc (i64.or (local.get $acc) (local.get 780)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 781))) (local.set $acc (i64.or (local.get $acc) (local.get 782)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 783))) (local.set $acc (i64.or (local.get $acc) (local.get 784)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 785))) (local.set $acc (i64.or (local.get $acc) (local.get 786)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 787))) (local.set $acc (i64.or (local.get $acc) (local.get 788)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 789))) (local.set $acc (i64.or (local.get $acc) (local.get 790)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 791))) (local.set $acc (i64.or (local.get $acc) (local.get 792)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 793))) (local.set $acc (i64.or (local.get $acc) (local.get 794)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 795))) (local.set $acc (i64.or (local.get $acc) (local.get 796)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 797))) (local.set $acc (i64.or (local.get $acc) (local.get 798)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 799))) (local.set $acc (i64.or (local.get $acc) (local.get 800)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 801))) (local.set $acc (i64.or (local.get $acc) (local.get 802)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 803))) (local.set $acc (i64.or (local.get $acc) (local.get 804)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 805))) (local.set $acc (i64.or (local.get $acc) (local.get 806)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 807))) (local.set $acc (i64.or (local.get $acc) (local.get 808)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 809))) (local.set $acc (i64.or (local.get $acc) (local.get 810)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 811))) (local.set $acc (i64.or (local.get $acc) (local.get 812)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 813))) (local.set $acc (i64.or (local.get $acc) (local.get 814)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 815))) (local.set $acc (i64.or (local.get $acc) (local.get 816)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 817))) (local.set $acc (i64.or (local.get $acc) (local.get 818)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 819))) (local.set $acc (i64.or (local.get $acc) (local.get 820)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 821))) (local.set $acc (i64.or (local.get $acc) (local.get 822)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 823))) (local.set $acc (i64.or (local.get $acc) (local.get 824)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 825))) (local.set $acc (i64.or (local.get $acc) (local.get 826)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 827))) (local.set $acc (i64.or (local.get $acc) (local.get 828)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 829))) (local.set $acc (i64.or (local.get $acc) (local.get 830)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 831))) (local.set $acc (i64.or (local.get $acc) (local.get 832)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 833))) (local.set $acc (i64.or (local.get $acc) (local.get 834)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 835))) (local.set $acc (i64.or (local.get $acc) (local.get 836)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 837))) (local.set $acc (i64.or (local.get $acc) (local.get 838)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 839))) (local.set $acc (i64.or (local.get $acc) (local.get 840)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 841))) (local.set $acc (i64.or (local.get $acc) (local.get 842)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 843))) (local.set $acc (i64.or (local.get $acc) (local.get 844)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 845))) (local.set $acc (i64.or (local.get $acc) (local.get 846)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 847))) (local.set $acc (i64.or (local.get $acc) (local.get 848)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 849))) (local.set $acc (i64.or (local.get $acc) (local.get 850)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 851))) (local.set $acc (i64.or (local.get $acc) (local.get 852)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 853))) (local.set $acc (i64.or (local.get $acc) (local.get 854)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 855))) (local.set $acc (i64.or (local.get $acc) (local.get 856)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 857))) (local.set $acc (i64.or (local.get $acc) (local.get 858)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 859))) (local.set $acc (i64.or (local.get $acc) (local.get 860)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 861))) (local.set $acc (i64.or (local.get $acc) (local.get 862)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 863))) (local.set $acc (i64.or (local.get $acc) (local.get 864)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 865))) (local.set $acc (i64.or (local.get $acc) (local.get 866)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 867))) (local.set $acc (i64.or (local.get $acc) (local.get 868)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 869))) (local.set $acc (i64.or (local.get $acc) (local.get 870)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 871))) (local.set $acc (i64.or (local.get $acc) (local.get 872)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 873))) (local.set $acc (i64.or (local.get $acc) (local.get 874)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 875))) (local.set $acc (i64.or (local.get $acc) (local.get 876)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 877))) (local.set $acc (i64.or (local.get $acc) (local.get 878)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 879))) (local.set $acc (i64.or (local.get $acc) (local.get 880)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 881))) (local.set $acc (i64.or (local.get $acc) (local.get 882)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 883))) (local.set $acc (i64.or (local.get $acc) (local.get 884)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 885))) (local.set $acc (i64.or (local.get $acc) (local.get 886)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 887))) (local.set $acc (i64.or (local.get $acc) (local.get 888)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 889))) (local.set $acc (i64.or (local.get $acc) (local.get 890)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 891))) (local.set $acc (i64.or (local.get $acc) (local.get 892)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 893))) (local.set $acc (i64.or (local.get $acc) (local.get 894)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 895))) (local.set $acc (i64.or (local.get $acc) (local.get 896)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 897))) (local.set $acc (i64.or (local.get $acc) (local.get 898)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 899))) (local.set $acc (i64.or (local.get $acc) (local.get 900)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 901))) (local.set $acc (i64.or (local.get $acc) (local.get 902)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 903))) (local.set $acc (i64.or (local.get $acc) (local.get 904)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 905))) (local.set $acc (i64.or (local.get $acc) (local.get 906)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 907))) (local.set $acc (i64.or (local.get $acc) (local.get 908)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 909))) (local.set $acc (i64.or (local.get $acc) (local.get 910)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 911))) (local.set $acc (i64.or (local.get $acc) (local.get 912)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 913))) (local.set $acc (i64.or (local.get $acc) (local.get 914)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 915))) (local.set $acc (i64.or (local.get $acc) (local.get 916)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 917))) (local.set $acc (i64.or (local.get $acc) (local.get 918)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 919))) (local.set $acc (i64.or (local.get $acc) (local.get 920)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 921))) (local.set $acc (i64.or (local.get $acc) (local.get 922)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 923))) (local.set $acc (i64.or (local.get $acc) (local.get 924)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 925))) (local.set $acc (i64.or (local.get $acc) (local.get 926)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 927))) (local.set $acc (i64.or (local.get $acc) (local.get 928)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 929))) (local.set $acc (i64.or (local.get $acc) (local.get 930)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 931))) (local.set $acc (i64.or (local.get $acc) (local.get 932)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 933))) (local.set $acc (i64.or (local.get $acc) (local.get 934)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 935))) (local.set $acc (i64.or (local.get $acc) (local.get 936)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 937))) (local.set $acc (i64.or (local.get $acc) (local.get 938)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 939))) (local.set $acc (i64.or (local.get $acc) (local.get 940)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 941))) (local.set $acc (i64.or (local.get $acc) (local.get 942)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 943))) (local.set $acc (i64.or (local.get $acc) (local.get 944)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 945))) (local.set $acc (i64.or (local.get $acc) (local.get 946)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 947))) (local.set $acc (i64.or (local.get $acc) (local.get 948)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 949))) (local.set $acc (i64.or (local.get $acc) (local.get 950)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 951))) (local.set $acc (i64.or (local.get $acc) (local.get 952)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 953))) (local.set $acc (i64.or (local.get $acc) (local.get 954)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 955))) (local.set $acc (i64.or (local.get $acc) (local.get 956)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 957))) (local.set $acc (i64.or (local.get $acc) (local.get 958)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 959))) (local.set $acc (i64.or (local.get $acc) (local.get 960)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 961))) (local.set $acc (i64.or (local.get $acc) (local.get 962)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 963))) (local.set $acc (i64.or (local.get $acc) (local.get 964)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 965))) (local.set $acc (i64.or (local.get $acc) (local.get 966)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 967))) (local.set $acc (i64.or (local.get $acc) (local.get 968)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 969))) (local.set $acc (i64.or (local.get $acc) (local.get 970)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 971))) (local.set $acc (i64.or (local.get $acc) (local.get 972)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 973))) (local.set $acc (i64.or (local.get $acc) (local.get 974)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 975))) (local.set $acc (i64.or (local.get $acc) (local.get 976)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 977))) (local.set $acc (i64.or (local.get $acc) (local.get 978)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 979))) (local.set $acc (i64.or (local.get $acc) (local.get 980)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 981))) (local.set $acc (i64.or (local.get $acc) (local.get 982)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 983))) (local.set $acc (i64.or (local.get $acc) (local.get 984)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 985))) (local.set $acc (i64.or (local.get $acc) (local.get 986)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 987))) (local.set $acc (i64.or (local.get $acc) (local.get 988)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 989))) (local.set $acc (i64.or (local.get $acc) (local.get 990)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 991))) (local.set $acc (i64.or (local.get $acc) (local.get 992)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 993))) (local.set $acc (i64.or (local.get $acc) (local.get 994)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 995))) (local.set $acc (i64.or (local.get $acc) (local.get 996)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 997))) (local.set $acc (i64.or (local.get $acc) (local.get 998)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 999))) (local.set $acc (i64.or (local.get $acc) (local.get 1000)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1001))) (local.set $acc (i64.or (local.get $acc) (local.get 1002)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1003))) (local.set $acc (i64.or (local.get $acc) (local.get 1004)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1005))) (local.set $acc (i64.or (local.get $acc) (local.get 1006)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1007))) (local.set $acc (i64.or (local.get $acc) (local.get 1008)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1009))) (local.set $acc (i64.or (local.get $acc) (local.get 1010)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1011))) (local.set $acc (i64.or (local.get $acc) (local.get 1012)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1013))) (local.set $acc (i64.or (local.get $acc) (local.get 1014)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1015))) (local.set $acc (i64.or (local.get $acc) (local.get 1016)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1017))) (local.set $acc (i64.or (local.get $acc) (local.get 1018)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1019))) (local.set $acc (i64.or (local.get $acc) (local.get 1020)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1021))) (local.set $acc (i64.or (local.get $acc) (local.get 1022)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1023))) (local.set $acc (i64.or (local.get $acc) (local.get 1024)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1025))) (local.set $acc (i64.or (local.get $acc) (local.get 1026)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1027))) (local.set $acc (i64.or (local.get $acc) (local.get 1028)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1029))) (local.set $acc (i64.or (local.get $acc) (local.get 1030)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1031))) (local.set $acc (i64.or (local.get $acc) (local.get 1032)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1033))) (local.set $acc (i64.or (local.get $acc) (local.get 1034)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1035))) (local.set $acc (i64.or (local.get $acc) (local.get 1036)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1037))) (local.set $acc (i64.or (local.get $acc) (local.get 1038)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1039))) (local.set $acc (i64.or (local.get $acc) (local.get 1040)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1041))) (local.set $acc (i64.or (local.get $acc) (local.get 1042)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1043))) (local.set $acc (i64.or (local.get $acc) (local.get 1044)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1045))) (local.set $acc (i64.or (local.get $acc) (local.get 1046)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1047))) (local.set $acc (i64.or (local.get $acc) (local.get 1048)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1049))) (local.set $acc (i64.or (local.get $acc) (local.get 1050)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1051))) (local.set $acc (i64.or (local.get $acc) (local.get 1052)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1053))) (local.set $acc (i64.or (local.get $acc) (local.get 1054)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1055))) (local.set $acc (i64.or (local.get $acc) (local.get 1056)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1057))) (local.set $acc (i64.or (local.get $acc) (local.get 1058)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1059))) (local.set $acc (i64.or (local.get $acc) (local.get 1060)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1061))) (local.set $acc (i64.or (local.get $acc) (local.get 1062)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1063))) (local.set $acc (i64.or (local.get $acc) (local.get 1064)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1065))) (local.set $acc (i64.or (local.get $acc) (local.get 1066)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1067))) (local.set $acc (i64.or (local.get $acc) (local.get 1068)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1069))) (local.set $acc (i64.or (local.get $acc) (local.get 1070)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1071))) (local.set $acc (i64.or (local.get $acc) (local.get 1072)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1073))) (local.set $acc (i64.or (local.get $acc) (local.get 1074)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1075))) (local.set $acc (i64.or (local.get $acc) (local.get 1076)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1077))) (local.set $acc (i64.or (local.get $acc) (local.get 1078)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1079))) (local.set $acc (i64.or (local.get $acc) (local.get 1080)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1081))) (local.set $acc (i64.or (local.get $acc) (local.get 1082)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1083))) (local.set $acc (i64.or (local.get $acc) (local.get 1084)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1085))) (local.set $acc (i64.or (local.get $acc) (local.get 1086)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1087))) (local.set $acc (i64.or (local.get $acc) (local.get 1088)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1089))) (local.set $acc (i64.or (local.get $acc) (local.get 1090)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1091))) (local.set $acc (i64.or (local.get $acc) (local.get 1092)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1093))) (local.set $acc (i64.or (local.get $acc) (local.get 1094)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1095))) (local.set $acc (i64.or (local.get $acc) (local.get 1096)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1097))) (local.set $acc (i64.or (local.get $acc) (local.get 1098)))
+    (local.set $acc (i64.or (local.get $acc) (local.get 1099))) (local.set $acc (i64.or (local.get $acc) (local.get 600)))
+    (local.get $acc)
+  )
+)
+
+(assert_return (invoke "dirty-6") (i64.const -2))
+(assert_return (invoke "read-6") (i64.const 0))
+(assert_return (invoke "dirty-40") (i64.const -2))
+(assert_return (invoke "read-40") (i64.const 0))
+(assert_return (invoke "dirty-1100") (i64.const -2))
+(assert_return (invoke "read-1100") (i64.const 0))